@@ -85,6 +85,9 @@ class Callbacks:
             **kwargs,
         )
 
+    def on_error(self, context: str, message: str, **kwargs: Any) -> None:
+        self._notify(function_name="on_error", context=context, message=message, reverse=True, **kwargs)
+
     def on_compose_config(
         self,
         config: DictConfig,
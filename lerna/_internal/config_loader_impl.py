@@ -357,6 +357,9 @@ class ConfigLoaderImpl(ConfigLoader):
                         )
                 elif override.is_force_add():
                     OmegaConf.update(cfg, key, value, merge=True, force_add=True)
+                elif override.is_list_extend() and override.list_operation is None:
+                    # `key+=value` rather than a list function such as append()
+                    ConfigLoaderImpl._apply_add_override(cfg, override, value)
                 elif override.is_list_extend():
                     config_val = OmegaConf.select(cfg, key, throw_on_missing=True)
                     if not OmegaConf.is_list(config_val):
@@ -409,6 +412,18 @@ class ConfigLoaderImpl(ConfigLoader):
                     elif list_op == ListOperationType.CLEAR:
                         # Clear all items from the list
                         config_val.clear()
+                    elif list_op == ListOperationType.APPEND_UNIQUE:
+                        # Append only items not already in the list
+                        for item in extend_value:
+                            if item not in config_val:
+                                config_val.append(item)
+                    elif list_op == ListOperationType.PREPEND_UNIQUE:
+                        # Prepend only items not already in the list, keeping their order
+                        pos = 0
+                        for item in extend_value:
+                            if item not in config_val:
+                                config_val.insert(pos, item)
+                                pos += 1
                 else:
                     try:
                         OmegaConf.update(cfg, key, value, merge=True)
@@ -419,6 +434,60 @@ class ConfigLoaderImpl(ConfigLoader):
             except OmegaConfBaseException as ex:
                 raise ConfigCompositionException(f"Error merging override {override.input_line}").with_traceback(sys.exc_info()[2]) from ex
 
+    @staticmethod
+    def _apply_add_override(cfg: DictConfig, override: Override, value: Any) -> None:
+        """Apply `key+=value` to the current value, as the Rust override merge does.
+
+        Strings (including interpolations) are concatenated, numbers are added
+        and lists are extended by a list or a single item. A key that is
+        missing, null or ??? is set as with `key=value`.
+        """
+        key = override.key_or_group
+        last_dot = key.rfind(".")
+        parent = cfg if last_dot == -1 else OmegaConf.select(cfg, key[:last_dot], throw_on_missing=False)
+        leaf = key[last_dot + 1 :]
+        node = None
+        if isinstance(parent, DictConfig):
+            node = parent._get_node(leaf, validate_access=False)
+        elif isinstance(parent, ListConfig) and leaf.isdigit() and int(leaf) < len(parent):
+            node = parent._get_node(int(leaf))
+
+        if node is None or node._is_none() or node._is_missing():
+            OmegaConf.update(cfg, key, value, merge=True, force_add=True)
+            return
+
+        current = node if isinstance(node, Container) else node._value()
+        if isinstance(current, ListConfig):
+            current.extend(value if isinstance(value, (list, ListConfig)) else [value])
+            return
+
+        def is_number(v: Any) -> bool:
+            return isinstance(v, (int, float)) and not isinstance(v, bool)
+
+        def kind(v: Any) -> str:
+            if v is None:
+                return "null"
+            if isinstance(v, bool):
+                return "a bool"
+            if isinstance(v, int):
+                return "an int"
+            if isinstance(v, float):
+                return "a float"
+            if isinstance(v, str):
+                return "a string"
+            if isinstance(v, (list, ListConfig)):
+                return "a list"
+            return "a dict"
+
+        if isinstance(current, str) and isinstance(value, (str, int, float)):
+            text = str(value).lower() if isinstance(value, bool) else str(value)
+            new_value: Any = current + text
+        elif is_number(current) and is_number(value):
+            new_value = current + value
+        else:
+            raise ConfigCompositionException(f"Cannot apply '+=' to '{key}': cannot add {kind(value)} to {kind(current)}")
+        OmegaConf.update(cfg, key, new_value, merge=True)
+
     def _load_single_config(self, default: ResultDefault, repo: IConfigRepository) -> ConfigResult:
         config_path = default.config_path
 
@@ -372,12 +372,13 @@ def _sort_sweep(sweep: Union[ChoiceSweep, RangeSweep], reverse: bool) -> Union[C
         assert False
 
 
-def glob(include: Union[List[str], str], exclude: Optional[Union[List[str], str]] = None) -> Glob:
+def glob(include: Union[List[str], str], exclude: Optional[Union[List[str], str]] = None, case_insensitive: bool = False) -> Glob:
     """
     A glob selects from all options in the config group.
     inputs are in glob format. e.g: *, foo*, *foo.
     :param include: a string or a list of strings to use as include globs
     :param exclude: a string or a list of strings to use as exclude globs
+    :param case_insensitive: match option names regardless of case
     """
 
     if isinstance(include, str):
@@ -387,7 +388,7 @@ def glob(include: Union[List[str], str], exclude: Optional[Union[List[str], str]
     elif isinstance(exclude, str):
         exclude = [exclude]
 
-    return Glob(include=include, exclude=exclude)
+    return Glob(include=include, exclude=exclude, case_insensitive=case_insensitive)
 
 
 def extend_list(*args: Any) -> ListExtensionOverrideValue:
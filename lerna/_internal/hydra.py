@@ -113,18 +113,22 @@ class Hydra:
         callbacks = Callbacks(cfg)
         callbacks.on_run_start(config=cfg, config_name=config_name)
 
-        ret = run_job(
-            hydra_context=HydraContext(config_loader=self.config_loader, callbacks=callbacks),
-            task_function=task_function,
-            config=cfg,
-            job_dir_key="hydra.run.dir",
-            job_subdir_key=None,
-            configure_logging=with_log_configuration,
-        )
-        callbacks.on_run_end(config=cfg, config_name=config_name, job_return=ret)
+        try:
+            ret = run_job(
+                hydra_context=HydraContext(config_loader=self.config_loader, callbacks=callbacks),
+                task_function=task_function,
+                config=cfg,
+                job_dir_key="hydra.run.dir",
+                job_subdir_key=None,
+                configure_logging=with_log_configuration,
+            )
+            callbacks.on_run_end(config=cfg, config_name=config_name, job_return=ret)
 
-        # access the result to trigger an exception in case the job failed.
-        _ = ret.return_value
+            # access the result to trigger an exception in case the job failed.
+            _ = ret.return_value
+        except Exception as e:
+            callbacks.on_error(config=cfg, context="run", message=str(e))
+            raise
 
         return ret
 
@@ -145,14 +149,18 @@ class Hydra:
         callbacks = Callbacks(cfg)
         callbacks.on_multirun_start(config=cfg, config_name=config_name)
 
-        sweeper = Plugins.instance().instantiate_sweeper(
-            config=cfg,
-            hydra_context=HydraContext(config_loader=self.config_loader, callbacks=callbacks),
-            task_function=task_function,
-        )
-        task_overrides = OmegaConf.to_container(cfg.hydra.overrides.task, resolve=False)
-        assert isinstance(task_overrides, list)
-        ret = sweeper.sweep(arguments=task_overrides)
+        try:
+            sweeper = Plugins.instance().instantiate_sweeper(
+                config=cfg,
+                hydra_context=HydraContext(config_loader=self.config_loader, callbacks=callbacks),
+                task_function=task_function,
+            )
+            task_overrides = OmegaConf.to_container(cfg.hydra.overrides.task, resolve=False)
+            assert isinstance(task_overrides, list)
+            ret = sweeper.sweep(arguments=task_overrides)
+        except Exception as e:
+            callbacks.on_error(config=cfg, context="multirun", message=str(e))
+            raise
         callbacks.on_multirun_end(config=cfg, config_name=config_name)
         return ret
 
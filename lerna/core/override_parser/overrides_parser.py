@@ -10,6 +10,7 @@ Note: The `functions` parameter is kept for backward compatibility but is
 not used - the Rust parser has built-in functions (choice, range, sort, etc.).
 """
 
+import os
 import sys
 from typing import Any, List, Optional
 
@@ -56,6 +57,7 @@ class OverridesParser:
         cls,
         config_loader: Optional[ConfigLoader] = None,
         searchpath: Optional[List[str]] = None,
+        escapes: Optional[bool] = None,
     ) -> "OverridesParser":
         """Create an OverridesParser instance.
 
@@ -63,29 +65,41 @@ class OverridesParser:
             config_loader: ConfigLoader for resolving glob sweeps
             searchpath: Optional searchpath from hydra.searchpath config,
                        used to ensure pkg:// sources are available for glob sweeps
+            escapes: Whether quoted strings support escape sequences such as
+                     \\n, \\t and \\uXXXX. Defaults to on when the
+                     HYDRA_OVERRIDE_ESCAPES environment variable is "1".
         """
-        return cls(functions=None, config_loader=config_loader, searchpath=searchpath)
+        return cls(functions=None, config_loader=config_loader, searchpath=searchpath, escapes=escapes)
 
     def __init__(
         self,
         functions: Optional[Functions] = None,
         config_loader: Optional[ConfigLoader] = None,
         searchpath: Optional[List[str]] = None,
+        escapes: Optional[bool] = None,
     ):
         self.config_loader = config_loader
         self.searchpath = searchpath
         self._functions = functions
+        # Escapes are opt-in: Hydra keeps backslashes in quoted strings as written,
+        # which quoted Windows paths and escaped interpolations rely on
+        if escapes is None:
+            escapes = os.environ.get("HYDRA_OVERRIDE_ESCAPES") == "1"
         # Pass functions to Rust parser if provided
-        if functions is not None:
-            self._rust_parser = _rs.OverrideParser(functions)
-        else:
-            self._rust_parser = _rs.OverrideParser()
+        self._rust_parser = _rs.OverrideParser(functions, escapes=escapes)
 
     def _parse_with_rust(self, s: str) -> Override:
         """Parse using Rust parser and convert to Python Override."""
         data = self._rust_parser.parse_to_dict(s)
         return _rust_dict_to_override(data, self.config_loader, self.searchpath)
 
+    def _parse_expanded_with_rust(self, s: str) -> List[Override]:
+        """Parse using Rust parser, expanding key@pkg1,pkg2=value into one Override per package."""
+        return [
+            _rust_dict_to_override(data, self.config_loader, self.searchpath)
+            for data in self._rust_parser.parse_to_dicts(s)
+        ]
+
     def parse_rule(self, s: str, rule_name: str) -> Any:
         """Parse a rule using the Rust parser.
 
@@ -169,18 +183,18 @@ class OverridesParser:
         ret: List[Override] = []
         for idx, override in enumerate(overrides):
             try:
-                parsed = self.parse_rule(override, "override")
-            except (HydraException, ValueError) as e:
-                msg = f"Error parsing override '{override}'\n{e}"
+                expanded = self._parse_expanded_with_rust(override)
+            except Exception as e:
+                msg = f"Error parsing override '{override}'\nParse error: {e}"
                 raise OverrideParseException(
                     override=override,
                     message=f"Error when parsing index: {idx}, string: {override} out of {overrides}."
                     f"\n{msg}"
                     f"\nSee https://hydra.cc/docs/1.2/advanced/override_grammar/basic for details",
-                ) from e.__cause__ if isinstance(e, HydraException) else e
-            assert isinstance(parsed, Override)
-            parsed.config_loader = self.config_loader
-            ret.append(parsed)
+                ) from e
+            for parsed in expanded:
+                parsed.config_loader = self.config_loader
+                ret.append(parsed)
         return ret
 
 
@@ -264,6 +278,8 @@ def _parse_list_operation(operation_str: str) -> ListOperationType:
         "REMOVE_AT": ListOperationType.REMOVE_AT,
         "REMOVE_VALUE": ListOperationType.REMOVE_VALUE,
         "CLEAR": ListOperationType.CLEAR,
+        "APPEND_UNIQUE": ListOperationType.APPEND_UNIQUE,
+        "PREPEND_UNIQUE": ListOperationType.PREPEND_UNIQUE,
     }
     return operation_map.get(operation_str, ListOperationType.APPEND)
 
@@ -376,6 +392,7 @@ def _rust_dict_to_override(
             value = Glob(
                 include=raw_value.get("include", []),
                 exclude=raw_value.get("exclude", []),
+                case_insensitive=raw_value.get("case_insensitive", False),
             )
             # Glob is a sweep type
             value_type = ValueType.GLOB_CHOICE_SWEEP
@@ -207,6 +207,8 @@ class ListOperationType(Enum):
     REMOVE_AT = 4  # Remove item at index
     REMOVE_VALUE = 5  # Remove first matching value
     CLEAR = 6  # Clear the list
+    APPEND_UNIQUE = 7  # Append items not already present
+    PREPEND_UNIQUE = 8  # Prepend items not already present
 
 
 @dataclass
@@ -220,18 +222,22 @@ class Key:
 class Glob:
     include: List[str] = field(default_factory=list)
     exclude: List[str] = field(default_factory=list)
+    case_insensitive: bool = False
 
     def filter(self, names: List[str]) -> List[str]:
         """Filter names based on include and exclude patterns."""
         if _HAS_RUST:
             # Use Rust implementation for performance
-            rust_glob = _rs.Glob(self.include, self.exclude)
+            rust_glob = _rs.Glob(self.include, self.exclude, self.case_insensitive)
             return rust_glob.filter(names)
 
         # Fallback to Python implementation
         def match(s: str, globs: List[str]) -> bool:
             for g in globs:
-                if fnmatch.fnmatch(s, g):
+                if self.case_insensitive:
+                    if fnmatch.fnmatchcase(s.lower(), g.lower()):
+                        return True
+                elif fnmatch.fnmatch(s, g):
                     return True
             return False
 
@@ -60,6 +60,14 @@ class Callback:
         """
         ...
 
+    def on_error(self, context: str, message: str, **kwargs: Any) -> None:
+        """
+        Called when a run or multirun fails, before the error propagates, so callbacks can clean up.
+        `context` names the failing phase ("run", "multirun" or "compose") and `message` is the error.
+        In RUN and MULTIRUN modes the composed config is passed as `config`.
+        """
+        ...
+
     def on_compose_config(
         self,
         config: DictConfig,
@@ -106,6 +106,14 @@ list:
         assert result is not None
         assert result["key1"] == "value1"
 
+    def test_rust_wide_dict_resolve(self, benchmark: Any) -> None:
+        """Benchmark key lookups in a 10k-key dict by resolving references to every key."""
+        config = {f"k{i}": i for i in range(10_000)}
+        config["refs"] = {f"r{i}": f"${{k{i}}}" for i in range(10_000)}
+
+        result = benchmark(_rs.resolve_interpolations, config)
+        assert result["refs"]["r9999"] == 9999
+
     def test_rust_config_repo_load(self, temp_config_dir: str, benchmark: Any) -> None:
         """Benchmark config loading with Rust ConfigRepository."""
         repo = _rs.RustCachingConfigRepository([("test", f"file://{temp_config_dir}")])
@@ -0,0 +1,25 @@
+# Copyright (c) Facebook, Inc. and its affiliates. All Rights Reserved
+
+import logging
+from typing import Any
+
+from omegaconf import DictConfig
+
+import lerna
+from lerna.experimental.callback import Callback
+
+log = logging.getLogger(__name__)
+
+
+class OnErrorCallback(Callback):
+    def on_error(self, context: str, message: str, **kwargs: Any) -> None:
+        log.info(f"on_error {context}: {message}")
+
+
+@lerna.main(version_base=None, config_path=".", config_name="config")
+def my_app(cfg: DictConfig) -> None:
+    raise ValueError("job failed")
+
+
+if __name__ == "__main__":
+    my_app()
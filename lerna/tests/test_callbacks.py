@@ -136,6 +136,20 @@ def test_app_with_callbacks(
     )
 
 
+def test_app_with_on_error_callback(tmpdir: Path) -> None:
+    cmd = [
+        "lerna/tests/test_apps/app_with_callbacks/on_error_callback/my_app.py",
+        f'hydra.run.dir="{normalize_path_for_override(tmpdir)}"',
+        "hydra.job.chdir=True",
+        "hydra.hydra_logging.formatters.simple.format='[HYDRA] %(message)s'",
+        "hydra.job_logging.formatters.simple.format='[JOB] %(message)s'",
+    ]
+    result, err = run_python_script(cmd, print_error=False, raise_exception=False)
+
+    assert "on_error run: job failed" in result
+    assert "job failed" in err
+
+
 @mark.parametrize("multirun", [True, False])
 def test_experimental_save_job_info_callback(tmpdir: Path, multirun: bool) -> None:
     app_path = "lerna/tests/test_apps/app_with_pickle_job_info_callback/my_app.py"
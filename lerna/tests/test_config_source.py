@@ -62,6 +62,15 @@ class TestConfigResult:
         result = ConfigResult(provider="nested", path="nested.yaml", config=config)
         assert result.get_config() == config
 
+    def test_config_result_package_directive(self):
+        """ConfigResult distinguishes a missing package from an empty one."""
+        missing = ConfigResult(provider="p", path="a.yaml", config={})
+        empty = ConfigResult(provider="p", path="b.yaml", config={}, header={"package": ""})
+
+        assert missing.package() is None
+        assert empty.package() == ""
+        assert not empty.global_package()
+
 
 class TestRustFileConfigSource:
     """Test RustFileConfigSource - Rust FileConfigSource exposed to Python."""
@@ -137,6 +146,18 @@ class TestRustFileConfigSource:
             db_contents = source.list("db")
             assert "mysql" in db_contents
 
+    def test_file_config_source_list_after_clear_cache(self):
+        """FileConfigSource.list reflects new files after clearing the listing cache."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            (Path(tmpdir) / "db").mkdir()
+            (Path(tmpdir) / "db" / "mysql.yaml").write_text("driver: mysql\n")
+            source = RustFileConfigSource(provider="file", path=tmpdir)
+            assert source.list("db") == ["mysql"]
+
+            (Path(tmpdir) / "db" / "postgres.yaml").write_text("driver: postgres\n")
+            source.clear_listing_cache()
+            assert source.list("db") == ["mysql", "postgres"]
+
     def test_file_config_source_load_config(self):
         """FileConfigSource.load_config loads YAML files."""
         with tempfile.TemporaryDirectory() as tmpdir:
@@ -156,6 +177,28 @@ debug: true
             assert config["server"]["port"] == 8080
             assert config["debug"] is True
 
+    def test_file_config_source_header_accessors(self):
+        """FileConfigSource results expose typed header directives."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            (Path(tmpdir) / "global.yaml").write_text("# @package _global_\nvalue: 1\n")
+            (Path(tmpdir) / "nested.yaml").write_text("# @package foo.bar\n# @schema db_schema\nvalue: 1\n")
+            (Path(tmpdir) / "plain.yaml").write_text("value: 1\n")
+            source = RustFileConfigSource(provider="file", path=tmpdir)
+
+            result = source.load_config("global.yaml")
+            assert result.package() == "_global_"
+            assert result.global_package()
+            assert result.schema() is None
+
+            result = source.load_config("nested.yaml")
+            assert result.package() == "foo.bar"
+            assert not result.global_package()
+            assert result.schema() == "db_schema"
+
+            result = source.load_config("plain.yaml")
+            assert result.package() is None
+            assert not result.global_package()
+
 
 class TestConfigSourceManager:
     """Test ConfigSourceManager - manages multiple config sources."""
@@ -65,6 +65,131 @@ class TestRustBasicLauncher:
 
         assert results[0].job_name == "job_10"
 
+    def test_basic_launcher_captures_output(self):
+        """BasicLauncher captures task stdout/stderr when capture_output is set."""
+        import sys
+
+        def task(overrides):
+            print(f"running {' '.join(overrides)}")
+            print("warning", file=sys.stderr)
+
+        launcher = RustBasicLauncher(capture_output=True)
+        assert launcher.capture_output
+        launcher.setup({}, "capture_task")
+        launcher.set_task(task)
+
+        results = launcher.launch([["db=mysql"]], 0)
+
+        assert results[0].is_success()
+        assert results[0].stdout == "running db=mysql\n"
+        assert results[0].stderr == "warning\n"
+
+    def test_basic_launcher_captures_failure(self):
+        """A failing task reports a non-zero status and its traceback on stderr."""
+
+        def task(overrides):
+            raise ValueError("boom")
+
+        launcher = RustBasicLauncher(capture_output=True)
+        launcher.set_task(task)
+
+        results = launcher.launch([[]], 0)
+
+        assert not results[0].is_success()
+        assert "ValueError: boom" in results[0].stderr
+
+    def test_basic_launcher_no_capture_by_default(self):
+        """Without capture_output, JobReturn has no captured output."""
+        launcher = RustBasicLauncher()
+        launcher.set_task(lambda overrides: None)
+
+        results = launcher.launch([["key=value"]], 0)
+
+        assert results[0].stdout is None
+        assert results[0].stderr is None
+
+    def test_basic_launcher_parallel(self):
+        """With max_parallel > 1, jobs run concurrently and results keep input order."""
+        import threading
+
+        seen = []
+        # Each job waits for all four to start, which only happens if they run at once
+        all_started = threading.Barrier(4, timeout=10)
+        launcher = RustBasicLauncher(max_parallel=4)
+        assert launcher.max_parallel == 4
+        launcher.set_task(lambda overrides: (all_started.wait(), seen.append(overrides[0])))
+
+        results = launcher.launch([[f"seed={i}"] for i in range(4)], 5)
+
+        assert all(r.is_success() for r in results)
+        assert sorted(seen) == [f"seed={i}" for i in range(4)]
+        assert [r.job_name for r in results] == ["job_5", "job_6", "job_7", "job_8"]
+
+    def test_basic_launcher_parallel_captures_output_per_job(self):
+        """Parallel capturing jobs each get their own output and restore sys.stdout."""
+        import sys
+        import time
+
+        def task(overrides):
+            for _ in range(3):
+                print(overrides[0])
+                time.sleep(0.01)
+
+        stdout = sys.stdout
+        launcher = RustBasicLauncher(capture_output=True, max_parallel=4)
+        launcher.set_task(task)
+
+        results = launcher.launch([[f"seed={i}"] for i in range(8)], 0)
+
+        assert [r.stdout for r in results] == [f"seed={i}\n" * 3 for i in range(8)]
+        assert sys.stdout is stdout
+
+    def test_basic_launcher_serial_by_default(self):
+        """max_parallel defaults to 1, running jobs one after another."""
+        import threading
+        import time
+
+        lock = threading.Lock()
+        running = []
+        peak = []
+
+        def task(overrides):
+            with lock:
+                running.append(overrides[0])
+                peak.append(len(running))
+            time.sleep(0.01)
+            with lock:
+                running.remove(overrides[0])
+
+        launcher = RustBasicLauncher()
+        assert launcher.max_parallel == 1
+        launcher.set_task(task)
+
+        results = launcher.launch([["a=1"], ["a=2"], ["a=3"]], 0)
+
+        assert peak == [1, 1, 1]
+        assert [r.job_name for r in results] == ["job_0", "job_1", "job_2"]
+
+    def test_basic_launcher_output_dir_collisions(self):
+        """Jobs resolving to the same sweep output dir are rejected unless allowed."""
+        config = {"hydra": {"sweep": {"dir": "/sweep", "subdir": "fixed"}}}
+        job_overrides = [["lr=0.1"], ["lr=0.01"]]
+
+        launcher = RustBasicLauncher()
+        assert not launcher.allow_collisions
+        launcher.setup(config, "train")
+        with pytest.raises(RuntimeError, match=r"/sweep/fixed \(#0, #1\)"):
+            launcher.launch(job_overrides, 0)
+
+        launcher = RustBasicLauncher(allow_collisions=True)
+        launcher.setup(config, "train")
+        assert len(launcher.launch(job_overrides, 0)) == 2
+
+        config["hydra"]["sweep"]["subdir"] = "${hydra.job.num}"
+        launcher = RustBasicLauncher()
+        launcher.setup(config, "train")
+        assert len(launcher.launch(job_overrides, 0)) == 2
+
 
 class TestLauncherManager:
     """Test LauncherManager - manages launcher instances."""
@@ -124,6 +249,49 @@ class TestLauncherManager:
         assert manager.has_launcher()
 
 
+class TestLauncherRegistry:
+    """Test registering launchers by name and activating one."""
+
+    @staticmethod
+    def make_launcher(prefix):
+        class NamedLauncher:
+            def launch(self, job_overrides, initial_job_idx):
+                return [
+                    JobReturn(
+                        job_name=f"{prefix}_{initial_job_idx + idx}",
+                        task_name=prefix,
+                        working_dir="/tmp",
+                        output_dir="/outputs",
+                    )
+                    for idx in range(len(job_overrides))
+                ]
+
+        return NamedLauncher()
+
+    def test_register_and_activate(self):
+        """The active launcher is chosen by its registered name."""
+        manager = LauncherManager()
+        manager.register("local", self.make_launcher("local"))
+        manager.register("remote", self.make_launcher("remote"))
+
+        assert manager.available_names() == ["local", "remote"]
+        assert not manager.has_launcher()
+
+        manager.activate("remote")
+        assert manager.launch([["db=mysql"]], 0)[0].task_name == "remote"
+
+        manager.activate("local")
+        assert manager.launch([["db=mysql"]], 0)[0].task_name == "local"
+
+    def test_activate_unknown(self):
+        """Activating an unregistered name lists the available launchers."""
+        manager = LauncherManager()
+        manager.register("local", self.make_launcher("local"))
+
+        with pytest.raises(RuntimeError, match=r"Unknown launcher 'slurm'.*\[local\]"):
+            manager.activate("slurm")
+
+
 class TestPythonLauncherIntegration:
     """Test Python launcher integration with Rust manager."""
 
@@ -1,5 +1,5 @@
 # Copyright (c) Lerna Contributors. All Rights Reserved
-"""Tests for list operations from CLI (append, prepend, insert, remove_at, remove_value, list_clear).
+"""Tests for list operations from CLI (append, prepend, append_unique, prepend_unique, insert, remove_at, remove_value, list_clear).
 
 These tests verify the fix for Hydra issues:
 - #1547: Append to list from CLI
@@ -88,6 +88,18 @@ class TestListOperationParsing:
         assert result.list_index is None
         assert result._value == []
 
+    def test_append_unique_parsing(self, parser):
+        result = parser.parse_override("tags=append_unique(a,b)")
+        assert result.type == OverrideType.EXTEND_LIST
+        assert result.list_operation == ListOperationType.APPEND_UNIQUE
+        assert result._value == ["a", "b"]
+
+    def test_prepend_unique_parsing(self, parser):
+        result = parser.parse_override("tags=prepend_unique(first)")
+        assert result.type == OverrideType.EXTEND_LIST
+        assert result.list_operation == ListOperationType.PREPEND_UNIQUE
+        assert result._value == ["first"]
+
 
 class TestListOperationsIntegration:
     """Test list operations in full compose workflow."""
@@ -184,6 +196,21 @@ items:
             cfg = compose(config_name="config", overrides=["tags=list_clear()"])
             assert list(cfg["tags"]) == []
 
+    def test_append_unique_existing(self, config_dir):
+        with initialize_config_dir(version_base=None, config_dir=config_dir):
+            cfg = compose(config_name="config", overrides=["tags=append_unique(two)"])
+            assert list(cfg["tags"]) == ["one", "two", "three"]
+
+    def test_append_unique_new(self, config_dir):
+        with initialize_config_dir(version_base=None, config_dir=config_dir):
+            cfg = compose(config_name="config", overrides=["tags=append_unique(two,four)"])
+            assert list(cfg["tags"]) == ["one", "two", "three", "four"]
+
+    def test_prepend_unique(self, config_dir):
+        with initialize_config_dir(version_base=None, config_dir=config_dir):
+            cfg = compose(config_name="config", overrides=["tags=prepend_unique(zero,one,half)"])
+            assert list(cfg["tags"]) == ["zero", "half", "one", "two", "three"]
+
     def test_multiple_operations_sequential(self, config_dir):
         """Test that multiple list operations work sequentially."""
         with initialize_config_dir(version_base=None, config_dir=config_dir):
@@ -213,6 +240,23 @@ db:
             cfg = compose(config_name="config", overrides=["db.hosts=append(replica2)"])
             assert list(cfg["db"]["hosts"]) == ["localhost", "replica1", "replica2"]
 
+    def test_add_override(self, tmp_path):
+        """Test `key+=value` on strings, numbers, lists and missing keys."""
+        conf_dir = tmp_path / "conf"
+        conf_dir.mkdir()
+        (conf_dir / "config.yaml").write_text("name: run\nport: 8000\nlr: 0.5\ntags: [a]\nout: ???\n")
+        with initialize_config_dir(version_base=None, config_dir=str(conf_dir)):
+            cfg = compose(
+                config_name="config",
+                overrides=["name+=_v2", "port+=80", "lr+=1", "tags+=b", "tags+=[c,d]", "out+=x", "extra+=1"],
+            )
+            assert cfg.name == "run_v2"
+            assert cfg.port == 8080
+            assert cfg.lr == 1.5
+            assert list(cfg.tags) == ["a", "b", "c", "d"]
+            assert cfg.out == "x"
+            assert cfg.extra == 1
+
 
 class TestListOperationErrors:
     """Test error handling for list operations."""
@@ -244,6 +288,12 @@ name: not_a_list
             with pytest.raises(Exception, match="not a list"):
                 compose(config_name="config", overrides=["name=append(new)"])
 
+    def test_add_override_type_mismatch(self, config_dir):
+        """`key+=value` fails when the values cannot be added."""
+        with initialize_config_dir(version_base=None, config_dir=config_dir):
+            with pytest.raises(Exception, match="Cannot apply '\\+=' to 'name': cannot add a list to a string"):
+                compose(config_name="config", overrides=["name+=[1]"])
+
     def test_remove_at_out_of_bounds(self, config_dir):
         """Remove at out-of-bounds index should fail."""
         with initialize_config_dir(version_base=None, config_dir=config_dir):
@@ -1140,6 +1140,31 @@ def test_parse_overrides() -> None:
     assert ret == expected
 
 
+def test_parse_overrides_package_list() -> None:
+    ret = parser.parse_overrides(["model@gpu0,gpu1=resnet", "x=1"])
+    assert [(o.key_or_group, o.package, o.value()) for o in ret] == [
+        ("model", "gpu0", "resnet"),
+        ("model", "gpu1", "resnet"),
+        ("x", None, 1),
+    ]
+    assert [o.input_line for o in ret] == ["model@gpu0=resnet", "model@gpu1=resnet", "x=1"]
+
+
+def test_parse_overrides_escapes(monkeypatch: Any) -> None:
+    overrides = [r'name="line1\nline2"', r"path='c:\new\\x'"]
+    ret = OverridesParser.create(escapes=True).parse_overrides(overrides[:1])
+    assert ret[0].value() == "line1\nline2"
+
+    # Off by default, keeping backslashes as Hydra does
+    monkeypatch.delenv("HYDRA_OVERRIDE_ESCAPES", raising=False)
+    ret = OverridesParser.create().parse_overrides(overrides)
+    assert [o.value() for o in ret] == [r"line1\nline2", r"c:\new\\x"]
+
+    monkeypatch.setenv("HYDRA_OVERRIDE_ESCAPES", "1")
+    ret = OverridesParser.create().parse_overrides(overrides[:1])
+    assert ret[0].value() == "line1\nline2"
+
+
 @mark.parametrize(
     "override,expected",
     [
@@ -1,5 +1,7 @@
 """Tests for Rust callback extension point bindings."""
 
+import pytest
+
 from lerna import CallbackManager, JobReturn
 
 
@@ -55,6 +57,24 @@ class TestCallbackManager:
         assert callback.calls[0][0] == "on_run_start"
         assert "key" in callback.calls[0][1]
 
+    def test_callback_receives_nested_config(self):
+        """Test callbacks receive nested dicts, lists and scalars as native Python values."""
+        received = []
+
+        class MyCallback:
+            def on_run_start(self, config, kwargs):
+                received.append(config)
+
+        cm = CallbackManager()
+        cm.add_callback(MyCallback())
+
+        config = {"db": {"host": "localhost", "port": 3306}, "tags": ["a", "b"], "debug": False}
+        cm.on_run_start(config)
+
+        assert received == [config]
+        assert received[0]["db"]["port"] == 3306
+        assert received[0]["tags"] == ["a", "b"]
+
     def test_multiple_callbacks(self):
         """Test multiple callbacks are all called."""
         calls = []
@@ -111,6 +131,18 @@ class TestJobReturn:
         assert jr_success.is_success()
         assert not jr_failure.is_success()
 
+    def test_status(self):
+        """Test status is derived from status_code."""
+        from lerna.lerna import RustJobStatus
+
+        jr_success = JobReturn("job", "task", "/w", "/o", 0)
+        jr_failure = JobReturn("job", "task", "/w", "/o", 1)
+
+        assert jr_success.status == "COMPLETED"
+        assert jr_failure.status == "FAILED"
+        assert jr_success.job_status == RustJobStatus.COMPLETED
+        assert jr_failure.job_status == RustJobStatus.FAILED
+
     def test_setters(self):
         """Test that fields can be modified."""
         jr = JobReturn("job", "task", "/w", "/o", 0)
@@ -136,3 +168,47 @@ class TestCallbackWithJobEnd:
         cm.on_job_end({}, jr)
 
         assert len(received) == 1
+
+
+class TestComposeConfigWithCallbacks:
+    """Test on_compose_config fired by Rust-side composition."""
+
+    def test_callback_observes_composed_config(self, tmp_path):
+        """Test on_compose_config receives the composed config and overrides."""
+        from lerna.lerna import compose_config_with_callbacks
+
+        received = []
+
+        class MyCallback:
+            def on_compose_config(self, config, config_name, overrides):
+                received.append((config, config_name, overrides))
+
+        (tmp_path / "config.yaml").write_text("db:\n  host: localhost\n  port: 3306\n")
+
+        cm = CallbackManager()
+        cm.add_callback(MyCallback())
+        cfg = compose_config_with_callbacks(str(tmp_path), "config", ["db.port=5432"], cm)
+
+        assert cfg == {"db": {"host": "localhost", "port": 5432}}
+        assert received == [(cfg, "config", ["db.port=5432"])]
+
+    def test_callback_on_error_when_composition_fails(self, tmp_path):
+        """Test on_error receives the composition error message."""
+        from lerna.lerna import compose_config_with_callbacks
+
+        errors = []
+
+        class MyCallback:
+            def on_error(self, context, message):
+                errors.append((context, message))
+
+        (tmp_path / "config.yaml").write_text("url: ${db.host}\n")
+
+        cm = CallbackManager()
+        cm.add_callback(MyCallback())
+        with pytest.raises(RuntimeError) as excinfo:
+            compose_config_with_callbacks(str(tmp_path), "config", [], cm)
+
+        assert len(errors) == 1
+        assert errors[0][0] == "compose"
+        assert errors[0][1] in str(excinfo.value)
@@ -4,6 +4,8 @@
 import os
 import tempfile
 
+import pytest
+
 
 class TestRustConfigRepository:
     """Tests for RustConfigRepository from lerna.lerna."""
@@ -111,6 +113,68 @@ class TestRustConfigRepository:
             assert "mysql" in options
             assert "postgres" in options
 
+    def test_get_group_options_by_provider(self):
+        """Test restricting get_group_options to one provider's sources."""
+        from lerna.lerna import RustConfigRepository
+
+        with tempfile.TemporaryDirectory() as main, tempfile.TemporaryDirectory() as user:
+            for root, names in ((main, ["mysql", "postgres"]), (user, ["mysql", "sqlite"])):
+                os.makedirs(os.path.join(root, "db"))
+                for name in names:
+                    with open(os.path.join(root, "db", f"{name}.yaml"), "w") as f:
+                        f.write(f"driver: {name}\n")
+
+            repo = RustConfigRepository([("main", main), ("user", user)])
+
+            assert repo.get_group_options("db") == ["mysql", "postgres", "sqlite"]
+            assert repo.get_group_options("db", provider="user") == ["mysql", "sqlite"]
+            assert repo.get_group_options("db", provider="main") == ["mysql", "postgres"]
+            assert repo.get_group_options("db", provider="other") == []
+
+    def test_alias_loads_target(self):
+        """Test that loading an alias returns the target's content."""
+        from lerna.lerna import RustConfigRepository
+
+        with tempfile.TemporaryDirectory() as td:
+            os.makedirs(os.path.join(td, "db"))
+            with open(os.path.join(td, "db", "production.yaml"), "w") as f:
+                f.write("driver: postgres\n")
+
+            repo = RustConfigRepository([("main", td)])
+            repo.add_alias("db", "prod", "production")
+
+            assert repo.config_exists("db/prod")
+            assert repo.load_config("db/prod") == {"driver": "postgres"}
+            assert repo.get_group_options("db") == ["production"]
+            assert repo.get_group_options("db", include_aliases=True) == ["prod", "production"]
+
+    def test_alias_cycle_errors(self):
+        """Test that a cyclic alias is rejected."""
+        from lerna.lerna import RustConfigRepository
+
+        with tempfile.TemporaryDirectory() as td:
+            repo = RustConfigRepository([("main", td)])
+            repo.add_alias("db", "a", "b")
+
+            with pytest.raises(ValueError, match="Cyclic config alias"):
+                repo.add_alias("db", "b", "a")
+
+    def test_exists_any(self):
+        """Test batch existence checks keep the order of the input paths."""
+        from lerna.lerna import RustConfigRepository
+
+        with tempfile.TemporaryDirectory() as td:
+            os.makedirs(os.path.join(td, "db"))
+            with open(os.path.join(td, "config.yaml"), "w") as f:
+                f.write("key: value\n")
+            with open(os.path.join(td, "db", "mysql.yaml"), "w") as f:
+                f.write("driver: mysql\n")
+
+            repo = RustConfigRepository([("main", td)])
+
+            assert repo.exists_any(["db/mysql", "db/oracle", "config", "db"]) == [True, False, True, False]
+            assert repo.group_exists_batch(["db", "server", "db/mysql"]) == [True, False, False]
+
     def test_load_config_full(self):
         """Test load_config_full returns full result with header."""
         from lerna.lerna import RustConfigRepository
@@ -282,6 +346,20 @@ class TestRustCachingConfigRepository:
             config = result["config"]
             assert config["db"]["port"] == 5432
 
+    def test_load_and_compose_unused_overrides(self):
+        """Test that overrides matching nothing are reported as unused."""
+        from lerna.lerna import RustCachingConfigRepository
+
+        with tempfile.TemporaryDirectory() as td:
+            with open(os.path.join(td, "config.yaml"), "w") as f:
+                f.write("db:\n  host: localhost\n  port: 3306\n")
+
+            repo = RustCachingConfigRepository([("main", td)])
+            result = repo.load_and_compose("config", ["db.port=5432", "db.prot=5432"])
+
+            assert result["applied_overrides"] == ["db.port=5432"]
+            assert result["unused_overrides"] == ["db.prot=5432"]
+
     def test_clear_cache(self):
         """Test that clear_cache works."""
         from lerna.lerna import RustCachingConfigRepository
@@ -293,3 +371,29 @@ class TestRustCachingConfigRepository:
             repo = RustCachingConfigRepository([("main", td)])
             repo.load_config("config")
             repo.clear_cache()  # Should not raise
+
+    def test_load_group(self):
+        """Test loading every config in a group at once."""
+        from lerna.lerna import RustCachingConfigRepository
+
+        with tempfile.TemporaryDirectory() as td:
+            os.makedirs(os.path.join(td, "db"))
+            with open(os.path.join(td, "db", "mysql.yaml"), "w") as f:
+                f.write("driver: mysql\nport: 3306\n")
+            with open(os.path.join(td, "db", "postgres.yaml"), "w") as f:
+                f.write("driver: postgres\nport: 5432\n")
+
+            repo = RustCachingConfigRepository([("main", td)])
+            expected = {
+                "mysql": {"driver": "mysql", "port": 3306},
+                "postgres": {"driver": "postgres", "port": 5432},
+            }
+            assert repo.load_group("db") == (expected, {})
+            assert repo.load_group("missing") == ({}, {})
+
+            with open(os.path.join(td, "db", "broken.yaml"), "w") as f:
+                f.write("driver: [unclosed\n")
+            repo.clear_cache()
+            configs, errors = repo.load_group("db")
+            assert configs == expected
+            assert list(errors) == ["broken"]
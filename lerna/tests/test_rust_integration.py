@@ -62,6 +62,19 @@ class TestRustSweepIntegration:
         assert ["db=mysql", "port=3306"] in combos
         assert ["db=postgres", "port=3306"] in combos
 
+    def test_seeded_shuffle_sweep(self):
+        """Test that a seed gives shuffled ranges a stable order"""
+        import lerna.lerna as rs
+
+        combos = rs.expand_sweeps(["x=shuffle(range(0,20))", "db=mysql"], seed=3)
+        assert combos == rs.expand_sweeps(["x=shuffle(range(0,20))", "db=mysql"], seed=3)
+        assert sorted(combos) == sorted([f"x={i}", "db=mysql"] for i in range(20))
+        assert combos != [[f"x={i}", "db=mysql"] for i in range(20)]
+
+        unseeded = rs.expand_sweeps(["x=shuffle(range(0,20))", "db=mysql"])
+        assert sorted(unseeded) == sorted(combos)
+        assert sorted(rs.expand_sweeps(["x=shuffle(a,b,c)"], seed=3)) == [["x=a"], ["x=b"], ["x=c"]]
+
     def test_count_combinations(self):
         """Test combination counting"""
         import lerna.lerna as rs
@@ -78,6 +91,27 @@ class TestRustSweepIntegration:
         assert len(combos) == 1
         assert combos[0] == []
 
+    def test_sweep_to_override_string(self):
+        """Test rendering sweeps back to override syntax"""
+        import lerna.lerna as rs
+
+        cases = {
+            "db=mysql,postgres": "db=mysql,postgres",
+            "db=choice(mysql,postgres)": "db=choice(mysql,postgres)",
+            "x=range(1,10,2)": "x=range(1,10,2)",
+            "x=interval(0,1)": "x=interval(0.0,1.0)",
+            "x=shuffle(choice(a,b))": "x=shuffle(choice(a,b))",
+            "x=tag(lr,grid,shuffle(range(1,3)))": "x=tag(grid,lr,shuffle(range(1,3)))",
+            "model@gpu0=resnet": "model@gpu0=resnet",
+        }
+        for source, expected in cases.items():
+            rendered = rs.sweep_to_override_string(source)
+            assert rendered == expected
+            assert rs.sweep_to_override_string(rendered) == rendered
+
+        with pytest.raises(ValueError):
+            rs.sweep_to_override_string("~db")
+
 
 class TestRustDefaultsListIntegration:
     """Test Rust defaults list integration"""
@@ -169,6 +203,71 @@ db:
         result = rs.parse_yaml("items:\n  - a\n  - b\n  - c")
         assert result["items"] == ["a", "b", "c"]
 
+    def test_parse_yaml_include(self, tmp_path):
+        """Test resolving !include tags relative to base_dir"""
+        import lerna.lerna as rs
+
+        (tmp_path / "db.yaml").write_text("host: localhost\nport: 3306\n")
+        result = rs.parse_yaml("db: !include db.yaml\n", base_dir=str(tmp_path))
+        assert result == {"db": {"host": "localhost", "port": 3306}}
+
+        (tmp_path / "loop.yaml").write_text("again: !include loop.yaml\n")
+        with pytest.raises(RuntimeError, match="Circular include"):
+            rs.load_yaml_file(str(tmp_path / "loop.yaml"))
+
+    def test_load_config_dir(self, tmp_path):
+        """Test deep-merging every YAML file in a directory in name order"""
+        import lerna.lerna as rs
+
+        (tmp_path / "01-base.yaml").write_text("db:\n  host: localhost\n  port: 3306\nname: base\n")
+        (tmp_path / "02-db.yaml").write_text("db:\n  port: 5432\n")
+        (tmp_path / "03-overrides.yaml").write_text("name: prod\n")
+        result = rs.load_config_dir(str(tmp_path))
+        assert result == {"db": {"host": "localhost", "port": 5432}, "name": "prod"}
+
+        with pytest.raises(RuntimeError, match="Failed to read directory"):
+            rs.load_config_dir(str(tmp_path / "missing"))
+
+    def test_resolve_path(self):
+        """Test resolving interpolations only under one subtree"""
+        import lerna.lerna as rs
+
+        config = {"name": "app", "hydra": {"run": {"dir": "outputs/${name}"}, "job": "${name}"}, "url": "${missing}"}
+        result = rs.resolve_path(config, "hydra.run.dir")
+        assert result == {"name": "app", "hydra": {"run": {"dir": "outputs/app"}, "job": "${name}"}, "url": "${missing}"}
+
+        with pytest.raises(RuntimeError, match="Key 'sweep' not found"):
+            rs.resolve_path(config, "hydra.sweep")
+
+    def test_apply_overrides_cloned(self):
+        """Test applying overrides to a copy leaves the original unchanged"""
+        import lerna.lerna as rs
+
+        config = {"db": {"host": "localhost", "port": 3306}, "tags": ["a"], "debug": True}
+        result = rs.apply_overrides_cloned(config, ["db.port=5432", "+db.user=admin", "tags=append(b)", "~debug"])
+
+        assert result == {"db": {"host": "localhost", "port": 5432, "user": "admin"}, "tags": ["a", "b"]}
+        assert config == {"db": {"host": "localhost", "port": 3306}, "tags": ["a"], "debug": True}
+
+    def test_apply_overrides_cloned_delete_list_element(self):
+        """Test deleting list elements by index, including from the end"""
+        import lerna.lerna as rs
+
+        config = {"servers": ["a", "b", "c"]}
+        assert rs.apply_overrides_cloned(config, ["~servers[0]", "~servers[-1]"]) == {"servers": ["b"]}
+        with pytest.raises(ValueError, match=r"Cannot delete 'servers\[5\]': index 5 is out of range for a list of 3 items"):
+            rs.apply_overrides_cloned(config, ["~servers[5]"])
+
+    def test_apply_overrides_cloned_add_assign(self):
+        """Test `key+=value` concatenating strings, adding numbers and setting missing keys"""
+        import lerna.lerna as rs
+
+        config = {"name": "run", "port": 3306, "debug": True}
+        result = rs.apply_overrides_cloned(config, ["name+=_v2", "port+=2", "workers+=4"])
+        assert result == {"name": "run_v2", "port": 3308, "debug": True, "workers": 4}
+        with pytest.raises(ValueError, match="Cannot apply '\\+=' to 'debug': cannot add an int to a bool"):
+            rs.apply_overrides_cloned(config, ["debug+=1"])
+
 
 class TestRustGlobIntegration:
     """Test Rust glob pattern integration"""
@@ -182,6 +281,133 @@ class TestRustGlobIntegration:
         # Glob class exists and is usable
         assert g is not None
 
+    def test_glob_case_insensitive(self):
+        """Test matching mixed-case names with and without case_insensitive"""
+        import lerna.lerna as rs
+
+        names = ["model_resnet", "Model_VIT", "MODEL1", "optimizer"]
+        assert rs.Glob(["Model*", "model?"]).filter(names) == ["Model_VIT"]
+
+        g = rs.Glob(["Model*", "model?"], ["*_vit"], case_insensitive=True)
+        assert g.case_insensitive
+        assert g.filter(names) == ["model_resnet", "MODEL1"]
+
+
+class TestRustJobRunnerIntegration:
+    """Test Rust job runner serialization"""
+
+    def test_config_to_yaml_redact(self):
+        """Test that redacted paths are masked in the YAML output"""
+        import lerna.lerna as rs
+
+        config = {
+            "db": {"host": "localhost", "password": "secret"},
+            "api": {"token": "abc123", "retries": 3},
+        }
+        yaml = rs.config_to_yaml(config, redact=["*.password", "*.token"])
+        assert "password: '***'" in yaml
+        assert "token: '***'" in yaml
+        assert "host: localhost" in yaml
+        assert "retries: 3" in yaml
+        assert "secret" not in yaml
+        assert "abc123" not in yaml
+        # The input config is left intact
+        assert config["db"]["password"] == "secret"
+
+    def test_config_to_yaml_quotes_ambiguous_strings(self):
+        """Test that strings YAML would read as another type are quoted"""
+        import lerna.lerna as rs
+
+        yaml = rs.config_to_yaml({"port": "3306", "flag": "true", "empty": "~", "host": "localhost"})
+        assert yaml == "empty: '~'\nflag: 'true'\nhost: localhost\nport: '3306'"
+
+    def test_save_config_redact(self, tmp_path):
+        """Test that save_config masks redacted paths in the written file"""
+        import lerna.lerna as rs
+
+        path = rs.save_config({"db": {"password": "secret"}}, "config.yaml", str(tmp_path), redact=["*.password"])
+        with open(path) as f:
+            assert f.read() == "db:\n  password: '***'"
+
+    def test_save_overrides_normalized(self, tmp_path):
+        """Test that save_overrides can sort overrides and render them canonically"""
+        import warnings
+
+        import lerna.lerna as rs
+
+        path = rs.save_overrides(["b=2", "a=1"], "overrides.yaml", str(tmp_path), sort=True)
+        with open(path) as f:
+            assert f.read() == "- a=1\n- b=2"
+
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            path = rs.save_overrides(["lr=interval(0, 1)", "db=mysql, postgres", "bad=[1"], "overrides.yaml", str(tmp_path), normalize=True)
+        assert len(caught) == 1
+        assert "'bad=[1'" in str(caught[0].message)
+        with open(path) as f:
+            assert f.read() == "- lr=interval(0.0,1.0)\n- db=mysql,postgres\n- bad=[1"
+
+    def test_compute_output_dir_groups_by_sweep_tag(self):
+        """Test that ${sweep.tag} places tagged sweep jobs under a per-tag directory"""
+        import lerna.lerna as rs
+
+        out = rs.compute_output_dir("/out/${sweep.tag}", "0", tags=["grid"])
+        assert out.replace("\\", "/").endswith("/out/grid/0")
+        out = rs.compute_output_dir("/out/${sweep.tag}", "0", tags=[])
+        assert out.replace("\\", "/").endswith("/out/0")
+
+    def test_job_status_round_trip(self):
+        """Test that job statuses round-trip through their names"""
+        import lerna.lerna as rs
+
+        for status in (rs.RustJobStatus.UNKNOWN, rs.RustJobStatus.COMPLETED, rs.RustJobStatus.FAILED, rs.RustJobStatus.RUNNING):
+            assert rs.RustJobStatus.from_str(status.name) == status
+            assert str(status) == status.name
+
+        with pytest.raises(ValueError, match="Unknown job status 'done'"):
+            rs.RustJobStatus.from_str("done")
+
+
+class TestRustJobContextGitIntegration:
+    """Test git capture in the Rust job context"""
+
+    def test_capture_git(self, tmp_path):
+        """Test that the commit is captured inside a git repo and absent outside one"""
+        import os
+        import subprocess
+
+        import lerna.lerna as rs
+
+        repo = tmp_path / "repo"
+        repo.mkdir()
+        (repo / "train.py").write_text("print('hi')\n")
+        git = ["git", "-c", "user.name=test", "-c", "user.email=test@example.com"]
+        subprocess.run([*git, "init", "-q"], cwd=repo, check=True)
+        subprocess.run([*git, "add", "train.py"], cwd=repo, check=True)
+        subprocess.run([*git, "commit", "-q", "-m", "init"], cwd=repo, check=True)
+        head = subprocess.run(["git", "rev-parse", "HEAD"], cwd=repo, check=True, capture_output=True, text=True).stdout.strip()
+
+        outside = tmp_path / "outside"
+        outside.mkdir()
+        cwd = os.getcwd()
+        try:
+            os.chdir(repo)
+            ctx = rs.RustJobContext("train", "0", 0, capture_git=True)
+            os.chdir(outside)
+            outside_ctx = rs.RustJobContext("train", "0", 0, capture_git=True)
+        finally:
+            os.chdir(cwd)
+
+        assert ctx.git_commit == head
+        assert ctx.git_dirty is False
+        path = rs.save_job_metadata(ctx, "job.yaml", str(tmp_path))
+        with open(path) as f:
+            assert f"commit: {head}" in f.read()
+
+        assert outside_ctx.git_commit is None
+        assert outside_ctx.git_dirty is None
+        assert rs.RustJobContext("train", "0", 0).git_commit is None
+
 
 class TestRustOverrideParserIntegration:
     """Test Rust override parser integration"""
@@ -213,6 +439,111 @@ class TestRustOverrideParserIntegration:
         assert ovr.key_or_group == "db"
         assert ovr.is_delete()
 
+    def test_join_argv(self):
+        """Test reassembling overrides split across argv tokens"""
+        import lerna.lerna as rs
+
+        assert rs.OverrideParser.join_argv(["db", "=", "mysql"]) == ["db=mysql"]
+        assert rs.OverrideParser.join_argv(["db=", "mysql", "x=1"]) == ["db=mysql", "x=1"]
+        assert rs.OverrideParser.join_argv(["~", "db", "+", "x=1"]) == ["~db", "+x=1"]
+        assert rs.OverrideParser.join_argv(["msg='a", "b'"]) == ["msg='a b'"]
+        assert rs.OverrideParser.join_argv(["db=mysql", "~server"]) == ["db=mysql", "~server"]
+
+    def test_parse_error_kind(self):
+        """Test that parse errors expose a machine-readable kind"""
+        import lerna.lerna as rs
+
+        parser = rs.OverrideParser()
+        with pytest.raises(rs.OverrideParseError) as excinfo:
+            parser.parse("db")
+        assert excinfo.value.kind == "ExpectedEquals"
+        assert isinstance(excinfo.value, ValueError)
+
+        with pytest.raises(ValueError) as excinfo:
+            parser.parse("x=choic(1,2)")
+        assert excinfo.value.kind == "UnknownFunction"
+        assert "Unknown function: choic" in str(excinfo.value)
+
+    def test_parse_many_collect(self):
+        """Test that parse_many_collect reports every bad override"""
+        import lerna.lerna as rs
+
+        parser = rs.OverrideParser()
+        results, errors = parser.parse_many_collect(["db=mysql", "db", "m@a,b=x", "x=choic(1,2)"])
+        assert [[(o.key_or_group, o.package) for o in r] if r else None for r in results] == [
+            [("db", None)],
+            None,
+            [("m", "a"), ("m", "b")],
+            None,
+        ]
+        assert [idx for idx, _ in errors] == [1, 3]
+        assert "Unknown function: choic" in errors[1][1]
+
+    def test_parse_to_dicts_package_list(self):
+        """Test that parse_to_dicts gives one dict per listed package"""
+        import lerna.lerna as rs
+
+        parser = rs.OverrideParser()
+        dicts = parser.parse_to_dicts("model@gpu0,gpu1=resnet")
+        assert [(d["key_or_group"], d["package"], d["input_line"]) for d in dicts] == [
+            ("model", "gpu0", "model@gpu0=resnet"),
+            ("model", "gpu1", "model@gpu1=resnet"),
+        ]
+        assert [d["package"] for d in parser.parse_to_dicts("model@gpu0=resnet")] == ["gpu0"]
+
+    def test_parse_escapes(self):
+        """Test that escape sequences in quoted strings are opt-in"""
+        import lerna.lerna as rs
+
+        assert rs.OverrideParser().parse_to_dict(r"msg='a\tb'")["value"].text == r"a\tb"
+        parser = rs.OverrideParser(escapes=True)
+        assert parser.parse_to_dict(r"msg='a\tb'")["value"].text == "a\tb"
+        assert [o.key_or_group for o in parser.parse_many([r"m@x,y='\n'"])] == ["m", "m"]
+        with pytest.raises(rs.OverrideParseError) as excinfo:
+            parser.parse(r"msg='a\qb'")
+        assert excinfo.value.kind == "InvalidEscape"
+
+
+class TestRustOverrideTypesIntegration:
+    """Test Rust override types equality and hashing"""
+
+    def test_key_equality(self):
+        """Test keys built from the same parts are equal and hash equal"""
+        import lerna.lerna as rs
+
+        key1 = rs.Key("db", "pkg")
+        key2 = rs.Key("db", "pkg")
+        assert key1 == key2
+        assert hash(key1) == hash(key2)
+        assert rs.Key("db") != rs.Key("db", "pkg")
+        assert rs.Key("db") != rs.Key("server")
+
+    def test_key_set_membership(self):
+        """Test keys can be deduplicated in a set"""
+        import lerna.lerna as rs
+
+        keys = {rs.Key("db"), rs.Key("db"), rs.Key("db", "pkg")}
+        assert len(keys) == 2
+        assert rs.Key("db") in keys
+        assert rs.Key("db", "pkg") in keys
+        assert rs.Key("server") not in keys
+
+    def test_key_repr(self):
+        """Test key repr"""
+        import lerna.lerna as rs
+
+        assert repr(rs.Key("db")) == "Key(key_or_group='db', package=None)"
+        assert repr(rs.Key("db", "pkg")) == "Key(key_or_group='db', package='pkg')"
+
+    def test_enum_hashing(self):
+        """Test override and value type enums can be used in sets"""
+        import lerna.lerna as rs
+
+        types = {rs.OverrideType.CHANGE, rs.OverrideType.CHANGE, rs.OverrideType.ADD}
+        assert types == {rs.OverrideType.CHANGE, rs.OverrideType.ADD}
+        assert hash(rs.ValueType.ELEMENT) == hash(rs.ValueType.ELEMENT)
+        assert rs.ValueType.ELEMENT in {rs.ValueType.ELEMENT}
+
 
 class TestRustValidationIntegration:
     """Test Rust validation integration"""
@@ -230,6 +561,96 @@ class TestRustValidationIntegration:
         ts_opt = rs.validation.TypeSpec.parse("Optional[int]")
         assert ts_opt is not None
 
+    def test_check_override_types(self):
+        """Test that type-changing overrides produce warnings"""
+        import lerna.lerna as rs
+
+        config = {"db": {"port": 3306}, "name": "app"}
+
+        assert rs.validation.check_override_types(config, ["db.port=5432", "name=other"]) == []
+
+        warnings = rs.validation.check_override_types(config, ["db.port=mysql"])
+        assert len(warnings) == 1
+        assert warnings[0].path == "db.port"
+        assert warnings[0].message == "Override changes type from int to str"
+
+    def test_validate_interpolations(self):
+        """Test reporting references to missing keys and unknown resolvers"""
+        import lerna.lerna as rs
+
+        config = {"db": {"host": "localhost", "url": "${db.host}:${missing.key}"}, "name": "${bogus:x}", "home": "${oc.env:HOME}"}
+        errors = rs.validation.validate_interpolations(config)
+        assert [(e.path, e.message) for e in errors] == [
+            ("db.url", "Reference '${missing.key}' points at a missing key"),
+            ("name", "Unknown resolver 'bogus' in '${bogus:x}'"),
+        ]
+        assert rs.validation.validate_interpolations({"a": 1, "b": "${a}"}) == []
+        assert rs.validation.validate_interpolations({"dir": "${now:%H}/${hydra:job.name}"}) == []
+        assert rs.validation.validate_interpolations({"name": "${bogus:x}"}, ["bogus"]) == []
+
+    def test_validate_defaults(self, tmp_path):
+        """Test detecting conflicting and missing defaults"""
+        import lerna.lerna as rs
+
+        def group_default(path):
+            return rs.ResultDefault(config_path=path, override_key=path.split("/")[0])
+
+        valid = [group_default("db/mysql"), group_default("server/dev")]
+        assert rs.validation.validate_defaults(valid) == []
+
+        errors = rs.validation.validate_defaults(valid + [group_default("db/postgres")])
+        assert len(errors) == 1
+        assert errors[0].path == "db"
+        assert errors[0].message == "Conflicting selections for 'db': 'db/mysql' and 'db/postgres'"
+
+        (tmp_path / "db").mkdir()
+        (tmp_path / "db" / "mysql.yaml").write_text("port: 3306\n")
+        repo = rs.RustConfigRepository([("main", str(tmp_path))])
+        errors = rs.validation.validate_defaults(valid, repo)
+        assert [e.path for e in errors] == ["server/dev"]
+
+    def test_repository_load_config_or(self, tmp_path):
+        """Test falling back to another config when the requested one is missing"""
+        import lerna.lerna as rs
+
+        (tmp_path / "db").mkdir()
+        (tmp_path / "db" / "base.yaml").write_text("port: 3306\n")
+        (tmp_path / "db" / "mysql.yaml").write_text("port: 3307\n")
+        repo = rs.RustConfigRepository([("main", str(tmp_path))])
+
+        assert repo.load_config_or("db/oracle", "db/base") == ({"port": 3306}, "db/base")
+        assert repo.load_config_or("db/mysql", "db/base") == ({"port": 3307}, "db/mysql")
+        assert repo.load_config_or("db/oracle", "db/sqlite") is None
+
+    def test_repository_overlay(self, tmp_path):
+        """Test that in overlay mode a config in a later directory replaces the earlier one"""
+        import lerna.lerna as rs
+
+        for name, port in (("base", 3306), ("overlay", 3307)):
+            (tmp_path / name / "db").mkdir(parents=True)
+            (tmp_path / name / "db" / "mysql.yaml").write_text(f"port: {port}\n")
+        (tmp_path / "base" / "db" / "postgres.yaml").write_text("port: 5432\n")
+        search_path = [("base", str(tmp_path / "base")), ("overlay", str(tmp_path / "overlay"))]
+
+        assert rs.RustConfigRepository(search_path).load_config("db/mysql") == {"port": 3306}
+        repo = rs.RustConfigRepository(search_path, overlay=True)
+        assert repo.load_config("db/mysql") == {"port": 3307}
+        assert repo.load_config("db/postgres") == {"port": 5432}
+        assert repo.get_group_options("db") == ["mysql", "postgres"]
+
+    def test_repository_walk(self, tmp_path):
+        """Test listing nested configs and filtering them with ** globs"""
+        import lerna.lerna as rs
+
+        for path in ("model/tiny", "model/a/b/tiny", "model/base"):
+            (tmp_path / f"{path}.yaml").parent.mkdir(parents=True, exist_ok=True)
+            (tmp_path / f"{path}.yaml").write_text("x: 1\n")
+        repo = rs.RustConfigRepository([("main", str(tmp_path))])
+
+        assert repo.walk("model") == ["a/b/tiny", "base", "tiny"]
+        assert repo.walk("", "model/**/tiny") == ["model/a/b/tiny", "model/tiny"]
+        assert repo.walk("model", "*") == ["base", "tiny"]
+
     def test_type_spec_constructors(self):
         """Test TypeSpec factory methods"""
         import lerna.lerna as rs
@@ -489,6 +910,15 @@ class TestRustPackageIntegration:
         resolver = resolver.with_package_override("_name_")
         assert resolver.resolve() == "mysql"
 
+    def test_compute_final_package(self):
+        """Test final package resolution with parent package inheritance"""
+        import lerna.lerna as rs
+
+        assert rs.package.compute_final_package("server/db", "mysql") == "server.db"
+        assert rs.package.compute_final_package("db", "mysql", "_group_._name_") == "db.mysql"
+        assert rs.package.compute_final_package("db", "mysql", "backend", parent_package="server") == "server.backend"
+        assert rs.package.compute_final_package("db", "mysql", "_global_", parent_package="server") == ""
+
     def test_parse_package_header(self):
         """Test parsing @package directive from header"""
         import lerna.lerna as rs
@@ -550,6 +980,29 @@ class TestRustMergeIntegration:
         assert result["db"]["host"] == "localhost"
         assert result["db"]["port"] == 5432
 
+    def test_merge_null(self):
+        """Test that None sets a key to None unless null_deletes is passed"""
+        import lerna.lerna as rs
+
+        base = {"a": 1, "db": {"host": "localhost", "port": 3306}}
+        other = {"a": None, "db": {"port": None}}
+
+        result = rs.merge.merge_config_dicts(base, other)
+        assert result == {"a": None, "db": {"host": "localhost", "port": None}}
+
+        result = rs.merge.merge_config_dicts(base, other, null_deletes=True)
+        assert result == {"db": {"host": "localhost"}}
+
+    def test_merge_annotation_concat(self):
+        """Test that __merge__: concat concatenates lists in its subtree only"""
+        import lerna.lerna as rs
+
+        base = {"tags": ["a"], "model": {"__merge__": "concat", "layers": [64]}}
+        other = {"tags": ["b"], "model": {"layers": [32]}}
+
+        result = rs.merge.merge_config_dicts(base, other)
+        assert result == {"tags": ["b"], "model": {"layers": [64, 32]}}
+
     def test_merge_multiple_configs(self):
         """Test merging multiple configs in order"""
         import lerna.lerna as rs
@@ -626,6 +1079,156 @@ class TestRustMergeIntegration:
         assert "c" in diff
 
 
+class TestRustConversionIntegration:
+    """Test that configs round-trip unchanged through every binding module"""
+
+    CONFIG = {
+        "none": None,
+        "flag": True,
+        "count": 3,
+        "ratio": 0.5,
+        "name": "lerna",
+        "ref": "${name}",
+        "todo": "???",
+        "items": [1, "two", [3.0, None]],
+        "db": {"host": "localhost", "port": 3306, "opts": {"ssl": False}},
+    }
+
+    def test_round_trip_merge(self):
+        import lerna.lerna as rs
+
+        assert rs.merge.merge_config_dicts(self.CONFIG, {}) == self.CONFIG
+        assert rs.merge.merge_multiple_configs([self.CONFIG]) == self.CONFIG
+
+    def test_round_trip_numeric_lists(self):
+        import lerna.lerna as rs
+
+        config = {
+            "ints": list(range(100_000)),
+            "floats": [i * 0.5 for i in range(1000)],
+            "flags": [True, False, 1, 0.0],
+            "big": [2**70, 1],
+        }
+        result = rs.merge.merge_config_dicts(config, {})
+
+        assert result == config
+        assert [type(v) for v in result["flags"]] == [bool, bool, int, float]
+        assert type(result["big"][0]) is float
+
+    def test_round_trip_apply_overrides(self):
+        import lerna.lerna as rs
+
+        assert rs.apply_overrides_cloned(self.CONFIG, []) == self.CONFIG
+
+    def test_round_trip_config_store(self):
+        import lerna.lerna as rs
+
+        store = rs.RustConfigStore()
+        store.store("conversion_round_trip", self.CONFIG, group="test_conversions")
+        node = store.load("test_conversions/conversion_round_trip")
+
+        assert node.node == self.CONFIG
+
+
+class TestRustConfigStoreIntegration:
+    """Test Rust ConfigStore queries"""
+
+    def test_list_by_package(self):
+        """Test listing a group's configs by the package they were stored under"""
+        import lerna.lerna as rs
+
+        store = rs.RustConfigStore()
+        store.store("mysql", {"port": 3306}, group="test_by_package/db", package="primary")
+        store.store("postgres", {"port": 5432}, group="test_by_package/db", package="replica")
+        store.store("sqlite", {"path": "db.sqlite"}, group="test_by_package/db", package="replica")
+
+        assert store.list_by_package("test_by_package/db", "primary") == ["mysql.yaml"]
+        assert store.list_by_package("test_by_package/db", "replica") == ["postgres.yaml", "sqlite.yaml"]
+        assert store.list_by_package("test_by_package/db", "backup") == []
+
+
+class TestRustConfigStatsIntegration:
+    """Test config size and shape introspection"""
+
+    def test_config_stats(self):
+        import lerna.lerna as rs
+
+        config = {"a": 1, "b": {"c": [1, 2, {"d": "${a}"}], "e": "???"}}
+        assert rs.config_stats(config) == {
+            "dicts": 3,
+            "lists": 1,
+            "scalars": 3,
+            "leaves": 5,
+            "max_depth": 4,
+            "interpolations": 1,
+            "missing": 1,
+        }
+
+    def test_infer_schema(self):
+        import lerna.lerna as rs
+
+        config = {"db": {"host": "localhost", "port": 3306, "ssl": False}, "ports": [80, 443], "mixed": [1, "a", 0.5]}
+        assert rs.infer_schema(config) == {
+            "db": {"host": "str", "port": "int", "ssl": "bool"},
+            "ports": ["int"],
+            "mixed": ["any"],
+        }
+
+    def test_msgpack_round_trip(self):
+        import lerna.lerna as rs
+
+        rs.register_enum("TestColor", ["RED", "GREEN"])
+        config = {
+            "db": {"host": "localhost", "port": 3306, "ratio": 0.5, "url": "${db.host}:${db.port}", "password": "???"},
+            "color": rs.make_enum("TestColor", "RED"),
+            "tags": [None, True, "a", []],
+        }
+        data = rs.to_msgpack(config)
+        assert isinstance(data, bytes)
+        assert rs.from_msgpack(data) == config
+        with pytest.raises(ValueError, match="Invalid MessagePack config"):
+            rs.from_msgpack(data[:-1])
+
+
+class TestRustEnumIntegration:
+    """Test enum values in configs"""
+
+    def test_make_enum(self):
+        import lerna.lerna as rs
+
+        rs.register_enum("TestColor", ["RED", "GREEN"])
+        red = rs.make_enum("TestColor", "RED")
+        assert red.type_name == "TestColor"
+        assert red.value == "RED"
+        assert rs.enum_values("TestColor") == ["RED", "GREEN"]
+        assert rs.enum_values("TestMissingEnum") is None
+
+        with pytest.raises(ValueError, match="Invalid value 'BLUE' for enum TestColor"):
+            rs.make_enum("TestColor", "BLUE")
+
+    def test_load_enum_tag(self):
+        import lerna.lerna as rs
+
+        rs.register_enum("TestColor", ["RED", "GREEN"])
+        config = rs.parse_yaml("color: !enum TestColor GREEN\n")
+        assert config["color"] == rs.make_enum("TestColor", "GREEN")
+
+        with pytest.raises(RuntimeError, match="Invalid value 'BLUE'"):
+            rs.parse_yaml("color: !enum TestColor BLUE\n")
+
+    def test_merge_enums(self):
+        import lerna.lerna as rs
+
+        rs.register_enum("TestColor", ["RED", "GREEN"])
+        base = {"color": rs.make_enum("TestColor", "RED")}
+
+        result = rs.merge.merge_config_dicts(base, {"color": rs.make_enum("TestColor", "GREEN")})
+        assert result["color"] == rs.make_enum("TestColor", "GREEN")
+
+        result = rs.merge.merge_config_dicts(base, {"color": "GREEN"})
+        assert result["color"] == rs.make_enum("TestColor", "GREEN")
+
+
 class TestRustSearchPathIntegration:
     """Test Rust search path integration"""
 
@@ -678,6 +1281,24 @@ class TestRustSearchPathIntegration:
 
         assert query.matches(elem)
 
+    def test_search_path_from_config(self):
+        """Test reading hydra.searchpath entries from a config"""
+        import lerna.lerna as rs
+
+        config = {"hydra": {"searchpath": ["pkg://my.configs", "file:///abs/path"]}}
+        entries = rs.search_path_from_config(config)
+
+        assert [e.path for e in entries] == ["pkg://my.configs", "file:///abs/path"]
+        assert [e.scheme() for e in entries] == ["pkg", "file"]
+        assert entries[0].provider == "hydra.searchpath in main"
+
+    def test_search_path_from_config_invalid_entry(self):
+        """Test that an invalid hydra.searchpath entry raises ValueError"""
+        import lerna.lerna as rs
+
+        with pytest.raises(ValueError, match="http://nope"):
+            rs.search_path_from_config({"hydra": {"searchpath": ["http://nope"]}})
+
     def test_config_search_path_append(self):
         """Test appending to search path"""
         import lerna.lerna as rs
@@ -925,6 +1546,35 @@ class TestRustEnvIntegration:
 
         del os.environ["GET_ENV_TEST"]
 
+    def test_load_dotenv(self, tmp_path):
+        """Test loading a .env file with quotes and comments"""
+        import os
+
+        import lerna.lerna as rs
+
+        dotenv = tmp_path / ".env"
+        dotenv.write_text('# settings\nexport DOTENV_HOST="db host"  # quoted\nDOTENV_PORT=5432 # unquoted\nDOTENV_PATH=\'/tmp\'\n')
+
+        assert rs.env.load_dotenv(str(dotenv)) == 3
+        assert os.environ["DOTENV_HOST"] == "db host"
+        assert rs.env.get_env("DOTENV_HOST") == "db host"
+        assert rs.env.get_env("DOTENV_PORT") == "5432"
+        assert rs.env.resolve_env_string("${oc.env:DOTENV_PATH}") == "/tmp"
+
+        # Variables already set are only replaced with override=True
+        dotenv.write_text("DOTENV_PORT=6543\n")
+        assert rs.env.load_dotenv(str(dotenv)) == 0
+        assert os.environ["DOTENV_PORT"] == "5432"
+        assert rs.env.load_dotenv(str(dotenv), override=True) == 1
+        assert os.environ["DOTENV_PORT"] == "6543"
+        assert rs.env.get_env("DOTENV_PORT") == "6543"
+
+        dotenv.write_text("NOT VALID\n")
+        with pytest.raises(ValueError, match="line 1"):
+            rs.env.load_dotenv(str(dotenv))
+        with pytest.raises(OSError):
+            rs.env.load_dotenv(str(tmp_path / "missing.env"))
+
     def test_is_env_set_function(self):
         """Test is_env_set module function"""
         import os
@@ -999,6 +1649,14 @@ class TestRustConfigUtilsIntegration:
         assert rs.normalize_file_name("config.yaml") == "config.yaml"
         assert rs.normalize_file_name("config.yml") == "config.yml"
 
+    def test_normalize_output_file_name(self):
+        """Test sanitizing output file names while keeping their extension"""
+        import lerna.lerna as rs
+
+        assert rs.normalize_output_file_name("my model.yaml") == "my_model.yaml"
+        assert rs.normalize_output_file_name("my_model") == "my_model"
+        assert rs.normalize_output_file_name("lr=0.1.out", preserve_extensions=[".out"]) == "lr_0_1.out"
+
     def test_get_valid_filename(self):
         """Test getting valid filenames"""
         import lerna.lerna as rs
@@ -1007,6 +1665,32 @@ class TestRustConfigUtilsIntegration:
         assert rs.get_valid_filename("file@123") == "file123"
         assert rs.get_valid_filename("test-file.py") == "test-file.py"
 
+    def test_get_valid_filename_opts(self):
+        """Test custom replacement characters and length caps"""
+        import lerna.lerna as rs
+
+        assert rs.get_valid_filename_opts("my app") == "my_app"
+        assert rs.get_valid_filename_opts("file@123", replacement="-") == "file-123"
+
+        a = rs.get_valid_filename_opts("lr=0.1,batch_size=32,model=resnet", max_len=20)
+        b = rs.get_valid_filename_opts("lr=0.1,batch_size=64,model=resnet", max_len=20)
+        assert len(a) == len(b) == 20
+        assert a != b
+
+        with pytest.raises(ValueError, match="not a valid filename character"):
+            rs.get_valid_filename_opts("my app", replacement="/")
+
+    def test_validate_user_key(self):
+        """Test rejecting reserved Hydra keys"""
+        import lerna.lerna as rs
+
+        for key in ["hydra", "defaults", "_self_"]:
+            assert not rs.is_valid_user_key(key)
+            with pytest.raises(ValueError, match="reserved name"):
+                rs.validate_user_key(key)
+        assert rs.is_valid_user_key("db.host")
+        rs.validate_user_key("db.host")
+
     def test_sanitize_path_component(self):
         """Test sanitizing path components"""
         import lerna.lerna as rs
@@ -58,6 +58,79 @@ class TestRustBasicSweeper:
 
         assert len(results) == 4
 
+    def test_basic_sweeper_constraint(self):
+        """A constraint drops the combinations it rejects."""
+        sweeper = RustBasicSweeper()
+        sweeper.setup({}, "test_task")
+
+        seen = []
+
+        def constraint(combination):
+            seen.append(combination)
+            return not (combination["optimizer"] == "sgd" and combination["beta2"] == "0.9")
+
+        sweeper.add_constraint(constraint)
+        results = sweeper.sweep(["optimizer=sgd,adam", "beta2=0.9,0.99"])
+
+        assert len(results) == 3
+        assert len(seen) == 4
+        assert {"optimizer": "sgd", "beta2": "0.9"} in seen
+
+    def test_basic_sweeper_constraint_error(self):
+        """An exception in a constraint fails the sweep."""
+        sweeper = RustBasicSweeper()
+        sweeper.setup({}, "test_task")
+
+        def constraint(combination):
+            raise KeyError("missing")
+
+        sweeper.add_constraint(constraint)
+        with pytest.raises(RuntimeError, match="Sweep constraint failed"):
+            sweeper.sweep(["a=1,2"])
+
+    def test_basic_sweeper_resume_from(self, tmp_path):
+        """A resumed sweep skips the jobs in the checkpoint and records the rest."""
+        checkpoint = tmp_path / "completed.txt"
+        checkpoint.write_text("0\n1\n")
+
+        sweeper = RustBasicSweeper()
+        sweeper.setup({}, "test_task")
+        sweeper.resume_from(str(checkpoint))
+        assert sweeper.completed == [0, 1]
+
+        results = sweeper.sweep(["a=1,2,3,4"])
+
+        assert [r.job_name for r in results] == ["job_2", "job_3"]
+        assert checkpoint.read_text().split() == ["0", "1", "2", "3"]
+
+    def test_basic_sweeper_progress(self):
+        """The progress callback runs once per completed job, in order."""
+        sweeper = RustBasicSweeper(max_batch_size=2)
+        sweeper.setup({}, "test_task")
+
+        calls = []
+
+        def on_progress(idx, total, job_return):
+            calls.append((idx, total, job_return.job_name))
+
+        sweeper.set_progress(on_progress)
+        results = sweeper.sweep(["a=1,2,3"])
+
+        assert len(results) == 3
+        assert [(idx, total) for idx, total, _ in calls] == [(0, 3), (1, 3), (2, 3)]
+        assert [name for _, _, name in calls] == [r.job_name for r in results]
+
+    def test_basic_sweeper_seed(self):
+        """BasicSweeper accepts a seed for shuffled ranges."""
+        assert RustBasicSweeper().seed is None
+        sweeper = RustBasicSweeper(seed=7)
+        sweeper.setup({}, "test_task")
+
+        results = sweeper.sweep(["a=shuffle(range(0,5))"])
+
+        assert sweeper.seed == 7
+        assert len(results) == 5
+
     def test_basic_sweeper_three_params(self):
         """BasicSweeper handles multiple parameters."""
         sweeper = RustBasicSweeper()
@@ -78,6 +151,40 @@ class TestRustBasicSweeper:
 
         assert len(results) == 2
 
+    def test_basic_sweeper_zip(self):
+        """BasicSweeper pairs values position-wise in zip mode."""
+        sweeper = RustBasicSweeper(combination="zip")
+        sweeper.setup({}, "test_task")
+        assert sweeper.combination == "zip"
+
+        # 3 x 3 zipped = 3 combinations
+        results = sweeper.sweep(["a=1,2,3", "b=x,y,z"])
+
+        assert len(results) == 3
+
+    def test_basic_sweeper_zip_length_mismatch(self):
+        """BasicSweeper zip mode rejects parameters of different lengths."""
+        sweeper = RustBasicSweeper(combination="zip")
+        sweeper.setup({}, "test_task")
+
+        with pytest.raises(RuntimeError, match="different lengths"):
+            sweeper.sweep(["a=1,2,3", "b=x,y"])
+
+    def test_basic_sweeper_zip_groups(self):
+        """BasicSweeper zips only the listed groups and crosses the rest."""
+        sweeper = RustBasicSweeper(zip_groups=[["a", "b"]])
+        sweeper.setup({}, "test_task")
+
+        # (a, b) zipped = 3, crossed with c = 2 -> 6 combinations
+        results = sweeper.sweep(["a=1,2,3", "b=x,y,z", "c=p,q"])
+
+        assert len(results) == 6
+
+    def test_basic_sweeper_unknown_combination(self):
+        """BasicSweeper rejects unknown combination modes."""
+        with pytest.raises(ValueError, match="Unknown sweep combination"):
+            RustBasicSweeper(combination="product")
+
     def test_basic_sweeper_empty_args(self):
         """BasicSweeper handles empty arguments."""
         sweeper = RustBasicSweeper()
@@ -88,6 +195,16 @@ class TestRustBasicSweeper:
         # Empty sweep should still produce one job
         assert len(results) == 1
 
+    def test_basic_sweeper_sweep_tag_output_dir(self):
+        """BasicSweeper places tagged sweeps under ${sweep.tag}."""
+        sweeper = RustBasicSweeper()
+        config = {"hydra": {"sweep": {"dir": "/out/${sweep.tag}", "subdir": "${hydra.job.num}"}}}
+        sweeper.setup(config, "test_task")
+
+        results = sweeper.sweep(["db=tag(grid,choice(a,b))"])
+
+        assert [r.output_dir for r in results] == ["/out/grid/0", "/out/grid/1"]
+
 
 class TestSweeperManager:
     """Test SweeperManager - manages sweeper instances."""
@@ -142,6 +259,36 @@ class TestSweeperManager:
         assert manager.has_sweeper()
 
 
+class TestSweeperRegistry:
+    """Test registering sweepers by name and activating one."""
+
+    def test_register_and_activate(self):
+        """The active sweeper is chosen by its registered name."""
+
+        class CountingSweeper:
+            def __init__(self, jobs):
+                self.jobs = jobs
+
+            def sweep(self, arguments):
+                return [
+                    JobReturn(job_name=f"job_{i}", task_name="t", working_dir="/", output_dir="/out")
+                    for i in range(self.jobs)
+                ]
+
+        manager = SweeperManager()
+        manager.register("one", CountingSweeper(1))
+        manager.register("three", CountingSweeper(3))
+        assert manager.available_names() == ["one", "three"]
+
+        manager.activate("three")
+        assert len(manager.sweep([])) == 3
+        manager.activate("one")
+        assert len(manager.sweep([])) == 1
+
+        with pytest.raises(RuntimeError, match="Unknown sweeper 'optuna'"):
+            manager.activate("optuna")
+
+
 class TestPythonSweeperIntegration:
     """Test Python sweeper integration with Rust manager."""
 
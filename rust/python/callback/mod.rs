@@ -9,15 +9,10 @@ use lerna::callback::{
     Callback, CallbackManager, CallbackResult, JobReturn, LoggingCallback, NoOpCallback,
 };
 use lerna::config::ConfigDict;
-use lerna::ConfigValue;
+use lerna::JobStatus;
 
-fn config_to_py<'py>(py: Python<'py>, config: &ConfigDict) -> PyResult<Bound<'py, PyDict>> {
-    let dict = PyDict::new(py);
-    for (key, value) in config.iter() {
-        dict.set_item(key, value.to_string())?;
-    }
-    Ok(dict)
-}
+use crate::conversions::{config_dict_to_py, py_dict_to_config_dict};
+use crate::job_runner::PyJobStatus;
 
 fn kwargs_to_py<'py>(
     py: Python<'py>,
@@ -46,12 +41,16 @@ pub struct PyJobReturn {
     pub task_name: String,
     #[pyo3(get, set)]
     pub status_code: i32,
+    #[pyo3(get, set)]
+    pub stdout: Option<String>,
+    #[pyo3(get, set)]
+    pub stderr: Option<String>,
 }
 
 #[pymethods]
 impl PyJobReturn {
     #[new]
-    #[pyo3(signature = (job_name, task_name, working_dir, output_dir, status_code=0, return_value=None))]
+    #[pyo3(signature = (job_name, task_name, working_dir, output_dir, status_code=0, return_value=None, stdout=None, stderr=None))]
     fn new(
         job_name: String,
         task_name: String,
@@ -59,6 +58,8 @@ impl PyJobReturn {
         output_dir: String,
         status_code: i32,
         return_value: Option<String>,
+        stdout: Option<String>,
+        stderr: Option<String>,
     ) -> Self {
         Self {
             return_value,
@@ -67,12 +68,26 @@ impl PyJobReturn {
             job_name,
             task_name,
             status_code,
+            stdout,
+            stderr,
         }
     }
 
     fn is_success(&self) -> bool {
         self.status_code == 0
     }
+
+    /// Status name derived from `status_code`: "COMPLETED" for 0, else "FAILED"
+    #[getter]
+    fn status(&self) -> &'static str {
+        JobStatus::from_status_code(self.status_code).as_str()
+    }
+
+    /// Status derived from `status_code`, as a RustJobStatus
+    #[getter]
+    fn job_status(&self) -> PyJobStatus {
+        PyJobStatus(JobStatus::from_status_code(self.status_code))
+    }
 }
 
 impl From<&JobReturn> for PyJobReturn {
@@ -84,6 +99,8 @@ impl From<&JobReturn> for PyJobReturn {
             job_name: jr.job_name.clone(),
             task_name: jr.task_name.clone(),
             status_code: jr.status_code,
+            stdout: jr.stdout.clone(),
+            stderr: jr.stderr.clone(),
         }
     }
 }
@@ -97,6 +114,8 @@ impl From<&PyJobReturn> for JobReturn {
             job_name: pj.job_name.clone(),
             task_name: pj.task_name.clone(),
             status_code: pj.status_code,
+            stdout: pj.stdout.clone(),
+            stderr: pj.stderr.clone(),
         }
     }
 }
@@ -118,7 +137,7 @@ impl PyCallbackWrapper {
         kwargs: &HashMap<String, String>,
     ) -> CallbackResult<()> {
         Python::attach(|py| {
-            let py_config = config_to_py(py, config).map_err(|e| e.to_string())?;
+            let py_config = config_dict_to_py(py, config).map_err(|e| e.to_string())?;
             let py_kwargs = kwargs_to_py(py, kwargs).map_err(|e| e.to_string())?;
             let callback = self.py_callback.bind(py);
             if callback.hasattr(method).map_err(|e| e.to_string())? {
@@ -137,7 +156,7 @@ impl PyCallbackWrapper {
         kwargs: &HashMap<String, String>,
     ) -> CallbackResult<()> {
         Python::attach(|py| {
-            let py_config = config_to_py(py, config).map_err(|e| e.to_string())?;
+            let py_config = config_dict_to_py(py, config).map_err(|e| e.to_string())?;
             let py_job_return = PyJobReturn::from(job_return);
             let py_kwargs = kwargs_to_py(py, kwargs).map_err(|e| e.to_string())?;
             let callback = self.py_callback.bind(py);
@@ -157,7 +176,7 @@ impl PyCallbackWrapper {
         overrides: &[String],
     ) -> CallbackResult<()> {
         Python::attach(|py| {
-            let py_config = config_to_py(py, config).map_err(|e| e.to_string())?;
+            let py_config = config_dict_to_py(py, config).map_err(|e| e.to_string())?;
             let py_config_name = config_name.map(|s| s.to_string());
             let py_overrides: Vec<String> = overrides.to_vec();
             let callback = self.py_callback.bind(py);
@@ -175,6 +194,18 @@ impl PyCallbackWrapper {
             Ok(())
         })
     }
+
+    fn call_error(&self, context: &str, message: &str) -> CallbackResult<()> {
+        Python::attach(|py| {
+            let callback = self.py_callback.bind(py);
+            if callback.hasattr("on_error").map_err(|e| e.to_string())? {
+                callback
+                    .call_method1("on_error", (context, message))
+                    .map_err(|e| e.to_string())?;
+            }
+            Ok(())
+        })
+    }
 }
 
 impl Callback for PyCallbackWrapper {
@@ -229,6 +260,9 @@ impl Callback for PyCallbackWrapper {
     ) -> CallbackResult<()> {
         self.call_compose_config(config, config_name, overrides)
     }
+    fn on_error(&self, context: &str, message: &str) -> CallbackResult<()> {
+        self.call_error(context, message)
+    }
 }
 
 unsafe impl Send for PyCallbackWrapper {}
@@ -240,6 +274,13 @@ pub struct PyCallbackManager {
     inner: CallbackManager,
 }
 
+impl PyCallbackManager {
+    /// The wrapped Rust callback manager
+    pub fn inner(&self) -> &CallbackManager {
+        &self.inner
+    }
+}
+
 #[pymethods]
 impl PyCallbackManager {
     #[new]
@@ -266,7 +307,7 @@ impl PyCallbackManager {
 
     /// Trigger on_run_start for all callbacks
     fn on_run_start(&self, config: Bound<'_, PyDict>) -> PyResult<()> {
-        let rust_config = py_dict_to_config(&config)?;
+        let rust_config = py_dict_to_config_dict(&config)?;
         self.inner
             .on_run_start(&rust_config, &HashMap::new())
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
@@ -274,7 +315,7 @@ impl PyCallbackManager {
 
     /// Trigger on_run_end for all callbacks
     fn on_run_end(&self, config: Bound<'_, PyDict>) -> PyResult<()> {
-        let rust_config = py_dict_to_config(&config)?;
+        let rust_config = py_dict_to_config_dict(&config)?;
         self.inner
             .on_run_end(&rust_config, &HashMap::new())
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
@@ -282,7 +323,7 @@ impl PyCallbackManager {
 
     /// Trigger on_job_start for all callbacks
     fn on_job_start(&self, config: Bound<'_, PyDict>) -> PyResult<()> {
-        let rust_config = py_dict_to_config(&config)?;
+        let rust_config = py_dict_to_config_dict(&config)?;
         self.inner
             .on_job_start(&rust_config, &HashMap::new())
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
@@ -290,13 +331,20 @@ impl PyCallbackManager {
 
     /// Trigger on_job_end for all callbacks
     fn on_job_end(&self, config: Bound<'_, PyDict>, job_return: &PyJobReturn) -> PyResult<()> {
-        let rust_config = py_dict_to_config(&config)?;
+        let rust_config = py_dict_to_config_dict(&config)?;
         let rust_jr = JobReturn::from(job_return);
         self.inner
             .on_job_end(&rust_config, &rust_jr, &HashMap::new())
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
     }
 
+    /// Trigger on_error for all callbacks
+    fn on_error(&self, context: &str, message: &str) -> PyResult<()> {
+        self.inner
+            .on_error(context, message)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
     /// Number of registered callbacks
     fn len(&self) -> usize {
         self.inner.len()
@@ -313,17 +361,6 @@ impl PyCallbackManager {
     }
 }
 
-/// Convert Python dict to ConfigDict
-fn py_dict_to_config(dict: &Bound<'_, PyDict>) -> PyResult<ConfigDict> {
-    let mut config = ConfigDict::new();
-    for (key, value) in dict.iter() {
-        let key_str: String = key.extract()?;
-        let value_str: String = value.str()?.extract()?;
-        config.insert(key_str, ConfigValue::String(value_str));
-    }
-    Ok(config)
-}
-
 /// Register callback classes with the Python module
 pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyJobReturn>()?;
@@ -1,90 +1,23 @@
 // Copyright (c) Facebook, Inc. and its affiliates. All Rights Reserved
 //! PyO3 bindings for configuration loading
 
-use pyo3::exceptions::PyRuntimeError;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
+use pyo3::types::{PyBytes, PyDict, PyList};
 
-use lerna::config::interpolation::{resolve, ResolverContext};
-use lerna::config::value::{ConfigDict, ConfigValue};
+use lerna::config::interpolation::{resolve, resolve_path as rust_resolve_path, ResolverContext};
+use lerna::config::value::ConfigValue;
 use lerna::config::{
     CachingConfigRepository, ConfigRepository as RustConfigRepository,
     SearchPathElement as RustSearchPathElement,
 };
 use lerna::config::{ConfigLoader as RustConfigLoader, SearchPathEntry as RustSearchPathEntry};
-use lerna::ObjectType;
-
-/// Convert ConfigValue to a Python object
-fn config_value_to_py(py: Python, value: &ConfigValue) -> PyResult<Py<PyAny>> {
-    match value {
-        ConfigValue::Null => Ok(py.None()),
-        ConfigValue::Bool(b) => Ok((*b).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::Int(i) => Ok((*i).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::Float(f) => Ok((*f).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::String(s) => Ok(s.as_str().into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::Interpolation(s) => {
-            Ok(s.as_str().into_pyobject(py)?.to_owned().into_any().unbind())
-        }
-        ConfigValue::Missing => {
-            // Return the string "???" to represent missing values
-            Ok("???".into_pyobject(py)?.to_owned().into_any().unbind())
-        }
-        ConfigValue::List(items) => {
-            let list = PyList::empty(py);
-            for item in items {
-                list.append(config_value_to_py(py, item)?)?;
-            }
-            Ok(list.into_any().unbind())
-        }
-        ConfigValue::Dict(dict) => config_dict_to_py(py, dict),
-    }
-}
+use lerna::merge::try_apply_overrides_cloned as rust_try_apply_overrides_cloned;
+use lerna::{Glob, ObjectType, OverrideParser};
 
-/// Convert ConfigDict to a Python dict
-fn config_dict_to_py(py: Python, dict: &ConfigDict) -> PyResult<Py<PyAny>> {
-    let py_dict = PyDict::new(py);
-    for (key, value) in dict.iter() {
-        py_dict.set_item(key, config_value_to_py(py, value)?)?;
-    }
-    Ok(py_dict.into_any().unbind())
-}
-
-/// Convert a Python object to ConfigValue
-fn py_to_config_value(py: Python, obj: &Bound<'_, PyAny>) -> PyResult<ConfigValue> {
-    if obj.is_none() {
-        Ok(ConfigValue::Null)
-    } else if let Ok(b) = obj.extract::<bool>() {
-        Ok(ConfigValue::Bool(b))
-    } else if let Ok(i) = obj.extract::<i64>() {
-        Ok(ConfigValue::Int(i))
-    } else if let Ok(f) = obj.extract::<f64>() {
-        Ok(ConfigValue::Float(f))
-    } else if let Ok(s) = obj.extract::<String>() {
-        if s == "???" {
-            Ok(ConfigValue::Missing)
-        } else {
-            // Always store as String - interpolation resolution handles ${...}
-            Ok(ConfigValue::String(s))
-        }
-    } else if let Ok(list) = obj.cast::<PyList>() {
-        let mut items = Vec::new();
-        for item in list.iter() {
-            items.push(py_to_config_value(py, &item)?);
-        }
-        Ok(ConfigValue::List(items))
-    } else if let Ok(dict) = obj.cast::<PyDict>() {
-        let mut config_dict = ConfigDict::new();
-        for (key, value) in dict.iter() {
-            if let Ok(k) = key.extract::<String>() {
-                config_dict.insert(k, py_to_config_value(py, &value)?);
-            }
-        }
-        Ok(ConfigValue::Dict(config_dict))
-    } else {
-        // Fallback to string representation
-        Ok(ConfigValue::String(obj.str()?.to_string()))
-    }
-}
+use crate::callback::PyCallbackManager;
+use crate::conversions::{config_dict_to_py, config_value_to_py, py_to_config_value};
+use crate::parser::parse_error_to_py;
 
 /// A search path entry for config loading
 #[pyclass(name = "SearchPathEntry")]
@@ -205,11 +138,63 @@ impl PyConfigLoader {
     }
 }
 
+/// A value of a registered enum type, as loaded from `!enum Type VALUE`
+#[pyclass(name = "EnumValue", frozen)]
+#[derive(Clone)]
+pub struct PyEnumValue {
+    #[pyo3(get)]
+    pub type_name: String,
+    #[pyo3(get)]
+    pub value: String,
+}
+
+#[pymethods]
+impl PyEnumValue {
+    fn __eq__(&self, other: &Self) -> bool {
+        self.type_name == other.type_name && self.value == other.value
+    }
+
+    fn __repr__(&self) -> String {
+        format!("EnumValue({}.{})", self.type_name, self.value)
+    }
+}
+
+/// Register an enum type with its allowed values, replacing any earlier one
+#[pyfunction]
+fn register_enum(type_name: &str, values: Vec<String>) {
+    let values: Vec<&str> = values.iter().map(|v| v.as_str()).collect();
+    lerna::config::enums::instance().register(type_name, &values);
+}
+
+/// Allowed values of a registered enum type, or None if it is not registered
+#[pyfunction]
+fn enum_values(type_name: &str) -> Option<Vec<String>> {
+    lerna::config::enums::instance().allowed_values(type_name)
+}
+
+/// Build an enum value; raises ValueError for an unknown type or value
+#[pyfunction]
+fn make_enum(type_name: &str, value: &str) -> PyResult<PyEnumValue> {
+    lerna::config::enums::instance()
+        .make(type_name, value)
+        .map_err(|e| PyValueError::new_err(e.message))?;
+    Ok(PyEnumValue {
+        type_name: type_name.to_string(),
+        value: value.to_string(),
+    })
+}
+
 /// Parse a YAML string into a Python dict
+///
+/// With `base_dir`, `!include path.yaml` tags are resolved relative to it.
 #[pyfunction]
-fn parse_yaml(py: Python, content: &str) -> PyResult<Py<PyAny>> {
-    let config =
-        lerna::config::parse_yaml(content).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+#[pyo3(signature = (content, base_dir=None))]
+fn parse_yaml(py: Python, content: &str, base_dir: Option<&str>) -> PyResult<Py<PyAny>> {
+    let config = match base_dir {
+        Some(dir) => lerna::config::parse_yaml_with_includes(content, std::path::Path::new(dir)),
+        None => lerna::config::parse_yaml(content),
+    }
+    .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
     config_value_to_py(py, &config)
 }
 
@@ -221,6 +206,14 @@ fn load_yaml_file(py: Python, path: &str) -> PyResult<Py<PyAny>> {
     config_value_to_py(py, &config)
 }
 
+/// Load every .yaml file in a directory, deep-merged in file name order
+#[pyfunction]
+fn load_config_dir(py: Python, path: &str) -> PyResult<Py<PyAny>> {
+    let config = lerna::config::load_config_dir(std::path::Path::new(path))
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    config_value_to_py(py, &config)
+}
+
 /// Resolve interpolations in a config dict
 ///
 /// This resolves ${...} references in the config values.
@@ -229,7 +222,7 @@ fn load_yaml_file(py: Python, path: &str) -> PyResult<Py<PyAny>> {
 #[pyfunction]
 fn resolve_interpolations(py: Python, config: Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
     // Convert Python config to Rust
-    let config_value = py_to_config_value(py, &config)?;
+    let config_value = py_to_config_value(&config)?;
 
     // Ensure it's a dict
     let dict = match &config_value {
@@ -245,6 +238,90 @@ fn resolve_interpolations(py: Python, config: Bound<'_, PyAny>) -> PyResult<Py<P
     config_value_to_py(py, &resolved)
 }
 
+/// Resolve interpolations only in the subtree at a dotted path
+///
+/// The rest of the config is returned with its interpolations unresolved.
+#[pyfunction]
+fn resolve_path(py: Python, config: Bound<'_, PyAny>, path: &str) -> PyResult<Py<PyAny>> {
+    let config_value = py_to_config_value(&config)?;
+    let dict = match &config_value {
+        ConfigValue::Dict(d) => d.clone(),
+        _ => return Err(PyRuntimeError::new_err("Config must be a dictionary")),
+    };
+
+    let ctx = ResolverContext::new(&dict);
+    let resolved = rust_resolve_path(&config_value, path, &ctx)
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    config_value_to_py(py, &resolved)
+}
+
+/// Count the dicts, lists and leaves of a config and measure its depth
+///
+/// Returns a dict with keys dicts, lists, scalars, leaves, max_depth,
+/// interpolations and missing.
+#[pyfunction]
+fn config_stats<'py>(py: Python<'py>, config: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyDict>> {
+    let stats = py_to_config_value(&config)?.stats();
+    let dict = PyDict::new(py);
+    dict.set_item("dicts", stats.dicts)?;
+    dict.set_item("lists", stats.lists)?;
+    dict.set_item("scalars", stats.scalars)?;
+    dict.set_item("leaves", stats.leaves)?;
+    dict.set_item("max_depth", stats.max_depth)?;
+    dict.set_item("interpolations", stats.interpolations)?;
+    dict.set_item("missing", stats.missing)?;
+    Ok(dict)
+}
+
+/// Infer a skeleton of a config's leaf types, e.g. `{"port": "int"}`
+///
+/// Lists become a one-item list of their element type, `"any"` when mixed.
+#[pyfunction]
+fn infer_schema(py: Python, config: Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+    config_value_to_py(py, &py_to_config_value(&config)?.infer_schema())
+}
+
+/// Serialize a config value to MessagePack bytes
+#[pyfunction]
+fn to_msgpack<'py>(py: Python<'py>, config: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyBytes>> {
+    Ok(PyBytes::new(py, &py_to_config_value(&config)?.to_msgpack()))
+}
+
+/// Deserialize a config value from bytes written by `to_msgpack`
+///
+/// Raises ValueError if the bytes are not a valid encoding.
+#[pyfunction]
+fn from_msgpack(py: Python, data: &[u8]) -> PyResult<Py<PyAny>> {
+    let value = ConfigValue::from_msgpack(data).map_err(|e| PyValueError::new_err(e.message))?;
+    config_value_to_py(py, &value)
+}
+
+/// Apply override strings to a copy of a config dict
+///
+/// The input dict is left unchanged; the returned dict reflects the overrides.
+/// Raises ValueError for an override that cannot be applied: a delete or set
+/// whose list index is out of range, a set whose parent keys are missing
+/// (add them with `+key=value`), or a `+=` whose value cannot be added to the
+/// current one.
+#[pyfunction]
+fn apply_overrides_cloned(
+    py: Python,
+    config: Bound<'_, PyAny>,
+    overrides: Vec<String>,
+) -> PyResult<Py<PyAny>> {
+    let config_value = py_to_config_value(&config)?;
+    if !matches!(config_value, ConfigValue::Dict(_)) {
+        return Err(PyRuntimeError::new_err("Config must be a dictionary"));
+    }
+
+    let refs: Vec<&str> = overrides.iter().map(|s| s.as_str()).collect();
+    let parsed = OverrideParser::parse_many(&refs).map_err(parse_error_to_py)?;
+
+    let result = rust_try_apply_overrides_cloned(&config_value, &parsed)
+        .map_err(|e| PyValueError::new_err(e.message))?;
+    config_value_to_py(py, &result)
+}
+
 /// Compose a configuration entirely in Rust
 ///
 /// This loads a config file, processes defaults, applies overrides,
@@ -257,25 +334,32 @@ fn compose_config(
     config_name: Option<&str>,
     overrides: Option<Vec<String>>,
 ) -> PyResult<Py<PyAny>> {
-    // Create loader
-    let loader = RustConfigLoader::from_config_dir(config_dir);
-
-    // Load config with overrides
-    let overrides_ref: Vec<String> = overrides.unwrap_or_default();
-    let config = loader
-        .load_config(config_name, &overrides_ref)
+    let overrides: Vec<String> = overrides.unwrap_or_default();
+    let config = lerna::config::compose_config(config_dir, config_name, &overrides)
         .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
 
-    // Resolve interpolations
-    let dict = match &config {
-        ConfigValue::Dict(d) => d.clone(),
-        _ => return Err(PyRuntimeError::new_err("Config must be a dictionary")),
-    };
-
-    let ctx = ResolverContext::new(&dict);
-    let resolved = resolve(&config, &ctx).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    config_value_to_py(py, &ConfigValue::Dict(config))
+}
 
-    config_value_to_py(py, &resolved)
+/// Compose a configuration in Rust and fire on_compose_config on the given callbacks
+#[pyfunction]
+#[pyo3(signature = (config_dir, config_name, overrides, callback_manager))]
+fn compose_config_with_callbacks(
+    py: Python,
+    config_dir: &str,
+    config_name: Option<&str>,
+    overrides: Vec<String>,
+    callback_manager: PyRef<'_, PyCallbackManager>,
+) -> PyResult<Py<PyAny>> {
+    let config = lerna::config::compose_config_with_callbacks(
+        config_dir,
+        config_name,
+        &overrides,
+        callback_manager.inner(),
+    )
+    .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+    config_value_to_py(py, &ConfigValue::Dict(config))
 }
 
 /// Extract header directives from config file content
@@ -299,21 +383,25 @@ fn extract_header_dict(py: Python, content: &str) -> PyResult<Py<PyAny>> {
 /// This mirrors the Python IConfigRepository interface with optimized Rust implementation.
 #[pyclass(name = "RustConfigRepository")]
 pub struct PyConfigRepository {
-    inner: RustConfigRepository,
+    pub(crate) inner: RustConfigRepository,
 }
 
 #[pymethods]
 impl PyConfigRepository {
     /// Create a new config repository from search path elements
+    ///
+    /// With `overlay`, a config in a later search path replaces the same
+    /// config in an earlier one instead of the first match winning.
     #[new]
-    fn new(search_paths: Vec<(String, String)>) -> Self {
+    #[pyo3(signature = (search_paths, overlay=false))]
+    fn new(search_paths: Vec<(String, String)>, overlay: bool) -> Self {
         let elements: Vec<RustSearchPathElement> = search_paths
             .iter()
             .map(|(provider, path)| RustSearchPathElement::new(provider, path))
             .collect();
 
         Self {
-            inner: RustConfigRepository::new(&elements),
+            inner: RustConfigRepository::new(&elements).with_overlay(overlay),
         }
     }
 
@@ -327,6 +415,21 @@ impl PyConfigRepository {
         }
     }
 
+    /// Load a config, falling back to `fallback` if it does not exist
+    /// Returns (config, path_used), or None if neither exists
+    fn load_config_or(
+        &self,
+        py: Python,
+        config_path: &str,
+        fallback: &str,
+    ) -> PyResult<Option<(Py<PyAny>, String)>> {
+        match self.inner.load_config_or(config_path, fallback) {
+            Ok(Some((result, used))) => Ok(Some((config_value_to_py(py, &result.config)?, used))),
+            Ok(None) => Ok(None),
+            Err(e) => Err(PyRuntimeError::new_err(e.to_string())),
+        }
+    }
+
     /// Load a config and return full result with header
     fn load_config_full(&self, py: Python, config_path: &str) -> PyResult<Option<Py<PyAny>>> {
         match self.inner.load_config(config_path) {
@@ -360,15 +463,57 @@ impl PyConfigRepository {
         self.inner.config_exists(config_path)
     }
 
+    /// Check whether each of the given paths is a config, in order
+    fn exists_any(&self, paths: Vec<String>) -> Vec<bool> {
+        let refs: Vec<&str> = paths.iter().map(|s| s.as_str()).collect();
+        self.inner.exists_any(&refs)
+    }
+
+    /// Check whether each of the given paths is a group, in order
+    fn group_exists_batch(&self, paths: Vec<String>) -> Vec<bool> {
+        let refs: Vec<&str> = paths.iter().map(|s| s.as_str()).collect();
+        self.inner.group_exists_batch(&refs)
+    }
+
+    /// List every config under a group at any depth, as paths relative to it
+    ///
+    /// With `pattern`, only paths matching that glob are returned; `*` matches
+    /// within one path segment and `**` across segments.
+    #[pyo3(signature = (group_name, pattern=None))]
+    fn walk(&self, group_name: &str, pattern: Option<String>) -> Vec<String> {
+        match pattern {
+            Some(pattern) => self
+                .inner
+                .get_group_options_matching(group_name, &Glob::new().with_include(vec![pattern])),
+            None => self.inner.walk(group_name),
+        }
+    }
+
     /// Get available options for a config group
-    #[pyo3(signature = (group_name, results_filter=None))]
-    fn get_group_options(&self, group_name: &str, results_filter: Option<&str>) -> Vec<String> {
+    ///
+    /// When `provider` is given, only sources with that provider name are listed.
+    #[pyo3(signature = (group_name, results_filter=None, include_aliases=false, provider=None))]
+    fn get_group_options(
+        &self,
+        group_name: &str,
+        results_filter: Option<&str>,
+        include_aliases: bool,
+        provider: Option<&str>,
+    ) -> Vec<String> {
         let filter = match results_filter {
             Some("config") => Some(ObjectType::Config),
             Some("group") => Some(ObjectType::Group),
             _ => Some(ObjectType::Config), // default to config
         };
-        self.inner.get_group_options(group_name, filter)
+        self.inner
+            .get_group_options_with_aliases(group_name, filter, provider, include_aliases)
+    }
+
+    /// Alias a config name within a group, so `group/from` loads `group/to`
+    fn add_alias(&mut self, group: &str, from: &str, to: &str) -> PyResult<()> {
+        self.inner
+            .add_alias(group, from, to)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
     }
 
     /// Get the number of sources
@@ -426,15 +571,53 @@ impl PyCachingConfigRepository {
         self.inner.config_exists(config_path)
     }
 
-    /// Get available options for a config group
-    #[pyo3(signature = (group_name, results_filter=None))]
-    fn get_group_options(&self, group_name: &str, results_filter: Option<&str>) -> Vec<String> {
+    /// Check whether each of the given paths is a config, in order
+    fn exists_any(&self, paths: Vec<String>) -> Vec<bool> {
+        let refs: Vec<&str> = paths.iter().map(|s| s.as_str()).collect();
+        self.inner.exists_any(&refs)
+    }
+
+    /// Check whether each of the given paths is a group, in order
+    fn group_exists_batch(&self, paths: Vec<String>) -> Vec<bool> {
+        let refs: Vec<&str> = paths.iter().map(|s| s.as_str()).collect();
+        self.inner.group_exists_batch(&refs)
+    }
+
+    /// Get available options for a config group, optionally from one provider
+    #[pyo3(signature = (group_name, results_filter=None, provider=None))]
+    fn get_group_options(
+        &self,
+        group_name: &str,
+        results_filter: Option<&str>,
+        provider: Option<&str>,
+    ) -> Vec<String> {
         let filter = match results_filter {
             Some("config") => Some(ObjectType::Config),
             Some("group") => Some(ObjectType::Group),
             _ => Some(ObjectType::Config),
         };
-        self.inner.get_group_options(group_name, filter)
+        self.inner.get_group_options(group_name, filter, provider)
+    }
+
+    /// Load every config in a group
+    ///
+    /// Returns `(configs, errors)`: a dict of name -> config for the configs
+    /// that loaded, and a dict of name -> error message for those that failed.
+    fn load_group<'py>(
+        &mut self,
+        py: Python<'py>,
+        group_name: &str,
+    ) -> PyResult<(Bound<'py, PyDict>, Bound<'py, PyDict>)> {
+        let group = self.inner.load_group(group_name);
+        let configs = PyDict::new(py);
+        for (name, config) in &group.configs {
+            configs.set_item(name, config_value_to_py(py, config)?)?;
+        }
+        let errors = PyDict::new(py);
+        for (name, e) in &group.errors {
+            errors.set_item(name, e.to_string())?;
+        }
+        Ok((configs, errors))
     }
 
     /// Clear the internal cache
@@ -451,6 +634,8 @@ impl PyCachingConfigRepository {
     /// - config: The fully composed configuration
     /// - defaults: List of ResultDefault objects
     /// - overrides: Dict of overrides used
+    /// - applied_overrides: Overrides that took effect
+    /// - unused_overrides: Value overrides that matched no existing key
     #[pyo3(signature = (config_name=None, overrides=None))]
     fn load_and_compose(
         &mut self,
@@ -492,6 +677,9 @@ impl PyCachingConfigRepository {
         }
         dict.set_item("config_overrides", config_ovrs)?;
 
+        dict.set_item("applied_overrides", &result.applied_overrides)?;
+        dict.set_item("unused_overrides", &result.unused_overrides)?;
+
         Ok(dict.into_any().unbind())
     }
 
@@ -506,10 +694,22 @@ pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyConfigRepository>()?;
     m.add_class::<PyCachingConfigRepository>()?;
     m.add_class::<PyHybridConfigRepository>()?;
+    m.add_class::<PyEnumValue>()?;
+    m.add_function(wrap_pyfunction!(register_enum, m)?)?;
+    m.add_function(wrap_pyfunction!(enum_values, m)?)?;
+    m.add_function(wrap_pyfunction!(make_enum, m)?)?;
     m.add_function(wrap_pyfunction!(parse_yaml, m)?)?;
     m.add_function(wrap_pyfunction!(load_yaml_file, m)?)?;
+    m.add_function(wrap_pyfunction!(load_config_dir, m)?)?;
     m.add_function(wrap_pyfunction!(resolve_interpolations, m)?)?;
+    m.add_function(wrap_pyfunction!(resolve_path, m)?)?;
+    m.add_function(wrap_pyfunction!(apply_overrides_cloned, m)?)?;
+    m.add_function(wrap_pyfunction!(config_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(infer_schema, m)?)?;
+    m.add_function(wrap_pyfunction!(to_msgpack, m)?)?;
+    m.add_function(wrap_pyfunction!(from_msgpack, m)?)?;
     m.add_function(wrap_pyfunction!(compose_config, m)?)?;
+    m.add_function(wrap_pyfunction!(compose_config_with_callbacks, m)?)?;
     m.add_function(wrap_pyfunction!(extract_header_dict, m)?)?;
     Ok(())
 }
@@ -646,7 +846,7 @@ impl PyHybridConfigRepository {
                 let result = loader.call1(py, (module_path.as_str(), config_path))?;
                 if !result.is_none(py) {
                     // Convert Python dict to ConfigValue for caching
-                    let config_value = py_to_config_value(py, result.bind(py))?;
+                    let config_value = py_to_config_value(result.bind(py))?;
                     self.cache.insert(cache_key, Some(config_value.clone()));
                     return config_value_to_py(py, &config_value).map(Some);
                 }
@@ -658,7 +858,7 @@ impl PyHybridConfigRepository {
             if let Some(ref loader) = self.structured_loader {
                 let result = loader.call1(py, (config_path,))?;
                 if !result.is_none(py) {
-                    let config_value = py_to_config_value(py, result.bind(py))?;
+                    let config_value = py_to_config_value(result.bind(py))?;
                     self.cache.insert(cache_key, Some(config_value.clone()));
                     return config_value_to_py(py, &config_value).map(Some);
                 }
@@ -750,7 +950,7 @@ impl PyHybridConfigRepository {
                 Some("group") => Some(ObjectType::Group),
                 _ => Some(ObjectType::Config),
             };
-            options.extend(rust_repo.get_group_options(group_name, filter));
+            options.extend(rust_repo.get_group_options(group_name, filter, None));
         }
 
         // Get from pkg sources via Python
@@ -8,7 +8,7 @@ use lerna::{
     get_scheme as rust_get_scheme, is_absolute_config_path as rust_is_absolute,
     is_valid_config_name as rust_is_valid_config, is_valid_group_name as rust_is_valid_group,
     join_config_paths as rust_join, normalize_config_path as rust_normalize,
-    strip_scheme as rust_strip_scheme,
+    split_config_package as rust_split_package, strip_scheme as rust_strip_scheme,
 };
 
 /// Normalize a configuration path by removing redundant separators and dots
@@ -17,13 +17,23 @@ pub fn normalize_config_path(path: &str) -> String {
     rust_normalize(path)
 }
 
+/// Split a `@package` suffix off a configuration path
+///
+/// Returns `(path, package)`, with `package` None when there is no `@`.
+#[pyfunction]
+pub fn split_config_package(path: &str) -> (String, Option<String>) {
+    let (path, package) = rust_split_package(path);
+    (path.to_string(), package.map(str::to_string))
+}
+
 /// Get the parent directory of a configuration path
 #[pyfunction]
 pub fn get_parent_path(path: &str) -> Option<String> {
     rust_get_parent(path)
 }
 
-/// Get the basename (last component) of a configuration path
+/// Get the basename (last component) of a configuration path, including
+/// any `@package` suffix
 #[pyfunction]
 pub fn get_basename(path: &str) -> String {
     rust_get_basename(path)
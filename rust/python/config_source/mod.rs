@@ -6,70 +6,16 @@
 //! - `PyConfigResult` - exposes config load results to Python
 
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
+use pyo3::types::PyDict;
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use lerna::config::source::{ConfigResult, ConfigSource, FileConfigSource};
-use lerna::config::value::{ConfigDict, ConfigValue};
+use lerna::config::value::ConfigValue;
 use lerna::config::ConfigLoadError;
 use lerna::ObjectType;
 
-/// Convert ConfigValue to Python object
-fn config_value_to_py(py: Python<'_>, value: &ConfigValue) -> PyResult<Py<PyAny>> {
-    match value {
-        ConfigValue::Null => Ok(py.None()),
-        ConfigValue::Bool(b) => Ok((*b).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::Int(i) => Ok((*i).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::Float(f) => Ok((*f).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::String(s) => Ok(s.as_str().into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::List(items) => {
-            let list = PyList::empty(py);
-            for item in items {
-                list.append(config_value_to_py(py, item)?)?;
-            }
-            Ok(list.into_any().unbind())
-        }
-        ConfigValue::Dict(d) => {
-            let dict = PyDict::new(py);
-            for (k, v) in d.iter() {
-                dict.set_item(k, config_value_to_py(py, v)?)?;
-            }
-            Ok(dict.into_any().unbind())
-        }
-        ConfigValue::Missing => Ok("???".into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::Interpolation(s) => {
-            Ok(s.as_str().into_pyobject(py)?.to_owned().into_any().unbind())
-        }
-    }
-}
-
-/// Convert Python object to ConfigValue
-fn py_to_config_value(obj: &Bound<'_, PyAny>) -> PyResult<ConfigValue> {
-    if obj.is_none() {
-        Ok(ConfigValue::Null)
-    } else if let Ok(b) = obj.extract::<bool>() {
-        Ok(ConfigValue::Bool(b))
-    } else if let Ok(i) = obj.extract::<i64>() {
-        Ok(ConfigValue::Int(i))
-    } else if let Ok(f) = obj.extract::<f64>() {
-        Ok(ConfigValue::Float(f))
-    } else if let Ok(s) = obj.extract::<String>() {
-        Ok(ConfigValue::String(s))
-    } else if let Ok(list) = obj.cast::<PyList>() {
-        let items: PyResult<Vec<_>> = list.iter().map(|item| py_to_config_value(&item)).collect();
-        Ok(ConfigValue::List(items?))
-    } else if let Ok(dict) = obj.cast::<PyDict>() {
-        let mut config_dict = ConfigDict::new();
-        for (k, v) in dict.iter() {
-            let key: String = k.extract()?;
-            config_dict.insert(key, py_to_config_value(&v)?);
-        }
-        Ok(ConfigValue::Dict(config_dict))
-    } else {
-        Ok(ConfigValue::String(obj.str()?.to_string()))
-    }
-}
+use crate::conversions::{config_value_to_py, py_to_config_value};
 
 /// Python-accessible config load result
 #[pyclass(name = "ConfigResult")]
@@ -130,6 +76,21 @@ impl PyConfigResult {
         }
         Ok(dict.unbind())
     }
+
+    /// The @package directive, or None if absent ("" when present but empty)
+    fn package(&self) -> Option<&str> {
+        self.header.get("package").map(|s| s.as_str())
+    }
+
+    /// The @schema directive, or None if absent
+    fn schema(&self) -> Option<&str> {
+        self.header.get("schema").map(|s| s.as_str())
+    }
+
+    /// Whether the config is placed at the root via @package _global_
+    fn global_package(&self) -> bool {
+        self.package() == Some("_global_")
+    }
 }
 
 impl From<ConfigResult> for PyConfigResult {
@@ -317,6 +278,11 @@ impl PyFileConfigSource {
         self.inner.available()
     }
 
+    /// Drop cached directory listings
+    fn clear_listing_cache(&self) {
+        self.inner.clear_listing_cache();
+    }
+
     /// Load a config
     fn load_config(&self, _py: Python<'_>, config_path: &str) -> PyResult<PyConfigResult> {
         self.inner
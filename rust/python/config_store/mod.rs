@@ -3,78 +3,11 @@
 
 use pyo3::exceptions::PyKeyError;
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
 
 use lerna::config::value::{ConfigDict, ConfigValue};
 use lerna::config_store::{self, ConfigNode as RustConfigNode};
 
-/// Convert ConfigValue to a Python object
-fn config_value_to_py(py: Python, value: &ConfigValue) -> PyResult<Py<PyAny>> {
-    match value {
-        ConfigValue::Null => Ok(py.None()),
-        ConfigValue::Bool(b) => Ok((*b).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::Int(i) => Ok((*i).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::Float(f) => Ok((*f).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::String(s) => Ok(s.as_str().into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::Interpolation(s) => {
-            Ok(s.as_str().into_pyobject(py)?.to_owned().into_any().unbind())
-        }
-        ConfigValue::Missing => Ok("???".into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::List(items) => {
-            let list = PyList::empty(py);
-            for item in items {
-                list.append(config_value_to_py(py, item)?)?;
-            }
-            Ok(list.into_any().unbind())
-        }
-        ConfigValue::Dict(dict) => config_dict_to_py(py, dict),
-    }
-}
-
-/// Convert ConfigDict to a Python dict
-fn config_dict_to_py(py: Python, dict: &ConfigDict) -> PyResult<Py<PyAny>> {
-    let py_dict = PyDict::new(py);
-    for (key, value) in dict.iter() {
-        py_dict.set_item(key, config_value_to_py(py, value)?)?;
-    }
-    Ok(py_dict.into_any().unbind())
-}
-
-/// Convert a Python object to ConfigValue
-fn py_to_config_value(py: Python, obj: &Bound<'_, PyAny>) -> PyResult<ConfigValue> {
-    if obj.is_none() {
-        Ok(ConfigValue::Null)
-    } else if let Ok(b) = obj.extract::<bool>() {
-        Ok(ConfigValue::Bool(b))
-    } else if let Ok(i) = obj.extract::<i64>() {
-        Ok(ConfigValue::Int(i))
-    } else if let Ok(f) = obj.extract::<f64>() {
-        Ok(ConfigValue::Float(f))
-    } else if let Ok(s) = obj.extract::<String>() {
-        if s == "???" {
-            Ok(ConfigValue::Missing)
-        } else {
-            Ok(ConfigValue::String(s))
-        }
-    } else if let Ok(list) = obj.cast::<PyList>() {
-        let mut items = Vec::new();
-        for item in list.iter() {
-            items.push(py_to_config_value(py, &item)?);
-        }
-        Ok(ConfigValue::List(items))
-    } else if let Ok(dict) = obj.cast::<PyDict>() {
-        let mut config_dict = ConfigDict::new();
-        for (key, value) in dict.iter() {
-            if let Ok(k) = key.extract::<String>() {
-                config_dict.insert(k, py_to_config_value(py, &value)?);
-            }
-        }
-        Ok(ConfigValue::Dict(config_dict))
-    } else {
-        // Fallback to string representation
-        Ok(ConfigValue::String(obj.str()?.to_string()))
-    }
-}
+use crate::conversions::{config_dict_to_py, py_to_config_value};
 
 /// Python wrapper for a ConfigNode
 #[pyclass(name = "RustConfigNode")]
@@ -96,7 +29,7 @@ impl PyConfigNode {
 
     #[getter]
     fn node(&self, py: Python) -> PyResult<Py<PyAny>> {
-        config_dict_to_py(py, &self.node)
+        config_dict_to_py(py, &self.node).map(|d| d.into_any().unbind())
     }
 
     #[getter]
@@ -152,19 +85,18 @@ impl PyConfigStore {
     #[pyo3(signature = (name, node, group=None, package=None, provider=None))]
     fn store(
         &self,
-        py: Python,
         name: &str,
         node: &Bound<'_, PyAny>,
         group: Option<&str>,
         package: Option<&str>,
         provider: Option<&str>,
     ) -> PyResult<()> {
-        let config_dict = match py_to_config_value(py, node)? {
+        let config_dict = match py_to_config_value(node)? {
             ConfigValue::Dict(d) => d,
             _ => {
                 // If not a dict, wrap it
                 let mut d = ConfigDict::new();
-                d.insert("_value_".to_string(), py_to_config_value(py, node)?);
+                d.insert("_value_".to_string(), py_to_config_value(node)?);
                 d
             }
         };
@@ -228,6 +160,19 @@ impl PyConfigStore {
         }
     }
 
+    /// List the configs in a group stored under a package
+    ///
+    /// Args:
+    ///     group: The group path (e.g., "db")
+    ///     package: The package the configs were stored under
+    ///
+    /// Returns:
+    ///     Sorted list of config names, empty if there are none
+    fn list_by_package(&self, group: &str, package: &str) -> Vec<String> {
+        let store = config_store::instance();
+        store.list_by_package(group, package)
+    }
+
     /// Check if a config exists
     fn config_exists(&self, config_path: &str) -> bool {
         let store = config_store::instance();
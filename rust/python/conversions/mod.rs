@@ -0,0 +1,153 @@
+// Copyright (c) Facebook, Inc. and its affiliates. All Rights Reserved
+//! Conversions between Python objects and config values
+//!
+//! These are the canonical conversions shared by every binding module, so a
+//! config crosses the Python boundary the same way whichever API it goes
+//! through.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyFloat, PyInt, PyList};
+
+use lerna::config::value::{ConfigDict, ConfigValue};
+
+use crate::config::PyEnumValue;
+
+/// Convert a ConfigValue to a Python object
+///
+/// Interpolations are returned as their `${...}` source and missing values
+/// as `"???"`, matching how they are written in YAML. Enum values become
+/// `EnumValue` objects.
+pub(crate) fn config_value_to_py(py: Python<'_>, value: &ConfigValue) -> PyResult<Py<PyAny>> {
+    match value {
+        ConfigValue::Null => Ok(py.None()),
+        ConfigValue::Bool(b) => Ok((*b).into_pyobject(py)?.to_owned().into_any().unbind()),
+        ConfigValue::Int(i) => Ok((*i).into_pyobject(py)?.to_owned().into_any().unbind()),
+        ConfigValue::Float(f) => Ok((*f).into_pyobject(py)?.to_owned().into_any().unbind()),
+        ConfigValue::String(s) | ConfigValue::Interpolation(s) => {
+            Ok(s.as_str().into_pyobject(py)?.to_owned().into_any().unbind())
+        }
+        ConfigValue::Missing => Ok("???".into_pyobject(py)?.to_owned().into_any().unbind()),
+        ConfigValue::List(items) => Ok(config_list_to_py(py, items)?.into_any().unbind()),
+        ConfigValue::Dict(dict) => Ok(config_dict_to_py(py, dict)?.into_any().unbind()),
+        ConfigValue::Enum { type_name, value } => Ok(Py::new(
+            py,
+            PyEnumValue {
+                type_name: type_name.clone(),
+                value: value.clone(),
+            },
+        )?
+        .into_any()),
+    }
+}
+
+/// Convert a list of config values to a Python list
+///
+/// All-int and all-float lists, common for embedded numeric arrays, are built
+/// in one pass from native numbers; other lists convert item by item.
+fn config_list_to_py<'py>(py: Python<'py>, items: &[ConfigValue]) -> PyResult<Bound<'py, PyList>> {
+    let ints: Option<Vec<i64>> = items
+        .iter()
+        .map(|item| match item {
+            ConfigValue::Int(i) => Some(*i),
+            _ => None,
+        })
+        .collect();
+    if let Some(ints) = ints {
+        return PyList::new(py, ints);
+    }
+    let floats: Option<Vec<f64>> = items
+        .iter()
+        .map(|item| match item {
+            ConfigValue::Float(f) => Some(*f),
+            _ => None,
+        })
+        .collect();
+    if let Some(floats) = floats {
+        return PyList::new(py, floats);
+    }
+    let items: PyResult<Vec<_>> = items
+        .iter()
+        .map(|item| config_value_to_py(py, item))
+        .collect();
+    PyList::new(py, items?)
+}
+
+/// Convert a ConfigDict to a Python dict
+pub(crate) fn config_dict_to_py<'py>(
+    py: Python<'py>,
+    dict: &ConfigDict,
+) -> PyResult<Bound<'py, PyDict>> {
+    let py_dict = PyDict::new(py);
+    for (key, value) in dict.iter() {
+        py_dict.set_item(key, config_value_to_py(py, value)?)?;
+    }
+    Ok(py_dict)
+}
+
+/// Convert a Python object to a ConfigValue
+///
+/// `"???"` becomes `Missing`, strings are classified with
+/// [`ConfigValue::from_string`], and unsupported objects fall back to their
+/// `str()`.
+pub(crate) fn py_to_config_value(obj: &Bound<'_, PyAny>) -> PyResult<ConfigValue> {
+    if obj.is_none() {
+        Ok(ConfigValue::Null)
+    } else if let Some(value) = exact_number(obj) {
+        Ok(value)
+    } else if let Ok(b) = obj.extract::<bool>() {
+        Ok(ConfigValue::Bool(b))
+    } else if let Ok(i) = obj.extract::<i64>() {
+        Ok(ConfigValue::Int(i))
+    } else if let Ok(f) = obj.extract::<f64>() {
+        Ok(ConfigValue::Float(f))
+    } else if let Ok(s) = obj.extract::<String>() {
+        if s == "???" {
+            Ok(ConfigValue::Missing)
+        } else {
+            Ok(ConfigValue::from_string(s))
+        }
+    } else if let Ok(list) = obj.cast::<PyList>() {
+        let items: PyResult<Vec<_>> = list.iter().map(|item| py_to_config_value(&item)).collect();
+        Ok(ConfigValue::List(items?))
+    } else if let Ok(dict) = obj.cast::<PyDict>() {
+        Ok(ConfigValue::Dict(py_dict_to_config_dict(dict)?))
+    } else if let Ok(e) = obj.cast::<PyEnumValue>() {
+        let e = e.get();
+        Ok(ConfigValue::Enum {
+            type_name: e.type_name.clone(),
+            value: e.value.clone(),
+        })
+    } else {
+        Ok(ConfigValue::String(obj.str()?.to_string()))
+    }
+}
+
+/// Convert a plain `int` or `float` without trying the other extractions
+///
+/// Failed extractions raise (and discard) Python exceptions, which dominates
+/// the cost of converting large numeric lists. Subclasses such as `bool`, and
+/// ints too large for i64, are left to the general path.
+fn exact_number(obj: &Bound<'_, PyAny>) -> Option<ConfigValue> {
+    if let Ok(f) = obj.cast_exact::<PyFloat>() {
+        Some(ConfigValue::Float(f.value()))
+    } else if obj.is_exact_instance_of::<PyInt>() {
+        obj.extract().ok().map(ConfigValue::Int)
+    } else {
+        None
+    }
+}
+
+/// Convert a Python dict to a ConfigDict
+///
+/// Non-string keys (e.g. ints) are converted with `str()`.
+pub(crate) fn py_dict_to_config_dict(dict: &Bound<'_, PyDict>) -> PyResult<ConfigDict> {
+    let mut config_dict = ConfigDict::new();
+    for (key, value) in dict.iter() {
+        let key = match key.extract::<String>() {
+            Ok(key) => key,
+            Err(_) => key.str()?.to_string(),
+        };
+        config_dict.insert(key, py_to_config_value(&value)?);
+    }
+    Ok(config_dict)
+}
@@ -9,7 +9,7 @@ use pyo3::types::PyList;
 #[pyclass(name = "ResultDefault")]
 #[derive(Clone)]
 pub struct PyResultDefault {
-    inner: ResultDefault,
+    pub(crate) inner: ResultDefault,
 }
 
 #[pymethods]
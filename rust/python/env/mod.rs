@@ -2,10 +2,10 @@
 //! PyO3 bindings for environment variable handling
 
 use lerna::env::{
-    find_env_refs, get_all_env, get_many_env, is_env_set, parse_env_ref, resolve_env_string,
-    EnvResolver as RustEnvResolver,
+    find_env_refs, get_all_env, get_many_env, is_env_set, parse_env_ref, read_dotenv,
+    resolve_env_string, EnvResolver as RustEnvResolver,
 };
-use pyo3::exceptions::PyValueError;
+use pyo3::exceptions::{PyIOError, PyValueError};
 use pyo3::prelude::*;
 use std::collections::HashMap;
 
@@ -142,6 +142,32 @@ fn py_get_env(key: &str, default: Option<String>) -> Option<String> {
     }
 }
 
+/// Load a `.env` file into `os.environ`, returning the number of variables
+/// set
+///
+/// Variables that are already set are kept unless `override` is true.
+/// Setting them through `os.environ` also updates the process environment,
+/// so they are visible to `${oc.env:...}` resolution and `get_env`. Raises
+/// OSError if the file cannot be read and ValueError if it is malformed.
+#[pyfunction]
+#[pyo3(name = "load_dotenv")]
+#[pyo3(signature = (path, r#override=false))]
+fn py_load_dotenv(py: Python<'_>, path: &str, r#override: bool) -> PyResult<usize> {
+    let vars = read_dotenv(path).map_err(|e| match e.line {
+        Some(_) => PyValueError::new_err(e.message),
+        None => PyIOError::new_err(e.message),
+    })?;
+    let environ = PyModule::import(py, "os")?.getattr("environ")?;
+    let mut count = 0;
+    for (key, value) in vars {
+        if r#override || !environ.contains(&key)? {
+            environ.set_item(key, value)?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
 /// Register environment functions as a submodule
 pub fn register(parent: &Bound<'_, PyModule>) -> PyResult<()> {
     let m = PyModule::new(parent.py(), "env")?;
@@ -153,6 +179,7 @@ pub fn register(parent: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(py_is_env_set, &m)?)?;
     m.add_function(wrap_pyfunction!(py_get_many_env, &m)?)?;
     m.add_function(wrap_pyfunction!(py_get_env, &m)?)?;
+    m.add_function(wrap_pyfunction!(py_load_dotenv, &m)?)?;
     parent.add_submodule(&m)?;
     Ok(())
 }
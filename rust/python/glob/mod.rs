@@ -14,9 +14,13 @@ pub struct PyGlob {
 #[pymethods]
 impl PyGlob {
     #[new]
-    #[pyo3(signature = (include=None, exclude=None))]
-    fn new(include: Option<Vec<String>>, exclude: Option<Vec<String>>) -> Self {
-        let mut glob = Glob::new();
+    #[pyo3(signature = (include=None, exclude=None, case_insensitive=false))]
+    fn new(
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        case_insensitive: bool,
+    ) -> Self {
+        let mut glob = Glob::new().with_case_insensitive(case_insensitive);
         if let Some(inc) = include {
             glob = glob.with_include(inc);
         }
@@ -46,6 +50,17 @@ impl PyGlob {
         self.inner.exclude = value;
     }
 
+    /// Whether names are matched regardless of case
+    #[getter]
+    fn case_insensitive(&self) -> bool {
+        self.inner.case_insensitive
+    }
+
+    #[setter]
+    fn set_case_insensitive(&mut self, value: bool) {
+        self.inner.case_insensitive = value;
+    }
+
     /// Filter a list of names based on include and exclude patterns
     fn filter(&self, names: Vec<String>) -> Vec<String> {
         self.inner.filter(&names)
@@ -53,8 +68,14 @@ impl PyGlob {
 
     fn __repr__(&self) -> String {
         format!(
-            "Glob(include={:?}, exclude={:?})",
-            self.inner.include, self.inner.exclude
+            "Glob(include={:?}, exclude={:?}, case_insensitive={})",
+            self.inner.include,
+            self.inner.exclude,
+            if self.inner.case_insensitive {
+                "True"
+            } else {
+                "False"
+            }
         )
     }
 }
@@ -7,67 +7,9 @@ use lerna::interpolation::{
     ResolutionContext,
 };
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
+use pyo3::types::PyDict;
 
-/// Convert Python object to ConfigValue
-fn py_to_config_value(obj: &Bound<'_, PyAny>) -> PyResult<ConfigValue> {
-    if obj.is_none() {
-        return Ok(ConfigValue::Null);
-    }
-    if let Ok(b) = obj.extract::<bool>() {
-        return Ok(ConfigValue::Bool(b));
-    }
-    if let Ok(i) = obj.extract::<i64>() {
-        return Ok(ConfigValue::Int(i));
-    }
-    if let Ok(f) = obj.extract::<f64>() {
-        return Ok(ConfigValue::Float(f));
-    }
-    if let Ok(s) = obj.extract::<String>() {
-        return Ok(ConfigValue::String(s));
-    }
-    if let Ok(list) = obj.cast::<PyList>() {
-        let items: PyResult<Vec<ConfigValue>> =
-            list.iter().map(|item| py_to_config_value(&item)).collect();
-        return Ok(ConfigValue::List(items?));
-    }
-    if let Ok(dict) = obj.cast::<PyDict>() {
-        let mut result = ConfigDict::new();
-        for (key, val) in dict.iter() {
-            let k: String = key.extract()?;
-            let v = py_to_config_value(&val)?;
-            result.insert(k, v);
-        }
-        return Ok(ConfigValue::Dict(result));
-    }
-    Ok(ConfigValue::String(obj.str()?.to_string()))
-}
-
-/// Convert ConfigValue to Python object
-fn config_value_to_py(py: Python<'_>, value: &ConfigValue) -> PyResult<Py<PyAny>> {
-    match value {
-        ConfigValue::Null => Ok(py.None()),
-        ConfigValue::Bool(b) => Ok((*b).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::Int(i) => Ok((*i).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::Float(f) => Ok((*f).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::String(s) => Ok(s.as_str().into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::List(items) => {
-            let list = PyList::new(py, items.iter().map(|i| config_value_to_py(py, i).unwrap()))?;
-            Ok(list.into())
-        }
-        ConfigValue::Dict(dict) => {
-            let py_dict = PyDict::new(py);
-            for (k, v) in dict.iter() {
-                py_dict.set_item(k, config_value_to_py(py, v)?)?;
-            }
-            Ok(py_dict.into())
-        }
-        ConfigValue::Interpolation(s) => {
-            Ok(s.as_str().into_pyobject(py)?.to_owned().into_any().unbind())
-        }
-        ConfigValue::Missing => Ok(py.None()),
-    }
-}
+use crate::conversions::{config_value_to_py, py_to_config_value};
 
 /// Find all interpolations in a string
 /// Returns list of (start, end, interpolation_str) tuples
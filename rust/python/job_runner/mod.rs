@@ -3,71 +3,36 @@
 
 use pyo3::exceptions::{PyIOError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
+use pyo3::types::PyDict;
 use std::path::PathBuf;
 
-use lerna::config::value::{ConfigDict, ConfigValue};
+use lerna::config::value::ConfigDict;
 use lerna::job_runner::{
-    compute_output_dir as rust_compute_output_dir, create_output_dirs as rust_create_output_dirs,
-    save_config_file as rust_save_config_file, save_overrides_file as rust_save_overrides_file,
-    serialize_config_to_yaml, setup_job_environment as rust_setup_job_environment,
-    JobContext as RustJobContext, JobStatus as RustJobStatus,
+    compute_output_dir as rust_compute_output_dir,
+    compute_output_dir_with_tags as rust_compute_output_dir_with_tags,
+    create_output_dirs as rust_create_output_dirs, normalize_overrides, redact_config,
+    save_config_file as rust_save_config_file,
+    save_job_metadata_file as rust_save_job_metadata_file,
+    save_overrides_file_with as rust_save_overrides_file_with, serialize_config_to_yaml,
+    setup_job_environment as rust_setup_job_environment, JobContext as RustJobContext,
+    JobStatus as RustJobStatus,
 };
+use lerna::Glob;
 
-/// Convert Python dict to ConfigDict
-fn py_to_config_dict(py: Python, obj: &Bound<'_, PyAny>) -> PyResult<ConfigDict> {
-    if let Ok(dict) = obj.cast::<PyDict>() {
-        let mut config_dict = ConfigDict::new();
-        for (key, value) in dict.iter() {
-            if let Ok(k) = key.extract::<String>() {
-                config_dict.insert(k, py_to_config_value(py, &value)?);
-            }
-        }
-        Ok(config_dict)
-    } else {
-        Err(PyValueError::new_err("Expected a dict"))
-    }
-}
+use crate::conversions::py_dict_to_config_dict;
 
-/// Convert Python object to ConfigValue
-fn py_to_config_value(py: Python, obj: &Bound<'_, PyAny>) -> PyResult<ConfigValue> {
-    if obj.is_none() {
-        Ok(ConfigValue::Null)
-    } else if let Ok(b) = obj.extract::<bool>() {
-        Ok(ConfigValue::Bool(b))
-    } else if let Ok(i) = obj.extract::<i64>() {
-        Ok(ConfigValue::Int(i))
-    } else if let Ok(f) = obj.extract::<f64>() {
-        Ok(ConfigValue::Float(f))
-    } else if let Ok(s) = obj.extract::<String>() {
-        if s == "???" {
-            Ok(ConfigValue::Missing)
-        } else if s.contains("${") {
-            Ok(ConfigValue::Interpolation(s))
-        } else {
-            Ok(ConfigValue::String(s))
-        }
-    } else if let Ok(list) = obj.cast::<PyList>() {
-        let mut items = Vec::new();
-        for item in list.iter() {
-            items.push(py_to_config_value(py, &item)?);
-        }
-        Ok(ConfigValue::List(items))
-    } else if let Ok(_dict) = obj.cast::<PyDict>() {
-        Ok(ConfigValue::Dict(py_to_config_dict(
-            py,
-            &obj.clone().into_any(),
-        )?))
-    } else {
-        // Fallback: convert to string
-        Ok(ConfigValue::String(obj.str()?.to_string()))
-    }
+/// Convert Python dict to ConfigDict
+fn py_to_config_dict(obj: &Bound<'_, PyAny>) -> PyResult<ConfigDict> {
+    let dict = obj
+        .cast::<PyDict>()
+        .map_err(|_| PyValueError::new_err("Expected a dict"))?;
+    py_dict_to_config_dict(dict)
 }
 
 /// Python wrapper for JobStatus
 #[pyclass(name = "RustJobStatus")]
 #[derive(Clone, Copy)]
-pub struct PyJobStatus(RustJobStatus);
+pub struct PyJobStatus(pub(crate) RustJobStatus);
 
 #[pymethods]
 #[allow(non_snake_case)]
@@ -87,16 +52,35 @@ impl PyJobStatus {
         Self(RustJobStatus::Failed)
     }
 
+    #[classattr]
+    fn RUNNING() -> Self {
+        Self(RustJobStatus::Running)
+    }
+
+    /// Parse a status name such as "COMPLETED"; raises ValueError if unknown
+    #[staticmethod]
+    fn from_str(s: &str) -> PyResult<Self> {
+        s.parse()
+            .map(Self)
+            .map_err(|e: lerna::ParseJobStatusError| PyValueError::new_err(e.message))
+    }
+
+    /// The status name, e.g. "COMPLETED"
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.0.as_str()
+    }
+
     fn __eq__(&self, other: &Self) -> bool {
         self.0 == other.0
     }
 
+    fn __str__(&self) -> &'static str {
+        self.0.as_str()
+    }
+
     fn __repr__(&self) -> String {
-        match self.0 {
-            RustJobStatus::Unknown => "RustJobStatus.UNKNOWN".to_string(),
-            RustJobStatus::Completed => "RustJobStatus.COMPLETED".to_string(),
-            RustJobStatus::Failed => "RustJobStatus.FAILED".to_string(),
-        }
+        format!("RustJobStatus.{}", self.0.as_str())
     }
 }
 
@@ -110,10 +94,10 @@ pub struct PyJobContext {
 #[pymethods]
 impl PyJobContext {
     #[new]
-    #[pyo3(signature = (name, id, num))]
-    fn new(name: &str, id: &str, num: usize) -> Self {
+    #[pyo3(signature = (name, id, num, capture_git=false))]
+    fn new(name: &str, id: &str, num: usize, capture_git: bool) -> Self {
         Self {
-            inner: RustJobContext::new(name, id, num),
+            inner: RustJobContext::new(name, id, num).with_git_capture(capture_git),
         }
     }
 
@@ -157,6 +141,18 @@ impl PyJobContext {
         self.inner.overrides.clone()
     }
 
+    /// Commit hash of the original working directory, if captured
+    #[getter]
+    fn git_commit(&self) -> Option<String> {
+        self.inner.git.as_ref().map(|git| git.commit.clone())
+    }
+
+    /// Whether the working tree had uncommitted changes, if captured
+    #[getter]
+    fn git_dirty(&self) -> Option<bool> {
+        self.inner.git.as_ref().map(|git| git.dirty)
+    }
+
     /// Set the output directory
     fn set_output_dir(&mut self, dir: &str) {
         self.inner.output_dir = PathBuf::from(dir);
@@ -178,13 +174,35 @@ impl PyJobContext {
     }
 }
 
+/// Convert a Python dict to ConfigDict, redacting values whose dotted path
+/// matches one of the `redact` glob patterns
+fn py_to_redacted_config_dict(
+    obj: &Bound<'_, PyAny>,
+    redact: Option<Vec<String>>,
+) -> PyResult<ConfigDict> {
+    let config_dict = py_to_config_dict(obj)?;
+    Ok(match redact {
+        Some(patterns) => redact_config(&config_dict, &Glob::new().with_include(patterns)),
+        None => config_dict,
+    })
+}
+
 /// Compute output directory for a job
+///
+/// With `tags`, each `${sweep.tag}` in the values is replaced by the sweep's tags.
 #[pyfunction]
-#[pyo3(signature = (job_dir_value, job_subdir_value=None))]
-fn compute_output_dir(job_dir_value: &str, job_subdir_value: Option<&str>) -> String {
-    rust_compute_output_dir(job_dir_value, job_subdir_value)
-        .to_string_lossy()
-        .to_string()
+#[pyo3(signature = (job_dir_value, job_subdir_value=None, tags=None))]
+fn compute_output_dir(
+    job_dir_value: &str,
+    job_subdir_value: Option<&str>,
+    tags: Option<Vec<String>>,
+) -> String {
+    match tags {
+        Some(tags) => rust_compute_output_dir_with_tags(job_dir_value, job_subdir_value, &tags),
+        None => rust_compute_output_dir(job_dir_value, job_subdir_value),
+    }
+    .to_string_lossy()
+    .to_string()
 }
 
 /// Create output directories
@@ -196,41 +214,77 @@ fn create_output_dirs(output_dir: &str, subdir: Option<&str>) -> PyResult<String
         .map_err(|e| PyIOError::new_err(e.to_string()))
 }
 
-/// Save a config dictionary to a YAML file
+/// Save a config dictionary to a YAML file, masking values matched by `redact`
 #[pyfunction]
+#[pyo3(signature = (config, filename, output_dir, redact=None))]
 fn save_config(
-    py: Python,
     config: &Bound<'_, PyAny>,
     filename: &str,
     output_dir: &str,
+    redact: Option<Vec<String>>,
 ) -> PyResult<String> {
-    let config_dict = py_to_config_dict(py, config)?;
+    let config_dict = py_to_redacted_config_dict(config, redact)?;
     rust_save_config_file(&config_dict, filename, &PathBuf::from(output_dir))
         .map(|p| p.to_string_lossy().to_string())
         .map_err(|e| PyIOError::new_err(e.to_string()))
 }
 
-/// Save overrides to a YAML file
+/// Save a job's metadata (name, id, num and git state) to a YAML file
 #[pyfunction]
-fn save_overrides(overrides: Vec<String>, filename: &str, output_dir: &str) -> PyResult<String> {
-    rust_save_overrides_file(&overrides, filename, &PathBuf::from(output_dir))
+fn save_job_metadata(ctx: &PyJobContext, filename: &str, output_dir: &str) -> PyResult<String> {
+    rust_save_job_metadata_file(&ctx.inner, filename, &PathBuf::from(output_dir))
         .map(|p| p.to_string_lossy().to_string())
         .map_err(|e| PyIOError::new_err(e.to_string()))
 }
 
+/// Save overrides to a YAML file
+///
+/// With `normalize`, each override is parsed and rewritten canonically
+/// (malformed ones are kept as-is, with a `warnings.warn` for each); with
+/// `sort`, they are written in sorted order.
+#[pyfunction]
+#[pyo3(signature = (overrides, filename, output_dir, normalize=false, sort=false))]
+fn save_overrides(
+    py: Python<'_>,
+    overrides: Vec<String>,
+    filename: &str,
+    output_dir: &str,
+    normalize: bool,
+    sort: bool,
+) -> PyResult<String> {
+    let overrides = if normalize {
+        let (normalized, malformed) = normalize_overrides(&overrides);
+        let warn = PyModule::import(py, "warnings")?.getattr("warn")?;
+        for (o, e) in malformed {
+            warn.call1((format!("Writing malformed override '{}' as-is: {}", o, e),))?;
+        }
+        normalized
+    } else {
+        overrides
+    };
+    rust_save_overrides_file_with(
+        &overrides,
+        filename,
+        &PathBuf::from(output_dir),
+        false,
+        sort,
+    )
+    .map(|p| p.to_string_lossy().to_string())
+    .map_err(|e| PyIOError::new_err(e.to_string()))
+}
+
 /// Setup job execution environment (create dirs, save configs)
 #[pyfunction]
 #[pyo3(signature = (output_dir, hydra_subdir, task_config, hydra_config, overrides))]
 fn setup_job_environment(
-    py: Python,
     output_dir: &str,
     hydra_subdir: Option<&str>,
     task_config: &Bound<'_, PyAny>,
     hydra_config: &Bound<'_, PyAny>,
     overrides: Vec<String>,
 ) -> PyResult<String> {
-    let task_dict = py_to_config_dict(py, task_config)?;
-    let hydra_dict = py_to_config_dict(py, hydra_config)?;
+    let task_dict = py_to_config_dict(task_config)?;
+    let hydra_dict = py_to_config_dict(hydra_config)?;
 
     rust_setup_job_environment(
         &PathBuf::from(output_dir),
@@ -243,10 +297,11 @@ fn setup_job_environment(
     .map_err(|e| PyIOError::new_err(e.to_string()))
 }
 
-/// Serialize config dict to YAML string
+/// Serialize config dict to YAML string, masking values matched by `redact`
 #[pyfunction]
-fn config_to_yaml(py: Python, config: &Bound<'_, PyAny>) -> PyResult<String> {
-    let config_dict = py_to_config_dict(py, config)?;
+#[pyo3(signature = (config, redact=None))]
+fn config_to_yaml(config: &Bound<'_, PyAny>, redact: Option<Vec<String>>) -> PyResult<String> {
+    let config_dict = py_to_redacted_config_dict(config, redact)?;
     Ok(serialize_config_to_yaml(&config_dict))
 }
 
@@ -257,6 +312,7 @@ pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(compute_output_dir, m)?)?;
     m.add_function(wrap_pyfunction!(create_output_dirs, m)?)?;
     m.add_function(wrap_pyfunction!(save_config, m)?)?;
+    m.add_function(wrap_pyfunction!(save_job_metadata, m)?)?;
     m.add_function(wrap_pyfunction!(save_overrides, m)?)?;
     m.add_function(wrap_pyfunction!(setup_job_environment, m)?)?;
     m.add_function(wrap_pyfunction!(config_to_yaml, m)?)?;
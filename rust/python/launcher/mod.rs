@@ -7,51 +7,17 @@
 
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use lerna::callback::JobReturn;
-use lerna::config::value::{ConfigDict, ConfigValue};
-use lerna::launcher::{BasicLauncher, JobOverrideBatch, Launcher, LauncherError, LauncherManager};
+use lerna::config::value::ConfigDict;
+use lerna::launcher::{
+    BasicLauncher, JobOverrideBatch, JobOverrides, JobTask, Launcher, LauncherError,
+    LauncherManager, TaskOutput,
+};
 
 use crate::callback::PyJobReturn;
-
-/// Convert Python dict to ConfigDict
-fn py_dict_to_config_dict(dict: &Bound<'_, PyDict>) -> PyResult<ConfigDict> {
-    let mut config = ConfigDict::new();
-    for (key, value) in dict.iter() {
-        let k: String = key.extract()?;
-        let v = py_to_config_value(&value)?;
-        config.insert(k, v);
-    }
-    Ok(config)
-}
-
-/// Convert Python object to ConfigValue
-fn py_to_config_value(obj: &Bound<'_, PyAny>) -> PyResult<ConfigValue> {
-    if obj.is_none() {
-        Ok(ConfigValue::Null)
-    } else if let Ok(b) = obj.extract::<bool>() {
-        Ok(ConfigValue::Bool(b))
-    } else if let Ok(i) = obj.extract::<i64>() {
-        Ok(ConfigValue::Int(i))
-    } else if let Ok(f) = obj.extract::<f64>() {
-        Ok(ConfigValue::Float(f))
-    } else if let Ok(s) = obj.extract::<String>() {
-        Ok(ConfigValue::String(s))
-    } else if let Ok(list) = obj.cast::<PyList>() {
-        let items: PyResult<Vec<_>> = list.iter().map(|item| py_to_config_value(&item)).collect();
-        Ok(ConfigValue::List(items?))
-    } else if let Ok(dict) = obj.cast::<PyDict>() {
-        let mut config_dict = ConfigDict::new();
-        for (k, v) in dict.iter() {
-            let key: String = k.extract()?;
-            config_dict.insert(key, py_to_config_value(&v)?);
-        }
-        Ok(ConfigValue::Dict(config_dict))
-    } else {
-        Ok(ConfigValue::String(obj.str()?.to_string()))
-    }
-}
+use crate::conversions::py_dict_to_config_dict;
 
 /// Convert job overrides from Python
 fn py_to_job_overrides(overrides: &Bound<'_, PyList>) -> PyResult<JobOverrideBatch> {
@@ -170,6 +136,8 @@ impl Launcher for PyLauncherWrapper {
                     job_name,
                     task_name,
                     status_code,
+                    stdout: None,
+                    stderr: None,
                 });
             }
 
@@ -186,6 +154,72 @@ impl Launcher for PyLauncherWrapper {
 unsafe impl Send for PyLauncherWrapper {}
 unsafe impl Sync for PyLauncherWrapper {}
 
+/// Held while a capturing task runs: `sys.stdout` and `sys.stderr` are
+/// process-wide, so parallel jobs would otherwise write into each other's buffers
+static CAPTURE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Call a Python task with the job's overrides, redirecting `sys.stdout` and
+/// `sys.stderr` into the output sink while capturing
+fn run_py_task(
+    py: Python<'_>,
+    task: &Bound<'_, PyAny>,
+    overrides: &JobOverrides,
+    output: &mut TaskOutput,
+) -> PyResult<i32> {
+    if !output.is_capturing() {
+        return Ok(match task.call1((overrides.clone(),)) {
+            Ok(_) => 0,
+            Err(e) => {
+                e.display(py);
+                1
+            }
+        });
+    }
+
+    let sys = py.import("sys")?;
+    let io = py.import("io")?;
+    let stdout_buf = io.call_method0("StringIO")?;
+    let stderr_buf = io.call_method0("StringIO")?;
+    let old_stdout = sys.getattr("stdout")?;
+    let old_stderr = sys.getattr("stderr")?;
+
+    sys.setattr("stdout", &stdout_buf)?;
+    let succeeded = sys.setattr("stderr", &stderr_buf).map(|_| {
+        let result = task.call1((overrides.clone(),));
+        if let Err(e) = &result {
+            // Print the traceback while stderr is still redirected
+            e.display(py);
+        }
+        result.is_ok()
+    });
+    // Restore the original streams whether or not the task ran
+    let restored = sys
+        .setattr("stdout", old_stdout)
+        .and(sys.setattr("stderr", old_stderr));
+    let succeeded = succeeded?;
+    restored?;
+
+    let stdout: String = stdout_buf.call_method0("getvalue")?.extract()?;
+    let stderr: String = stderr_buf.call_method0("getvalue")?.extract()?;
+    output.write_stdout(&stdout);
+    output.write_stderr(&stderr);
+
+    Ok(if succeeded { 0 } else { 1 })
+}
+
+/// Wrap a Python callable as a launcher task
+///
+/// Capturing tasks run one at a time. The lock is taken before attaching to
+/// the interpreter so a waiting worker never holds the GIL.
+fn py_task(task: Py<PyAny>) -> JobTask {
+    Arc::new(move |overrides, output| {
+        let _guard = output
+            .is_capturing()
+            .then(|| CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner()));
+        Python::attach(|py| run_py_task(py, task.bind(py), overrides, output).unwrap_or(1))
+    })
+}
+
 /// Python-accessible Rust BasicLauncher
 #[pyclass(name = "RustBasicLauncher")]
 pub struct PyBasicLauncher {
@@ -195,12 +229,39 @@ pub struct PyBasicLauncher {
 #[pymethods]
 impl PyBasicLauncher {
     #[new]
-    fn new() -> Self {
+    #[pyo3(signature = (capture_output=false, max_parallel=1, allow_collisions=false))]
+    fn new(capture_output: bool, max_parallel: usize, allow_collisions: bool) -> Self {
         Self {
-            inner: BasicLauncher::new(),
+            inner: BasicLauncher::new()
+                .with_capture_output(capture_output)
+                .with_max_parallel(max_parallel)
+                .with_allow_collisions(allow_collisions),
         }
     }
 
+    /// Whether task stdout/stderr is captured into each JobReturn
+    #[getter]
+    fn capture_output(&self) -> bool {
+        self.inner.capture_output()
+    }
+
+    /// Maximum number of jobs run at once (1 = serial)
+    #[getter]
+    fn max_parallel(&self) -> usize {
+        self.inner.max_parallel()
+    }
+
+    /// Whether jobs may share an output directory
+    #[getter]
+    fn allow_collisions(&self) -> bool {
+        self.inner.allow_collisions()
+    }
+
+    /// Set the callable run for each job; it receives the job's override list
+    fn set_task(&mut self, task: Py<PyAny>) {
+        self.inner.set_task(py_task(task));
+    }
+
     /// Get launcher name
     fn name(&self) -> &str {
         self.inner.name()
@@ -215,15 +276,18 @@ impl PyBasicLauncher {
     }
 
     /// Launch jobs
+    ///
+    /// The GIL is released while jobs run so parallel workers can call the task.
+    /// With `capture_output` set, Python tasks still run one at a time.
     fn launch(
         &self,
+        py: Python<'_>,
         job_overrides: Bound<'_, PyList>,
         initial_job_idx: usize,
     ) -> PyResult<Vec<PyJobReturn>> {
         let overrides = py_to_job_overrides(&job_overrides)?;
-        let results = self
-            .inner
-            .launch(&overrides, initial_job_idx)
+        let results = py
+            .detach(|| self.inner.launch(&overrides, initial_job_idx))
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.message))?;
 
         Ok(results.iter().map(|r| PyJobReturn::from(r)).collect())
@@ -256,6 +320,24 @@ impl PyLauncherManager {
             .set_launcher(Arc::new(PyLauncherWrapper::new(launcher)));
     }
 
+    /// Register a Python launcher under a name
+    fn register(&mut self, name: &str, launcher: Py<PyAny>) {
+        self.inner
+            .register(name, Arc::new(PyLauncherWrapper::new(launcher)));
+    }
+
+    /// Make the launcher registered under `name` the active one
+    fn activate(&mut self, name: &str) -> PyResult<()> {
+        self.inner
+            .activate(name)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.message))
+    }
+
+    /// Names of all registered launchers, sorted
+    fn available_names(&self) -> Vec<String> {
+        self.inner.available_names()
+    }
+
     /// Check if a launcher is configured
     fn has_launcher(&self) -> bool {
         self.inner.launcher().is_some()
@@ -10,6 +10,7 @@ mod config;
 mod config_path;
 mod config_source;
 mod config_store;
+mod conversions;
 mod core;
 mod defaults;
 mod defaults_list;
@@ -45,6 +46,10 @@ fn lerna(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     // Parser types
     m.add_class::<parser::PyOverride>()?;
     m.add_class::<parser::PyOverrideParser>()?;
+    m.add(
+        "OverrideParseError",
+        m.py().get_type::<parser::OverrideParseError>(),
+    )?;
 
     // Default element types
     defaults::register(m)?;
@@ -95,16 +100,21 @@ fn lerna(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(utils::escape_special_characters, m)?)?;
     m.add_function(wrap_pyfunction!(utils::unescape_string, m)?)?;
     m.add_function(wrap_pyfunction!(utils::is_valid_key, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::is_valid_user_key, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::validate_user_key, m)?)?;
     m.add_function(wrap_pyfunction!(utils::split_key, m)?)?;
     m.add_function(wrap_pyfunction!(utils::join_key, m)?)?;
     m.add_function(wrap_pyfunction!(utils::normalize_file_name, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::normalize_output_file_name, m)?)?;
     m.add_function(wrap_pyfunction!(utils::get_valid_filename, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::get_valid_filename_opts, m)?)?;
     m.add_function(wrap_pyfunction!(utils::sanitize_path_component, m)?)?;
 
     // Config path functions
     m.add_function(wrap_pyfunction!(config_path::normalize_config_path, m)?)?;
     m.add_function(wrap_pyfunction!(config_path::get_parent_path, m)?)?;
     m.add_function(wrap_pyfunction!(config_path::get_basename, m)?)?;
+    m.add_function(wrap_pyfunction!(config_path::split_config_package, m)?)?;
     m.add_function(wrap_pyfunction!(config_path::join_config_paths, m)?)?;
     m.add_function(wrap_pyfunction!(config_path::is_absolute_config_path, m)?)?;
     m.add_function(wrap_pyfunction!(config_path::strip_scheme, m)?)?;
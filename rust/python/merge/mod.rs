@@ -1,84 +1,26 @@
 // Copyright (c) Facebook, Inc. and its affiliates. All Rights Reserved
 //! Python bindings for config merge module
 
-use lerna::config::{ConfigDict, ConfigValue};
+use lerna::config::ConfigValue;
 use lerna::merge::{
     apply_deletions, apply_override, collect_keys, diff_keys, get_nested, merge_configs,
-    merge_dicts,
+    merge_dicts_with,
 };
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
 
-/// Convert Python object to ConfigValue
-fn py_to_config_value(obj: &Bound<'_, PyAny>) -> PyResult<ConfigValue> {
-    if obj.is_none() {
-        return Ok(ConfigValue::Null);
-    }
-    if let Ok(b) = obj.extract::<bool>() {
-        return Ok(ConfigValue::Bool(b));
-    }
-    if let Ok(i) = obj.extract::<i64>() {
-        return Ok(ConfigValue::Int(i));
-    }
-    if let Ok(f) = obj.extract::<f64>() {
-        return Ok(ConfigValue::Float(f));
-    }
-    if let Ok(s) = obj.extract::<String>() {
-        // Check for MISSING marker
-        if s == "???" {
-            return Ok(ConfigValue::Missing);
-        }
-        return Ok(ConfigValue::String(s));
-    }
-    if let Ok(list) = obj.cast::<PyList>() {
-        let items: PyResult<Vec<ConfigValue>> =
-            list.iter().map(|item| py_to_config_value(&item)).collect();
-        return Ok(ConfigValue::List(items?));
-    }
-    if let Ok(dict) = obj.cast::<PyDict>() {
-        let mut result = ConfigDict::new();
-        for (key, val) in dict.iter() {
-            let k: String = key.extract()?;
-            let v = py_to_config_value(&val)?;
-            result.insert(k, v);
-        }
-        return Ok(ConfigValue::Dict(result));
-    }
-    Ok(ConfigValue::String(obj.str()?.to_string()))
-}
-
-/// Convert ConfigValue to Python object
-fn config_value_to_py(py: Python<'_>, value: &ConfigValue) -> PyResult<Py<PyAny>> {
-    match value {
-        ConfigValue::Null => Ok(py.None()),
-        ConfigValue::Bool(b) => Ok((*b).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::Int(i) => Ok((*i).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::Float(f) => Ok((*f).into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::String(s) => Ok(s.as_str().into_pyobject(py)?.to_owned().into_any().unbind()),
-        ConfigValue::List(items) => {
-            let list = PyList::new(py, items.iter().map(|i| config_value_to_py(py, i).unwrap()))?;
-            Ok(list.into())
-        }
-        ConfigValue::Dict(dict) => {
-            let py_dict = PyDict::new(py);
-            for (k, v) in dict.iter() {
-                py_dict.set_item(k, config_value_to_py(py, v)?)?;
-            }
-            Ok(py_dict.into())
-        }
-        ConfigValue::Interpolation(s) => {
-            Ok(s.as_str().into_pyobject(py)?.to_owned().into_any().unbind())
-        }
-        ConfigValue::Missing => Ok("???".into_pyobject(py)?.to_owned().into_any().unbind()),
-    }
-}
+use crate::conversions::{config_value_to_py, py_to_config_value};
 
 /// Merge two config dictionaries
+///
+/// A `None` in `other` sets the key to None, or removes it if `null_deletes`.
 #[pyfunction]
+#[pyo3(signature = (base, other, null_deletes=false))]
 fn merge_config_dicts(
     py: Python<'_>,
     base: &Bound<'_, PyDict>,
     other: &Bound<'_, PyDict>,
+    null_deletes: bool,
 ) -> PyResult<Py<PyAny>> {
     let base_val = py_to_config_value(&base.as_any())?;
     let other_val = py_to_config_value(&other.as_any())?;
@@ -93,7 +35,8 @@ fn merge_config_dicts(
         _ => return Err(pyo3::exceptions::PyValueError::new_err("Expected dict")),
     };
 
-    merge_dicts(&mut base_dict, &other_dict);
+    merge_dicts_with(&mut base_dict, &other_dict, null_deletes)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.message))?;
     config_value_to_py(py, &ConfigValue::Dict(base_dict))
 }
 
@@ -114,7 +57,8 @@ fn merge_multiple_configs(py: Python<'_>, configs: &Bound<'_, PyList>) -> PyResu
         }
     }
 
-    let result = merge_configs(&cfg_list);
+    let result =
+        merge_configs(&cfg_list).map_err(|e| pyo3::exceptions::PyValueError::new_err(e.message))?;
     config_value_to_py(py, &ConfigValue::Dict(result))
 }
 
@@ -150,7 +94,8 @@ fn apply_config_override(
     };
 
     let override_val = py_to_config_value(value)?;
-    apply_override(&mut dict, path, override_val);
+    apply_override(&mut dict, path, override_val)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.message))?;
     config_value_to_py(py, &ConfigValue::Dict(dict))
 }
 
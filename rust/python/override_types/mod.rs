@@ -2,6 +2,8 @@
 //! PyO3 bindings for override types
 
 use pyo3::prelude::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
 use lerna::{
     Key as RustKey, OverrideType as RustOverrideType, Quote as RustQuote,
@@ -51,7 +53,7 @@ impl From<PyQuote> for RustQuote {
 }
 
 /// Python-exposed OverrideType enum
-#[pyclass(name = "OverrideType", eq, eq_int)]
+#[pyclass(name = "OverrideType", eq, eq_int, frozen, hash)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum PyOverrideType {
     #[pyo3(name = "CHANGE")]
@@ -96,7 +98,7 @@ impl From<RustOverrideType> for PyOverrideType {
 }
 
 /// Python-exposed ValueType enum
-#[pyclass(name = "ValueType", eq, eq_int)]
+#[pyclass(name = "ValueType", eq, eq_int, frozen, hash)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum PyValueType {
     #[pyo3(name = "ELEMENT")]
@@ -242,11 +244,25 @@ impl PyKey {
     }
 
     fn __repr__(&self) -> String {
+        let package = match &self.inner.package {
+            Some(pkg) => format!("'{}'", pkg),
+            None => "None".to_string(),
+        };
         format!(
-            "Key(key_or_group={:?}, package={:?})",
-            self.inner.key_or_group, self.inner.package
+            "Key(key_or_group='{}', package={})",
+            self.inner.key_or_group, package
         )
     }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.inner.hash(&mut hasher);
+        hasher.finish()
+    }
 }
 
 impl From<RustKey> for PyKey {
@@ -2,7 +2,8 @@
 //! Python bindings for package resolution module
 
 use lerna::package::{
-    compute_target_path, join_path, parse_package_header, split_path, PackageResolver,
+    compute_final_package as rust_compute_final_package, compute_target_path, join_path,
+    parse_package_header, split_path, PackageResolver,
 };
 use pyo3::prelude::*;
 
@@ -61,6 +62,19 @@ fn compute_config_target_path(package: &str, key_path: &str) -> String {
     compute_target_path(package, key_path)
 }
 
+/// Compute a config's final package from its group, name, package header
+/// and parent package
+#[pyfunction]
+#[pyo3(signature = (group, name=None, package_header=None, parent_package=""))]
+fn compute_final_package(
+    group: &str,
+    name: Option<&str>,
+    package_header: Option<&str>,
+    parent_package: &str,
+) -> String {
+    rust_compute_final_package(group, name, package_header, parent_package)
+}
+
 /// Split a dotted path into components
 #[pyfunction]
 fn split_dotted_path(path: &str) -> Vec<String> {
@@ -79,6 +93,7 @@ pub fn register(parent: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyPackageResolver>()?;
     m.add_function(wrap_pyfunction!(parse_package_from_header, &m)?)?;
     m.add_function(wrap_pyfunction!(compute_config_target_path, &m)?)?;
+    m.add_function(wrap_pyfunction!(compute_final_package, &m)?)?;
     m.add_function(wrap_pyfunction!(split_dotted_path, &m)?)?;
     m.add_function(wrap_pyfunction!(join_dotted_path, &m)?)?;
     parent.add_submodule(&m)?;
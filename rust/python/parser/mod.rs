@@ -1,20 +1,40 @@
 // Copyright (c) Facebook, Inc. and its affiliates. All Rights Reserved
 //! PyO3 bindings for override parser
 
+use pyo3::create_exception;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList, PySet};
 use std::sync::Arc;
 
+use lerna::parser::split_package_list;
 use lerna::{
     ChoiceSweep as RustChoiceSweep, FunctionCallback, IntervalSweep as RustIntervalSweep,
     Override as RustOverride, OverrideParser as RustOverrideParser,
-    OverrideValue as RustOverrideValue, ParsedElement as RustParsedElement,
-    RangeSweep as RustRangeSweep, ValueType as RustValueType,
+    OverrideValue as RustOverrideValue, ParseError as RustParseError,
+    ParsedElement as RustParsedElement, RangeSweep as RustRangeSweep, ValueType as RustValueType,
 };
 
 use crate::override_types::{PyKey, PyOverrideType, PyQuotedString, PyValueType};
 
+// Raised for malformed overrides; subclasses ValueError so existing handlers keep working
+create_exception!(lerna, OverrideParseError, PyValueError);
+
+/// Convert a Rust parse error to OverrideParseError with `kind` and `position` attributes
+pub(crate) fn parse_error_to_py(e: RustParseError) -> PyErr {
+    Python::attach(|py| {
+        let err = OverrideParseError::new_err(format!("{}", e));
+        let value = err.value(py);
+        if let Err(attr_err) = value
+            .setattr("kind", e.kind.as_str())
+            .and_then(|_| value.setattr("position", e.position))
+        {
+            return attr_err;
+        }
+        err
+    })
+}
+
 /// Wrapper around Python Functions object to call user-defined functions from Rust.
 ///
 /// This allows the Rust parser to delegate unknown function calls to Python.
@@ -182,14 +202,7 @@ fn elem_to_source(elem: &RustParsedElement) -> String {
             }
         }
         RustParsedElement::Int(i) => i.to_string(),
-        RustParsedElement::Float(f) => {
-            let s = f.to_string();
-            if s.contains('.') {
-                s
-            } else {
-                format!("{}.0", s)
-            }
-        }
+        RustParsedElement::Float(f) => lerna::format_float(*f),
         RustParsedElement::String(s) => s.clone(),
         RustParsedElement::QuotedString(qs) => {
             let q = match qs.quote {
@@ -570,6 +583,7 @@ fn override_value_to_py(py: Python<'_>, value: &RustOverrideValue) -> PyResult<P
             }
             dict.set_item("include", include)?;
             dict.set_item("exclude", exclude)?;
+            dict.set_item("case_insensitive", glob.case_insensitive)?;
             Ok(dict.unbind().into_any())
         }
         RustOverrideValue::ListExtension(ext) => {
@@ -592,43 +606,32 @@ fn override_value_to_py(py: Python<'_>, value: &RustOverrideValue) -> PyResult<P
     }
 }
 
+/// `parse_many_collect` results: the overrides of each input, and the
+/// `(index, message)` of each failure
+type CollectedPyOverrides = (Vec<Option<Vec<Py<PyOverride>>>>, Vec<(usize, String)>);
+
 /// Python-exposed OverrideParser
 #[pyclass(name = "OverrideParser")]
 pub struct PyOverrideParser {
     /// Optional callback for user-defined functions
     callback: Option<Arc<dyn FunctionCallback>>,
+    /// Whether quoted strings support `\n`-style escape sequences
+    escapes: bool,
 }
 
-#[pymethods]
 impl PyOverrideParser {
-    #[new]
-    #[pyo3(signature = (functions=None))]
-    fn new(functions: Option<Py<PyAny>>) -> Self {
-        let callback =
-            functions.map(|f| Arc::new(PyFunctionCallback::new(f)) as Arc<dyn FunctionCallback>);
-        Self { callback }
-    }
-
-    /// Parse a single override string
-    fn parse(&self, s: &str) -> PyResult<PyOverride> {
-        let result = if let Some(ref callback) = self.callback {
-            RustOverrideParser::parse_with_callback(s, callback.clone())
-        } else {
-            RustOverrideParser::parse(s)
+    /// Parse one override with this parser's callback and options
+    fn parse_one(&self, s: &str) -> Result<RustOverride, RustParseError> {
+        let parser = match self.callback {
+            Some(ref callback) => RustOverrideParser::with_callback(s, callback.clone()),
+            None => RustOverrideParser::new(s),
         };
-        result
-            .map(|o| o.into())
-            .map_err(|e| PyValueError::new_err(format!("{}", e)))
+        parser.with_escapes(self.escapes).parse_complete()
     }
 
-    /// Parse and return full data as a dictionary for Python to use
-    fn parse_to_dict(&self, py: Python<'_>, s: &str) -> PyResult<Py<PyDict>> {
-        let result = if let Some(ref callback) = self.callback {
-            RustOverrideParser::parse_with_callback(s, callback.clone())
-        } else {
-            RustOverrideParser::parse(s)
-        }
-        .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+    /// Parse one override into a dictionary of its parts
+    fn parse_one_to_dict(&self, py: Python<'_>, s: &str) -> PyResult<Py<PyDict>> {
+        let result = self.parse_one(s).map_err(parse_error_to_py)?;
 
         let dict = PyDict::new(py);
 
@@ -672,16 +675,47 @@ impl PyOverrideParser {
 
         Ok(dict.unbind())
     }
+}
+
+#[pymethods]
+impl PyOverrideParser {
+    #[new]
+    #[pyo3(signature = (functions=None, escapes=false))]
+    fn new(functions: Option<Py<PyAny>>, escapes: bool) -> Self {
+        let callback =
+            functions.map(|f| Arc::new(PyFunctionCallback::new(f)) as Arc<dyn FunctionCallback>);
+        Self { callback, escapes }
+    }
+
+    /// Parse a single override string
+    fn parse(&self, s: &str) -> PyResult<PyOverride> {
+        self.parse_one(s)
+            .map(|o| o.into())
+            .map_err(parse_error_to_py)
+    }
+
+    /// Parse and return full data as a dictionary for Python to use
+    fn parse_to_dict(&self, py: Python<'_>, s: &str) -> PyResult<Py<PyDict>> {
+        self.parse_one_to_dict(py, s)
+    }
+
+    /// Parse an override into one dictionary per package
+    ///
+    /// An override naming several packages (`model@gpu0,gpu1=resnet`) gives
+    /// one dictionary for each, as in `parse_many`; any other override gives
+    /// a single dictionary.
+    fn parse_to_dicts(&self, py: Python<'_>, s: &str) -> PyResult<Vec<Py<PyDict>>> {
+        split_package_list(s)
+            .iter()
+            .map(|o| self.parse_one_to_dict(py, o))
+            .collect()
+    }
 
     /// Parse multiple override strings
     fn parse_many(&self, py: Python<'_>, overrides: Vec<String>) -> PyResult<Py<PyList>> {
         let str_refs: Vec<&str> = overrides.iter().map(|s| s.as_str()).collect();
-        let results = if let Some(ref callback) = self.callback {
-            RustOverrideParser::parse_many_with_callback(&str_refs, callback.clone())
-        } else {
-            RustOverrideParser::parse_many(&str_refs)
-        }
-        .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        let results = RustOverrideParser::parse_many_using(&str_refs, |s| self.parse_one(s))
+            .map_err(parse_error_to_py)?;
 
         let list = PyList::empty(py);
         for o in results {
@@ -691,6 +725,47 @@ impl PyOverrideParser {
         Ok(list.unbind())
     }
 
+    /// Parse multiple override strings, collecting every error
+    ///
+    /// Returns `(results, errors)`: for each input override, the list of
+    /// overrides it expands to (several for `key@pkg1,pkg2=value`) or None
+    /// where it failed to parse, and an `(index, message)` pair for each
+    /// failure.
+    fn parse_many_collect(
+        &self,
+        py: Python<'_>,
+        overrides: Vec<String>,
+    ) -> PyResult<CollectedPyOverrides> {
+        let str_refs: Vec<&str> = overrides.iter().map(|s| s.as_str()).collect();
+        let (results, errors) =
+            RustOverrideParser::parse_many_collect_using(&str_refs, |s| self.parse_one(s));
+
+        let results = results
+            .into_iter()
+            .map(|parsed| {
+                parsed
+                    .map(|parsed| {
+                        parsed
+                            .into_iter()
+                            .map(|o| Py::new(py, PyOverride::from(o)))
+                            .collect::<PyResult<Vec<_>>>()
+                    })
+                    .transpose()
+            })
+            .collect::<PyResult<_>>()?;
+        let errors = errors
+            .into_iter()
+            .map(|(idx, e)| (idx, e.to_string()))
+            .collect();
+        Ok((results, errors))
+    }
+
+    /// Reassemble overrides split across argv tokens (e.g. `["key", "=", "value"]`)
+    #[staticmethod]
+    fn join_argv(args: Vec<String>) -> Vec<String> {
+        RustOverrideParser::join_argv(&args)
+    }
+
     /// Parse many overrides and return full data as dictionaries
     fn parse_many_to_dict(&self, py: Python<'_>, overrides: Vec<String>) -> PyResult<Py<PyList>> {
         let list = PyList::empty(py);
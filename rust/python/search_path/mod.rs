@@ -2,11 +2,14 @@
 //! PyO3 bindings for config search path management
 
 use lerna::search_path::{
-    ConfigSearchPath as RustConfigSearchPath, SearchPathElement as RustSearchPathElement,
-    SearchPathQuery as RustSearchPathQuery,
+    from_config as rust_from_config, ConfigSearchPath as RustConfigSearchPath,
+    SearchPathElement as RustSearchPathElement, SearchPathQuery as RustSearchPathQuery,
 };
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
+use crate::conversions::py_to_config_value;
+
 /// A single element in the config search path
 #[pyclass(name = "SearchPathElement")]
 #[derive(Clone)]
@@ -251,10 +254,26 @@ impl PyConfigSearchPath {
     }
 }
 
+/// Read the `hydra.searchpath` entries from a config dict
+///
+/// Raises ValueError if the search path is not a list of valid entries.
+#[pyfunction]
+fn search_path_from_config(config: &Bound<'_, PyAny>) -> PyResult<Vec<PySearchPathElement>> {
+    let config = py_to_config_value(config)?;
+    let entries = rust_from_config(&config).map_err(|e| PyValueError::new_err(e.message))?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| PySearchPathElement {
+            inner: RustSearchPathElement::new(entry.provider, entry.path),
+        })
+        .collect())
+}
+
 /// Register search path types with the module
 pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PySearchPathElement>()?;
     m.add_class::<PySearchPathQuery>()?;
     m.add_class::<PyConfigSearchPath>()?;
+    m.add_function(wrap_pyfunction!(search_path_from_config, m)?)?;
     Ok(())
 }
@@ -1,9 +1,14 @@
 // Copyright (c) Facebook, Inc. and its affiliates. All Rights Reserved
 //! PyO3 bindings for sweep expansion
 
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyList;
 
+use lerna::OverrideParser;
+
+use crate::parser::parse_error_to_py;
+
 /// Expand sweep overrides into individual override sets.
 ///
 /// Given a list of overrides like:
@@ -17,10 +22,20 @@ use pyo3::types::PyList;
 /// - ["db=postgresql", "server=prod"]
 ///
 /// Also supports range sweeps like "x=range(1,10)".
+///
+/// Shuffled sweeps like "x=shuffle(range(1,10))" are permuted randomly, or
+/// deterministically from `seed` if given. Raises ValueError if an
+/// override does not parse.
 #[pyfunction]
-pub fn expand_sweeps(py: Python, overrides: Vec<String>) -> PyResult<Py<PyList>> {
+#[pyo3(signature = (overrides, seed=None))]
+pub fn expand_sweeps(
+    py: Python,
+    overrides: Vec<String>,
+    seed: Option<u64>,
+) -> PyResult<Py<PyList>> {
     let override_refs: Vec<&str> = overrides.iter().map(|s| s.as_str()).collect();
-    let result = lerna::expand_simple_sweeps(&override_refs);
+    let parsed = OverrideParser::parse_many(&override_refs).map_err(parse_error_to_py)?;
+    let result = lerna::expand_sweeps_with_seed(&parsed, seed);
 
     let outer_list = PyList::empty(py);
     for combo in result {
@@ -34,15 +49,38 @@ pub fn expand_sweeps(py: Python, overrides: Vec<String>) -> PyResult<Py<PyList>>
 /// Get the number of combinations for a set of overrides.
 ///
 /// This is useful for determining sweep size without expanding.
+/// Raises ValueError if an override does not parse.
 #[pyfunction]
-pub fn count_sweep_combinations(overrides: Vec<String>) -> usize {
+pub fn count_sweep_combinations(overrides: Vec<String>) -> PyResult<usize> {
     let override_refs: Vec<&str> = overrides.iter().map(|s| s.as_str()).collect();
-    let result = lerna::expand_simple_sweeps(&override_refs);
-    result.len()
+    let parsed = OverrideParser::parse_many(&override_refs).map_err(parse_error_to_py)?;
+    Ok(lerna::expand_sweeps_with_seed(&parsed, None).len())
+}
+
+/// Render an override in canonical Hydra syntax.
+///
+/// The override is parsed and its value re-emitted, e.g.
+/// "x=tag(lr,grid,range(1,10))" becomes "x=tag(grid,lr,range(1,10))".
+/// The result parses back to the same value.
+#[pyfunction]
+pub fn sweep_to_override_string(override_str: &str) -> PyResult<String> {
+    let parsed = OverrideParser::parse(override_str).map_err(parse_error_to_py)?;
+    let key = match &parsed.key.package {
+        Some(package) => format!("{}@{}", parsed.key.key_or_group, package),
+        None => parsed.key.key_or_group.clone(),
+    };
+    match &parsed.value {
+        Some(value) => Ok(lerna::sweep_to_override_string(&key, value)),
+        None => Err(PyValueError::new_err(format!(
+            "Override has no value to render: {}",
+            override_str
+        ))),
+    }
 }
 
 pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(expand_sweeps, m)?)?;
     m.add_function(wrap_pyfunction!(count_sweep_combinations, m)?)?;
+    m.add_function(wrap_pyfunction!(sweep_to_override_string, m)?)?;
     Ok(())
 }
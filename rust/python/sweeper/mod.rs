@@ -10,49 +10,15 @@ use pyo3::types::{PyDict, PyList};
 use std::sync::Arc;
 
 use lerna::callback::JobReturn;
-use lerna::config::value::{ConfigDict, ConfigValue};
+use lerna::config::value::ConfigDict;
 use lerna::launcher::{BasicLauncher, Launcher};
-use lerna::sweeper::{BasicSweeper, Sweeper, SweeperError, SweeperManager};
+use lerna::sweeper::{
+    BasicSweeper, SweepCombination, SweepConstraint, SweepProgress, Sweeper, SweeperError,
+    SweeperManager,
+};
 
 use crate::callback::PyJobReturn;
-
-/// Convert Python dict to ConfigDict
-fn py_dict_to_config_dict(dict: &Bound<'_, PyDict>) -> PyResult<ConfigDict> {
-    let mut config = ConfigDict::new();
-    for (key, value) in dict.iter() {
-        let k: String = key.extract()?;
-        let v = py_to_config_value(&value)?;
-        config.insert(k, v);
-    }
-    Ok(config)
-}
-
-/// Convert Python object to ConfigValue
-fn py_to_config_value(obj: &Bound<'_, PyAny>) -> PyResult<ConfigValue> {
-    if obj.is_none() {
-        Ok(ConfigValue::Null)
-    } else if let Ok(b) = obj.extract::<bool>() {
-        Ok(ConfigValue::Bool(b))
-    } else if let Ok(i) = obj.extract::<i64>() {
-        Ok(ConfigValue::Int(i))
-    } else if let Ok(f) = obj.extract::<f64>() {
-        Ok(ConfigValue::Float(f))
-    } else if let Ok(s) = obj.extract::<String>() {
-        Ok(ConfigValue::String(s))
-    } else if let Ok(list) = obj.cast::<PyList>() {
-        let items: PyResult<Vec<_>> = list.iter().map(|item| py_to_config_value(&item)).collect();
-        Ok(ConfigValue::List(items?))
-    } else if let Ok(dict) = obj.cast::<PyDict>() {
-        let mut config_dict = ConfigDict::new();
-        for (k, v) in dict.iter() {
-            let key: String = k.extract()?;
-            config_dict.insert(key, py_to_config_value(&v)?);
-        }
-        Ok(ConfigValue::Dict(config_dict))
-    } else {
-        Ok(ConfigValue::String(obj.str()?.to_string()))
-    }
-}
+use crate::conversions::py_dict_to_config_dict;
 
 /// Wrapper that allows Python Sweeper to implement Rust Sweeper trait
 pub struct PySweeperWrapper {
@@ -155,6 +121,8 @@ impl Sweeper for PySweeperWrapper {
                     job_name,
                     task_name,
                     status_code,
+                    stdout: None,
+                    stderr: None,
                 });
             }
 
@@ -181,12 +149,28 @@ pub struct PyBasicSweeper {
 #[pymethods]
 impl PyBasicSweeper {
     #[new]
-    #[pyo3(signature = (max_batch_size=None))]
-    fn new(max_batch_size: Option<usize>) -> Self {
-        Self {
-            inner: BasicSweeper::new(max_batch_size),
-            launcher: None,
+    #[pyo3(signature = (max_batch_size=None, combination="grid", zip_groups=None, seed=None))]
+    fn new(
+        max_batch_size: Option<usize>,
+        combination: &str,
+        zip_groups: Option<Vec<Vec<String>>>,
+        seed: Option<u64>,
+    ) -> PyResult<Self> {
+        let combination: SweepCombination = combination
+            .parse()
+            .map_err(|e: SweeperError| pyo3::exceptions::PyValueError::new_err(e.message))?;
+        let mut inner = BasicSweeper::new(max_batch_size).with_combination(combination);
+        for group in zip_groups.unwrap_or_default() {
+            let keys: Vec<&str> = group.iter().map(String::as_str).collect();
+            inner.add_zip_group(&keys);
         }
+        if let Some(seed) = seed {
+            inner.set_seed(seed);
+        }
+        Ok(Self {
+            inner,
+            launcher: None,
+        })
     }
 
     /// Get sweeper name
@@ -194,6 +178,73 @@ impl PyBasicSweeper {
         self.inner.name()
     }
 
+    /// Get the combination mode ("grid" or "zip")
+    #[getter]
+    fn combination(&self) -> &str {
+        match self.inner.combination() {
+            SweepCombination::Grid => "grid",
+            SweepCombination::Zip => "zip",
+        }
+    }
+
+    /// Seed for shuffled ranges, or None for a random order
+    #[getter]
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    /// Add a constraint `fn(dict) -> bool` that receives each combination as a
+    /// dict of override keys to values; combinations it rejects are not launched
+    fn add_constraint(&mut self, constraint: Py<PyAny>) {
+        let constraint: SweepConstraint = Arc::new(move |combination| {
+            Python::attach(|py| {
+                let dict = PyDict::new(py);
+                for (key, value) in combination {
+                    dict.set_item(key, value)
+                        .map_err(|e| SweeperError::new(e.to_string()))?;
+                }
+                constraint
+                    .call1(py, (dict,))
+                    .and_then(|result| result.bind(py).is_truthy())
+                    .map_err(|e| SweeperError::new(format!("Sweep constraint failed: {}", e)))
+            })
+        });
+        self.inner.add_constraint(constraint);
+    }
+
+    /// Set a callable `on_progress(idx, total, job_return)` run after each
+    /// completed job; exceptions it raises are printed and ignored
+    fn set_progress(&mut self, on_progress: Py<PyAny>) {
+        let progress: SweepProgress = Arc::new(move |idx, total, job_return| {
+            Python::attach(|py| {
+                let job_return = PyJobReturn::from(job_return);
+                if let Err(e) = on_progress.call1(py, (idx, total, job_return)) {
+                    e.display(py);
+                }
+            })
+        });
+        self.inner.set_progress(progress);
+    }
+
+    /// Resume an interrupted sweep: skip the job indices listed in the
+    /// checkpoint file at `path` and record newly completed jobs there
+    fn resume_from(&mut self, path: &str) -> PyResult<()> {
+        self.inner
+            .resume_from(path)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.message))
+    }
+
+    /// Append the index of each successful job to the checkpoint file at `path`
+    fn set_checkpoint(&mut self, path: &str) {
+        self.inner.set_checkpoint(path);
+    }
+
+    /// Indices of the jobs skipped as already completed, sorted
+    #[getter]
+    fn completed(&self) -> Vec<usize> {
+        self.inner.completed().iter().copied().collect()
+    }
+
     /// Setup the sweeper with config (creates internal BasicLauncher)
     fn setup(&mut self, config: Bound<'_, PyDict>, task_name: &str) -> PyResult<()> {
         let config_dict = py_dict_to_config_dict(&config)?;
@@ -250,6 +301,24 @@ impl PySweeperManager {
             .set_sweeper(Arc::new(PySweeperWrapper::new(sweeper)));
     }
 
+    /// Register a Python sweeper under a name
+    fn register(&mut self, name: &str, sweeper: Py<PyAny>) {
+        self.inner
+            .register(name, Arc::new(PySweeperWrapper::new(sweeper)));
+    }
+
+    /// Make the sweeper registered under `name` the active one
+    fn activate(&mut self, name: &str) -> PyResult<()> {
+        self.inner
+            .activate(name)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.message))
+    }
+
+    /// Names of all registered sweepers, sorted
+    fn available_names(&self) -> Vec<String> {
+        self.inner.available_names()
+    }
+
     /// Check if a sweeper is configured
     fn has_sweeper(&self) -> bool {
         self.inner.sweeper().is_some()
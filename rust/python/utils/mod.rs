@@ -1,14 +1,18 @@
 // Copyright (c) Facebook, Inc. and its affiliates. All Rights Reserved
 //! PyO3 bindings for utility functions
 
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
 use lerna::{
     escape_special_characters as rust_escape, get_valid_filename as rust_get_valid_filename,
-    is_valid_key as rust_is_valid_key, join_key as rust_join_key,
+    get_valid_filename_opts as rust_get_valid_filename_opts, is_valid_key as rust_is_valid_key,
+    is_valid_user_key as rust_is_valid_user_key, join_key as rust_join_key,
     normalize_file_name as rust_normalize_file_name,
+    normalize_output_file_name as rust_normalize_output_file_name,
     sanitize_path_component as rust_sanitize_path_component, split_key as rust_split_key,
-    unescape_string as rust_unescape,
+    unescape_string as rust_unescape, validate_user_key as rust_validate_user_key,
+    OUTPUT_FILE_EXTENSIONS,
 };
 
 /// Escape special characters in a string for use in configuration values
@@ -29,7 +33,22 @@ pub fn is_valid_key(key: &str) -> bool {
     rust_is_valid_key(key)
 }
 
+/// Check if a key is valid and not reserved by Hydra (hydra, defaults, _self_)
+#[pyfunction]
+pub fn is_valid_user_key(key: &str) -> bool {
+    rust_is_valid_user_key(key)
+}
+
+/// Validate a user config key, raising ValueError with the reason on rejection
+#[pyfunction]
+pub fn validate_user_key(key: &str) -> PyResult<()> {
+    rust_validate_user_key(key).map_err(PyValueError::new_err)
+}
+
 /// Split a dotted key into parts
+///
+/// Empty parts are kept (`a..b` gives `["a", "", "b"]`) and an escaped dot
+/// (`a\.b`) does not split, so `join_key(split_key(s)) == s`.
 #[pyfunction]
 pub fn split_key(key: &str) -> Vec<String> {
     rust_split_key(key)
@@ -51,12 +70,40 @@ pub fn normalize_file_name(filename: &str) -> String {
     rust_normalize_file_name(filename)
 }
 
+/// Sanitize an output file name, keeping a known trailing extension
+///
+/// `preserve_extensions` defaults to common config and output extensions.
+#[pyfunction]
+#[pyo3(signature = (name, preserve_extensions=None))]
+pub fn normalize_output_file_name(name: &str, preserve_extensions: Option<Vec<String>>) -> String {
+    match preserve_extensions {
+        Some(exts) => {
+            let refs: Vec<&str> = exts.iter().map(|s| s.as_str()).collect();
+            rust_normalize_output_file_name(name, &refs)
+        }
+        None => rust_normalize_output_file_name(name, OUTPUT_FILE_EXTENSIONS),
+    }
+}
+
 /// Get a valid filename by stripping invalid characters
 #[pyfunction]
 pub fn get_valid_filename(s: &str) -> String {
     rust_get_valid_filename(s)
 }
 
+/// Get a valid filename, replacing invalid characters and capping its length
+///
+/// Raises ValueError if `replacement` is not itself a valid filename character.
+#[pyfunction]
+#[pyo3(signature = (s, replacement='_', max_len=None))]
+pub fn get_valid_filename_opts(
+    s: &str,
+    replacement: char,
+    max_len: Option<usize>,
+) -> PyResult<String> {
+    rust_get_valid_filename_opts(s, replacement, max_len).map_err(PyValueError::new_err)
+}
+
 /// Sanitize a string for use in a file path
 #[pyfunction]
 pub fn sanitize_path_component(s: &str) -> String {
@@ -2,10 +2,20 @@
 //! Python bindings for config validation
 
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
+use pyo3::types::PyDict;
 
-use lerna::config::value::{ConfigDict, ConfigValue};
-use lerna::validation::{ConfigSchema, TypeSpec};
+use lerna::config::interpolation::ResolverContext;
+use lerna::config::value::ConfigValue;
+use lerna::validation::{
+    check_override_types as rust_check_override_types, validate_defaults as rust_validate_defaults,
+    validate_defaults_in_repo, validate_interpolations_with, ConfigSchema, TypeSpec,
+};
+use lerna::{OverrideParser, ResultDefault};
+
+use crate::config::PyConfigRepository;
+use crate::conversions::{py_dict_to_config_dict, py_to_config_value};
+use crate::defaults::PyResultDefault;
+use crate::parser::parse_error_to_py;
 
 /// Python wrapper for TypeSpec
 #[pyclass(name = "TypeSpec")]
@@ -104,6 +114,27 @@ impl PyValidationError {
     }
 }
 
+/// Python wrapper for ValidationWarning
+#[pyclass(name = "ValidationWarning")]
+#[derive(Clone)]
+pub struct PyValidationWarning {
+    #[pyo3(get)]
+    pub path: String,
+    #[pyo3(get)]
+    pub message: String,
+}
+
+#[pymethods]
+impl PyValidationWarning {
+    fn __repr__(&self) -> String {
+        format!("{}: {}", self.path, self.message)
+    }
+
+    fn __str__(&self) -> String {
+        format!("{}: {}", self.path, self.message)
+    }
+}
+
 /// Python wrapper for ConfigSchema
 #[pyclass(name = "ConfigSchema")]
 #[derive(Clone)]
@@ -168,43 +199,6 @@ impl PyConfigSchema {
     }
 }
 
-/// Convert a Python object to ConfigValue
-fn py_to_config_value(obj: &Bound<'_, PyAny>) -> PyResult<ConfigValue> {
-    if obj.is_none() {
-        Ok(ConfigValue::Null)
-    } else if let Ok(b) = obj.extract::<bool>() {
-        Ok(ConfigValue::Bool(b))
-    } else if let Ok(i) = obj.extract::<i64>() {
-        Ok(ConfigValue::Int(i))
-    } else if let Ok(f) = obj.extract::<f64>() {
-        Ok(ConfigValue::Float(f))
-    } else if let Ok(s) = obj.extract::<String>() {
-        Ok(ConfigValue::String(s))
-    } else if let Ok(list) = obj.cast::<PyList>() {
-        let mut items = Vec::new();
-        for item in list.iter() {
-            items.push(py_to_config_value(&item)?);
-        }
-        Ok(ConfigValue::List(items))
-    } else if let Ok(dict) = obj.cast::<PyDict>() {
-        let config_dict = py_dict_to_config_dict(dict)?;
-        Ok(ConfigValue::Dict(config_dict))
-    } else {
-        Ok(ConfigValue::String(obj.str()?.to_string()))
-    }
-}
-
-/// Convert a Python dict to ConfigDict
-fn py_dict_to_config_dict(dict: &Bound<'_, PyDict>) -> PyResult<ConfigDict> {
-    let mut config_dict = ConfigDict::new();
-    for (key, value) in dict.iter() {
-        if let Ok(k) = key.extract::<String>() {
-            config_dict.insert(k, py_to_config_value(&value)?);
-        }
-    }
-    Ok(config_dict)
-}
-
 /// Validate a config against a type spec
 #[pyfunction]
 fn validate_type(value: &Bound<'_, PyAny>, type_spec: &PyTypeSpec) -> PyResult<bool> {
@@ -212,13 +206,86 @@ fn validate_type(value: &Bound<'_, PyAny>, type_spec: &PyTypeSpec) -> PyResult<b
     Ok(type_spec.inner.matches(&config_value))
 }
 
+/// Check that override strings keep the types of the config values they replace
+#[pyfunction]
+fn check_override_types(
+    config: &Bound<'_, PyDict>,
+    overrides: Vec<String>,
+) -> PyResult<Vec<PyValidationWarning>> {
+    let config = ConfigValue::Dict(py_dict_to_config_dict(config)?);
+    let parsed = overrides
+        .iter()
+        .map(|line| OverrideParser::parse(line).map_err(parse_error_to_py))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(rust_check_override_types(&config, &parsed)
+        .into_iter()
+        .map(|w| PyValidationWarning {
+            path: w.path,
+            message: w.message,
+        })
+        .collect())
+}
+
+/// Check a resolved defaults list for conflicting group selections
+///
+/// With `repo`, defaults whose config does not exist are reported too.
+#[pyfunction]
+#[pyo3(signature = (defaults, repo=None))]
+fn validate_defaults(
+    defaults: Vec<PyRef<'_, PyResultDefault>>,
+    repo: Option<PyRef<'_, PyConfigRepository>>,
+) -> Vec<PyValidationError> {
+    let defaults: Vec<ResultDefault> = defaults.iter().map(|d| d.inner.clone()).collect();
+    let errors = match repo {
+        Some(repo) => validate_defaults_in_repo(&defaults, &repo.inner),
+        None => rust_validate_defaults(&defaults),
+    };
+    errors
+        .into_iter()
+        .map(|e| PyValidationError {
+            path: e.path,
+            message: e.message,
+        })
+        .collect()
+}
+
+/// Check that every `${...}` reference in a config points at an existing key
+/// or a known resolver
+///
+/// `resolvers` names the resolvers registered by the application, on top of
+/// the built-in ones.
+#[pyfunction]
+#[pyo3(signature = (config, resolvers=Vec::new()))]
+fn validate_interpolations(
+    config: &Bound<'_, PyDict>,
+    resolvers: Vec<String>,
+) -> PyResult<Vec<PyValidationError>> {
+    let root = py_dict_to_config_dict(config)?;
+    let config = ConfigValue::Dict(root.clone());
+    let mut ctx = ResolverContext::new(&root);
+    for name in &resolvers {
+        ctx.register_resolver(name, |_| Ok(ConfigValue::Null));
+    }
+    Ok(validate_interpolations_with(&config, &ctx)
+        .into_iter()
+        .map(|e| PyValidationError {
+            path: e.path,
+            message: e.message,
+        })
+        .collect())
+}
+
 /// Register the module
 pub fn register(parent: &Bound<'_, PyModule>) -> PyResult<()> {
     let m = PyModule::new(parent.py(), "validation")?;
     m.add_class::<PyTypeSpec>()?;
     m.add_class::<PyValidationError>()?;
+    m.add_class::<PyValidationWarning>()?;
     m.add_class::<PyConfigSchema>()?;
     m.add_function(wrap_pyfunction!(validate_type, &m)?)?;
+    m.add_function(wrap_pyfunction!(check_override_types, &m)?)?;
+    m.add_function(wrap_pyfunction!(validate_defaults, &m)?)?;
+    m.add_function(wrap_pyfunction!(validate_interpolations, &m)?)?;
     parent.add_submodule(&m)?;
     Ok(())
 }
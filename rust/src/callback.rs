@@ -57,6 +57,10 @@ pub struct JobReturn {
     pub task_name: String,
     /// Status code (0 = success)
     pub status_code: i32,
+    /// Captured standard output, when the launcher captures task output
+    pub stdout: Option<String>,
+    /// Captured standard error, when the launcher captures task output
+    pub stderr: Option<String>,
 }
 
 /// Callback trait for lifecycle hooks
@@ -156,6 +160,12 @@ pub trait Callback: Send + Sync {
     ) -> CallbackResult<()> {
         Ok(())
     }
+
+    /// Called when composition or resolution fails, so callbacks can clean up.
+    /// `context` names the failing phase (e.g. "compose"); `message` is the error.
+    fn on_error(&self, _context: &str, _message: &str) -> CallbackResult<()> {
+        Ok(())
+    }
 }
 
 /// No-op callback that does nothing
@@ -286,6 +296,19 @@ impl Callback for CallbackManager {
         }
         Ok(())
     }
+
+    /// Every callback is notified, even if an earlier one fails; the first
+    /// failure is returned
+    fn on_error(&self, context: &str, message: &str) -> CallbackResult<()> {
+        let mut result = Ok(());
+        for callback in &self.callbacks {
+            let outcome = callback.on_error(context, message);
+            if result.is_ok() {
+                result = outcome;
+            }
+        }
+        result
+    }
 }
 
 /// A callback that logs lifecycle events (uses eprintln for simplicity)
@@ -360,6 +383,11 @@ impl Callback for LoggingCallback {
         );
         Ok(())
     }
+
+    fn on_error(&self, context: &str, message: &str) -> CallbackResult<()> {
+        eprintln!("[Callback] on_error: {}: {}", context, message);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -378,6 +406,7 @@ mod tests {
         assert!(callback
             .on_job_end(&config, &JobReturn::default(), &kwargs)
             .is_ok());
+        assert!(callback.on_error("compose", "boom").is_ok());
     }
 
     #[test]
@@ -0,0 +1,140 @@
+// Copyright (c) Facebook, Inc. and its affiliates. All Rights Reserved
+//! Registry of enum types for structured configs
+//!
+//! An enum value is stored as [`ConfigValue::Enum`], distinct from a plain
+//! string, and is only valid if its type has been registered with a set of
+//! allowed values. In YAML an enum value is written `!enum Color RED`.
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+use crate::config::value::ConfigValue;
+
+/// Error for an unknown enum type or a value it does not allow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumError {
+    pub message: String,
+}
+
+impl EnumError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for EnumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for EnumError {}
+
+/// Enum types and their allowed values, keyed by type name
+#[derive(Debug, Default)]
+pub struct EnumRegistry {
+    types: RwLock<HashMap<String, Vec<String>>>,
+}
+
+impl EnumRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an enum type, replacing any earlier definition
+    pub fn register(&self, type_name: &str, values: &[&str]) {
+        self.types
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                type_name.to_string(),
+                values.iter().map(|v| v.to_string()).collect(),
+            );
+    }
+
+    /// Allowed values of a registered enum type, in registration order
+    pub fn allowed_values(&self, type_name: &str) -> Option<Vec<String>> {
+        self.types
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(type_name)
+            .cloned()
+    }
+
+    /// Build an enum value, checking it against the registered type
+    pub fn make(&self, type_name: &str, value: &str) -> Result<ConfigValue, EnumError> {
+        let allowed = self
+            .allowed_values(type_name)
+            .ok_or_else(|| EnumError::new(format!("Unknown enum type '{}'", type_name)))?;
+        if !allowed.iter().any(|v| v == value) {
+            return Err(EnumError::new(format!(
+                "Invalid value '{}' for enum {}, expected one of: {}",
+                value,
+                type_name,
+                allowed.join(", ")
+            )));
+        }
+        Ok(ConfigValue::Enum {
+            type_name: type_name.to_string(),
+            value: value.to_string(),
+        })
+    }
+
+    /// Check every enum value inside `value` against the registry
+    pub fn validate(&self, value: &ConfigValue) -> Result<(), EnumError> {
+        match value {
+            ConfigValue::Enum { type_name, value } => self.make(type_name, value).map(|_| ()),
+            ConfigValue::List(items) => items.iter().try_for_each(|item| self.validate(item)),
+            ConfigValue::Dict(dict) => dict.iter().try_for_each(|(_, v)| self.validate(v)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Remove all registered types
+    pub fn clear(&self) {
+        self.types
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+static INSTANCE: OnceLock<EnumRegistry> = OnceLock::new();
+
+/// Get the global enum registry, used when loading `!enum` YAML tags
+pub fn instance() -> &'static EnumRegistry {
+    INSTANCE.get_or_init(EnumRegistry::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_make_and_validate() {
+        let registry = EnumRegistry::new();
+        registry.register("Color", &["RED", "GREEN"]);
+
+        let red = registry.make("Color", "RED").unwrap();
+        assert_eq!(
+            red,
+            ConfigValue::Enum {
+                type_name: "Color".to_string(),
+                value: "RED".to_string()
+            }
+        );
+        assert!(registry
+            .validate(&ConfigValue::List(vec![red, ConfigValue::Int(1)]))
+            .is_ok());
+
+        let err = registry.make("Color", "BLUE").unwrap_err();
+        assert_eq!(
+            err.message,
+            "Invalid value 'BLUE' for enum Color, expected one of: RED, GREEN"
+        );
+        let err = registry.make("Shape", "SQUARE").unwrap_err();
+        assert_eq!(err.message, "Unknown enum type 'Shape'");
+    }
+}
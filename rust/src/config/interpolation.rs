@@ -3,10 +3,14 @@
 //!
 //! Resolves ${...} references in configuration values.
 
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::env;
 
 use crate::config::value::{ConfigDict, ConfigValue};
+use crate::core::override_types::{OverrideValue, ParsedElement};
+use crate::merge::element_to_config_value;
+use crate::parser::OverrideParser;
 
 /// Error during interpolation resolution
 #[derive(Debug, Clone)]
@@ -43,14 +47,43 @@ impl std::fmt::Display for InterpolationError {
 
 impl std::error::Error for InterpolationError {}
 
+/// A resolver function, called with the comma-separated arguments
+pub type ResolverFn = Box<dyn Fn(&[&str]) -> Result<ConfigValue, InterpolationError>>;
+
+/// A built-in resolver that reads the config root
+type RootResolverFn =
+    fn(&[&str], &ResolverContext, usize) -> Result<ConfigValue, InterpolationError>;
+
+/// Resolvers that need the config root, so are dispatched here rather than
+/// registered on a [`ResolverContext`]
+const ROOT_RESOLVERS: &[(&str, RootResolverFn)] = &[
+    ("oc.select", resolve_select),
+    ("oc.dict.keys", |args, ctx, depth| {
+        resolve_dict_entries(args, ctx, depth, true)
+    }),
+    ("oc.dict.values", |args, ctx, depth| {
+        resolve_dict_entries(args, ctx, depth, false)
+    }),
+];
+
 /// Resolver context for interpolation
+///
+/// Within one [`resolve`] call, each interpolation expression is resolved
+/// once and its result reused for later references. Results that depend on
+/// an impure resolver (e.g. `uuid`) are never reused.
 pub struct ResolverContext<'a> {
     /// The root config for resolving references
     pub root: &'a ConfigDict,
     /// Custom resolvers (e.g., "oc.env" -> resolver function)
-    pub resolvers: HashMap<String, Box<dyn Fn(&[&str]) -> Result<ConfigValue, InterpolationError>>>,
+    pub resolvers: HashMap<String, ResolverFn>,
+    /// Resolvers with side effects, whose results must not be memoized
+    pub impure_resolvers: HashSet<String>,
     /// Maximum recursion depth
     pub max_depth: usize,
+    /// Resolved expressions of the current pass, keyed by expression
+    memo: RefCell<HashMap<String, ConfigValue>>,
+    /// Number of impure resolver calls made so far in the current pass
+    impure_calls: Cell<usize>,
 }
 
 impl<'a> ResolverContext<'a> {
@@ -58,12 +91,43 @@ impl<'a> ResolverContext<'a> {
         let mut ctx = Self {
             root,
             resolvers: HashMap::new(),
+            impure_resolvers: HashSet::new(),
             max_depth: 10,
+            memo: RefCell::new(HashMap::new()),
+            impure_calls: Cell::new(0),
         };
         ctx.register_default_resolvers();
         ctx
     }
 
+    /// Register a pure resolver, whose result depends only on its arguments
+    pub fn register_resolver(
+        &mut self,
+        name: &str,
+        resolver: impl Fn(&[&str]) -> Result<ConfigValue, InterpolationError> + 'static,
+    ) {
+        self.impure_resolvers.remove(name);
+        self.resolvers.insert(name.to_string(), Box::new(resolver));
+    }
+
+    /// Register a resolver with side effects, called again for every reference
+    pub fn register_impure_resolver(
+        &mut self,
+        name: &str,
+        resolver: impl Fn(&[&str]) -> Result<ConfigValue, InterpolationError> + 'static,
+    ) {
+        self.impure_resolvers.insert(name.to_string());
+        self.resolvers.insert(name.to_string(), Box::new(resolver));
+    }
+
+    /// Whether `name` is a resolver, built-in or registered
+    pub fn has_resolver(&self, name: &str) -> bool {
+        ROOT_RESOLVERS
+            .iter()
+            .any(|(root_name, _)| *root_name == name)
+            || self.resolvers.contains_key(name)
+    }
+
     fn register_default_resolvers(&mut self) {
         // oc.env resolver: ${oc.env:VAR_NAME} or ${oc.env:VAR_NAME,default}
         self.resolvers.insert(
@@ -99,25 +163,7 @@ impl<'a> ResolverContext<'a> {
                 if args.is_empty() {
                     return Err(InterpolationError::new("oc.decode requires an argument"));
                 }
-                let value = args[0].trim();
-                // Try to parse as different types
-                if value == "null" || value == "~" {
-                    return Ok(ConfigValue::Null);
-                }
-                if value == "true" {
-                    return Ok(ConfigValue::Bool(true));
-                }
-                if value == "false" {
-                    return Ok(ConfigValue::Bool(false));
-                }
-                if let Ok(i) = value.parse::<i64>() {
-                    return Ok(ConfigValue::Int(i));
-                }
-                if let Ok(f) = value.parse::<f64>() {
-                    return Ok(ConfigValue::Float(f));
-                }
-                // Default to string
-                Ok(ConfigValue::String(value.to_string()))
+                Ok(decode_value(args[0]))
             }),
         );
 
@@ -140,17 +186,100 @@ impl<'a> ResolverContext<'a> {
                 )))
             }),
         );
+
+        // uuid resolver: ${uuid:} - A fresh random UUID for every reference
+        self.register_impure_resolver("uuid", |_args: &[&str]| Ok(ConfigValue::String(uuid4())));
+    }
+}
+
+/// Generate a random (version 4) UUID string
+fn uuid4() -> String {
+    let bits =
+        (rand::random::<u128>() & !(0xf000 << 64 | 0xc000 << 48)) | (0x4000 << 64 | 0x8000 << 48);
+    let hex = format!("{:032x}", bits);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Convert a resolver argument to its typed representation
+///
+/// The argument is parsed like an override value, so `123` becomes an int,
+/// `[1,2]` a list and `{a: 1}` a dict. Quotes around the whole argument are
+/// removed first, so `'123'` decodes to an int too. `#` never starts a
+/// comment here, and an argument that decodes to a plain string is returned
+/// as written. Anything that is not a single value is kept as a string.
+fn decode_value(value: &str) -> ConfigValue {
+    let value = value.trim();
+    let value = ['\'', '"']
+        .iter()
+        .find_map(|q| value.strip_prefix(*q)?.strip_suffix(*q))
+        .unwrap_or(value);
+    if value == "~" {
+        return ConfigValue::Null;
+    }
+    let parsed = OverrideParser::new(&format!("decode={}", value))
+        .with_comments(false)
+        .parse_complete()
+        .map(|o| o.value);
+    match parsed {
+        Ok(Some(OverrideValue::Element(ParsedElement::String(_)))) => {
+            ConfigValue::String(value.to_string())
+        }
+        Ok(Some(OverrideValue::Element(element))) => element_to_config_value(&element),
+        _ => ConfigValue::String(value.to_string()),
     }
 }
 
 /// Resolve all interpolations in a config value
+///
+/// Recurses into lists and dicts, so `${...}` inside list elements and
+/// nested dict values is substituted in the same pass.
 pub fn resolve(
     value: &ConfigValue,
     ctx: &ResolverContext,
 ) -> Result<ConfigValue, InterpolationError> {
+    ctx.memo.borrow_mut().clear();
     resolve_with_depth(value, ctx, 0)
 }
 
+/// Resolve interpolations only in the subtree at a dotted `path`
+///
+/// Returns the whole config with that subtree resolved and everything else
+/// left as written, so `hydra.run.dir` can be resolved before the rest of
+/// the config is ready. References from inside the subtree are looked up in
+/// `ctx.root` as usual. An empty path resolves the whole config.
+pub fn resolve_path(
+    config: &ConfigValue,
+    path: &str,
+    ctx: &ResolverContext,
+) -> Result<ConfigValue, InterpolationError> {
+    let mut result = config.clone();
+    let mut target = &mut result;
+    if !path.is_empty() {
+        for part in path.split('.') {
+            target = match target {
+                ConfigValue::Dict(dict) => dict.get_mut(part).ok_or_else(|| {
+                    InterpolationError::with_key(&format!("Key '{}' not found", part), path)
+                })?,
+                _ => {
+                    return Err(InterpolationError::with_key(
+                        "Cannot traverse non-dict value",
+                        path,
+                    ))
+                }
+            };
+        }
+    }
+    *target = resolve(target, ctx)?;
+    Ok(result)
+}
+
 fn resolve_with_depth(
     value: &ConfigValue,
     ctx: &ResolverContext,
@@ -208,21 +337,62 @@ fn resolve_with_depth(
     }
 }
 
-/// Resolve a single interpolation expression
+/// Resolve a single interpolation expression, reusing an earlier result
+/// from the same pass when it did not involve an impure resolver
+///
+/// Only successful results are memoized, so a reference cycle still runs
+/// into the depth limit on every path that reaches it.
 fn resolve_interpolation(
     expr: &str,
     ctx: &ResolverContext,
     depth: usize,
+) -> Result<ConfigValue, InterpolationError> {
+    if let Some(value) = ctx.memo.borrow().get(expr) {
+        return Ok(value.clone());
+    }
+
+    let impure_calls = ctx.impure_calls.get();
+    let value = resolve_interpolation_uncached(expr, ctx, depth)?;
+    if ctx.impure_calls.get() == impure_calls {
+        ctx.memo
+            .borrow_mut()
+            .insert(expr.to_string(), value.clone());
+    }
+    Ok(value)
+}
+
+fn resolve_interpolation_uncached(
+    expr: &str,
+    ctx: &ResolverContext,
+    depth: usize,
 ) -> Result<ConfigValue, InterpolationError> {
     // Check for resolver syntax: resolver_name:arg1,arg2,...
     if let Some(colon_pos) = expr.find(':') {
         let resolver_name = &expr[..colon_pos];
         let args_str = &expr[colon_pos + 1..];
 
-        // Split args by comma (simple split, doesn't handle nested commas)
-        let args: Vec<&str> = args_str.split(',').map(|s| s.trim()).collect();
+        // oc.decode takes a single argument, which may be a list or dict.
+        let args: Vec<String> = if resolver_name == "oc.decode" {
+            vec![args_str.trim().to_string()]
+        } else {
+            split_resolver_args(args_str)
+                .into_iter()
+                .map(|arg| resolve_arg_interpolations(arg, ctx, depth))
+                .collect::<Result<_, _>>()?
+        };
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        if let Some((_, resolve)) = ROOT_RESOLVERS
+            .iter()
+            .find(|(name, _)| *name == resolver_name)
+        {
+            return resolve(&args, ctx, depth);
+        }
 
         if let Some(resolver) = ctx.resolvers.get(resolver_name) {
+            if ctx.impure_resolvers.contains(resolver_name) {
+                ctx.impure_calls.set(ctx.impure_calls.get() + 1);
+            }
             return resolver(&args);
         }
         // If no resolver found, try as a path lookup
@@ -235,11 +405,143 @@ fn resolve_interpolation(
     resolve_with_depth(&value, ctx, depth + 1)
 }
 
+/// Split a resolver's argument list on top-level commas
+///
+/// Commas inside a quoted argument, brackets, braces or a nested `${...}`
+/// do not split, so `VAR,"a,b"` and `key,[1,2]` are two arguments each.
+/// Arguments are trimmed, and quotes around a whole argument are removed
+/// (with `\'` or `\"` inside unescaped): `"a,b"` is passed as `a,b`.
+fn split_resolver_args(args: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut depth = 0usize;
+    let mut chars = args.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (_, '\\') => {
+                current.push(c);
+                current.extend(chars.next());
+                continue;
+            }
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            // Only a quote opening an argument starts a quoted string,
+            // so an apostrophe inside a bare word is kept as-is
+            (None, '\'' | '"') if current.trim().is_empty() => quote = Some(c),
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                parts.push(unquote_arg(&std::mem::take(&mut current)));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    parts.push(unquote_arg(&current));
+    parts
+}
+
+/// Trim a resolver argument and remove quotes around the whole of it
+fn unquote_arg(arg: &str) -> String {
+    let arg = arg.trim();
+    for quote in ['\'', '"'] {
+        let inner = arg
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote));
+        if let Some(inner) = inner {
+            return inner.replace(&format!("\\{}", quote), &quote.to_string());
+        }
+    }
+    arg.to_string()
+}
+
+/// Substitute interpolations nested in a resolver argument, as in
+/// `${oc.env:PORT,"${db.port}"}`
+fn resolve_arg_interpolations(
+    arg: String,
+    ctx: &ResolverContext,
+    depth: usize,
+) -> Result<String, InterpolationError> {
+    if !arg.contains("${") {
+        return Ok(arg);
+    }
+    let resolved = resolve_string_interpolations(&arg, ctx, depth + 1)?;
+    Ok(interpolated_text(&resolved))
+}
+
+/// Resolve `${oc.select:path,default}`
+///
+/// Returns the value at `path`, or `default` when the path is missing, null
+/// or `???`. Without a default a missing path is an error.
+fn resolve_select(
+    args: &[&str],
+    ctx: &ResolverContext,
+    depth: usize,
+) -> Result<ConfigValue, InterpolationError> {
+    let path = match args.first() {
+        Some(path) if !path.is_empty() => *path,
+        _ => return Err(InterpolationError::new("oc.select requires a key")),
+    };
+    let default = args.get(1).map(|d| decode_value(d));
+
+    match (lookup_path(path, ctx.root), default) {
+        (Ok(ConfigValue::Null | ConfigValue::Missing), Some(default)) | (Err(_), Some(default)) => {
+            Ok(default)
+        }
+        (Ok(value), _) => resolve_with_depth(&value, ctx, depth + 1),
+        (Err(err), None) => Err(err),
+    }
+}
+
+/// Resolve `${oc.dict.keys:path}` (`keys`) or `${oc.dict.values:path}`
+///
+/// Returns a list of the keys, or of the resolved values, of the dict at
+/// `path`, in insertion order.
+fn resolve_dict_entries(
+    args: &[&str],
+    ctx: &ResolverContext,
+    depth: usize,
+    keys: bool,
+) -> Result<ConfigValue, InterpolationError> {
+    let resolver = if keys {
+        "oc.dict.keys"
+    } else {
+        "oc.dict.values"
+    };
+    let path = match args {
+        [path] if !path.is_empty() => *path,
+        _ => {
+            return Err(InterpolationError::new(&format!(
+                "{} requires a single key",
+                resolver
+            )))
+        }
+    };
+
+    let ConfigValue::Dict(dict) = lookup_path(path, ctx.root)? else {
+        return Err(InterpolationError::with_key(
+            &format!("{} expects a dict", resolver),
+            path,
+        ));
+    };
+    if keys {
+        return Ok(ConfigValue::List(
+            dict.keys().map(ConfigValue::from).collect(),
+        ));
+    }
+    dict.values()
+        .map(|value| resolve_with_depth(value, ctx, depth + 1))
+        .collect::<Result<_, _>>()
+        .map(ConfigValue::List)
+}
+
 /// Resolve interpolations embedded in a string
 fn resolve_string_interpolations(
     s: &str,
     ctx: &ResolverContext,
-    _depth: usize,
+    depth: usize,
 ) -> Result<ConfigValue, InterpolationError> {
     // Check if the string is exactly a single interpolation: ${...}
     let trimmed = s.trim();
@@ -262,7 +564,7 @@ fn resolve_string_interpolations(
         }
         if is_single && brace_count == 0 {
             // It's a single interpolation, preserve the type
-            return resolve_interpolation(inner, ctx, 0);
+            return resolve_interpolation(inner, ctx, depth);
         }
     }
 
@@ -274,11 +576,11 @@ fn resolve_string_interpolations(
         if c == '$' && chars.peek() == Some(&'{') {
             chars.next(); // consume '{'
 
-            // Extract interpolation expression
+            // Extract interpolation expression up to the matching brace
             let mut expr = String::new();
             let mut brace_depth = 1;
 
-            while let Some(c) = chars.next() {
+            for c in chars.by_ref() {
                 if c == '{' {
                     brace_depth += 1;
                     expr.push(c);
@@ -293,16 +595,15 @@ fn resolve_string_interpolations(
                 }
             }
 
-            let resolved = resolve_interpolation(&expr, ctx, 0)?;
-
-            match &resolved {
-                ConfigValue::String(s) => result.push_str(s),
-                ConfigValue::Int(i) => result.push_str(&i.to_string()),
-                ConfigValue::Float(f) => result.push_str(&f.to_string()),
-                ConfigValue::Bool(b) => result.push_str(&b.to_string()),
-                ConfigValue::Null => result.push_str("null"),
-                _ => result.push_str(&format!("{:?}", resolved)),
+            if brace_depth > 0 {
+                return Err(InterpolationError::new(&format!(
+                    "Unterminated interpolation in '{}'",
+                    s
+                )));
             }
+
+            let resolved = resolve_interpolation(&expr, ctx, depth)?;
+            result.push_str(&interpolated_text(&resolved));
         } else {
             result.push(c);
         }
@@ -311,32 +612,41 @@ fn resolve_string_interpolations(
     Ok(ConfigValue::String(result))
 }
 
+/// Text of a resolved value substituted into a larger string
+fn interpolated_text(value: &ConfigValue) -> String {
+    match value {
+        ConfigValue::String(s) => s.clone(),
+        ConfigValue::Int(i) => i.to_string(),
+        ConfigValue::Float(f) => f.to_string(),
+        ConfigValue::Bool(b) => b.to_string(),
+        ConfigValue::Null => "null".to_string(),
+        _ => format!("{:?}", value),
+    }
+}
+
 /// Lookup a dotted path in the config
 fn lookup_path(path: &str, root: &ConfigDict) -> Result<ConfigValue, InterpolationError> {
-    let parts: Vec<&str> = path.split('.').collect();
-    let mut current = ConfigValue::Dict(root.clone());
-
-    for part in parts {
-        match current {
-            ConfigValue::Dict(dict) => match dict.get(part) {
-                Some(value) => current = value.clone(),
-                None => {
-                    return Err(InterpolationError::with_key(
-                        &format!("Key '{}' not found", part),
-                        path,
-                    ));
-                }
-            },
-            _ => {
+    // Walk by reference so a lookup in a wide config does not copy it
+    let mut current: Option<&ConfigValue> = None;
+
+    for part in path.split('.') {
+        let dict = match current {
+            None => root,
+            Some(ConfigValue::Dict(dict)) => dict,
+            Some(_) => {
                 return Err(InterpolationError::with_key(
                     "Cannot traverse non-dict value",
                     path,
                 ));
             }
-        }
+        };
+        current = Some(dict.get(part).ok_or_else(|| {
+            InterpolationError::with_key(&format!("Key '{}' not found", part), path)
+        })?);
     }
 
-    Ok(current)
+    // `split` yields at least one part, so `current` is always set here
+    Ok(current.cloned().unwrap_or(ConfigValue::Null))
 }
 
 #[cfg(test)]
@@ -423,6 +733,42 @@ mod tests {
         assert_eq!(result, ConfigValue::String("default_val".to_string()));
     }
 
+    #[test]
+    fn test_resolver_quoted_arguments() {
+        let root = make_config();
+        let mut ctx = ResolverContext::new(&root);
+        ctx.register_resolver("args", |args: &[&str]| {
+            Ok(ConfigValue::List(args.iter().map(|&a| a.into()).collect()))
+        });
+        let resolve_str = |expr: &str| resolve_interpolation(expr, &ctx, 0).unwrap();
+
+        // A quoted fallback keeps its comma
+        assert_eq!(
+            resolve_str(r#"oc.env:NONEXISTENT_VAR_12345,"a,b""#),
+            ConfigValue::String("a,b".to_string())
+        );
+        // Colons inside an argument, quoted or not, stay in it
+        assert_eq!(
+            resolve_str("args:'http://x:80',y:z"),
+            ConfigValue::List(vec!["http://x:80".into(), "y:z".into()])
+        );
+        // Nested interpolations inside a quoted argument are resolved
+        assert_eq!(
+            resolve_str(r#"oc.env:NONEXISTENT_VAR_12345,"${db.host}:${db.port}""#),
+            ConfigValue::String("localhost:3306".to_string())
+        );
+        // Brackets, nested commas, escaped quotes and bare apostrophes
+        assert_eq!(
+            resolve_str(r#"args:[1,2],${oc.env:NONEXISTENT_VAR_12345,"c,d"},'it\'s',it's"#),
+            ConfigValue::List(vec![
+                "[1,2]".into(),
+                "c,d".into(),
+                "it's".into(),
+                "it's".into()
+            ])
+        );
+    }
+
     #[test]
     fn test_dict_resolution() {
         let mut inner = ConfigDict::new();
@@ -511,4 +857,316 @@ mod tests {
         let result = resolve_interpolation("oc.decode:hello", &ctx, 0).unwrap();
         assert_eq!(result, ConfigValue::String("hello".to_string()));
     }
+
+    #[test]
+    fn test_decode_resolver_parses_quoted_strings() {
+        let root = ConfigDict::new();
+        let ctx = ResolverContext::new(&root);
+
+        let result = resolve_interpolation("oc.decode:'123'", &ctx, 0).unwrap();
+        assert_eq!(result, ConfigValue::Int(123));
+
+        let result = resolve_interpolation("oc.decode:\"True\"", &ctx, 0).unwrap();
+        assert_eq!(result, ConfigValue::Bool(true));
+
+        let result = resolve_interpolation("oc.decode:[1, 2.5]", &ctx, 0).unwrap();
+        assert_eq!(
+            result,
+            ConfigValue::List(vec![ConfigValue::Int(1), ConfigValue::Float(2.5)])
+        );
+    }
+
+    #[test]
+    fn test_decode_resolver_keeps_hash_and_string_text() {
+        let root = ConfigDict::new();
+        let ctx = ResolverContext::new(&root);
+
+        // `#` is part of the value, never a comment
+        let result = resolve_interpolation("oc.decode:'tag#1'", &ctx, 0).unwrap();
+        assert_eq!(result, ConfigValue::from("tag#1"));
+        let result = resolve_interpolation("oc.decode:'a #1'", &ctx, 0).unwrap();
+        assert_eq!(result, ConfigValue::from("a #1"));
+        let result = resolve_interpolation("oc.decode:'[1, 2] # two'", &ctx, 0).unwrap();
+        assert_eq!(result, ConfigValue::from("[1, 2] # two"));
+
+        // A string decodes to its text as written
+        let result = resolve_interpolation("oc.decode:'a  b'", &ctx, 0).unwrap();
+        assert_eq!(result, ConfigValue::from("a  b"));
+    }
+
+    #[test]
+    fn test_dict_keys_and_values_resolvers() {
+        let config = resolve_yaml(
+            "db:\n  host: localhost\n  port: ${base_port}\nbase_port: 3306\n\
+             keys: ${oc.dict.keys:db}\nvalues: ${oc.dict.values:db}\n",
+        );
+        assert_eq!(
+            config.get("keys"),
+            Some(&ConfigValue::List(vec![
+                ConfigValue::from("host"),
+                ConfigValue::from("port")
+            ]))
+        );
+        assert_eq!(
+            config.get("values"),
+            Some(&ConfigValue::List(vec![
+                ConfigValue::from("localhost"),
+                ConfigValue::Int(3306)
+            ]))
+        );
+
+        let root = make_config();
+        let ctx = ResolverContext::new(&root);
+        let err = resolve_interpolation("oc.dict.keys:name", &ctx, 0).unwrap_err();
+        assert_eq!(err.message, "oc.dict.keys expects a dict");
+        assert_eq!(err.key.as_deref(), Some("name"));
+    }
+
+    #[test]
+    fn test_string_interpolation_with_literals() {
+        let mut root = make_config();
+        root.insert("base".to_string(), ConfigValue::String("/data".to_string()));
+
+        let value = ConfigValue::String("${base}/sub/${name}.yaml".to_string());
+        let result = resolve(&value, &ResolverContext::new(&root)).unwrap();
+        assert_eq!(
+            result,
+            ConfigValue::String("/data/sub/myapp.yaml".to_string())
+        );
+
+        let value = ConfigValue::String("prefix-${name}-suffix".to_string());
+        let result = resolve(&value, &ResolverContext::new(&root)).unwrap();
+        assert_eq!(
+            result,
+            ConfigValue::String("prefix-myapp-suffix".to_string())
+        );
+    }
+
+    #[test]
+    fn test_adjacent_interpolations() {
+        let root = make_config();
+        let ctx = ResolverContext::new(&root);
+
+        let value = ConfigValue::String("${db.host}${db.port}".to_string());
+        let result = resolve(&value, &ctx).unwrap();
+        assert_eq!(result, ConfigValue::String("localhost3306".to_string()));
+    }
+
+    #[test]
+    fn test_dollar_without_brace_untouched() {
+        let root = make_config();
+        let ctx = ResolverContext::new(&root);
+
+        let value = ConfigValue::String("$100".to_string());
+        assert_eq!(resolve(&value, &ctx).unwrap(), value);
+
+        let value = ConfigValue::String("$100 for ${name}".to_string());
+        let result = resolve(&value, &ctx).unwrap();
+        assert_eq!(result, ConfigValue::String("$100 for myapp".to_string()));
+    }
+
+    #[test]
+    fn test_unterminated_interpolation_error() {
+        let root = make_config();
+        let ctx = ResolverContext::new(&root);
+
+        let value = ConfigValue::String("x-${name".to_string());
+        assert!(resolve(&value, &ctx).is_err());
+    }
+
+    fn resolve_yaml(yaml: &str) -> ConfigDict {
+        let root = match crate::config::parser::parse_yaml(yaml).unwrap() {
+            ConfigValue::Dict(d) => d,
+            other => panic!("Expected dict, got {:?}", other),
+        };
+        let ctx = ResolverContext::new(&root);
+        match resolve(&ConfigValue::Dict(root.clone()), &ctx).unwrap() {
+            ConfigValue::Dict(d) => d,
+            other => panic!("Expected dict, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_list_element_resolution() {
+        let cfg = resolve_yaml(
+            "primary: db1\nsecondary: db2\nhosts: [\"${primary}\", \"${secondary}:5432\"]\n",
+        );
+        assert_eq!(
+            cfg.get("hosts"),
+            Some(&ConfigValue::List(vec!["db1".into(), "db2:5432".into()]))
+        );
+    }
+
+    #[test]
+    fn test_list_of_dicts_resolution() {
+        let cfg = resolve_yaml(
+            "port: 3306\nservers:\n  - name: a\n    port: ${port}\n  - name: b\n    url: ${servers_host}:${port}\nservers_host: example\n",
+        );
+        let servers = cfg.get("servers").and_then(|v| v.as_list()).unwrap();
+        assert_eq!(
+            servers[0].as_dict().unwrap().get("port"),
+            Some(&ConfigValue::Int(3306))
+        );
+        assert_eq!(
+            servers[1].as_dict().unwrap().get("url"),
+            Some(&ConfigValue::from("example:3306"))
+        );
+    }
+
+    #[test]
+    fn test_dict_value_referencing_dict_value() {
+        // `app.db` points at `db`, whose own values are interpolations;
+        // everything must be substituted in a single resolve pass
+        let cfg = resolve_yaml(
+            "base: prod\ndb:\n  host: ${base}-db\n  port: 5432\napp:\n  db: ${db}\n  dsn: ${db.host}:${db.port}\n",
+        );
+        let app = cfg.get("app").and_then(|v| v.as_dict()).unwrap();
+        let db = app.get("db").and_then(|v| v.as_dict()).unwrap();
+        assert_eq!(db.get("host"), Some(&ConfigValue::from("prod-db")));
+        assert_eq!(db.get("port"), Some(&ConfigValue::Int(5432)));
+        assert_eq!(app.get("dsn"), Some(&ConfigValue::from("prod-db:5432")));
+    }
+
+    #[test]
+    fn test_select_resolver_present_path() {
+        let config = make_config();
+        let ctx = ResolverContext::new(&config);
+
+        let result = resolve_interpolation("oc.select:db.port,1234", &ctx, 0).unwrap();
+        assert_eq!(result, ConfigValue::Int(3306));
+    }
+
+    #[test]
+    fn test_select_resolver_default() {
+        let config = resolve_yaml("db:\n  host: null\nport: ${oc.select:db.port,5432}\n");
+        assert_eq!(config.get("port"), Some(&ConfigValue::Int(5432)));
+
+        let config = make_config();
+        let ctx = ResolverContext::new(&config);
+        let result = resolve_interpolation("oc.select:db.user,admin", &ctx, 0).unwrap();
+        assert_eq!(result, ConfigValue::String("admin".to_string()));
+
+        let result = resolve_interpolation("oc.select:db.user,x#y", &ctx, 0).unwrap();
+        assert_eq!(result, ConfigValue::from("x#y"));
+        let result = resolve_interpolation("oc.select:db.user,'x #y'", &ctx, 0).unwrap();
+        assert_eq!(result, ConfigValue::from("x #y"));
+    }
+
+    #[test]
+    fn test_select_resolver_missing_without_default() {
+        let config = make_config();
+        let ctx = ResolverContext::new(&config);
+
+        let err = resolve_interpolation("oc.select:db.user", &ctx, 0).unwrap_err();
+        assert_eq!(err.key.as_deref(), Some("db.user"));
+    }
+
+    #[test]
+    fn test_resolve_wide_config() {
+        // Each lookup borrows the root; copying it per reference made this
+        // quadratic in the number of keys
+        let n = 10_000;
+        let mut refs = ConfigDict::new();
+        let mut root = ConfigDict::new();
+        for i in 0..n {
+            root.insert(format!("k{}", i), ConfigValue::Int(i));
+            refs.insert(
+                format!("r{}", i),
+                ConfigValue::Interpolation(format!("${{k{}}}", i)),
+            );
+        }
+        root.insert("refs".to_string(), ConfigValue::Dict(refs));
+
+        let ctx = ResolverContext::new(&root);
+        let resolved = resolve(&ConfigValue::Dict(root.clone()), &ctx).unwrap();
+        assert_eq!(
+            resolved.as_dict().unwrap().select("refs.r9999"),
+            Some(ConfigValue::Int(9999))
+        );
+    }
+
+    #[test]
+    fn test_repeated_references_resolve_once() {
+        use std::rc::Rc;
+
+        let mut yaml = String::from("expensive: ${count:x}\n");
+        for i in 0..100 {
+            yaml.push_str(&format!("ref{}: ${{expensive}}\n", i));
+        }
+        let root = match crate::config::parser::parse_yaml(&yaml).unwrap() {
+            ConfigValue::Dict(d) => d,
+            other => panic!("Expected dict, got {:?}", other),
+        };
+
+        let calls = Rc::new(Cell::new(0));
+        let mut ctx = ResolverContext::new(&root);
+        let counter = calls.clone();
+        ctx.register_resolver("count", move |args: &[&str]| {
+            counter.set(counter.get() + 1);
+            Ok(ConfigValue::String(args[0].to_string()))
+        });
+
+        let resolved = resolve(&ConfigValue::Dict(root.clone()), &ctx).unwrap();
+        assert_eq!(calls.get(), 1);
+        let resolved = resolved.as_dict().unwrap();
+        assert_eq!(resolved.get("ref99"), Some(&ConfigValue::from("x")));
+
+        // A new pass starts with an empty memo
+        resolve(&ConfigValue::Dict(root.clone()), &ctx).unwrap();
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_impure_resolver_not_memoized() {
+        let cfg = resolve_yaml("id: ${uuid:}\na: ${uuid:}\nb: ${id}\nc: ${id}\n");
+        let ids: Vec<&str> = ["id", "a", "b", "c"]
+            .iter()
+            .map(|k| cfg.get(k).and_then(|v| v.as_str()).unwrap())
+            .collect();
+        assert!(ids
+            .iter()
+            .all(|id| id.len() == 36 && id.as_bytes()[14] == b'4'));
+        let unique: HashSet<&str> = ids.iter().copied().collect();
+        assert_eq!(unique.len(), ids.len());
+    }
+
+    #[test]
+    fn test_cycle_still_detected_with_memo() {
+        let root = match crate::config::parser::parse_yaml("a: ${b}\nb: ${a}\nc: ${a}\n").unwrap() {
+            ConfigValue::Dict(d) => d,
+            other => panic!("Expected dict, got {:?}", other),
+        };
+        let ctx = ResolverContext::new(&root);
+        assert!(resolve(&ConfigValue::Dict(root.clone()), &ctx).is_err());
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let config = crate::config::parser::parse_yaml(
+            "name: app\nhydra:\n  run:\n    dir: outputs/${name}\n  job: ${name}\nurl: ${missing}\n",
+        )
+        .unwrap();
+        let root = config.as_dict().unwrap();
+        let ctx = ResolverContext::new(root);
+
+        let result = resolve_path(&config, "hydra.run.dir", &ctx).unwrap();
+        let dict = result.as_dict().unwrap();
+        assert_eq!(
+            dict.select("hydra.run.dir"),
+            Some(ConfigValue::from("outputs/app"))
+        );
+        // Everything outside the subtree is left as written
+        assert_eq!(dict.select("hydra.job"), root.select("hydra.job"));
+        assert_eq!(dict.get("url"), root.get("url"));
+
+        let result = resolve_path(&config, "hydra", &ctx).unwrap();
+        assert_eq!(
+            result.as_dict().unwrap().select("hydra.job"),
+            Some(ConfigValue::from("app"))
+        );
+
+        let err = resolve_path(&config, "hydra.sweep", &ctx).unwrap_err();
+        assert_eq!(err.message, "Key 'sweep' not found");
+        assert!(resolve_path(&config, "", &ctx).is_err());
+    }
 }
@@ -3,9 +3,12 @@
 
 use std::collections::HashMap;
 
+use crate::callback::{Callback, CallbackManager};
+use crate::config::interpolation::{resolve, ResolverContext};
 use crate::config::parser::ConfigLoadError;
 use crate::config::source::{ConfigResult, ConfigSource, FileConfigSource};
 use crate::config::value::{ConfigDict, ConfigValue};
+use crate::merge::{merge_dicts_annotated, strip_merge_annotations};
 use crate::ObjectType;
 
 /// A search path entry
@@ -98,13 +101,13 @@ impl ConfigLoader {
                 }
 
                 // Merge the primary config (excluding defaults)
-                for (key, value) in dict.iter() {
-                    if key != "defaults" {
-                        merged_config.insert(key.to_string(), value.clone());
-                    }
-                }
+                let mut primary_dict = dict.clone();
+                primary_dict.remove("defaults");
+                merge_dicts_annotated(&mut merged_config, &primary_dict)?;
             }
         }
+        // Annotations apply across every default, so they go only at the end
+        strip_merge_annotations(&mut merged_config);
 
         // Apply value overrides
         for override_str in &value_overrides {
@@ -183,7 +186,7 @@ impl ConfigLoader {
                     if name != "_self_" {
                         let result = self.load_single_config(name)?;
                         if let ConfigValue::Dict(dict) = &result.config {
-                            self.merge_config(merged_config, dict, None);
+                            self.merge_config(merged_config, dict, None)?;
                         }
                     }
                 }
@@ -213,7 +216,7 @@ impl ConfigLoader {
                                         .map(|s| s.as_str())
                                         .unwrap_or(group);
 
-                                    self.merge_config(merged_config, cfg_dict, Some(package));
+                                    self.merge_config(merged_config, cfg_dict, Some(package))?;
                                 }
                             }
                             Err(e) => {
@@ -238,26 +241,37 @@ impl ConfigLoader {
     }
 
     /// Merge a config dict into the target, optionally at a package path
-    fn merge_config(&self, target: &mut ConfigDict, source: &ConfigDict, package: Option<&str>) {
+    fn merge_config(
+        &self,
+        target: &mut ConfigDict,
+        source: &ConfigDict,
+        package: Option<&str>,
+    ) -> Result<(), ConfigLoadError> {
         if let Some(pkg) = package {
             if pkg == "_global_" || pkg.is_empty() {
                 // Merge at root
-                target.merge(source);
+                merge_dicts_annotated(target, source)?;
             } else {
                 // Merge at package path
                 let parts: Vec<&str> = pkg.split('.').collect();
-                self.merge_at_path(target, source, &parts);
+                self.merge_at_path(target, source, &parts)?;
             }
         } else {
-            target.merge(source);
+            merge_dicts_annotated(target, source)?;
         }
+        Ok(())
     }
 
     /// Merge source at a nested path in target
-    fn merge_at_path(&self, target: &mut ConfigDict, source: &ConfigDict, path: &[&str]) {
+    fn merge_at_path(
+        &self,
+        target: &mut ConfigDict,
+        source: &ConfigDict,
+        path: &[&str],
+    ) -> Result<(), ConfigLoadError> {
         if path.is_empty() {
-            target.merge(source);
-            return;
+            merge_dicts_annotated(target, source)?;
+            return Ok(());
         }
 
         let key = path[0];
@@ -270,9 +284,10 @@ impl ConfigLoader {
 
         if let Some(nested) = target.get_mut(key) {
             if let Some(nested_dict) = nested.as_dict_mut() {
-                self.merge_at_path(nested_dict, source, remaining);
+                return self.merge_at_path(nested_dict, source, remaining);
             }
         }
+        Ok(())
     }
 
     /// Load a single config file from the sources
@@ -487,6 +502,47 @@ fn get_scheme(path: &str) -> String {
     }
 }
 
+/// Compose a config from a directory: load it, apply overrides and resolve interpolations
+pub fn compose_config(
+    config_dir: &str,
+    config_name: Option<&str>,
+    overrides: &[String],
+) -> Result<ConfigDict, ConfigLoadError> {
+    let loader = ConfigLoader::from_config_dir(config_dir);
+    let config = loader.load_config(config_name, overrides)?;
+
+    let dict = match &config {
+        ConfigValue::Dict(d) => d,
+        _ => return Err(ConfigLoadError::new("Config must be a dictionary")),
+    };
+
+    let ctx = ResolverContext::new(dict);
+    match resolve(&config, &ctx).map_err(|e| ConfigLoadError::new(e.to_string()))? {
+        ConfigValue::Dict(resolved) => Ok(resolved),
+        _ => Err(ConfigLoadError::new("Config must be a dictionary")),
+    }
+}
+
+/// Compose a config like `compose_config`, then fire `on_compose_config` callbacks
+///
+/// If composition fails, `on_error` is fired with the "compose" context and
+/// the error message before the error is returned.
+pub fn compose_config_with_callbacks(
+    config_dir: &str,
+    config_name: Option<&str>,
+    overrides: &[String],
+    callbacks: &CallbackManager,
+) -> Result<ConfigDict, ConfigLoadError> {
+    let config = compose_config(config_dir, config_name, overrides).inspect_err(|e| {
+        // The composition error is the one worth reporting
+        let _ = callbacks.on_error("compose", &e.message);
+    })?;
+    callbacks
+        .on_compose_config(&config, config_name, overrides)
+        .map_err(|e| ConfigLoadError::new(e.message))?;
+    Ok(config)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -597,6 +653,106 @@ mod tests {
         assert!(items.contains(&"mysql".to_string()));
         assert!(items.contains(&"postgres".to_string()));
     }
+
+    #[test]
+    fn test_compose_config_with_callbacks() {
+        use crate::callback::CallbackResult;
+        use std::sync::{Arc, Mutex};
+
+        struct ComposeCall {
+            config: ConfigDict,
+            config_name: Option<String>,
+            overrides: Vec<String>,
+        }
+
+        #[derive(Default)]
+        struct RecordingCallback {
+            seen: Mutex<Vec<ComposeCall>>,
+        }
+
+        impl Callback for RecordingCallback {
+            fn on_compose_config(
+                &self,
+                config: &ConfigDict,
+                config_name: Option<&str>,
+                overrides: &[String],
+            ) -> CallbackResult<()> {
+                self.seen.lock().unwrap().push(ComposeCall {
+                    config: config.clone(),
+                    config_name: config_name.map(|s| s.to_string()),
+                    overrides: overrides.to_vec(),
+                });
+                Ok(())
+            }
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        create_config_file(
+            temp_dir.path(),
+            "config.yaml",
+            "db:\n  host: localhost\n  url: ${db.host}:3306\n",
+        );
+
+        let recorder = Arc::new(RecordingCallback::default());
+        let callbacks = CallbackManager::new().with(recorder.clone());
+        let overrides = vec!["db.host=remotehost".to_string()];
+
+        let config = compose_config_with_callbacks(
+            temp_dir.path().to_str().unwrap(),
+            Some("config"),
+            &overrides,
+            &callbacks,
+        )
+        .unwrap();
+
+        let seen = recorder.seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].config, config);
+        assert_eq!(seen[0].config_name.as_deref(), Some("config"));
+        assert_eq!(seen[0].overrides, overrides);
+
+        let db = config.get("db").unwrap().as_dict().unwrap();
+        assert_eq!(db.get("url").unwrap().as_str(), Some("remotehost:3306"));
+    }
+
+    #[test]
+    fn test_compose_config_with_callbacks_on_error() {
+        use crate::callback::CallbackResult;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Default)]
+        struct ErrorRecorder {
+            errors: Mutex<Vec<(String, String)>>,
+        }
+
+        impl Callback for ErrorRecorder {
+            fn on_error(&self, context: &str, message: &str) -> CallbackResult<()> {
+                self.errors
+                    .lock()
+                    .unwrap()
+                    .push((context.to_string(), message.to_string()));
+                Ok(())
+            }
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        create_config_file(temp_dir.path(), "config.yaml", "url: ${db.host}\n");
+
+        let recorder = Arc::new(ErrorRecorder::default());
+        let callbacks = CallbackManager::new().with(recorder.clone());
+
+        let err = compose_config_with_callbacks(
+            temp_dir.path().to_str().unwrap(),
+            Some("config"),
+            &[],
+            &callbacks,
+        )
+        .unwrap_err();
+
+        let errors = recorder.errors.lock().unwrap();
+        assert_eq!(*errors, vec![("compose".to_string(), err.message.clone())]);
+        assert!(err.message.contains("db"), "{}", err.message);
+    }
 }
 
 /// A caching wrapper around ConfigLoader
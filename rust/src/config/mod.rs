@@ -1,18 +1,31 @@
 // Copyright (c) Facebook, Inc. and its affiliates. All Rights Reserved
 //! Configuration loading and management
 
+pub mod enums;
 pub mod interpolation;
 pub mod loader;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
 pub mod parser;
 pub mod repository;
 pub mod source;
 pub mod value;
 
-pub use interpolation::{resolve, InterpolationError, ResolverContext};
-pub use loader::{CachingConfigLoader, ConfigLoader, SearchPathEntry};
-pub use parser::{extract_header, load_yaml_file, parse_yaml, ConfigLoadError};
+pub use enums::{EnumError, EnumRegistry};
+pub use interpolation::{resolve, resolve_path, InterpolationError, ResolverContext};
+pub use loader::{
+    compose_config, compose_config_with_callbacks, CachingConfigLoader, ConfigLoader,
+    SearchPathEntry,
+};
+#[cfg(feature = "msgpack")]
+pub use msgpack::MsgpackError;
+pub use parser::{
+    extract_header, load_config_dir, load_yaml_file, parse_yaml, parse_yaml_with_includes,
+    ConfigLoadError,
+};
 pub use repository::{
-    get_scheme as get_path_scheme, CachingConfigRepository, ConfigRepository, SearchPathElement,
+    get_scheme as get_path_scheme, CachingConfigRepository, ConfigRepository, GroupLoad,
+    SearchPathElement,
 };
 pub use source::{ConfigResult, ConfigSource, FileConfigSource};
-pub use value::{ConfigDict, ConfigValue};
+pub use value::{ConfigDict, ConfigStats, ConfigValue};
@@ -0,0 +1,246 @@
+// Copyright (c) Facebook, Inc. and its affiliates. All Rights Reserved
+//! Binary (MessagePack) serialization of config values
+//!
+//! Plain values map onto the matching MessagePack types, with dicts written
+//! as maps in key order. The variants with no MessagePack counterpart are
+//! written as extension types:
+//!
+//! - `Missing` as ext type 1 with an empty payload
+//! - `Interpolation` as ext type 2 holding the UTF-8 source
+//! - `Enum` as ext type 3 holding a `[type_name, value]` array
+//!
+//! Strings decode back to `String` as-is, without classifying them again.
+
+use std::fmt::Display;
+
+use rmp::decode;
+use rmp::encode;
+use rmp::Marker;
+
+use crate::config::value::{ConfigDict, ConfigValue};
+
+const EXT_MISSING: i8 = 1;
+const EXT_INTERPOLATION: i8 = 2;
+const EXT_ENUM: i8 = 3;
+
+/// Error for bytes that do not decode to a config value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsgpackError {
+    pub message: String,
+}
+
+impl MsgpackError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for MsgpackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for MsgpackError {}
+
+fn invalid(e: impl Display) -> MsgpackError {
+    MsgpackError::new(format!("Invalid MessagePack config: {}", e))
+}
+
+impl ConfigValue {
+    /// Serialize to MessagePack bytes
+    pub fn to_msgpack(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_value(&mut buf, self).expect("writing to a Vec cannot fail");
+        buf
+    }
+
+    /// Deserialize from bytes written by [`ConfigValue::to_msgpack`]
+    pub fn from_msgpack(bytes: &[u8]) -> Result<ConfigValue, MsgpackError> {
+        let mut rd = bytes;
+        let value = decode_value(&mut rd)?;
+        if !rd.is_empty() {
+            return Err(invalid(format!("{} trailing bytes", rd.len())));
+        }
+        Ok(value)
+    }
+}
+
+type EncodeResult = Result<(), std::io::Error>;
+
+fn encode_value(buf: &mut Vec<u8>, value: &ConfigValue) -> EncodeResult {
+    match value {
+        ConfigValue::Null => encode::write_nil(buf)?,
+        ConfigValue::Bool(b) => encode::write_bool(buf, *b)?,
+        ConfigValue::Int(i) => {
+            encode::write_sint(buf, *i)?;
+        }
+        ConfigValue::Float(f) => encode::write_f64(buf, *f)?,
+        ConfigValue::String(s) => encode::write_str(buf, s)?,
+        ConfigValue::List(items) => {
+            encode::write_array_len(buf, items.len() as u32)?;
+            for item in items {
+                encode_value(buf, item)?;
+            }
+        }
+        ConfigValue::Dict(dict) => {
+            encode::write_map_len(buf, dict.len() as u32)?;
+            for (key, value) in dict.iter() {
+                encode::write_str(buf, key)?;
+                encode_value(buf, value)?;
+            }
+        }
+        ConfigValue::Missing => {
+            encode::write_ext_meta(buf, 0, EXT_MISSING)?;
+        }
+        ConfigValue::Interpolation(s) => {
+            encode::write_ext_meta(buf, s.len() as u32, EXT_INTERPOLATION)?;
+            buf.extend_from_slice(s.as_bytes());
+        }
+        ConfigValue::Enum { type_name, value } => {
+            let mut payload = Vec::new();
+            encode::write_array_len(&mut payload, 2)?;
+            encode::write_str(&mut payload, type_name)?;
+            encode::write_str(&mut payload, value)?;
+            encode::write_ext_meta(buf, payload.len() as u32, EXT_ENUM)?;
+            buf.extend_from_slice(&payload);
+        }
+    }
+    Ok(())
+}
+
+/// Take the next `len` bytes from the reader
+fn take<'a>(rd: &mut &'a [u8], len: u32) -> Result<&'a [u8], MsgpackError> {
+    let len = len as usize;
+    if rd.len() < len {
+        return Err(invalid("unexpected end of input"));
+    }
+    let (head, tail) = rd.split_at(len);
+    *rd = tail;
+    Ok(head)
+}
+
+fn read_string(rd: &mut &[u8]) -> Result<String, MsgpackError> {
+    let len = decode::read_str_len(rd).map_err(invalid)?;
+    let data = take(rd, len)?;
+    String::from_utf8(data.to_vec()).map_err(invalid)
+}
+
+fn decode_value(rd: &mut &[u8]) -> Result<ConfigValue, MsgpackError> {
+    let marker = match rd.first() {
+        Some(b) => Marker::from_u8(*b),
+        None => return Err(invalid("unexpected end of input")),
+    };
+    Ok(match marker {
+        Marker::Null => {
+            decode::read_nil(rd).map_err(invalid)?;
+            ConfigValue::Null
+        }
+        Marker::True | Marker::False => ConfigValue::Bool(decode::read_bool(rd).map_err(invalid)?),
+        Marker::FixPos(_)
+        | Marker::FixNeg(_)
+        | Marker::U8
+        | Marker::U16
+        | Marker::U32
+        | Marker::U64
+        | Marker::I8
+        | Marker::I16
+        | Marker::I32
+        | Marker::I64 => ConfigValue::Int(decode::read_int(rd).map_err(invalid)?),
+        Marker::F32 => ConfigValue::Float(decode::read_f32(rd).map_err(invalid)? as f64),
+        Marker::F64 => ConfigValue::Float(decode::read_f64(rd).map_err(invalid)?),
+        Marker::FixStr(_) | Marker::Str8 | Marker::Str16 | Marker::Str32 => {
+            ConfigValue::String(read_string(rd)?)
+        }
+        Marker::FixArray(_) | Marker::Array16 | Marker::Array32 => {
+            let len = decode::read_array_len(rd).map_err(invalid)?;
+            let items: Result<Vec<_>, _> = (0..len).map(|_| decode_value(rd)).collect();
+            ConfigValue::List(items?)
+        }
+        Marker::FixMap(_) | Marker::Map16 | Marker::Map32 => {
+            let len = decode::read_map_len(rd).map_err(invalid)?;
+            let mut dict = ConfigDict::new();
+            for _ in 0..len {
+                let key = read_string(rd)?;
+                dict.insert(key, decode_value(rd)?);
+            }
+            ConfigValue::Dict(dict)
+        }
+        Marker::FixExt1
+        | Marker::FixExt2
+        | Marker::FixExt4
+        | Marker::FixExt8
+        | Marker::FixExt16
+        | Marker::Ext8
+        | Marker::Ext16
+        | Marker::Ext32 => {
+            let meta = decode::read_ext_meta(rd).map_err(invalid)?;
+            let mut payload = take(rd, meta.size)?;
+            match meta.typeid {
+                EXT_MISSING => ConfigValue::Missing,
+                EXT_INTERPOLATION => ConfigValue::Interpolation(
+                    String::from_utf8(payload.to_vec()).map_err(invalid)?,
+                ),
+                EXT_ENUM => {
+                    if decode::read_array_len(&mut payload).map_err(invalid)? != 2 {
+                        return Err(invalid("enum payload must hold a type and a value"));
+                    }
+                    let type_name = read_string(&mut payload)?;
+                    let value = read_string(&mut payload)?;
+                    ConfigValue::Enum { type_name, value }
+                }
+                other => return Err(invalid(format!("unknown extension type {}", other))),
+            }
+        }
+        other => return Err(invalid(format!("unsupported marker {:?}", other))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_msgpack_round_trip() {
+        let mut inner = ConfigDict::new();
+        inner.insert("port".to_string(), ConfigValue::Int(-5432));
+        inner.insert("ratio".to_string(), ConfigValue::Float(0.25));
+        inner.insert("password".to_string(), ConfigValue::Missing);
+        inner.insert(
+            "url".to_string(),
+            ConfigValue::Interpolation("${db.host}:${db.port}".to_string()),
+        );
+        let mut root = ConfigDict::new();
+        root.insert("z_first".to_string(), ConfigValue::Null);
+        root.insert("db".to_string(), ConfigValue::Dict(inner));
+        root.insert(
+            "items".to_string(),
+            ConfigValue::List(vec![
+                ConfigValue::Bool(true),
+                ConfigValue::String("${not_an_interpolation}".to_string()),
+                ConfigValue::Enum {
+                    type_name: "Color".to_string(),
+                    value: "RED".to_string(),
+                },
+                ConfigValue::Int(i64::MAX),
+                ConfigValue::List(vec![]),
+            ]),
+        );
+        let value = ConfigValue::Dict(root);
+
+        let bytes = value.to_msgpack();
+        let decoded = ConfigValue::from_msgpack(&bytes).unwrap();
+        assert_eq!(decoded, value);
+        let keys: Vec<_> = decoded.as_dict().unwrap().keys().collect();
+        assert_eq!(keys, vec!["z_first", "db", "items"]);
+
+        let err = ConfigValue::from_msgpack(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert!(err.message.starts_with("Invalid MessagePack config"));
+        let mut extra = bytes.clone();
+        extra.push(0xc0);
+        let err = ConfigValue::from_msgpack(&extra).unwrap_err();
+        assert_eq!(err.message, "Invalid MessagePack config: 1 trailing bytes");
+    }
+}
@@ -2,8 +2,9 @@
 //! YAML configuration parser
 
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::config::enums::{self, EnumError};
 use crate::config::value::{ConfigDict, ConfigValue};
 
 /// Error type for config loading
@@ -41,15 +42,36 @@ impl std::fmt::Display for ConfigLoadError {
 
 impl std::error::Error for ConfigLoadError {}
 
+impl From<EnumError> for ConfigLoadError {
+    fn from(e: EnumError) -> Self {
+        ConfigLoadError::new(e.message)
+    }
+}
+
 /// Parse a YAML string into a ConfigValue
+///
+/// `!enum Type VALUE` tags become [`ConfigValue::Enum`] values, checked
+/// against the global [`enums::instance`] registry. `<<` merge keys are
+/// applied. Other tags, `!include` among them, are dropped and their value
+/// kept as written; use [`parse_yaml_with_includes`] to resolve includes.
 pub fn parse_yaml(content: &str) -> Result<ConfigValue, ConfigLoadError> {
+    parse_yaml_value(content, None)
+}
+
+fn parse_yaml_value(
+    content: &str,
+    includes: Option<&mut IncludeScope<'_>>,
+) -> Result<ConfigValue, ConfigLoadError> {
     let normalized = normalize_legacy_bool_scalars(content);
 
-    // Use serde_yaml for parsing
     let yaml: serde_yaml::Value = serde_yaml::from_str(&normalized)
         .map_err(|e| ConfigLoadError::new(format!("YAML parse error: {}", e)))?;
 
-    Ok(yaml_to_config_value(&yaml))
+    let value = yaml_to_config_value(&yaml, includes)?;
+    enums::instance()
+        .validate(&value)
+        .map_err(|e| ConfigLoadError::new(e.message))?;
+    Ok(value)
 }
 
 fn normalize_legacy_bool_scalars(content: &str) -> String {
@@ -169,26 +191,140 @@ fn find_trimmed_legacy_bool(
 }
 
 /// Load a YAML file and parse it
+///
+/// `!include path.yaml` tags are resolved relative to the file's directory
+/// (see [`parse_yaml_with_includes`]).
 pub fn load_yaml_file(path: &Path) -> Result<ConfigValue, ConfigLoadError> {
+    load_yaml_file_including(path, &mut Vec::new())
+}
+
+/// Load every `.yaml` file in a directory and deep-merge them into one dict
+///
+/// Files are merged in name order, so in a "conf.d" layout `02-db.yaml`
+/// overrides `01-base.yaml`. Subdirectories and other files are ignored,
+/// and empty files contribute nothing. Every other file must hold a dict.
+/// An entry of the directory that cannot be read is an error.
+pub fn load_config_dir(dir: &Path) -> Result<ConfigValue, ConfigLoadError> {
+    let dir_str = dir.to_string_lossy().to_string();
+    let read_error = |e: std::io::Error| {
+        ConfigLoadError::with_path(format!("Failed to read directory: {}", e), &dir_str)
+    };
+
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(read_error)? {
+        let path = entry.map_err(read_error)?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "yaml") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut merged = ConfigDict::new();
+    for path in paths {
+        match load_yaml_file(&path)? {
+            ConfigValue::Dict(dict) => merged
+                .merge(&dict)
+                .map_err(|e| ConfigLoadError::with_path(e.message, path.to_string_lossy()))?,
+            ConfigValue::Null => {}
+            _ => {
+                return Err(ConfigLoadError::with_path(
+                    "Config must be a dictionary",
+                    path.to_string_lossy(),
+                ))
+            }
+        }
+    }
+    Ok(ConfigValue::Dict(merged))
+}
+
+/// Parse a YAML string, resolving `!include path.yaml` tags
+///
+/// A tag used as a value (`db: !include db.yaml`) is replaced by the whole
+/// included document, and a tag at the root replaces the document itself.
+/// Used as a merge key (`<<: !include base.yaml`) the included mapping is
+/// merged into the enclosing one, whose own keys win. Relative paths are
+/// resolved against `base_dir`, and nested includes against the directory
+/// of the including file. Circular includes are an error.
+pub fn parse_yaml_with_includes(
+    content: &str,
+    base_dir: &Path,
+) -> Result<ConfigValue, ConfigLoadError> {
+    let mut stack = Vec::new();
+    parse_yaml_value(
+        content,
+        Some(&mut IncludeScope {
+            base_dir,
+            stack: &mut stack,
+        }),
+    )
+}
+
+/// Where `!include` paths are resolved, and the files including them
+struct IncludeScope<'a> {
+    base_dir: &'a Path,
+    stack: &'a mut Vec<PathBuf>,
+}
+
+impl IncludeScope<'_> {
+    fn load(&mut self, path: &str) -> Result<ConfigValue, ConfigLoadError> {
+        load_yaml_file_including(&self.base_dir.join(path), self.stack)
+    }
+}
+
+/// Load a file as part of an include chain (`stack` holds the including files)
+fn load_yaml_file_including(
+    path: &Path,
+    stack: &mut Vec<PathBuf>,
+) -> Result<ConfigValue, ConfigLoadError> {
     let path_str = path.to_string_lossy().to_string();
 
     if !path.exists() {
         return Err(ConfigLoadError::with_path("Config not found", &path_str));
     }
 
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if let Some(start) = stack.iter().position(|p| *p == canonical) {
+        let chain: Vec<String> = stack[start..]
+            .iter()
+            .chain(std::iter::once(&canonical))
+            .map(|p| p.display().to_string())
+            .collect();
+        return Err(ConfigLoadError::with_path(
+            format!("Circular include: {}", chain.join(" -> ")),
+            &path_str,
+        ));
+    }
+
     let content = fs::read_to_string(path).map_err(|e| {
         ConfigLoadError::with_path(format!("Failed to read file: {}", e), &path_str)
     })?;
 
-    parse_yaml(&content).map_err(|mut e| {
-        e.path = Some(path_str);
+    stack.push(canonical);
+    let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let result = parse_yaml_value(
+        &content,
+        Some(&mut IncludeScope {
+            base_dir,
+            stack: &mut *stack,
+        }),
+    );
+    stack.pop();
+
+    // Errors from included files keep the path of the file they occurred in
+    result.map_err(|mut e| {
+        e.path.get_or_insert(path_str);
         e
     })
 }
 
 /// Convert serde_yaml::Value to ConfigValue
-fn yaml_to_config_value(yaml: &serde_yaml::Value) -> ConfigValue {
-    match yaml {
+///
+/// `includes` is `None` when `!include` tags are not resolved.
+fn yaml_to_config_value(
+    yaml: &serde_yaml::Value,
+    mut includes: Option<&mut IncludeScope<'_>>,
+) -> Result<ConfigValue, ConfigLoadError> {
+    Ok(match yaml {
         serde_yaml::Value::Null => ConfigValue::Null,
         serde_yaml::Value::Bool(b) => ConfigValue::Bool(*b),
         serde_yaml::Value::Number(n) => {
@@ -204,26 +340,84 @@ fn yaml_to_config_value(yaml: &serde_yaml::Value) -> ConfigValue {
             // Handle special values
             if s == "???" {
                 ConfigValue::Missing
-            } else if s.contains("${") && s.contains('}') {
-                ConfigValue::Interpolation(s.clone())
             } else {
-                ConfigValue::String(s.clone())
+                ConfigValue::from_string(s.clone())
             }
         }
-        serde_yaml::Value::Sequence(seq) => {
-            let values: Vec<ConfigValue> = seq.iter().map(yaml_to_config_value).collect();
-            ConfigValue::List(values)
-        }
+        serde_yaml::Value::Sequence(seq) => ConfigValue::List(
+            seq.iter()
+                .map(|item| yaml_to_config_value(item, includes.as_deref_mut()))
+                .collect::<Result<_, _>>()?,
+        ),
         serde_yaml::Value::Mapping(map) => {
+            // Merged keys come first, so the mapping's own keys override them
             let mut dict = ConfigDict::new();
+            if let Some(merge) = map.get("<<") {
+                merge_into(
+                    &mut dict,
+                    yaml_to_config_value(merge, includes.as_deref_mut())?,
+                )?;
+            }
             for (key, value) in map {
                 if let serde_yaml::Value::String(k) = key {
-                    dict.insert(k.clone(), yaml_to_config_value(value));
+                    if k != "<<" {
+                        dict.insert(
+                            k.clone(),
+                            yaml_to_config_value(value, includes.as_deref_mut())?,
+                        );
+                    }
                 }
             }
             ConfigValue::Dict(dict)
         }
+        serde_yaml::Value::Tagged(tagged) => {
+            let tag = &tagged.tag;
+            let text = tagged.value.as_str();
+            if *tag == "enum" {
+                let args: Vec<&str> = text.unwrap_or_default().split_whitespace().collect();
+                let [type_name, member] = args[..] else {
+                    return Err(ConfigLoadError::new(format!(
+                        "!enum requires a type name and a value, got '{}'",
+                        text.unwrap_or_default()
+                    )));
+                };
+                ConfigValue::Enum {
+                    type_name: type_name.to_string(),
+                    value: member.to_string(),
+                }
+            } else if let (true, Some(scope)) = (*tag == "include", includes.as_deref_mut()) {
+                match text {
+                    Some(path) if !path.is_empty() => scope.load(path)?,
+                    _ => return Err(ConfigLoadError::new("!include requires a file path")),
+                }
+            } else {
+                yaml_to_config_value(&tagged.value, includes)?
+            }
+        }
+    })
+}
+
+/// Apply a `<<` merge key: a mapping, or a list of mappings where earlier ones win
+fn merge_into(dict: &mut ConfigDict, merge: ConfigValue) -> Result<(), ConfigLoadError> {
+    let sources = match merge {
+        ConfigValue::Dict(source) => vec![source],
+        ConfigValue::List(items) => items
+            .into_iter()
+            .map(|item| match item {
+                ConfigValue::Dict(source) => Ok(source),
+                _ => Err(ConfigLoadError::new("'<<' merges only mappings")),
+            })
+            .collect::<Result<_, _>>()?,
+        _ => return Err(ConfigLoadError::new("'<<' merges only mappings")),
+    };
+    for source in sources {
+        for (key, value) in source.iter() {
+            if !dict.contains_key(key) {
+                dict.insert(key.to_string(), value.clone());
+            }
+        }
     }
+    Ok(())
 }
 
 /// Extract the defaults list from a config
@@ -236,6 +430,9 @@ pub fn extract_defaults(config: &ConfigValue) -> Option<Vec<ConfigValue>> {
     None
 }
 
+/// Header directives that may be written without a value (`# @package`)
+const HEADER_DIRECTIVES: &[&str] = &["package", "schema"];
+
 /// Extract the package header from YAML content
 pub fn extract_header(content: &str) -> std::collections::HashMap<String, String> {
     let mut header = std::collections::HashMap::new();
@@ -264,14 +461,14 @@ pub fn extract_header(content: &str) -> std::collections::HashMap<String, String
                         header.insert(key.to_string(), value.to_string());
                     }
                 } else if parts.len() == 1 {
-                    // Handle @package:value or @package without value
+                    // Handle @package:value or @package without value. An
+                    // empty value is kept only for the known directives, so a
+                    // comment like `# @todo` does not become a header key
                     let part = parts[0].trim_start_matches('@');
-                    if let Some(idx) = part.find(':') {
-                        let key = &part[..idx];
-                        let value = part[idx + 1..].trim();
-                        if !key.is_empty() && !value.is_empty() {
-                            header.insert(key.to_string(), value.to_string());
-                        }
+                    let (key, value) = part.split_once(':').unwrap_or((part, ""));
+                    let value = value.trim();
+                    if !key.is_empty() && (!value.is_empty() || HEADER_DIRECTIVES.contains(&key)) {
+                        header.insert(key.to_string(), value.to_string());
                     }
                 }
             }
@@ -287,6 +484,7 @@ pub fn extract_header(content: &str) -> std::collections::HashMap<String, String
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
 
     #[test]
     fn test_parse_simple_yaml() {
@@ -446,6 +644,32 @@ name: myapp
         assert_eq!(header.get("package"), Some(&"db".to_string()));
     }
 
+    #[test]
+    fn test_extract_header_empty_value() {
+        let header = extract_header(
+            "# @package
+host: localhost
+",
+        );
+        assert_eq!(header.get("package"), Some(&String::new()));
+
+        let header = extract_header(
+            "# @package:
+# @schema:db
+host: localhost
+",
+        );
+        assert_eq!(header.get("package"), Some(&String::new()));
+        assert_eq!(header.get("schema"), Some(&"db".to_string()));
+    }
+
+    #[test]
+    fn test_extract_header_ignores_bare_unknown_directives() {
+        let header = extract_header("# @todo\n# @note:\n# @package\nhost: localhost\n");
+        assert_eq!(header.len(), 1);
+        assert_eq!(header.get("package"), Some(&String::new()));
+    }
+
     #[test]
     fn test_extract_header_stops_at_content() {
         let yaml = "# @package db\nhost: localhost\n# @ignored comment\n";
@@ -455,4 +679,217 @@ name: myapp
         assert_eq!(header.len(), 1);
         assert_eq!(header.get("package"), Some(&"db".to_string()));
     }
+
+    #[test]
+    fn test_include_file() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("db")).unwrap();
+        fs::write(
+            dir.path().join("db/mysql.yaml"),
+            "host: localhost\nport: 3306\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("config.yaml"),
+            "db: !include db/mysql.yaml  # inlined\nreplicas:\n  - !include db/mysql.yaml\nname: '!include literal'\n",
+        )
+        .unwrap();
+
+        let config = load_yaml_file(&dir.path().join("config.yaml")).unwrap();
+        let dict = config.as_dict().unwrap();
+        let db = dict.get("db").unwrap().as_dict().unwrap();
+        assert_eq!(db.get("port"), Some(&ConfigValue::Int(3306)));
+        assert_eq!(
+            dict.get("replicas").unwrap().as_list().unwrap()[0],
+            ConfigValue::Dict(db.clone())
+        );
+        assert_eq!(dict.get("name").unwrap().as_str(), Some("!include literal"));
+
+        // A root-level include replaces the whole document
+        let root = parse_yaml_with_includes("!include config.yaml\n", dir.path()).unwrap();
+        assert_eq!(root, config);
+    }
+
+    #[test]
+    fn test_include_merge_key() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("base.yaml"),
+            "host: localhost\nport: 3306\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("extra.yaml"), "port: 1\ntimeout: 30\n").unwrap();
+
+        // The mapping's own keys win, and earlier merged mappings beat later ones
+        let config = parse_yaml_with_includes(
+            "db:\n  port: 5432\n  <<: [!include base.yaml, !include extra.yaml]\n",
+            dir.path(),
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            parse_yaml("db: {host: localhost, port: 5432, timeout: 30}\n").unwrap()
+        );
+
+        let config = parse_yaml("base: &b {x: 1, y: 2}\nchild:\n  <<: *b\n  y: 3\n").unwrap();
+        assert_eq!(
+            config.as_dict().unwrap().get("child"),
+            Some(&parse_yaml("{x: 1, y: 3}\n").unwrap())
+        );
+
+        fs::write(dir.path().join("list.yaml"), "- a\n").unwrap();
+        let err = parse_yaml_with_includes("<<: !include list.yaml\n", dir.path()).unwrap_err();
+        assert_eq!(err.message, "'<<' merges only mappings");
+    }
+
+    #[test]
+    fn test_include_ignores_text_and_reads_flow_collections() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("db.yaml"), "port: 3306\n").unwrap();
+
+        let config = parse_yaml_with_includes(
+            concat!(
+                "script: |\n",
+                "  echo start\n",
+                "  db: !include db.yaml\n",
+                "folded: >\n",
+                "  - !include db.yaml\n",
+                "quoted: \"first line\n",
+                "  x: !include db.yaml\"\n",
+                "flow: {a: !include db.yaml, b: 1}\n",
+                "list: [!include db.yaml, 2]\n",
+                "anchored: &db !include db.yaml\n",
+                "alias: *db\n",
+            ),
+            dir.path(),
+        )
+        .unwrap();
+        let dict = config.as_dict().unwrap();
+        let db = parse_yaml("port: 3306\n").unwrap();
+
+        // Tags inside block scalars and quoted strings are plain text
+        assert_eq!(
+            dict.get("script").unwrap().as_str(),
+            Some("echo start\ndb: !include db.yaml\n")
+        );
+        assert_eq!(
+            dict.get("folded").unwrap().as_str(),
+            Some("- !include db.yaml\n")
+        );
+        assert_eq!(
+            dict.get("quoted").unwrap().as_str(),
+            Some("first line x: !include db.yaml")
+        );
+
+        assert_eq!(dict.select("flow.a"), Some(db.clone()));
+        assert_eq!(dict.select("flow.b"), Some(ConfigValue::Int(1)));
+        assert_eq!(
+            dict.get("list"),
+            Some(&ConfigValue::List(vec![db.clone(), ConfigValue::Int(2)]))
+        );
+        assert_eq!(dict.get("anchored"), Some(&db));
+        assert_eq!(dict.get("alias"), Some(&db));
+    }
+
+    #[test]
+    fn test_load_config_dir() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("01-base.yaml"),
+            "db:\n  host: localhost\n  port: 3306\n  pool: {min: 1, max: 4}\nname: base\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("02-db.yaml"),
+            "db:\n  port: 5432\n  pool: {max: 16}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("03-overrides.yaml"),
+            "name: prod\ndb:\n  host: db.internal\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("README.md"), "not: config\n").unwrap();
+        fs::create_dir(dir.path().join("00-nested.yaml")).unwrap();
+
+        let config = load_config_dir(dir.path()).unwrap();
+        assert_eq!(
+            config,
+            parse_yaml(
+                "db:\n  host: db.internal\n  port: 5432\n  pool: {min: 1, max: 16}\nname: prod\n"
+            )
+            .unwrap()
+        );
+
+        fs::write(dir.path().join("04-list.yaml"), "- a\n").unwrap();
+        let err = load_config_dir(dir.path()).unwrap_err();
+        assert_eq!(err.message, "Config must be a dictionary");
+        assert!(err.path.unwrap().ends_with("04-list.yaml"));
+
+        let err = load_config_dir(&dir.path().join("missing")).unwrap_err();
+        assert!(err.message.starts_with("Failed to read directory"));
+    }
+
+    #[test]
+    fn test_include_circular() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.yaml"), "b: !include b.yaml\n").unwrap();
+        fs::write(dir.path().join("b.yaml"), "a: !include a.yaml\n").unwrap();
+
+        let err = load_yaml_file(&dir.path().join("a.yaml")).unwrap_err();
+        assert!(
+            err.message.starts_with("Circular include: "),
+            "{}",
+            err.message
+        );
+        assert!(err.message.ends_with("a.yaml"), "{}", err.message);
+
+        let err = parse_yaml_with_includes("x: !include missing.yaml\n", dir.path()).unwrap_err();
+        assert_eq!(err.message, "Config not found");
+    }
+
+    #[test]
+    fn test_parse_enum_tag() {
+        enums::instance().register("ParserTestColor", &["RED", "GREEN"]);
+
+        let config = parse_yaml("color: !enum ParserTestColor RED  # primary\nname: x\n").unwrap();
+        let dict = config.as_dict().unwrap();
+        assert_eq!(
+            dict.get("color"),
+            Some(&ConfigValue::Enum {
+                type_name: "ParserTestColor".to_string(),
+                value: "RED".to_string()
+            })
+        );
+
+        let err = parse_yaml("color: !enum ParserTestColor BLUE\n").unwrap_err();
+        assert!(err
+            .message
+            .contains("Invalid value 'BLUE' for enum ParserTestColor"));
+        assert!(parse_yaml("color: !enum ParserTestColor\n").is_err());
+        assert!(parse_yaml("color: !enum ParserTestUnknown RED\n").is_err());
+
+        // Tag text inside block scalars and quoted strings is left alone
+        let config = parse_yaml(
+            "doc: |\n  color: !enum ParserTestColor BLUE\nnote: 'x: !enum A B C'\n\
+             colors: [!enum ParserTestColor GREEN]\n",
+        )
+        .unwrap();
+        let dict = config.as_dict().unwrap();
+        assert_eq!(
+            dict.get("doc").and_then(|v| v.as_str()),
+            Some("color: !enum ParserTestColor BLUE\n")
+        );
+        assert_eq!(
+            dict.get("note").and_then(|v| v.as_str()),
+            Some("x: !enum A B C")
+        );
+        assert_eq!(
+            dict.get("colors"),
+            Some(&ConfigValue::List(vec![ConfigValue::Enum {
+                type_name: "ParserTestColor".to_string(),
+                value: "GREEN".to_string()
+            }]))
+        );
+    }
 }
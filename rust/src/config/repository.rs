@@ -5,10 +5,14 @@
 //! configuration sources, mirroring the Python IConfigRepository interface.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 
+use crate::config::enums::EnumError;
 use crate::config::parser::ConfigLoadError;
 use crate::config::source::{ConfigResult, ConfigSource, FileConfigSource};
 use crate::config::value::{ConfigDict, ConfigValue};
+use crate::glob::Glob;
+use crate::merge::{get_nested, merge_dicts_annotated, strip_merge_annotations};
 use crate::ObjectType;
 
 /// Search path element for config loading
@@ -36,9 +40,28 @@ pub fn get_scheme(path: &str) -> String {
     }
 }
 
+/// Join a group and a config name into a config path, without a `.yaml` suffix
+fn group_config_path(group: &str, name: &str) -> String {
+    let name = name.strip_suffix(".yaml").unwrap_or(name);
+    if group.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", group, name)
+    }
+}
+
 /// Configuration repository for managing and loading configs
+///
+/// By default a config is loaded from the first source that has it. In
+/// overlay mode (see [`ConfigRepository::with_overlay`]) the last source
+/// that has it wins instead, replacing earlier copies of the file whole.
+/// Group listings always cover every source.
 pub struct ConfigRepository {
     sources: Vec<Box<dyn ConfigSource>>,
+    /// Config name aliases, keyed by the aliased config path (e.g. "db/prod" -> "db/production")
+    aliases: HashMap<String, String>,
+    /// Whether later sources take priority over earlier ones
+    overlay: bool,
 }
 
 impl ConfigRepository {
@@ -49,7 +72,78 @@ impl ConfigRepository {
             .map(|sp| Self::create_source(sp))
             .collect();
 
-        Self { sources }
+        Self {
+            sources,
+            aliases: HashMap::new(),
+            overlay: false,
+        }
+    }
+
+    /// Enable or disable overlay mode, where a config in a later source
+    /// overrides the same config in an earlier one entirely (not merged)
+    pub fn with_overlay(mut self, overlay: bool) -> Self {
+        self.overlay = overlay;
+        self
+    }
+
+    /// Whether later sources take priority over earlier ones
+    pub fn is_overlay(&self) -> bool {
+        self.overlay
+    }
+
+    /// Sources in the order configs are looked up in: search path order,
+    /// or reversed in overlay mode
+    fn sources_by_priority(&self) -> Vec<&dyn ConfigSource> {
+        let sources = self.sources.iter().map(|s| s.as_ref());
+        if self.overlay {
+            sources.rev().collect()
+        } else {
+            sources.collect()
+        }
+    }
+
+    /// Alias a config name within a group, so `group/from` loads `group/to`
+    ///
+    /// Aliases may chain, but an alias that would form a cycle is rejected.
+    pub fn add_alias(&mut self, group: &str, from: &str, to: &str) -> Result<(), ConfigLoadError> {
+        let from_path = group_config_path(group, from);
+        let to_path = group_config_path(group, to);
+
+        let mut chain = vec![from_path.clone(), to_path.clone()];
+        let mut current = to_path.clone();
+        loop {
+            if current == from_path {
+                return Err(ConfigLoadError::with_path(
+                    format!("Cyclic config alias: {}", chain.join(" -> ")),
+                    from_path,
+                ));
+            }
+            match self.aliases.get(&current) {
+                Some(next) => {
+                    chain.push(next.clone());
+                    current = next.clone();
+                }
+                None => break,
+            }
+        }
+
+        self.aliases.insert(from_path, to_path);
+        Ok(())
+    }
+
+    /// Follow aliases for a config path to the config it refers to
+    ///
+    /// Aliases are keyed without the `.yaml` suffix, so `db/prod.yaml`
+    /// resolves like `db/prod`. A path with no alias is returned unchanged.
+    pub fn resolve_alias(&self, config_path: &str) -> String {
+        let name = config_path.strip_suffix(".yaml").unwrap_or(config_path);
+        let Some(mut current) = self.aliases.get(name) else {
+            return config_path.to_string();
+        };
+        while let Some(target) = self.aliases.get(current) {
+            current = target;
+        }
+        current.to_string()
     }
 
     /// Create a config source from a search path element
@@ -71,17 +165,35 @@ impl ConfigRepository {
         &self.sources
     }
 
-    /// Load a config by path
+    /// Load a config by path from the highest-priority source that has it
     pub fn load_config(&self, config_path: &str) -> Result<Option<ConfigResult>, ConfigLoadError> {
-        for source in &self.sources {
-            if source.is_config(config_path) {
-                let result = source.load_config(config_path)?;
+        let config_path = self.resolve_alias(config_path);
+        for source in self.sources_by_priority() {
+            if source.is_config(&config_path) {
+                let result = source.load_config(&config_path)?;
                 return Ok(Some(result));
             }
         }
         Ok(None)
     }
 
+    /// Load a config by path, falling back to `fallback` if it does not exist
+    ///
+    /// Returns the loaded config together with the path that was used, or
+    /// None when neither exists.
+    pub fn load_config_or(
+        &self,
+        config_path: &str,
+        fallback: &str,
+    ) -> Result<Option<(ConfigResult, String)>, ConfigLoadError> {
+        for path in [config_path, fallback] {
+            if let Some(result) = self.load_config(path)? {
+                return Ok(Some((result, path.to_string())));
+            }
+        }
+        Ok(None)
+    }
+
     /// Check if a group (directory) exists
     pub fn group_exists(&self, config_path: &str) -> bool {
         self.sources.iter().any(|s| s.is_group(config_path))
@@ -89,46 +201,193 @@ impl ConfigRepository {
 
     /// Check if a config file exists
     pub fn config_exists(&self, config_path: &str) -> bool {
-        self.sources.iter().any(|s| s.is_config(config_path))
+        let config_path = self.resolve_alias(config_path);
+        self.sources.iter().any(|s| s.is_config(&config_path))
+    }
+
+    /// Check whether each of `paths` is a config, in order
+    ///
+    /// Each parent group is listed once per source, so checking many configs
+    /// in the same group reuses one (cached) directory listing.
+    pub fn exists_any(&self, paths: &[&str]) -> Vec<bool> {
+        let resolved: Vec<String> = paths.iter().map(|p| self.resolve_alias(p)).collect();
+        let mut listings = HashMap::new();
+        resolved
+            .iter()
+            .map(|path| {
+                let path = path.trim_matches('/');
+                // Listings name only `.yaml` configs; a `.yml` file is loaded
+                // only when its name is spelled out
+                if path.ends_with(".yml") {
+                    return self.sources.iter().any(|s| s.is_config(path));
+                }
+                let path = path.trim_end_matches(".yaml");
+                self.listed_in_parent(path, ObjectType::Config, &mut listings)
+            })
+            .collect()
+    }
+
+    /// Check whether each of `paths` is a group, in order
+    ///
+    /// Like [`ConfigRepository::exists_any`], listings are shared between
+    /// paths with the same parent.
+    pub fn group_exists_batch(&self, paths: &[&str]) -> Vec<bool> {
+        let mut listings = HashMap::new();
+        paths
+            .iter()
+            .map(|path| {
+                let path = path.trim_matches('/');
+                if path.is_empty() {
+                    self.group_exists(path)
+                } else {
+                    self.listed_in_parent(path, ObjectType::Group, &mut listings)
+                }
+            })
+            .collect()
+    }
+
+    /// Check if any source lists `path` in its parent group
+    fn listed_in_parent(
+        &self,
+        path: &str,
+        object_type: ObjectType,
+        listings: &mut HashMap<(usize, String), Vec<String>>,
+    ) -> bool {
+        let (parent, name) = match path.rfind('/') {
+            Some(idx) => (&path[..idx], &path[idx + 1..]),
+            None => ("", path),
+        };
+        self.sources.iter().enumerate().any(|(idx, source)| {
+            listings
+                .entry((idx, parent.to_string()))
+                .or_insert_with(|| source.list(parent, Some(object_type)))
+                .iter()
+                .any(|item| item == name)
+        })
     }
 
     /// Get available options for a config group
+    ///
+    /// When `provider` is set, only sources with that provider name contribute.
     pub fn get_group_options(
         &self,
         group_name: &str,
         results_filter: Option<ObjectType>,
+        provider: Option<&str>,
     ) -> Vec<String> {
+        self.get_group_options_with_aliases(group_name, results_filter, provider, false)
+    }
+
+    /// Get available options for a config group, optionally listing aliases
+    /// whose target config exists alongside the real configs
+    pub fn get_group_options_with_aliases(
+        &self,
+        group_name: &str,
+        results_filter: Option<ObjectType>,
+        provider: Option<&str>,
+        include_aliases: bool,
+    ) -> Vec<String> {
+        let sources: Vec<&dyn ConfigSource> = self
+            .sources
+            .iter()
+            .map(|s| s.as_ref())
+            .filter(|s| provider.is_none_or(|p| s.provider() == p))
+            .collect();
         let mut options: Vec<String> = Vec::new();
 
-        for source in &self.sources {
+        for source in &sources {
             if source.is_group(group_name) {
                 let items = source.list(group_name, results_filter);
                 options.extend(items);
             }
         }
 
+        if include_aliases && results_filter != Some(ObjectType::Group) {
+            for alias_path in self.aliases.keys() {
+                let (group, name) = match alias_path.rfind('/') {
+                    Some(idx) => (&alias_path[..idx], &alias_path[idx + 1..]),
+                    None => ("", alias_path.as_str()),
+                };
+                let target = self.resolve_alias(alias_path);
+                if group == group_name && sources.iter().any(|s| s.is_config(&target)) {
+                    options.push(name.to_string());
+                }
+            }
+        }
+
         // Remove duplicates and sort
         options.sort();
         options.dedup();
         options
     }
 
-    /// Find the source that contains a config or group
+    /// List every config under a group at any depth
+    ///
+    /// Paths are relative to the group, e.g. `a/b/tiny` for
+    /// `model/a/b/tiny.yaml` when walking `model`; an empty group walks the
+    /// whole repository. A group whose directory is also one of its
+    /// ancestors (a symlink cycle) is not entered again.
+    pub fn walk(&self, group_name: &str) -> Vec<String> {
+        let join = |parent: &str, name: &str| {
+            if parent.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}/{}", parent, name)
+            }
+        };
+
+        let mut configs = Vec::new();
+        // Each pending group carries the canonical directories above it
+        let mut pending: Vec<(String, Vec<PathBuf>)> = vec![(String::new(), Vec::new())];
+        while let Some((relative, mut ancestors)) = pending.pop() {
+            let group = join(group_name, &relative);
+            let dirs: Vec<PathBuf> = self
+                .sources
+                .iter()
+                .filter_map(|s| s.canonical_group(&group))
+                .collect();
+            if dirs.iter().any(|dir| ancestors.contains(dir)) {
+                continue;
+            }
+            ancestors.extend(dirs);
+
+            for name in self.get_group_options(&group, Some(ObjectType::Config), None) {
+                configs.push(join(&relative, &name));
+            }
+            for name in self.get_group_options(&group, Some(ObjectType::Group), None) {
+                pending.push((join(&relative, &name), ancestors.clone()));
+            }
+        }
+        configs.sort();
+        configs.dedup();
+        configs
+    }
+
+    /// Get the configs under a group, at any depth, whose relative path
+    /// matches `glob`
+    ///
+    /// `*` matches within one path segment and `**` across segments, so
+    /// `**/tiny` finds every `tiny` config however deeply it is nested.
+    pub fn get_group_options_matching(&self, group_name: &str, glob: &Glob) -> Vec<String> {
+        glob.filter(&self.walk(group_name))
+    }
+
+    /// Find the highest-priority source that contains a config or group
     pub fn find_source(
         &self,
         config_path: &str,
         object_type: ObjectType,
     ) -> Option<&dyn ConfigSource> {
-        for source in &self.sources {
+        for source in self.sources_by_priority() {
             match object_type {
                 ObjectType::Config => {
                     if source.is_config(config_path) {
-                        return Some(source.as_ref());
+                        return Some(source);
                     }
                 }
                 ObjectType::Group => {
                     if source.is_group(config_path) {
-                        return Some(source.as_ref());
+                        return Some(source);
                     }
                 }
                 ObjectType::NotFound => {}
@@ -138,6 +397,15 @@ impl ConfigRepository {
     }
 }
 
+/// The configs of a group loaded by [`CachingConfigRepository::load_group`]
+#[derive(Debug, Default)]
+pub struct GroupLoad {
+    /// `(name, config)` pairs, sorted by name
+    pub configs: Vec<(String, ConfigValue)>,
+    /// Options that failed to load, with their errors
+    pub errors: Vec<(String, ConfigLoadError)>,
+}
+
 /// Caching wrapper for ConfigRepository
 pub struct CachingConfigRepository {
     delegate: ConfigRepository,
@@ -178,13 +446,51 @@ impl CachingConfigRepository {
         self.delegate.config_exists(config_path)
     }
 
-    /// Get group options
+    /// Check whether each of `paths` is a config, in order
+    pub fn exists_any(&self, paths: &[&str]) -> Vec<bool> {
+        self.delegate.exists_any(paths)
+    }
+
+    /// Check whether each of `paths` is a group, in order
+    pub fn group_exists_batch(&self, paths: &[&str]) -> Vec<bool> {
+        self.delegate.group_exists_batch(paths)
+    }
+
+    /// Alias a config name within a group, invalidating cached loads
+    pub fn add_alias(&mut self, group: &str, from: &str, to: &str) -> Result<(), ConfigLoadError> {
+        self.delegate.add_alias(group, from, to)?;
+        self.cache.clear();
+        Ok(())
+    }
+
+    /// Get group options, optionally restricted to one provider
     pub fn get_group_options(
         &self,
         group_name: &str,
         results_filter: Option<ObjectType>,
+        provider: Option<&str>,
     ) -> Vec<String> {
-        self.delegate.get_group_options(group_name, results_filter)
+        self.delegate
+            .get_group_options(group_name, results_filter, provider)
+    }
+
+    /// Load every config option in a group, going through the cache
+    pub fn load_group(&mut self, group_name: &str) -> GroupLoad {
+        let mut configs = Vec::new();
+        let mut errors = Vec::new();
+        for name in self.get_group_options(group_name, Some(ObjectType::Config), None) {
+            let config_path = if group_name.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", group_name, name)
+            };
+            match self.load_config(&config_path) {
+                Ok(Some(result)) => configs.push((name, result.config)),
+                Ok(None) => {}
+                Err(e) => errors.push((name, e)),
+            }
+        }
+        GroupLoad { configs, errors }
     }
 
     /// Get sources
@@ -238,11 +544,9 @@ impl CachingConfigRepository {
         // Compose the final config by merging all defaults
         let mut merged = ConfigDict::new();
 
+        // Defaults are ordered so that each config's own content (its `_self_`
+        // entry) lands between the defaults before and after it
         for result_default in &defaults_result.defaults {
-            if result_default.is_self {
-                continue; // _self_ is handled inline
-            }
-
             if let Some(ref config_path) = result_default.config_path {
                 match repo.load_config(config_path)? {
                     Some(config_result) => {
@@ -259,7 +563,8 @@ impl CachingConfigRepository {
                                 &mut merged,
                                 &filtered,
                                 result_default.package.as_deref(),
-                            );
+                            )
+                            .map_err(|e| ConfigLoadError::with_path(e.message, config_path))?;
                         }
                     }
                     None => {
@@ -268,22 +573,31 @@ impl CachingConfigRepository {
                 }
             }
         }
+        strip_merge_annotations(&mut merged);
 
         // Apply config overrides (key.path=value style)
         for ovr in &defaults_result.config_overrides {
             apply_override_to_dict(&mut merged, ovr)?;
         }
 
-        // Also apply overrides from the original list (for value overrides)
-        for ovr in overrides {
-            if ovr.contains('.') && !ovr.starts_with('+') && !ovr.starts_with('~') {
-                apply_override_to_dict(&mut merged, ovr)?;
+        // Apply value overrides from the original list. Group overrides that
+        // matched nothing already failed the defaults list build.
+        let mut applied_overrides = Vec::new();
+        let mut unused_overrides = Vec::new();
+        for ovr in overrides.iter().map(|ovr| ovr.trim()) {
+            let used = !is_value_override(ovr) || apply_override_to_dict(&mut merged, ovr)?;
+            if used {
+                applied_overrides.push(ovr.to_string());
+            } else {
+                unused_overrides.push(ovr.to_string());
             }
         }
 
         Ok(ComposedConfig {
             config: merged,
             defaults_result,
+            applied_overrides,
+            unused_overrides,
         })
     }
 }
@@ -295,31 +609,39 @@ pub struct ComposedConfig {
     pub config: ConfigDict,
     /// The defaults list result for debugging/inspection
     pub defaults_result: crate::defaults_list::DefaultsListResult,
+    /// Overrides that took effect, trimmed, in the order given
+    pub applied_overrides: Vec<String>,
+    /// Value overrides that matched nothing: changing or deleting a key the
+    /// composed config does not have (a change still sets the key)
+    pub unused_overrides: Vec<String>,
 }
 
 /// Merge source dict at a package path into target
-fn merge_at_package(target: &mut ConfigDict, source: &ConfigDict, package: Option<&str>) {
-    use crate::config::value::merge_dicts;
-
+///
+/// `__merge__` annotations are kept so they apply to later configs too.
+fn merge_at_package(
+    target: &mut ConfigDict,
+    source: &ConfigDict,
+    package: Option<&str>,
+) -> Result<(), EnumError> {
     match package {
-        None | Some("") | Some("_global_") => {
-            merge_dicts(target, source);
-        }
+        None | Some("") | Some("_global_") => merge_dicts_annotated(target, source),
         Some(pkg) => {
             // Navigate/create nested path and merge there
             let parts: Vec<&str> = pkg.split('.').collect();
-            merge_at_path(target, source, &parts);
+            merge_at_path(target, source, &parts)
         }
     }
 }
 
 /// Merge source at a nested path in target
-fn merge_at_path(target: &mut ConfigDict, source: &ConfigDict, path: &[&str]) {
-    use crate::config::value::merge_dicts;
-
+fn merge_at_path(
+    target: &mut ConfigDict,
+    source: &ConfigDict,
+    path: &[&str],
+) -> Result<(), EnumError> {
     if path.is_empty() {
-        merge_dicts(target, source);
-        return;
+        return merge_dicts_annotated(target, source);
     }
 
     let key = path[0];
@@ -332,38 +654,54 @@ fn merge_at_path(target: &mut ConfigDict, source: &ConfigDict, path: &[&str]) {
 
     if let Some(nested) = target.get_mut(key) {
         if let Some(nested_dict) = nested.as_dict_mut() {
-            merge_at_path(nested_dict, source, remaining);
+            return merge_at_path(nested_dict, source, remaining);
         }
     }
+    Ok(())
 }
 
-/// Apply a key=value override to a dict
+/// Whether an override sets a config value (`db.port=3306`, `~db.port`)
+/// rather than selecting a config group option
+fn is_value_override(override_str: &str) -> bool {
+    let key = override_str.split('=').next().unwrap_or_default();
+    let key = key.trim_start_matches(['~', '+']);
+    key.split('@').next().unwrap_or(key).contains('.')
+}
+
+/// Apply a key=value or ~key override to a dict
+///
+/// Returns whether the override matched: `+key=value` always does, while
+/// changing or deleting a key only matches if the key already exists.
 fn apply_override_to_dict(
     config: &mut ConfigDict,
     override_str: &str,
-) -> Result<(), ConfigLoadError> {
-    if let Some(eq_pos) = override_str.find('=') {
-        let key = &override_str[..eq_pos];
-        let value_str = &override_str[eq_pos + 1..];
-
-        // Handle deletion (starts with ~)
-        if key.starts_with('~') {
-            let actual_key = &key[1..];
-            delete_at_path(config, actual_key);
-            return Ok(());
-        }
+) -> Result<bool, ConfigLoadError> {
+    let (key, value_str) = override_str.split_once('=').unwrap_or((override_str, ""));
+
+    // Handle deletion (starts with ~)
+    if let Some(actual_key) = key.strip_prefix('~') {
+        let matched = get_nested(config, actual_key).is_some();
+        delete_at_path(config, actual_key);
+        return Ok(matched);
+    }
+    if !override_str.contains('=') {
+        return Ok(false);
+    }
 
-        // Handle addition (starts with +)
-        let actual_key = if key.starts_with('+') { &key[1..] } else { key };
+    // Handle addition (starts with + or ++)
+    let (actual_key, is_add) = match key.strip_prefix("++").or_else(|| key.strip_prefix('+')) {
+        Some(actual_key) => (actual_key, true),
+        None => (key, false),
+    };
+    let matched = is_add || get_nested(config, actual_key).is_some();
 
-        // Parse the value
-        let value = parse_override_value(value_str);
+    // Parse the value
+    let value = parse_override_value(value_str);
 
-        // Set the value at the path
-        set_at_path(config, actual_key, value);
-    }
+    // Set the value at the path
+    set_at_path(config, actual_key, value);
 
-    Ok(())
+    Ok(matched)
 }
 
 /// Parse an override value string
@@ -501,6 +839,91 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_repository_load_config_or() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_configs(&temp_dir);
+
+        let search_path = vec![SearchPathElement::new(
+            "main",
+            temp_dir.path().to_str().unwrap(),
+        )];
+        let repo = ConfigRepository::new(&search_path);
+
+        let (result, used) = repo
+            .load_config_or("db/oracle", "db/mysql")
+            .unwrap()
+            .unwrap();
+        assert_eq!(used, "db/mysql");
+        assert_eq!(
+            result.config.as_dict().unwrap().get("driver"),
+            Some(&"mysql".into())
+        );
+        let (_, used) = repo
+            .load_config_or("db/postgres", "db/mysql")
+            .unwrap()
+            .unwrap();
+        assert_eq!(used, "db/postgres");
+        assert!(repo
+            .load_config_or("db/oracle", "db/sqlite")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_repository_overlay_later_dir_wins() {
+        let base_dir = TempDir::new().unwrap();
+        setup_test_configs(&base_dir);
+        let overlay_dir = TempDir::new().unwrap();
+        fs::create_dir(overlay_dir.path().join("db")).unwrap();
+        fs::write(
+            overlay_dir.path().join("db/mysql.yaml"),
+            "driver: mariadb\n",
+        )
+        .unwrap();
+        fs::write(
+            overlay_dir.path().join("db/sqlite.yaml"),
+            "driver: sqlite\n",
+        )
+        .unwrap();
+
+        let search_path = vec![
+            SearchPathElement::new("base", base_dir.path().to_str().unwrap()),
+            SearchPathElement::new("overlay", overlay_dir.path().to_str().unwrap()),
+        ];
+        let driver = |repo: &ConfigRepository, path: &str| {
+            let result = repo.load_config(path).unwrap().unwrap();
+            let dict = result.config.as_dict().unwrap().clone();
+            (result.provider, dict)
+        };
+
+        // By default the first source wins
+        let repo = ConfigRepository::new(&search_path);
+        let (provider, dict) = driver(&repo, "db/mysql");
+        assert_eq!(provider, "base");
+        assert_eq!(dict.get("driver"), Some(&"mysql".into()));
+
+        // In overlay mode the later file replaces the earlier one whole
+        let repo = ConfigRepository::new(&search_path).with_overlay(true);
+        let (provider, dict) = driver(&repo, "db/mysql");
+        assert_eq!(provider, "overlay");
+        assert_eq!(dict.get("driver"), Some(&"mariadb".into()));
+        assert_eq!(dict.get("port"), None);
+        assert_eq!(
+            repo.find_source("db/mysql", ObjectType::Config)
+                .unwrap()
+                .provider(),
+            "overlay"
+        );
+
+        // Configs only in the earlier source are still found, and listed
+        assert_eq!(driver(&repo, "db/postgres").0, "base");
+        assert_eq!(
+            repo.get_group_options("db", Some(ObjectType::Config), None),
+            vec!["mysql", "postgres", "sqlite"]
+        );
+    }
+
     #[test]
     fn test_repository_group_exists() {
         let temp_dir = TempDir::new().unwrap();
@@ -543,11 +966,115 @@ mod tests {
         )];
         let repo = ConfigRepository::new(&search_path);
 
-        let options = repo.get_group_options("db", Some(ObjectType::Config));
+        let options = repo.get_group_options("db", Some(ObjectType::Config), None);
         assert!(options.contains(&"mysql".to_string()));
         assert!(options.contains(&"postgres".to_string()));
     }
 
+    #[test]
+    fn test_repository_get_group_options_by_provider() {
+        let main_dir = TempDir::new().unwrap();
+        setup_test_configs(&main_dir);
+        let user_dir = TempDir::new().unwrap();
+        fs::create_dir(user_dir.path().join("db")).unwrap();
+        fs::write(user_dir.path().join("db/mysql.yaml"), "driver: mariadb\n").unwrap();
+        fs::write(user_dir.path().join("db/sqlite.yaml"), "driver: sqlite\n").unwrap();
+
+        let search_path = vec![
+            SearchPathElement::new("main", main_dir.path().to_str().unwrap()),
+            SearchPathElement::new("user", user_dir.path().to_str().unwrap()),
+        ];
+        let repo = ConfigRepository::new(&search_path);
+
+        let all = repo.get_group_options("db", Some(ObjectType::Config), None);
+        assert_eq!(all, vec!["mysql", "postgres", "sqlite"]);
+        let user = repo.get_group_options("db", Some(ObjectType::Config), Some("user"));
+        assert_eq!(user, vec!["mysql", "sqlite"]);
+        let main = repo.get_group_options("db", Some(ObjectType::Config), Some("main"));
+        assert_eq!(main, vec!["mysql", "postgres"]);
+        assert!(repo
+            .get_group_options("db", Some(ObjectType::Config), Some("other"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_repository_walk_recursive_glob() {
+        let temp_dir = TempDir::new().unwrap();
+        for path in [
+            "model/tiny",
+            "model/a/b/tiny",
+            "model/a/small",
+            "model/base",
+        ] {
+            let file = temp_dir.path().join(format!("{}.yaml", path));
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, "x: 1\n").unwrap();
+        }
+        let search_path = vec![SearchPathElement::new(
+            "main",
+            temp_dir.path().to_str().unwrap(),
+        )];
+        let repo = ConfigRepository::new(&search_path);
+
+        assert_eq!(
+            repo.walk("model"),
+            vec!["a/b/tiny", "a/small", "base", "tiny"]
+        );
+        assert_eq!(
+            repo.walk(""),
+            vec![
+                "model/a/b/tiny",
+                "model/a/small",
+                "model/base",
+                "model/tiny"
+            ]
+        );
+
+        let matching = |pattern: &str| {
+            let glob = Glob::new().with_include(vec![pattern.to_string()]);
+            repo.get_group_options_matching("", &glob)
+        };
+        assert_eq!(
+            matching("model/**/tiny"),
+            vec!["model/a/b/tiny", "model/tiny"]
+        );
+        assert_eq!(matching("model/*"), vec!["model/base", "model/tiny"]);
+        assert_eq!(matching("**/small"), vec!["model/a/small"]);
+
+        let glob = Glob::new().with_include(vec!["**/tiny".to_string()]);
+        assert_eq!(
+            repo.get_group_options_matching("model", &glob),
+            vec!["a/b/tiny", "tiny"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_repository_walk_stops_at_symlink_cycle() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("model/a")).unwrap();
+        fs::write(temp_dir.path().join("model/a/small.yaml"), "x: 1\n").unwrap();
+        // model/a/loop points back at model, and model/shared at model/a
+        std::os::unix::fs::symlink(
+            temp_dir.path().join("model"),
+            temp_dir.path().join("model/a/loop"),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(
+            temp_dir.path().join("model/a"),
+            temp_dir.path().join("model/shared"),
+        )
+        .unwrap();
+        let search_path = vec![SearchPathElement::new(
+            "main",
+            temp_dir.path().to_str().unwrap(),
+        )];
+        let repo = ConfigRepository::new(&search_path);
+
+        // A symlink to a sibling is listed; one back to an ancestor is not followed
+        assert_eq!(repo.walk("model"), vec!["a/small", "shared/small"]);
+    }
+
     #[test]
     fn test_caching_repository() {
         let temp_dir = TempDir::new().unwrap();
@@ -571,4 +1098,287 @@ mod tests {
         // Clear cache
         caching_repo.clear_cache();
     }
+
+    #[test]
+    fn test_caching_repository_load_group() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_configs(&temp_dir);
+        fs::write(
+            temp_dir.path().join("db/broken.yaml"),
+            "driver: [unclosed\n",
+        )
+        .unwrap();
+
+        let search_path = vec![SearchPathElement::new(
+            "main",
+            temp_dir.path().to_str().unwrap(),
+        )];
+        let mut caching_repo = CachingConfigRepository::new(ConfigRepository::new(&search_path));
+
+        let group = caching_repo.load_group("db");
+        let names: Vec<&str> = group
+            .configs
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(names, vec!["mysql", "postgres"]);
+        assert_eq!(
+            group.configs[1].1.as_dict().unwrap().get("driver"),
+            Some(&ConfigValue::from("postgres"))
+        );
+        assert_eq!(group.errors.len(), 1);
+        assert_eq!(group.errors[0].0, "broken");
+
+        assert!(caching_repo.load_group("missing").configs.is_empty());
+    }
+
+    #[test]
+    fn test_repository_alias_loads_target() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_configs(&temp_dir);
+
+        let search_path = vec![SearchPathElement::new(
+            "main",
+            temp_dir.path().to_str().unwrap(),
+        )];
+        let mut repo = ConfigRepository::new(&search_path);
+        repo.add_alias("db", "prod", "postgres").unwrap();
+
+        assert!(repo.config_exists("db/prod"));
+        assert_eq!(repo.resolve_alias("db/prod.yaml"), "db/postgres");
+        assert_eq!(repo.resolve_alias("db/mysql.yaml"), "db/mysql.yaml");
+        repo.add_alias("db", "staging.yaml", "postgres.yaml")
+            .unwrap();
+        assert!(repo.config_exists("db/staging"));
+        let config = repo.load_config("db/prod.yaml").unwrap().unwrap().config;
+        if let ConfigValue::Dict(dict) = config {
+            assert_eq!(dict.get("driver").unwrap().as_str(), Some("postgres"));
+        } else {
+            panic!("Expected dict config");
+        }
+
+        let options = repo.get_group_options("db", Some(ObjectType::Config), None);
+        assert!(!options.contains(&"prod".to_string()));
+        let options =
+            repo.get_group_options_with_aliases("db", Some(ObjectType::Config), None, true);
+        assert_eq!(options, vec!["mysql", "postgres", "prod", "staging"]);
+    }
+
+    #[test]
+    fn test_repository_alias_cycle() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_configs(&temp_dir);
+
+        let search_path = vec![SearchPathElement::new(
+            "main",
+            temp_dir.path().to_str().unwrap(),
+        )];
+        let mut repo = ConfigRepository::new(&search_path);
+        repo.add_alias("db", "a", "b").unwrap();
+
+        let err = repo.add_alias("db", "b", "a").unwrap_err();
+        assert_eq!(err.message, "Cyclic config alias: db/b -> db/a -> db/b");
+        assert!(repo.add_alias("db", "c", "c").is_err());
+    }
+
+    fn compose_port(defaults: &str) -> i64 {
+        compose_port_with(defaults, &[])
+    }
+
+    fn compose_port_with(defaults: &str, overrides: &[&str]) -> i64 {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_configs(&temp_dir);
+        fs::write(
+            temp_dir.path().join("app.yaml"),
+            format!("defaults:\n{}\ndb:\n  port: 1234\n", defaults),
+        )
+        .unwrap();
+
+        let search_path = vec![SearchPathElement::new(
+            "main",
+            temp_dir.path().to_str().unwrap(),
+        )];
+        let mut repo = CachingConfigRepository::new(ConfigRepository::new(&search_path));
+        let overrides: Vec<String> = overrides.iter().map(|s| s.to_string()).collect();
+        let composed = repo.load_and_compose(Some("app"), &overrides).unwrap();
+
+        let db = composed.config.get("db").unwrap().as_dict().unwrap();
+        db.get("port").unwrap().as_int().unwrap()
+    }
+
+    #[test]
+    fn test_compose_self_first_lets_defaults_win() {
+        assert_eq!(compose_port("  - _self_\n  - db: mysql"), 3306);
+    }
+
+    #[test]
+    fn test_compose_self_last_overrides_defaults() {
+        assert_eq!(compose_port("  - db: mysql\n  - _self_"), 1234);
+        // Without _self_ the config's own content is applied last
+        assert_eq!(compose_port("  - db: mysql"), 1234);
+    }
+
+    #[test]
+    fn test_compose_overridden_default_keeps_self_position() {
+        // The overridden default stays before _self_, so the config still wins
+        assert_eq!(
+            compose_port_with("  - db: mysql\n  - _self_", &["db=postgres"]),
+            1234
+        );
+        // ...and after it, so the selected option wins
+        assert_eq!(
+            compose_port_with("  - _self_\n  - db: mysql", &["db=postgres"]),
+            5432
+        );
+    }
+
+    #[test]
+    fn test_compose_reports_unused_overrides() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_configs(&temp_dir);
+
+        let search_path = vec![SearchPathElement::new(
+            "main",
+            temp_dir.path().to_str().unwrap(),
+        )];
+        let mut repo = CachingConfigRepository::new(ConfigRepository::new(&search_path));
+        let overrides: Vec<String> = [
+            " db.port=5432",
+            "db.name=test",
+            "+db.user=admin",
+            "~db.host",
+            "~db.missing",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let composed = repo.load_and_compose(Some("config"), &overrides).unwrap();
+
+        assert_eq!(
+            composed.applied_overrides,
+            vec!["db.port=5432", "+db.user=admin", "~db.host"]
+        );
+        assert_eq!(
+            composed.unused_overrides,
+            vec!["db.name=test", "~db.missing"]
+        );
+
+        let db = composed.config.get("db").unwrap().as_dict().unwrap();
+        assert_eq!(db.get("port"), Some(&ConfigValue::Int(5432)));
+        assert_eq!(db.get("user"), Some(&ConfigValue::from("admin")));
+        assert_eq!(db.get("host"), None);
+    }
+
+    #[test]
+    fn test_compose_appended_group() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_configs(&temp_dir);
+        fs::create_dir(temp_dir.path().join("experiment")).unwrap();
+        fs::write(
+            temp_dir.path().join("experiment/fast.yaml"),
+            "lr: 0.1\ndb:\n  port: 9999\n",
+        )
+        .unwrap();
+
+        let search_path = vec![SearchPathElement::new(
+            "main",
+            temp_dir.path().to_str().unwrap(),
+        )];
+        let mut repo = CachingConfigRepository::new(ConfigRepository::new(&search_path));
+        let composed = repo
+            .load_and_compose(Some("config"), &["+experiment=fast".to_string()])
+            .unwrap();
+
+        let experiment = composed
+            .config
+            .get("experiment")
+            .unwrap()
+            .as_dict()
+            .unwrap();
+        assert_eq!(experiment.get("lr"), Some(&ConfigValue::Float(0.1)));
+        // The appended config is packaged under its group
+        let db = composed.config.get("db").unwrap().as_dict().unwrap();
+        assert_eq!(db.get("port"), Some(&ConfigValue::Int(3306)));
+    }
+
+    #[test]
+    fn test_compose_merge_annotation() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_configs(&temp_dir);
+        fs::write(
+            temp_dir.path().join("db/replicated.yaml"),
+            "__merge__: concat\nreplicas: [a]\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("app.yaml"),
+            "defaults:\n  - db: replicated\n  - _self_\ndb:\n  replicas: [b]\n",
+        )
+        .unwrap();
+
+        let search_path = vec![SearchPathElement::new(
+            "main",
+            temp_dir.path().to_str().unwrap(),
+        )];
+        let mut repo = CachingConfigRepository::new(ConfigRepository::new(&search_path));
+        let composed = repo.load_and_compose(Some("app"), &[]).unwrap();
+
+        // The annotation from the group option applies when the primary
+        // config is merged after it, and is not left in the result
+        let db = composed.config.get("db").unwrap().as_dict().unwrap();
+        assert_eq!(
+            db.get("replicas"),
+            Some(&ConfigValue::List(vec![
+                ConfigValue::from("a"),
+                ConfigValue::from("b")
+            ]))
+        );
+        assert_eq!(db.get("__merge__"), None);
+
+        // ConfigLoader composes the same defaults list the same way
+        let loader =
+            crate::config::loader::ConfigLoader::from_config_dir(temp_dir.path().to_str().unwrap());
+        let loaded = loader.load_config(Some("app"), &[]).unwrap();
+        assert_eq!(
+            loaded.as_dict().unwrap().get("db"),
+            composed.config.get("db")
+        );
+    }
+
+    #[test]
+    fn test_exists_any() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_configs(&temp_dir);
+
+        let search_path = vec![SearchPathElement::new(
+            "main",
+            temp_dir.path().to_str().unwrap(),
+        )];
+        let mut repo = ConfigRepository::new(&search_path);
+        repo.add_alias("db", "prod", "postgres").unwrap();
+
+        assert_eq!(
+            repo.exists_any(&[
+                "db/mysql",
+                "db/oracle",
+                "config",
+                "db",
+                "db/prod",
+                "missing/x"
+            ]),
+            vec![true, false, true, false, true, false]
+        );
+
+        // Like config_exists, a bare name only matches a `.yaml` file
+        fs::write(temp_dir.path().join("db/legacy.yml"), "x: 1\n").unwrap();
+        assert_eq!(
+            repo.exists_any(&["db/legacy", "db/legacy.yml"]),
+            vec![false, true]
+        );
+        assert!(!repo.config_exists("db/legacy"));
+        assert_eq!(
+            repo.group_exists_batch(&["db", "server", "", "db/mysql"]),
+            vec![true, false, true, false]
+        );
+    }
 }
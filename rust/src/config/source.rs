@@ -3,7 +3,10 @@
 
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::SystemTime;
 
 use crate::config::parser::{extract_header, load_yaml_file, ConfigLoadError};
 use crate::config::value::ConfigValue;
@@ -24,6 +27,26 @@ pub struct ConfigResult {
     pub is_schema_source: bool,
 }
 
+impl ConfigResult {
+    /// The `@package` directive, if present
+    ///
+    /// Returns None when the header has no package directive and `Some("")`
+    /// when the directive is present but empty.
+    pub fn package(&self) -> Option<&str> {
+        self.header.get("package").map(|s| s.as_str())
+    }
+
+    /// The `@schema` directive, if present
+    pub fn schema(&self) -> Option<&str> {
+        self.header.get("schema").map(|s| s.as_str())
+    }
+
+    /// Whether the config is placed at the root via `@package _global_`
+    pub fn global_package(&self) -> bool {
+        self.package() == Some("_global_")
+    }
+}
+
 /// Trait for configuration sources
 pub trait ConfigSource: Send + Sync {
     /// Get the scheme for this source (e.g., "file", "pkg")
@@ -54,12 +77,40 @@ pub trait ConfigSource: Send + Sync {
 
     /// List items in a config path
     fn list(&self, config_path: &str, results_filter: Option<ObjectType>) -> Vec<String>;
+
+    /// The canonical location of a group, for sources backed by a filesystem
+    ///
+    /// Lets a walk notice a group that is its own ancestor through a symlink.
+    fn canonical_group(&self, _config_path: &str) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// An entry in a cached directory listing
+#[derive(Clone, Debug)]
+struct ListingEntry {
+    file_name: String,
+    is_group: bool,
+    is_config: bool,
+}
+
+/// A directory listing, valid while the directory's mtime is unchanged
+#[derive(Clone, Debug)]
+struct CachedListing {
+    mtime: SystemTime,
+    entries: Vec<ListingEntry>,
 }
 
 /// File-based configuration source
+///
+/// Directory listings are cached per directory and reused until the
+/// directory's modification time changes.
 pub struct FileConfigSource {
     provider_name: String,
     base_path: PathBuf,
+    listing_cache: Mutex<HashMap<PathBuf, CachedListing>>,
+    /// Number of directory reads, for observing cache hits
+    listing_reads: AtomicUsize,
 }
 
 impl FileConfigSource {
@@ -74,9 +125,55 @@ impl FileConfigSource {
         Self {
             provider_name: provider.to_string(),
             base_path: PathBuf::from(clean_path),
+            listing_cache: Mutex::new(HashMap::new()),
+            listing_reads: AtomicUsize::new(0),
         }
     }
 
+    /// Drop all cached directory listings
+    pub fn clear_listing_cache(&self) {
+        self.listing_cache.lock().unwrap().clear();
+    }
+
+    /// Get the entries of a directory, from the cache if its mtime is unchanged
+    fn read_listing(&self, dir: &Path) -> Vec<ListingEntry> {
+        let mtime = match fs::metadata(dir).and_then(|m| m.modified()) {
+            Ok(mtime) => mtime,
+            Err(_) => return Vec::new(),
+        };
+
+        if let Some(cached) = self.listing_cache.lock().unwrap().get(dir) {
+            if cached.mtime == mtime {
+                return cached.entries.clone();
+            }
+        }
+
+        self.listing_reads.fetch_add(1, Ordering::Relaxed);
+        let mut entries = Vec::new();
+        if let Ok(read_dir) = fs::read_dir(dir) {
+            for entry in read_dir.flatten() {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                let path = entry.path();
+                // Bare config names resolve to `.yaml` files, so only those are listed
+                let is_config = path.is_file() && file_name.ends_with(".yaml");
+                entries.push(ListingEntry {
+                    is_group: path.is_dir(),
+                    is_config,
+                    file_name,
+                });
+            }
+        }
+
+        self.listing_cache.lock().unwrap().insert(
+            dir.to_path_buf(),
+            CachedListing {
+                mtime,
+                entries: entries.clone(),
+            },
+        );
+        entries
+    }
+
     fn normalize_config_path(&self, config_path: &str) -> PathBuf {
         let mut path = config_path.to_string();
 
@@ -143,47 +240,41 @@ impl ConfigSource for FileConfigSource {
         full_path.is_file()
     }
 
+    fn canonical_group(&self, config_path: &str) -> Option<PathBuf> {
+        self.full_path(config_path).canonicalize().ok()
+    }
+
     fn list(&self, config_path: &str, results_filter: Option<ObjectType>) -> Vec<String> {
         let full_path = self.full_path(config_path);
         let mut items = Vec::new();
 
-        if let Ok(entries) = fs::read_dir(&full_path) {
-            for entry in entries.flatten() {
-                let file_name = entry.file_name().to_string_lossy().to_string();
+        for entry in self.read_listing(&full_path) {
+            let ListingEntry {
+                file_name,
+                is_group,
+                is_config,
+            } = entry;
 
-                // Skip pycache and __init__.py
-                if file_name == "__pycache__" || file_name == "__init__.py" {
-                    continue;
-                }
+            // Skip pycache and __init__.py
+            if file_name == "__pycache__" || file_name == "__init__.py" {
+                continue;
+            }
 
-                let _file_path = if config_path.is_empty() {
-                    file_name.clone()
+            let include = match results_filter {
+                None => is_group || is_config,
+                Some(ObjectType::Group) => is_group,
+                Some(ObjectType::Config) => is_config,
+                Some(ObjectType::NotFound) => false,
+            };
+
+            if include {
+                // Remove .yaml extension for config files
+                let name = if is_config && !is_group {
+                    file_name.trim_end_matches(".yaml").to_string()
                 } else {
-                    format!("{}/{}", config_path, file_name)
+                    file_name
                 };
-                let is_group = entry.path().is_dir();
-                let is_config = entry.path().is_file()
-                    && (file_name.ends_with(".yaml") || file_name.ends_with(".yml"));
-
-                let include = match results_filter {
-                    None => is_group || is_config,
-                    Some(ObjectType::Group) => is_group,
-                    Some(ObjectType::Config) => is_config,
-                    Some(ObjectType::NotFound) => false,
-                };
-
-                if include {
-                    // Remove .yaml extension for config files
-                    let name = if is_config && !is_group {
-                        file_name
-                            .trim_end_matches(".yaml")
-                            .trim_end_matches(".yml")
-                            .to_string()
-                    } else {
-                        file_name
-                    };
-                    items.push(name);
-                }
+                items.push(name);
             }
         }
 
@@ -251,4 +342,70 @@ mod tests {
         assert!(groups.contains(&"group".to_string()));
         assert!(!groups.contains(&"a".to_string()));
     }
+
+    #[test]
+    fn test_config_result_header_accessors() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_config(
+            temp_dir.path(),
+            "global.yaml",
+            "# @package _global_\nvalue: 1\n",
+        );
+        create_test_config(
+            temp_dir.path(),
+            "nested.yaml",
+            "# @package foo.bar\n# @schema db_schema\nvalue: 1\n",
+        );
+        create_test_config(temp_dir.path(), "plain.yaml", "value: 1\n");
+        create_test_config(temp_dir.path(), "empty.yaml", "# @package\nvalue: 1\n");
+
+        let source = FileConfigSource::new("test", temp_dir.path().to_str().unwrap());
+
+        let global = source.load_config("global").unwrap();
+        assert_eq!(global.package(), Some("_global_"));
+        assert!(global.global_package());
+        assert_eq!(global.schema(), None);
+
+        let nested = source.load_config("nested").unwrap();
+        assert_eq!(nested.package(), Some("foo.bar"));
+        assert!(!nested.global_package());
+        assert_eq!(nested.schema(), Some("db_schema"));
+
+        let plain = source.load_config("plain").unwrap();
+        assert_eq!(plain.package(), None);
+        assert!(!plain.global_package());
+
+        let empty = source.load_config("empty").unwrap();
+        assert_eq!(empty.package(), Some(""));
+        assert!(!empty.global_package());
+    }
+
+    #[test]
+    fn test_file_source_list_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_config(temp_dir.path(), "db/mysql.yaml", "driver: mysql\n");
+
+        let source = FileConfigSource::new("test", temp_dir.path().to_str().unwrap());
+
+        // Repeated listings of an unchanged directory read it once
+        assert_eq!(source.list("db", None), vec!["mysql"]);
+        assert_eq!(source.list("db", Some(ObjectType::Config)), vec!["mysql"]);
+        assert_eq!(source.listing_reads.load(Ordering::Relaxed), 1);
+
+        // A new file changes the directory mtime and invalidates the listing.
+        // Bump the mtime explicitly so coarse filesystem timestamps can't hide it.
+        create_test_config(temp_dir.path(), "db/postgres.yaml", "driver: postgres\n");
+        let dir = temp_dir.path().join("db");
+        let cached_mtime = source.listing_cache.lock().unwrap()[&dir].mtime;
+        fs::File::open(&dir)
+            .unwrap()
+            .set_modified(cached_mtime + std::time::Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(source.list("db", None), vec!["mysql", "postgres"]);
+        assert_eq!(source.listing_reads.load(Ordering::Relaxed), 2);
+
+        // Clearing the cache forces a fresh read
+        source.clear_listing_cache();
+        assert_eq!(source.list("db", None), vec!["mysql", "postgres"]);
+    }
 }
@@ -1,6 +1,7 @@
 // Copyright (c) Facebook, Inc. and its affiliates. All Rights Reserved
 //! Configuration value types for Hydra config loading
 
+use super::enums::EnumError;
 use std::collections::HashMap;
 use std::fmt;
 
@@ -25,9 +26,23 @@ pub enum ConfigValue {
     Interpolation(String),
     /// Missing value marker
     Missing,
+    /// Value of a registered enum type (see [`crate::config::enums`])
+    Enum { type_name: String, value: String },
 }
 
 impl ConfigValue {
+    /// Classify a string as an interpolation or a plain string
+    ///
+    /// Strings containing a balanced, unescaped `${...}` anywhere (including
+    /// mixed literals like `jdbc:${db.host}`) become `Interpolation`.
+    pub fn from_string(s: String) -> Self {
+        if has_interpolation(&s) {
+            ConfigValue::Interpolation(s)
+        } else {
+            ConfigValue::String(s)
+        }
+    }
+
     /// Check if this value is null
     pub fn is_null(&self) -> bool {
         matches!(self, ConfigValue::Null)
@@ -80,6 +95,14 @@ impl ConfigValue {
         }
     }
 
+    /// Get mutable list if this is a list
+    pub fn as_list_mut(&mut self) -> Option<&mut Vec<ConfigValue>> {
+        match self {
+            ConfigValue::List(l) => Some(l),
+            _ => None,
+        }
+    }
+
     /// Get as dict if this is a dict
     pub fn as_dict(&self) -> Option<&ConfigDict> {
         match self {
@@ -100,6 +123,216 @@ impl ConfigValue {
     pub fn is_interpolation(&self) -> bool {
         matches!(self, ConfigValue::Interpolation(_))
     }
+
+    /// Compare two values semantically, the way Python would
+    ///
+    /// Ints and floats compare numerically (`1 == 1.0`), an enum equals a
+    /// string holding its value, and dicts compare regardless of key order.
+    /// Everything else uses structural equality.
+    pub fn semantic_eq(&self, other: &ConfigValue) -> bool {
+        match (self, other) {
+            (ConfigValue::Int(a), ConfigValue::Float(b))
+            | (ConfigValue::Float(b), ConfigValue::Int(a)) => (*a as f64) == *b,
+            (ConfigValue::Enum { value: a, .. }, ConfigValue::String(b))
+            | (ConfigValue::String(b), ConfigValue::Enum { value: a, .. }) => a == b,
+            (ConfigValue::List(a), ConfigValue::List(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.semantic_eq(y))
+            }
+            (ConfigValue::Dict(a), ConfigValue::Dict(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(k, v)| b.get(k).is_some_and(|other| v.semantic_eq(other)))
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Compare two values like [`semantic_eq`](Self::semantic_eq), with a
+    /// tolerance for numbers
+    ///
+    /// Ints and floats within `epsilon` of each other compare equal
+    /// (`Int(1)` vs `Float(1.0000001)`). Containers are compared recursively.
+    pub fn approx_eq(&self, other: &ConfigValue, epsilon: f64) -> bool {
+        let close = |a: f64, b: f64| a == b || (a - b).abs() <= epsilon;
+        match (self, other) {
+            (ConfigValue::Int(a), ConfigValue::Int(b)) => a == b,
+            (ConfigValue::Int(a), ConfigValue::Float(b))
+            | (ConfigValue::Float(b), ConfigValue::Int(a)) => close(*a as f64, *b),
+            (ConfigValue::Float(a), ConfigValue::Float(b)) => close(*a, *b),
+            (ConfigValue::List(a), ConfigValue::List(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.approx_eq(y, epsilon))
+            }
+            (ConfigValue::Dict(a), ConfigValue::Dict(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(k, v)| b.get(k).is_some_and(|other| v.approx_eq(other, epsilon)))
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Check if this list contains a value (by semantic equality)
+    pub fn list_contains(&self, value: &ConfigValue) -> bool {
+        self.as_list()
+            .is_some_and(|l| l.iter().any(|item| item.semantic_eq(value)))
+    }
+
+    /// Append values not already present in this list, preserving their order
+    ///
+    /// Returns the number of values added, or `None` if this is not a list.
+    pub fn append_unique(&mut self, values: Vec<ConfigValue>) -> Option<usize> {
+        let list = self.as_list_mut()?;
+        let mut added = 0;
+        for value in values {
+            if !list.iter().any(|item| item.semantic_eq(&value)) {
+                list.push(value);
+                added += 1;
+            }
+        }
+        Some(added)
+    }
+
+    /// Prepend values not already present in this list, preserving their order
+    ///
+    /// Returns the number of values added, or `None` if this is not a list.
+    pub fn prepend_unique(&mut self, values: Vec<ConfigValue>) -> Option<usize> {
+        let list = self.as_list_mut()?;
+        let mut added = 0;
+        for value in values {
+            if !list.iter().any(|item| item.semantic_eq(&value)) {
+                list.insert(added, value);
+                added += 1;
+            }
+        }
+        Some(added)
+    }
+
+    /// Rebuild this value with every leaf replaced by `f(path, leaf)`
+    ///
+    /// Dicts and lists are walked recursively; `path` is the dotted path to
+    /// the leaf, with list items addressed by index (e.g. `servers.0.host`).
+    /// A top-level leaf is passed an empty path.
+    pub fn map_leaves(&self, f: impl Fn(&str, &ConfigValue) -> ConfigValue) -> ConfigValue {
+        self.map_leaves_at("", &f)
+    }
+
+    fn map_leaves_at(
+        &self,
+        path: &str,
+        f: &impl Fn(&str, &ConfigValue) -> ConfigValue,
+    ) -> ConfigValue {
+        let child_path = |key: &str| {
+            if path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", path, key)
+            }
+        };
+        match self {
+            ConfigValue::Dict(dict) => {
+                let mut mapped = ConfigDict::new();
+                for (key, value) in dict.iter() {
+                    mapped.insert(key.to_string(), value.map_leaves_at(&child_path(key), f));
+                }
+                ConfigValue::Dict(mapped)
+            }
+            ConfigValue::List(items) => ConfigValue::List(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| item.map_leaves_at(&child_path(&i.to_string()), f))
+                    .collect(),
+            ),
+            leaf => f(path, leaf),
+        }
+    }
+
+    /// Count the containers and leaves of this value and measure its depth
+    pub fn stats(&self) -> ConfigStats {
+        let mut stats = ConfigStats::default();
+        self.collect_stats(0, &mut stats);
+        stats
+    }
+
+    fn collect_stats(&self, depth: usize, stats: &mut ConfigStats) {
+        stats.max_depth = stats.max_depth.max(depth);
+        match self {
+            ConfigValue::Dict(dict) => {
+                stats.dicts += 1;
+                for value in dict.values() {
+                    value.collect_stats(depth + 1, stats);
+                }
+            }
+            ConfigValue::List(items) => {
+                stats.lists += 1;
+                for item in items {
+                    item.collect_stats(depth + 1, stats);
+                }
+            }
+            leaf => {
+                stats.leaves += 1;
+                match leaf {
+                    ConfigValue::Interpolation(_) => stats.interpolations += 1,
+                    ConfigValue::Missing => stats.missing += 1,
+                    _ => stats.scalars += 1,
+                }
+            }
+        }
+    }
+
+    /// Describe the type of every leaf, as a skeleton for a structured config
+    ///
+    /// Dicts keep their keys, with each leaf replaced by its type name:
+    /// `"str"`, `"int"`, `"float"`, `"bool"`, or the type name of an enum.
+    /// A list becomes a one-item list holding its element type, which is
+    /// `"any"` if the items differ or the list is empty. Values whose type is
+    /// not known until resolution (null, interpolations, `???`) are `"any"`.
+    pub fn infer_schema(&self) -> ConfigValue {
+        let type_name = match self {
+            ConfigValue::Dict(dict) => {
+                let mut schema = ConfigDict::new();
+                for (key, value) in dict.iter() {
+                    schema.insert(key.to_string(), value.infer_schema());
+                }
+                return ConfigValue::Dict(schema);
+            }
+            ConfigValue::List(items) => {
+                let mut schemas = items.iter().map(ConfigValue::infer_schema);
+                let first = schemas.next();
+                let element = match first {
+                    Some(first) if schemas.all(|schema| schema == first) => first,
+                    _ => ConfigValue::from("any"),
+                };
+                return ConfigValue::List(vec![element]);
+            }
+            ConfigValue::String(_) => "str",
+            ConfigValue::Int(_) => "int",
+            ConfigValue::Float(_) => "float",
+            ConfigValue::Bool(_) => "bool",
+            ConfigValue::Enum { type_name, .. } => type_name.as_str(),
+            ConfigValue::Null | ConfigValue::Interpolation(_) | ConfigValue::Missing => "any",
+        };
+        ConfigValue::from(type_name)
+    }
+}
+
+/// Size and shape of a config tree, from [`ConfigValue::stats`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConfigStats {
+    /// Number of dicts, including the root
+    pub dicts: usize,
+    /// Number of lists
+    pub lists: usize,
+    /// Leaves holding a concrete value (including null)
+    pub scalars: usize,
+    /// All leaves: scalars, interpolations and missing values
+    pub leaves: usize,
+    /// Deepest nesting level; a leaf in the root dict is at depth 1
+    pub max_depth: usize,
+    /// Leaves that are interpolations
+    pub interpolations: usize,
+    /// Leaves that are missing (`???`)
+    pub missing: usize,
 }
 
 impl Default for ConfigValue {
@@ -182,19 +415,78 @@ impl fmt::Display for ConfigValue {
             }
             ConfigValue::Dict(d) => write!(f, "{:?}", d),
             ConfigValue::Missing => write!(f, "???"),
+            ConfigValue::Enum { value, .. } => write!(f, "{}", value),
         }
     }
 }
 
+/// Lowercase the keys of any dicts inside a value
+fn fold_value_case(value: &ConfigValue) -> Result<ConfigValue, String> {
+    match value {
+        ConfigValue::Dict(d) => Ok(ConfigValue::Dict(d.fold_case()?)),
+        ConfigValue::List(l) => Ok(ConfigValue::List(
+            l.iter().map(fold_value_case).collect::<Result<_, _>>()?,
+        )),
+        other => Ok(other.clone()),
+    }
+}
+
+/// Whether `s` contains a `${` (not escaped as `\${`) closed by a matching `}`
+fn has_interpolation(s: &str) -> bool {
+    let chars: Vec<char> = s.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '$' if chars.get(i + 1) == Some(&'{') => {
+                let mut depth = 0;
+                let mut j = i + 1;
+                while j < chars.len() {
+                    match chars[j] {
+                        '\\' => j += 1,
+                        '{' => depth += 1,
+                        '}' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                return true;
+                            }
+                        }
+                        _ => {}
+                    }
+                    j += 1;
+                }
+                return false;
+            }
+            _ => i += 1,
+        }
+    }
+    false
+}
+
 /// A dictionary of configuration values
-#[derive(Clone, Debug, Default, PartialEq)]
+///
+/// Entries are kept in insertion order, with a key-to-position index so
+/// `get`, `insert` and `contains_key` are O(1) on wide dicts. Replacing the
+/// value of an existing key keeps its position; `remove` is O(n), shifting
+/// later entries down.
+///
+/// Equality ignores key order: two dicts are equal when they hold the same
+/// keys with equal values, as Python dicts are. Lists inside them still
+/// compare in order.
+#[derive(Clone, Debug, Default)]
 pub struct ConfigDict {
     /// The underlying storage - maintains insertion order
     entries: Vec<(String, ConfigValue)>,
-    /// Fast lookup index
+    /// Position of each key in `entries`
     index: HashMap<String, usize>,
 }
 
+impl PartialEq for ConfigDict {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
 impl ConfigDict {
     /// Create a new empty config dict
     pub fn new() -> Self {
@@ -217,6 +509,40 @@ impl ConfigDict {
         self.index.get(key).map(|&idx| &self.entries[idx].1)
     }
 
+    /// Get a value by key, ignoring case
+    ///
+    /// An exact match wins; otherwise the first key (in insertion order) that
+    /// matches case-insensitively is used.
+    pub fn get_ci(&self, key: &str) -> Option<&ConfigValue> {
+        if let Some(value) = self.get(key) {
+            return Some(value);
+        }
+        let folded = key.to_lowercase();
+        self.iter()
+            .find(|(k, _)| k.to_lowercase() == folded)
+            .map(|(_, v)| v)
+    }
+
+    /// Return a copy of this dict with all keys lowercased, recursively
+    ///
+    /// Fails if two keys fold to the same name (e.g. `Port` and `port`).
+    pub fn fold_case(&self) -> Result<ConfigDict, String> {
+        let mut folded = ConfigDict::new();
+        for (key, value) in self.iter() {
+            let lower = key.to_lowercase();
+            if folded.contains_key(&lower) {
+                let first = self.keys().find(|k| k.to_lowercase() == lower);
+                return Err(format!(
+                    "Key collision after case folding: '{}' and '{}'",
+                    first.unwrap_or_default(),
+                    key
+                ));
+            }
+            folded.insert(lower, fold_value_case(value)?);
+        }
+        Ok(folded)
+    }
+
     /// Get a mutable value by key
     pub fn get_mut(&mut self, key: &str) -> Option<&mut ConfigValue> {
         if let Some(&idx) = self.index.get(key) {
@@ -231,24 +557,21 @@ impl ConfigDict {
         self.index.contains_key(key)
     }
 
-    /// Remove a key
+    /// Remove a key, keeping the order of the remaining entries
     pub fn remove(&mut self, key: &str) -> Option<ConfigValue> {
-        if let Some(&idx) = self.index.get(key) {
-            self.index.remove(key);
-            // Mark as removed but keep entry to maintain indices
-            let old = std::mem::replace(&mut self.entries[idx].1, ConfigValue::Null);
-            Some(old)
-        } else {
-            None
+        let idx = self.index.remove(key)?;
+        let (_, old) = self.entries.remove(idx);
+        for (k, _) in &self.entries[idx..] {
+            if let Some(pos) = self.index.get_mut(k) {
+                *pos -= 1;
+            }
         }
+        Some(old)
     }
 
     /// Get number of entries
     pub fn len(&self) -> usize {
-        self.entries
-            .iter()
-            .filter(|(k, _)| self.index.contains_key(k))
-            .count()
+        self.entries.len()
     }
 
     /// Check if empty
@@ -256,12 +579,12 @@ impl ConfigDict {
         self.len() == 0
     }
 
-    /// Iterate over key-value pairs
+    /// Iterate over key-value pairs in insertion order
+    ///
+    /// A key that was removed and inserted again appears at its new
+    /// position.
     pub fn iter(&self) -> impl Iterator<Item = (&str, &ConfigValue)> {
-        self.entries
-            .iter()
-            .filter(|(k, _)| self.index.contains_key(k))
-            .map(|(k, v)| (k.as_str(), v))
+        self.entries.iter().map(|(k, v)| (k.as_str(), v))
     }
 
     /// Get all keys
@@ -274,6 +597,11 @@ impl ConfigDict {
         self.iter().map(|(_, v)| v)
     }
 
+    /// Get all values, mutably
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut ConfigValue> {
+        self.entries.iter_mut().map(|(_, v)| v)
+    }
+
     /// Select a value using a dotted path (e.g., "a.b.c")
     pub fn select(&self, path: &str) -> Option<ConfigValue> {
         let parts: Vec<&str> = path.split('.').collect();
@@ -299,32 +627,20 @@ impl ConfigDict {
     }
 
     /// Merge another dict into this one
-    pub fn merge(&mut self, other: &ConfigDict) {
-        for (key, value) in other.iter() {
-            let should_deep_merge = matches!(
-                (self.get(key), value),
-                (Some(ConfigValue::Dict(_)), ConfigValue::Dict(_))
-            );
-
-            if should_deep_merge {
-                if let ConfigValue::Dict(other_dict) = value {
-                    if let Some(self_val) = self.get_mut(key) {
-                        if let Some(self_dict) = self_val.as_dict_mut() {
-                            self_dict.merge(other_dict);
-                            continue;
-                        }
-                    }
-                }
-            }
-            // Replace value
-            self.insert(key.to_string(), value.clone());
-        }
+    ///
+    /// Keys already in `self` keep their position, even when their value is
+    /// replaced or merged; keys only in `other` are appended in `other`'s
+    /// order. Nested dicts follow the same rule. Values are merged as by
+    /// [`crate::merge::merge_dicts`], which also honors `__merge__`
+    /// annotations and removes them from the result.
+    pub fn merge(&mut self, other: &ConfigDict) -> Result<(), EnumError> {
+        crate::merge::merge_dicts(self, other)
     }
 }
 
 /// Merge two ConfigDicts (convenience function)
-pub fn merge_dicts(target: &mut ConfigDict, source: &ConfigDict) {
-    target.merge(source);
+pub fn merge_dicts(target: &mut ConfigDict, source: &ConfigDict) -> Result<(), EnumError> {
+    target.merge(source)
 }
 
 #[cfg(test)]
@@ -344,6 +660,123 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stats() {
+        let config = crate::config::parser::parse_yaml(
+            "a: 1\nb:\n  c: [1, 2, {d: '${a}'}]\n  e: ???\n  f: {}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.stats(),
+            ConfigStats {
+                dicts: 4,
+                lists: 1,
+                scalars: 3,
+                leaves: 5,
+                max_depth: 4,
+                interpolations: 1,
+                missing: 1,
+            }
+        );
+
+        let leaf = ConfigValue::Int(1).stats();
+        assert_eq!((leaf.leaves, leaf.max_depth), (1, 0));
+    }
+
+    #[test]
+    fn test_infer_schema() {
+        let config = crate::config::parser::parse_yaml(
+            "db:\n  host: localhost\n  port: 3306\n  ssl: {enabled: false, ratio: 0.5}\nports: [80, 443]\nmixed: [1, a]\nservers: [{host: a}, {host: b}]\nempty: []\nref: ${db.host}\n",
+        )
+        .unwrap();
+        let expected = crate::config::parser::parse_yaml(
+            "db:\n  host: str\n  port: int\n  ssl: {enabled: bool, ratio: float}\nports: [int]\nmixed: [any]\nservers: [{host: str}]\nempty: [any]\nref: any\n",
+        )
+        .unwrap();
+        assert_eq!(config.infer_schema(), expected);
+        assert_eq!(ConfigValue::Int(1).infer_schema(), ConfigValue::from("int"));
+    }
+
+    #[test]
+    fn test_approx_eq_int_and_float() {
+        assert!(ConfigValue::Int(1).approx_eq(&ConfigValue::Float(1.0), 0.0));
+        assert!(ConfigValue::Float(1.0).approx_eq(&ConfigValue::Int(1), 0.0));
+        assert!(!ConfigValue::Int(1).approx_eq(&ConfigValue::Int(2), 10.0));
+        assert!(!ConfigValue::Int(1).approx_eq(&ConfigValue::String("1".to_string()), 1.0));
+    }
+
+    #[test]
+    fn test_approx_eq_epsilon() {
+        let a = ConfigValue::Float(0.1 + 0.2);
+        let b = ConfigValue::Float(0.3);
+        assert!(!a.approx_eq(&b, 0.0));
+        assert!(a.approx_eq(&b, 1e-9));
+        assert!(!ConfigValue::Float(0.3).approx_eq(&ConfigValue::Float(0.31), 1e-9));
+        assert!(
+            ConfigValue::Float(f64::INFINITY).approx_eq(&ConfigValue::Float(f64::INFINITY), 0.0)
+        );
+        assert!(!ConfigValue::Float(f64::NAN).approx_eq(&ConfigValue::Float(f64::NAN), 1.0));
+    }
+
+    #[test]
+    fn test_approx_eq_containers() {
+        let mut a = ConfigDict::new();
+        a.insert("lr".to_string(), ConfigValue::Float(0.30000000000000004));
+        a.insert(
+            "sizes".to_string(),
+            ConfigValue::List(vec![ConfigValue::Int(1), ConfigValue::Float(2.0)]),
+        );
+        let mut b = ConfigDict::new();
+        b.insert(
+            "sizes".to_string(),
+            ConfigValue::List(vec![ConfigValue::Float(1.0), ConfigValue::Int(2)]),
+        );
+        b.insert("lr".to_string(), ConfigValue::Float(0.3));
+        assert!(ConfigValue::Dict(a.clone()).approx_eq(&ConfigValue::Dict(b.clone()), 1e-9));
+
+        b.insert("lr".to_string(), ConfigValue::Float(0.4));
+        assert!(!ConfigValue::Dict(a.clone()).approx_eq(&ConfigValue::Dict(b.clone()), 1e-9));
+
+        b.insert("lr".to_string(), ConfigValue::Float(0.3));
+        b.insert("extra".to_string(), ConfigValue::Null);
+        assert!(!ConfigValue::Dict(a).approx_eq(&ConfigValue::Dict(b), 1e-9));
+    }
+
+    #[test]
+    fn test_from_string_classification() {
+        let classify = |s: &str| ConfigValue::from_string(s.to_string());
+
+        for plain in [
+            "localhost",
+            "",
+            "$100",
+            "{a}",
+            "${unclosed",
+            "\\${escaped}",
+            "a}${",
+        ] {
+            assert_eq!(
+                classify(plain),
+                ConfigValue::String(plain.to_string()),
+                "{}",
+                plain
+            );
+        }
+        for interpolation in [
+            "${db.host}",
+            "${oc.env:HOME,${default}}",
+            "jdbc:${db.driver}://${db.host}",
+            "prefix ${x} suffix",
+        ] {
+            assert_eq!(
+                classify(interpolation),
+                ConfigValue::Interpolation(interpolation.to_string()),
+                "{}",
+                interpolation
+            );
+        }
+    }
+
     #[test]
     fn test_config_dict_basic() {
         let mut dict = ConfigDict::new();
@@ -377,13 +810,125 @@ mod tests {
         overlay.insert("b".to_string(), ConfigValue::Int(20));
         overlay.insert("c".to_string(), ConfigValue::Int(3));
 
-        base.merge(&overlay);
+        base.merge(&overlay).unwrap();
 
         assert_eq!(base.get("a").unwrap().as_int(), Some(1));
         assert_eq!(base.get("b").unwrap().as_int(), Some(20));
         assert_eq!(base.get("c").unwrap().as_int(), Some(3));
     }
 
+    #[test]
+    fn test_config_dict_preserves_order() {
+        let mut dict = ConfigDict::new();
+        for key in ["c", "a", "b"] {
+            dict.insert(key.to_string(), ConfigValue::Int(0));
+        }
+        dict.insert("a".to_string(), ConfigValue::Int(1));
+        assert_eq!(dict.keys().collect::<Vec<_>>(), vec!["c", "a", "b"]);
+        assert_eq!(dict.get("a"), Some(&ConfigValue::Int(1)));
+
+        assert_eq!(dict.remove("c"), Some(ConfigValue::Int(0)));
+        assert_eq!(dict.remove("c"), None);
+        dict.insert("c".to_string(), ConfigValue::Int(2));
+        assert_eq!(dict.keys().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        assert_eq!(dict.get("b"), Some(&ConfigValue::Int(0)));
+        assert_eq!(dict.len(), 3);
+
+        // Removing and re-adding leaves no trace in equality
+        let mut fresh = ConfigDict::new();
+        fresh.insert("a".to_string(), ConfigValue::Int(1));
+        fresh.insert("b".to_string(), ConfigValue::Int(0));
+        fresh.insert("c".to_string(), ConfigValue::Int(2));
+        assert_eq!(dict, fresh);
+    }
+
+    #[test]
+    fn test_config_dict_wide_lookup() {
+        let n = 10_000;
+        let mut dict = ConfigDict::new();
+        for i in 0..n {
+            dict.insert(format!("k{}", i), ConfigValue::Int(i as i64));
+        }
+        for i in (0..n).step_by(100) {
+            dict.remove(&format!("k{}", i));
+        }
+
+        // Lookups go through the index; with linear scans this would be
+        // ~10^8 comparisons
+        for round in 0..10 {
+            for i in 0..n {
+                let expected = (i % 100 != 0).then_some(ConfigValue::Int(i as i64));
+                assert_eq!(dict.get(&format!("k{}", i)), expected.as_ref(), "{}", round);
+            }
+        }
+        assert_eq!(dict.len(), n - n / 100);
+        assert_eq!(dict.keys().next(), Some("k1"));
+        assert_eq!(dict.keys().last(), Some("k9999"));
+    }
+
+    #[test]
+    fn test_config_dict_eq_ignores_key_order() {
+        let dict = |pairs: &[(&str, ConfigValue)]| {
+            let mut d = ConfigDict::new();
+            for (k, v) in pairs {
+                d.insert(k.to_string(), v.clone());
+            }
+            ConfigValue::Dict(d)
+        };
+        let list = |items: &[i64]| ConfigValue::List(items.iter().map(|&i| i.into()).collect());
+
+        let ab = dict(&[("a", 1.into()), ("b", list(&[1, 2]))]);
+        let ba = dict(&[("b", list(&[1, 2])), ("a", 1.into())]);
+        assert_eq!(ab, ba);
+        assert_eq!(
+            dict(&[("x", ab.clone()), ("y", ConfigValue::Null)]),
+            dict(&[("y", ConfigValue::Null), ("x", ba.clone())])
+        );
+
+        // Lists stay order-sensitive, also inside dicts
+        assert_ne!(list(&[1, 2]), list(&[2, 1]));
+        assert_ne!(ab, dict(&[("a", 1.into()), ("b", list(&[2, 1]))]));
+        // Same keys with different values, or a key more or less, differ
+        assert_ne!(ab, dict(&[("a", 2.into()), ("b", list(&[1, 2]))]));
+        assert_ne!(ab, dict(&[("a", 1.into())]));
+
+        // Removing and re-adding a key changes the order but not equality
+        let mut moved = ab.clone();
+        let d = moved.as_dict_mut().unwrap();
+        let a = d.remove("a").unwrap();
+        d.insert("a".to_string(), a);
+        assert_eq!(moved, ab);
+    }
+
+    #[test]
+    fn test_config_dict_merge_key_order() {
+        let mut base = ConfigDict::new();
+        for (key, value) in [("a", 1), ("b", 2), ("c", 3)] {
+            base.insert(key.to_string(), ConfigValue::Int(value));
+        }
+        base.insert("db".to_string(), ConfigValue::Dict(ConfigDict::new()));
+        // A removed and re-added key moves to the end
+        base.remove("a");
+        base.insert("a".to_string(), ConfigValue::Int(10));
+
+        let mut db = ConfigDict::new();
+        db.insert("port".to_string(), ConfigValue::Int(5432));
+        let mut overlay = ConfigDict::new();
+        overlay.insert("e".to_string(), ConfigValue::Int(5));
+        overlay.insert("db".to_string(), ConfigValue::Dict(db));
+        overlay.insert("b".to_string(), ConfigValue::Int(20));
+        overlay.insert("d".to_string(), ConfigValue::Int(4));
+
+        base.merge(&overlay).unwrap();
+
+        assert_eq!(
+            base.keys().collect::<Vec<_>>(),
+            vec!["b", "c", "db", "a", "e", "d"]
+        );
+        assert_eq!(base.len(), 6);
+        assert_eq!(base.get("b"), Some(&ConfigValue::Int(20)));
+    }
+
     #[test]
     fn test_interpolation_detection() {
         let v = ConfigValue::from("${foo.bar}");
@@ -392,4 +937,141 @@ mod tests {
         let v = ConfigValue::from("plain string");
         assert!(!v.is_interpolation());
     }
+
+    #[test]
+    fn test_append_unique() {
+        let mut tags = ConfigValue::List(vec!["a".into(), "b".into()]);
+
+        // Already present: no-op
+        assert_eq!(tags.append_unique(vec!["b".into()]), Some(0));
+        assert_eq!(tags, ConfigValue::List(vec!["a".into(), "b".into()]));
+
+        // New value: added, duplicates within the input collapse
+        assert_eq!(tags.append_unique(vec!["c".into(), "c".into()]), Some(1));
+        assert_eq!(
+            tags,
+            ConfigValue::List(vec!["a".into(), "b".into(), "c".into()])
+        );
+
+        assert_eq!(ConfigValue::Int(1).append_unique(vec![]), None);
+    }
+
+    #[test]
+    fn test_prepend_unique() {
+        let mut nums = ConfigValue::List(vec![ConfigValue::Int(1), ConfigValue::Int(2)]);
+
+        // 1.0 is semantically equal to 1
+        assert_eq!(nums.prepend_unique(vec![ConfigValue::Float(1.0)]), Some(0));
+        assert_eq!(
+            nums.prepend_unique(vec![ConfigValue::Int(3), ConfigValue::Int(4)]),
+            Some(2)
+        );
+        assert_eq!(
+            nums,
+            ConfigValue::List(vec![
+                ConfigValue::Int(3),
+                ConfigValue::Int(4),
+                ConfigValue::Int(1),
+                ConfigValue::Int(2),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_get_ci() {
+        let mut dict = ConfigDict::new();
+        dict.insert("Port".to_string(), ConfigValue::Int(3306));
+        dict.insert("HOST".to_string(), ConfigValue::from("localhost"));
+
+        assert_eq!(dict.get_ci("port"), Some(&ConfigValue::Int(3306)));
+        assert_eq!(dict.get_ci("PORT"), Some(&ConfigValue::Int(3306)));
+        assert_eq!(dict.get_ci("Host"), Some(&ConfigValue::from("localhost")));
+        assert_eq!(dict.get_ci("user"), None);
+        // Default lookup stays case-sensitive
+        assert_eq!(dict.get("port"), None);
+    }
+
+    #[test]
+    fn test_fold_case() {
+        let mut inner = ConfigDict::new();
+        inner.insert("Host".to_string(), ConfigValue::from("localhost"));
+        let mut dict = ConfigDict::new();
+        dict.insert("DB".to_string(), ConfigValue::Dict(inner));
+
+        let folded = dict.fold_case().unwrap();
+        assert_eq!(
+            folded.select("db.host"),
+            Some(ConfigValue::from("localhost"))
+        );
+    }
+
+    #[test]
+    fn test_fold_case_collision() {
+        let mut dict = ConfigDict::new();
+        dict.insert("Port".to_string(), ConfigValue::Int(1));
+        dict.insert("port".to_string(), ConfigValue::Int(2));
+
+        assert_eq!(
+            dict.fold_case(),
+            Err("Key collision after case folding: 'Port' and 'port'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_map_leaves_uppercase_strings() {
+        let value = crate::config::parser::parse_yaml(
+            "name: app\nport: 8080\ntags: [web, api]\ndb:\n  host: localhost\n",
+        )
+        .unwrap();
+
+        let mapped = value.map_leaves(|_, leaf| match leaf {
+            ConfigValue::String(s) => ConfigValue::String(s.to_uppercase()),
+            other => other.clone(),
+        });
+
+        let dict = mapped.as_dict().unwrap();
+        assert_eq!(dict.get("name"), Some(&ConfigValue::from("APP")));
+        assert_eq!(dict.get("port"), Some(&ConfigValue::Int(8080)));
+        assert_eq!(
+            dict.get("tags"),
+            Some(&ConfigValue::List(vec!["WEB".into(), "API".into()]))
+        );
+        assert_eq!(dict.select("db.host"), Some(ConfigValue::from("LOCALHOST")));
+    }
+
+    #[test]
+    fn test_map_leaves_redact_by_path() {
+        let value = crate::config::parser::parse_yaml(
+            "db:\n  user: admin\n  password: secret\nusers:\n  - name: bob\n    password: hunter2\n",
+        )
+        .unwrap();
+
+        let mut paths = std::cell::RefCell::new(Vec::new());
+        let mapped = value.map_leaves(|path, leaf| {
+            paths.borrow_mut().push(path.to_string());
+            if path.contains("password") {
+                ConfigValue::from("***")
+            } else {
+                leaf.clone()
+            }
+        });
+
+        assert_eq!(
+            paths.get_mut().as_slice(),
+            ["db.user", "db.password", "users.0.name", "users.0.password"]
+        );
+        let dict = mapped.as_dict().unwrap();
+        assert_eq!(dict.select("db.user"), Some(ConfigValue::from("admin")));
+        assert_eq!(dict.select("db.password"), Some(ConfigValue::from("***")));
+        let user = &dict.get("users").unwrap().as_list().unwrap()[0];
+        assert_eq!(
+            user.as_dict().unwrap().get("password"),
+            Some(&ConfigValue::from("***"))
+        );
+        // The original value is untouched
+        assert_eq!(
+            value.as_dict().unwrap().select("db.password"),
+            Some(ConfigValue::from("secret"))
+        );
+    }
 }
@@ -10,28 +10,61 @@ pub fn normalize_config_path(path: &str) -> String {
     parts.join("/")
 }
 
+/// Split a `@package` suffix off a configuration path
+///
+/// `db/mysql@backend` splits into `("db/mysql", Some("backend"))`. The
+/// package starts at the first `@`; a path without one has no package.
+pub fn split_config_package(path: &str) -> (&str, Option<&str>) {
+    match path.find('@') {
+        Some(idx) => (&path[..idx], Some(&path[idx + 1..])),
+        None => (path, None),
+    }
+}
+
 /// Get the parent directory of a configuration path
+///
+/// A `@package` suffix belongs to the last component, so the parent of
+/// `db/mysql@backend` is `db`.
 pub fn get_parent_path(path: &str) -> Option<String> {
+    let (path, _) = split_config_package(path);
     let normalized = normalize_config_path(path);
-    if let Some(idx) = normalized.rfind('/') {
-        Some(normalized[..idx].to_string())
-    } else {
-        None
-    }
+    normalized
+        .rfind('/')
+        .map(|idx| normalized[..idx].to_string())
 }
 
 /// Get the basename (last component) of a configuration path
+///
+/// The package travels with the basename: `db/mysql@backend` gives
+/// `mysql@backend`. Use [`split_config_package`] to separate the two.
 pub fn get_basename(path: &str) -> String {
+    let (path, package) = split_config_package(path);
     let normalized = normalize_config_path(path);
-    if let Some(idx) = normalized.rfind('/') {
-        normalized[idx + 1..].to_string()
-    } else {
-        normalized
+    let basename = match normalized.rfind('/') {
+        Some(idx) => &normalized[idx + 1..],
+        None => normalized.as_str(),
+    };
+    match package {
+        Some(package) => format!("{}@{}", basename, package.trim_end_matches('/')),
+        None => basename.to_string(),
     }
 }
 
 /// Join two configuration paths
+///
+/// An absolute `child` (`/model/resnet`, `pkg://...`) replaces `base` rather
+/// than being appended to it, like `Path::join`. Empty components and
+/// redundant or trailing slashes are ignored. Absolute config paths are
+/// relative to the config root, so `/model/resnet` joins to `model/resnet`.
 pub fn join_config_paths(base: &str, child: &str) -> String {
+    if is_absolute_config_path(child) {
+        return if get_scheme(child).is_some() {
+            child.trim_end_matches('/').to_string()
+        } else {
+            normalize_config_path(child)
+        };
+    }
+
     let base_norm = normalize_config_path(base);
     let child_norm = normalize_config_path(child);
 
@@ -130,6 +163,37 @@ mod tests {
         assert_eq!(get_basename("a"), "a");
     }
 
+    #[test]
+    fn test_paths_with_packages() {
+        assert_eq!(
+            split_config_package("db/mysql@backend"),
+            ("db/mysql", Some("backend"))
+        );
+        assert_eq!(split_config_package("db/mysql"), ("db/mysql", None));
+
+        assert_eq!(get_parent_path("db/mysql@backend"), Some("db".to_string()));
+        assert_eq!(get_basename("db/mysql@backend"), "mysql@backend");
+        assert_eq!(
+            get_parent_path("hydra/launcher/basic@hydra.launcher"),
+            Some("hydra/launcher".to_string())
+        );
+        assert_eq!(
+            get_basename("hydra/launcher/basic@hydra.launcher"),
+            "basic@hydra.launcher"
+        );
+
+        // Trailing slashes
+        assert_eq!(get_parent_path("db/mysql/"), Some("db".to_string()));
+        assert_eq!(get_basename("db/mysql/"), "mysql");
+        assert_eq!(get_basename("db/mysql@backend/"), "mysql@backend");
+
+        // Root-level names
+        assert_eq!(get_parent_path("mysql@backend"), None);
+        assert_eq!(get_basename("mysql@backend"), "mysql@backend");
+        assert_eq!(get_parent_path("/config"), None);
+        assert_eq!(get_basename("/config"), "config");
+    }
+
     #[test]
     fn test_join_config_paths() {
         assert_eq!(join_config_paths("a", "b"), "a/b");
@@ -138,6 +202,23 @@ mod tests {
         assert_eq!(join_config_paths("a", ""), "a");
     }
 
+    #[test]
+    fn test_join_config_paths_absolute_child() {
+        assert_eq!(join_config_paths("db", "/model/resnet"), "model/resnet");
+        assert_eq!(join_config_paths("", "/model/resnet"), "model/resnet");
+        assert_eq!(
+            join_config_paths("db", "pkg://my.configs/"),
+            "pkg://my.configs"
+        );
+    }
+
+    #[test]
+    fn test_join_config_paths_slashes() {
+        assert_eq!(join_config_paths("db/", "mysql"), "db/mysql");
+        assert_eq!(join_config_paths("db//", "mysql/"), "db/mysql");
+        assert_eq!(join_config_paths("", ""), "");
+    }
+
     #[test]
     fn test_is_absolute_config_path() {
         assert!(is_absolute_config_path("/absolute/path"));
@@ -5,7 +5,7 @@
 //! which stores structured configs that can be composed with file-based configs.
 
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use crate::config::value::ConfigDict;
 use crate::ObjectType;
@@ -55,7 +55,17 @@ pub enum RepoEntry {
 /// A singleton ConfigStore for structured configurations
 ///
 /// This is a thread-safe store that mirrors the Python ConfigStore.
-/// It uses RwLock for safe concurrent access.
+///
+/// # Concurrency
+///
+/// The repository tree is guarded by a single `RwLock`:
+/// - Reads (`load`, `list`, `get_type`, `config_exists`, `group_exists`)
+///   share the lock and never block each other.
+/// - Writes (`store`, `clear`) take the lock exclusively, so each is applied
+///   atomically and is visible to every read that starts after it returns.
+/// - A panic in another thread while holding the lock does not poison the
+///   store: the tree is only modified by complete insertions, so later calls
+///   keep using it.
 #[derive(Debug)]
 pub struct ConfigStore {
     /// The repository tree
@@ -70,6 +80,16 @@ impl ConfigStore {
         }
     }
 
+    /// Acquire the repository for reading, ignoring lock poisoning
+    fn read_repo(&self) -> RwLockReadGuard<'_, HashMap<String, RepoEntry>> {
+        self.repo.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Acquire the repository for writing, ignoring lock poisoning
+    fn write_repo(&self) -> RwLockWriteGuard<'_, HashMap<String, RepoEntry>> {
+        self.repo.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
     /// Store a config node
     ///
     /// # Arguments
@@ -86,7 +106,7 @@ impl ConfigStore {
         package: Option<&str>,
         provider: Option<&str>,
     ) {
-        let mut repo = self.repo.write().unwrap();
+        let mut repo = self.write_repo();
 
         // Navigate to the correct location
         let mut cur: &mut HashMap<String, RepoEntry> = &mut *repo;
@@ -136,7 +156,7 @@ impl ConfigStore {
     /// # Returns
     /// The config node if found
     pub fn load(&self, config_path: &str) -> Option<ConfigNode> {
-        let repo = self.repo.read().unwrap();
+        let repo = self.read_repo();
         self.load_from_repo(&repo, config_path)
     }
 
@@ -156,20 +176,8 @@ impl ConfigStore {
             let group_path = &path[..idx];
             let name = &path[idx + 1..];
 
-            // Navigate to the group
-            let mut cur = repo;
-            for part in group_path.split('/') {
-                if part.is_empty() {
-                    continue;
-                }
-                match cur.get(part) {
-                    Some(RepoEntry::Group(inner)) => cur = inner,
-                    _ => return None,
-                }
-            }
-
             // Get the config
-            match cur.get(name) {
+            match find_group(repo, group_path)?.get(name) {
                 Some(RepoEntry::Config(node)) => Some(node.clone()),
                 _ => None,
             }
@@ -184,7 +192,7 @@ impl ConfigStore {
 
     /// Get the type of a path (group or config)
     pub fn get_type(&self, path: &str) -> ObjectType {
-        let repo = self.repo.read().unwrap();
+        let repo = self.read_repo();
 
         if path.is_empty() {
             // Root is always a group if it has content
@@ -234,29 +242,37 @@ impl ConfigStore {
 
     /// List items in a path
     pub fn list(&self, path: &str) -> Option<Vec<String>> {
-        let repo = self.repo.read().unwrap();
+        let repo = self.read_repo();
+        let group = find_group(&repo, path)?;
 
-        if path.is_empty() {
-            // List root
-            let mut items: Vec<String> = repo.keys().cloned().collect();
-            items.sort();
-            return Some(items);
-        }
+        let mut items: Vec<String> = group.keys().cloned().collect();
+        items.sort();
+        Some(items)
+    }
 
-        let mut cur: &HashMap<String, RepoEntry> = &repo;
-        for part in path.split('/') {
-            if part.is_empty() {
-                continue;
-            }
-            match cur.get(part) {
-                Some(RepoEntry::Group(inner)) => cur = inner,
-                _ => return None,
-            }
-        }
+    /// List the configs in a group that were stored under a package
+    ///
+    /// Lets several instances of a schema share a group, e.g. `db` configs
+    /// stored for the `primary` and `replica` packages. Names are sorted and
+    /// carry the `.yaml` suffix, as in [`Self::list`]; subgroups are skipped.
+    /// An unknown group lists nothing.
+    pub fn list_by_package(&self, group: &str, package: &str) -> Vec<String> {
+        let repo = self.read_repo();
+        let Some(group) = find_group(&repo, group) else {
+            return Vec::new();
+        };
 
-        let mut items: Vec<String> = cur.keys().cloned().collect();
+        let mut items: Vec<String> = group
+            .iter()
+            .filter_map(|(name, entry)| match entry {
+                RepoEntry::Config(node) if node.package.as_deref() == Some(package) => {
+                    Some(name.clone())
+                }
+                _ => None,
+            })
+            .collect();
         items.sort();
-        Some(items)
+        items
     }
 
     /// Check if a config exists
@@ -271,11 +287,24 @@ impl ConfigStore {
 
     /// Clear all stored configs
     pub fn clear(&self) {
-        let mut repo = self.repo.write().unwrap();
+        let mut repo = self.write_repo();
         repo.clear();
     }
 }
 
+/// The entries of the group at `path` (`""` is the root), if it exists
+fn find_group<'a>(
+    repo: &'a HashMap<String, RepoEntry>,
+    path: &str,
+) -> Option<&'a HashMap<String, RepoEntry>> {
+    path.split('/')
+        .filter(|part| !part.is_empty())
+        .try_fold(repo, |cur, part| match cur.get(part) {
+            Some(RepoEntry::Group(inner)) => Some(inner),
+            _ => None,
+        })
+}
+
 impl Default for ConfigStore {
     fn default() -> Self {
         Self::new()
@@ -363,4 +392,87 @@ mod tests {
         let items = store.list("db").unwrap();
         assert_eq!(items, vec!["mysql.yaml", "postgres.yaml"]);
     }
+
+    #[test]
+    fn test_list_by_package() {
+        let store = ConfigStore::new();
+        let node = make_test_dict();
+
+        store.store("mysql", node.clone(), Some("db"), Some("primary"), None);
+        store.store("postgres", node.clone(), Some("db"), Some("primary"), None);
+        store.store("sqlite", node.clone(), Some("db"), Some("replica"), None);
+        store.store("default", node.clone(), Some("db"), None, None);
+        store.store(
+            "aurora",
+            node.clone(),
+            Some("db/cloud"),
+            Some("primary"),
+            None,
+        );
+
+        assert_eq!(
+            store.list_by_package("db", "primary"),
+            vec!["mysql.yaml", "postgres.yaml"]
+        );
+        assert_eq!(store.list_by_package("db", "replica"), vec!["sqlite.yaml"]);
+        assert_eq!(
+            store.list_by_package("db/cloud", "primary"),
+            vec!["aurora.yaml"]
+        );
+        assert!(store.list_by_package("db", "backup").is_empty());
+        assert!(store.list_by_package("cache", "primary").is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_store_and_read() {
+        use std::thread;
+
+        const THREADS: usize = 8;
+        const CONFIGS: usize = 50;
+
+        let store = Arc::new(ConfigStore::new());
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let store = Arc::clone(&store);
+                thread::spawn(move || {
+                    let group = format!("group{}", t);
+                    for i in 0..CONFIGS {
+                        let mut node = ConfigDict::new();
+                        node.insert("index".to_string(), ConfigValue::Int(i as i64));
+                        store.store(&format!("c{}", i), node, Some(&group), None, None);
+
+                        // Reads interleave with other threads' writes
+                        let loaded = store.load(&format!("{}/c{}", group, i)).unwrap();
+                        assert_eq!(loaded.node.get("index"), Some(&ConfigValue::Int(i as i64)));
+                        assert!(store.list("").unwrap().contains(&group));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(store.list("").unwrap().len(), THREADS);
+        for t in 0..THREADS {
+            let group = format!("group{}", t);
+            assert_eq!(store.list(&group).unwrap().len(), CONFIGS);
+            assert!(store.config_exists(&format!("{}/c{}", group, CONFIGS - 1)));
+        }
+    }
+
+    #[test]
+    fn test_store_survives_poisoned_lock() {
+        let store = Arc::new(ConfigStore::new());
+        let poisoner = Arc::clone(&store);
+        let result = std::thread::spawn(move || {
+            let _guard = poisoner.repo.write().unwrap();
+            panic!("poison the lock");
+        })
+        .join();
+        assert!(result.is_err());
+
+        store.store("config", ConfigDict::new(), None, None, None);
+        assert!(store.config_exists("config"));
+    }
 }
@@ -65,7 +65,7 @@ pub enum OverrideType {
     ForceAdd = 3,
     /// Delete value (~key)
     Del = 4,
-    /// Extend list (+key+=[...])
+    /// Add to the current value (key+=value)
     ExtendList = 5,
 }
 
@@ -109,7 +109,7 @@ impl std::fmt::Display for ValueType {
 }
 
 /// A key in the configuration (e.g., "db.driver" or "db[name]")
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Key {
     /// The key name (e.g., "db")
     pub key_or_group: String,
@@ -138,6 +138,11 @@ impl Key {
     pub fn has_package(&self) -> bool {
         self.package.is_some()
     }
+
+    /// Path segments of the key, with `[n]` as list indices
+    pub fn segments(&self) -> Vec<KeySegment> {
+        parse_key_segments(&self.key_or_group)
+    }
 }
 
 impl std::fmt::Display for Key {
@@ -150,6 +155,59 @@ impl std::fmt::Display for Key {
     }
 }
 
+/// One step of a key path: a dict key or a list index
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum KeySegment {
+    /// A dict key (e.g., "host" in "db.host")
+    Name(String),
+    /// A list index (e.g., 0 in "servers[0]"); negative indices count
+    /// from the end, so -1 is the last element
+    Index(i64),
+}
+
+impl std::fmt::Display for KeySegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeySegment::Name(name) => write!(f, "{}", name),
+            KeySegment::Index(index) => write!(f, "{}", index),
+        }
+    }
+}
+
+/// Split a key path such as "a.b[1].c" into its segments
+///
+/// Bracketed numbers, including negative ones like `[-1]`, become list
+/// indices; other bracketed text (as in
+/// "db[name]") is a dict key. An unclosed bracket is kept as part of the name.
+pub fn parse_key_segments(path: &str) -> Vec<KeySegment> {
+    let mut segments = Vec::new();
+    let mut name = String::new();
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        let rest = chars.as_str();
+        let close = if c == '[' { rest.find(']') } else { None };
+        if c != '.' && close.is_none() {
+            name.push(c);
+            continue;
+        }
+        if !name.is_empty() {
+            segments.push(KeySegment::Name(std::mem::take(&mut name)));
+        }
+        if let Some(end) = close {
+            let inner = &rest[..end];
+            segments.push(match inner.parse() {
+                Ok(index) => KeySegment::Index(index),
+                Err(_) => KeySegment::Name(inner.to_string()),
+            });
+            chars = rest[end + 1..].chars();
+        }
+    }
+    if !name.is_empty() {
+        segments.push(KeySegment::Name(name));
+    }
+    segments
+}
+
 /// Base trait for sweep types
 pub trait Sweep {
     fn tags(&self) -> &HashSet<String>;
@@ -268,6 +326,10 @@ pub enum ListOperationType {
     RemoveValue = 5,
     /// Clear the list
     Clear = 6,
+    /// Append items not already present
+    AppendUnique = 7,
+    /// Prepend items not already present
+    PrependUnique = 8,
 }
 
 impl std::fmt::Display for ListOperationType {
@@ -279,6 +341,8 @@ impl std::fmt::Display for ListOperationType {
             ListOperationType::RemoveAt => write!(f, "REMOVE_AT"),
             ListOperationType::RemoveValue => write!(f, "REMOVE_VALUE"),
             ListOperationType::Clear => write!(f, "CLEAR"),
+            ListOperationType::AppendUnique => write!(f, "APPEND_UNIQUE"),
+            ListOperationType::PrependUnique => write!(f, "PREPEND_UNIQUE"),
         }
     }
 }
@@ -300,6 +364,7 @@ pub struct GlobChoiceSweep {
     pub tags: HashSet<String>,
     pub include: Vec<String>,
     pub exclude: Vec<String>,
+    pub case_insensitive: bool,
 }
 
 impl Default for GlobChoiceSweep {
@@ -308,6 +373,7 @@ impl Default for GlobChoiceSweep {
             tags: HashSet::new(),
             include: Vec::new(),
             exclude: Vec::new(),
+            case_insensitive: false,
         }
     }
 }
@@ -335,7 +401,53 @@ pub enum ParsedElement {
     Dict(Vec<(String, ParsedElement)>),
 }
 
+/// The kind of a parsed element, ignoring its value
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElementKind {
+    Null,
+    Bool,
+    Int,
+    Float,
+    /// Quoted or unquoted string
+    String,
+    List,
+    Dict,
+    /// Elements of more than one kind, see [`ParsedElement::list_element_type`]
+    Mixed,
+}
+
 impl ParsedElement {
+    /// The kind of this element; quoted and unquoted strings are both `String`
+    pub fn kind(&self) -> ElementKind {
+        match self {
+            ParsedElement::Null => ElementKind::Null,
+            ParsedElement::Bool(_) => ElementKind::Bool,
+            ParsedElement::Int(_) => ElementKind::Int,
+            ParsedElement::Float(_) => ElementKind::Float,
+            ParsedElement::String(_) | ParsedElement::QuotedString(_) => ElementKind::String,
+            ParsedElement::List(_) => ElementKind::List,
+            ParsedElement::Dict(_) => ElementKind::Dict,
+        }
+    }
+
+    /// The kind shared by all elements of a list
+    ///
+    /// Returns `Some(ElementKind::Mixed)` if the elements differ in kind (ints
+    /// and floats count as different), and `None` for an empty list or a
+    /// non-list. Nested lists are compared by kind only, so `[[1], [a]]` is a
+    /// list of `List`.
+    pub fn list_element_type(&self) -> Option<ElementKind> {
+        let ParsedElement::List(items) = self else {
+            return None;
+        };
+        let first = items.first()?.kind();
+        if items.iter().all(|item| item.kind() == first) {
+            Some(first)
+        } else {
+            Some(ElementKind::Mixed)
+        }
+    }
+
     /// Check if the element is null
     pub fn is_null(&self) -> bool {
         matches!(self, ParsedElement::Null)
@@ -376,6 +488,37 @@ impl ParsedElement {
     }
 }
 
+/// Render a float the way Python's `str()` does
+///
+/// The shortest digits that parse back to `f`, with `.0` on whole numbers so
+/// the result never reads as an int (`2.0`, not `2`). Exponents below -4 or
+/// from 16 up use scientific notation with a signed, two-digit exponent
+/// (`1e-05`, `1.5e+16`), and the special values are `inf`, `-inf` and `nan`.
+pub fn format_float(f: f64) -> String {
+    if f.is_nan() {
+        return "nan".to_string();
+    }
+    if f.is_infinite() {
+        return if f > 0.0 { "inf" } else { "-inf" }.to_string();
+    }
+    let scientific = format!("{:e}", f);
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("{:e} output has an exponent");
+    let exponent: i32 = exponent.parse().expect("{:e} exponent is an integer");
+    if (-4..16).contains(&exponent) {
+        let s = f.to_string();
+        if s.contains('.') {
+            s
+        } else {
+            format!("{}.0", s)
+        }
+    } else {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!("{}e{}{:02}", mantissa, sign, exponent.abs())
+    }
+}
+
 /// The value part of an override
 #[derive(Clone, Debug, PartialEq)]
 pub enum OverrideValue {
@@ -485,6 +628,31 @@ mod tests {
         assert_eq!(qs.with_quotes(), "'it\\'s'");
     }
 
+    #[test]
+    fn test_key_segments() {
+        use KeySegment::{Index, Name};
+        let name = |s: &str| Name(s.to_string());
+
+        assert_eq!(
+            Key::new("a.b[1].c".to_string()).segments(),
+            vec![name("a"), name("b"), Index(1), name("c")]
+        );
+        assert_eq!(
+            parse_key_segments("servers[0][2]"),
+            vec![name("servers"), Index(0), Index(2)]
+        );
+        assert_eq!(
+            parse_key_segments("db[name]"),
+            vec![name("db"), name("name")]
+        );
+        assert_eq!(
+            parse_key_segments("servers[-1].host"),
+            vec![name("servers"), Index(-1), name("host")]
+        );
+        assert_eq!(parse_key_segments("a[0"), vec![name("a[0")]);
+        assert!(parse_key_segments("").is_empty());
+    }
+
     #[test]
     fn test_override_type_display() {
         assert_eq!(format!("{}", OverrideType::Change), "CHANGE");
@@ -512,6 +680,56 @@ mod tests {
         assert_eq!(elem.as_float(), Some(42.0));
     }
 
+    #[test]
+    fn test_format_float() {
+        assert_eq!(format_float(2.0), "2.0");
+        assert_eq!(format_float(-3.0), "-3.0");
+        assert_eq!(format_float(0.0), "0.0");
+        assert_eq!(format_float(-0.0), "-0.0");
+        assert_eq!(format_float(0.1), "0.1");
+        assert_eq!(format_float(-2.5), "-2.5");
+        assert_eq!(format_float(0.0001), "0.0001");
+        assert_eq!(format_float(1e15), "1000000000000000.0");
+
+        assert_eq!(format_float(1e-5), "1e-05");
+        assert_eq!(format_float(-2.5e-7), "-2.5e-07");
+        assert_eq!(format_float(1e16), "1e+16");
+        assert_eq!(format_float(1.5e300), "1.5e+300");
+
+        assert_eq!(format_float(f64::INFINITY), "inf");
+        assert_eq!(format_float(f64::NEG_INFINITY), "-inf");
+        assert_eq!(format_float(f64::NAN), "nan");
+    }
+
+    #[test]
+    fn test_list_element_type() {
+        let list = |items: Vec<ParsedElement>| ParsedElement::List(items);
+        let string = |s: &str| ParsedElement::String(s.to_string());
+
+        assert_eq!(
+            list(vec![ParsedElement::Int(1), ParsedElement::Int(2)]).list_element_type(),
+            Some(ElementKind::Int)
+        );
+        assert_eq!(
+            list(vec![ParsedElement::Int(1), string("a")]).list_element_type(),
+            Some(ElementKind::Mixed)
+        );
+        assert_eq!(list(vec![]).list_element_type(), None);
+        assert_eq!(ParsedElement::Int(1).list_element_type(), None);
+        assert_eq!(
+            list(vec![
+                list(vec![ParsedElement::Int(1)]),
+                list(vec![string("a")]),
+            ])
+            .list_element_type(),
+            Some(ElementKind::List)
+        );
+        assert_eq!(
+            list(vec![list(vec![]), ParsedElement::Int(1)]).list_element_type(),
+            Some(ElementKind::Mixed)
+        );
+    }
+
     #[test]
     fn test_override_change() {
         let key = Key::new("db.port".to_string());
@@ -1,6 +1,8 @@
 // Copyright (c) Facebook, Inc. and its affiliates. All Rights Reserved
 //! Default element types for configuration composition
 
+use crate::package::compute_final_package;
+
 /// Result of resolving a default
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ResultDefault {
@@ -232,6 +234,16 @@ impl ConfigDefault {
     pub fn get_default_package(&self) -> String {
         self.get_group_path().replace("/", ".")
     }
+
+    /// Get the final package, nested under the parent package
+    pub fn get_final_package(&self, default_to_package_header: bool) -> String {
+        compute_final_package(
+            &self.get_group_path(),
+            self.get_name(),
+            self.base.get_package(default_to_package_header),
+            self.base.parent_package.as_deref().unwrap_or(""),
+        )
+    }
 }
 
 /// A config group default
@@ -373,23 +385,14 @@ impl GroupDefault {
         }
     }
 
-    /// Get the final package
+    /// Get the final package, nested under the parent package
     pub fn get_final_package(&self, default_to_package_header: bool) -> String {
-        let parent_package = self.base.parent_package.as_deref().unwrap_or("");
-        let package = self.base.get_package(default_to_package_header);
-
-        let pkg = match package {
-            Some(p) => p.to_string(),
-            None => self.get_group_path().replace("/", "."),
-        };
-
-        if parent_package.is_empty() {
-            pkg
-        } else if pkg.is_empty() {
-            parent_package.to_string()
-        } else {
-            format!("{}.{}", parent_package, pkg)
-        }
+        compute_final_package(
+            &self.get_group_path(),
+            self.value.as_single(),
+            self.base.get_package(default_to_package_header),
+            self.base.parent_package.as_deref().unwrap_or(""),
+        )
     }
 
     pub fn is_missing(&self) -> bool {
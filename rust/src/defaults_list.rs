@@ -79,8 +79,10 @@ pub struct Overrides {
     pub override_metadata: HashMap<String, OverrideMetadata>,
     /// Groups that have been deleted
     pub deletions: HashMap<String, Deletion>,
-    /// Groups to append
+    /// Groups to append (+group=value)
     pub appends: Vec<GroupDefault>,
+    /// Groups to override if present in the defaults list, or append otherwise (++group=value)
+    pub force_appends: Vec<GroupDefault>,
     /// Known choices (group -> option)
     pub known_choices: HashMap<String, Option<String>>,
     /// Known choices per group (for error messages)
@@ -101,8 +103,10 @@ impl Overrides {
                 let key = &ovr[..eq_pos];
                 let value = &ovr[eq_pos + 1..];
 
-                // Skip value overrides (contain dots) - only process group overrides
-                if key.contains('.') {
+                // Skip value overrides (contain dots) - only process group overrides.
+                // Dots after '@' belong to the package, e.g. db@backend.primary=mysql
+                let group_part = key.split('@').next().unwrap_or(key);
+                if group_part.contains('.') {
                     continue;
                 }
 
@@ -118,12 +122,24 @@ impl Overrides {
                         used: false,
                     };
                     result.deletions.insert(group.to_string(), deletion);
-                } else if key.starts_with('+') {
-                    // Addition: +db=mysql
-                    let group = &key[1..];
+                } else if let Some(group) = key.strip_prefix("++") {
+                    // Force addition: ++db=mysql overrides db if it is in the
+                    // defaults list, and appends it otherwise
+                    let gd = Self::external_append(group, value);
                     result
-                        .appends
-                        .push(GroupDefault::new(group.to_string(), value.to_string()));
+                        .choices
+                        .insert(group.to_string(), Some(value.to_string()));
+                    result.override_metadata.insert(
+                        group.to_string(),
+                        OverrideMetadata {
+                            external_override: true,
+                            ..OverrideMetadata::default()
+                        },
+                    );
+                    result.force_appends.push(gd);
+                } else if let Some(group) = key.strip_prefix('+') {
+                    // Addition: +db=mysql
+                    result.appends.push(Self::external_append(group, value));
                 } else {
                     // Regular override: db=mysql
                     result
@@ -139,8 +155,8 @@ impl Overrides {
                         },
                     );
                 }
-            } else if ovr.starts_with('~') {
-                // Delete without value: ~db
+            } else if ovr.starts_with('~') && !ovr.contains('.') {
+                // Delete without value: ~db (~db.port deletes a config value)
                 let group = &ovr[1..];
                 result
                     .deletions
@@ -151,6 +167,18 @@ impl Overrides {
         result
     }
 
+    /// Build the group default appended by a `+group@pkg=value` override
+    fn external_append(key: &str, value: &str) -> GroupDefault {
+        let (group, package) = match key.split_once('@') {
+            Some((group, pkg)) => (group, Some(pkg.to_string())),
+            None => (key, None),
+        };
+        let mut gd = GroupDefault::new(group.to_string(), value.to_string());
+        gd.base.package = package;
+        gd.external_append = true;
+        gd
+    }
+
     /// Check if a group has an override
     pub fn get_override(&self, group: &str) -> Option<&str> {
         self.choices.get(group).and_then(|v| v.as_deref())
@@ -371,11 +399,45 @@ impl<'a> DefaultsListBuilder<'a> {
             root.children = Some(vec![primary_node]);
         }
 
-        // Append any +group=value overrides
-        if !self.overrides.appends.is_empty() {
+        // Append any +group=value overrides, and ++group=value overrides
+        // whose group was not already in the defaults list
+        let mut appended = Vec::new();
+        for gd in &self.overrides.appends {
+            let key = gd.get_override_key();
+            if let Some(existing) = self.overrides.known_choices.get(&key) {
+                let value = gd.value.as_single().unwrap_or_default();
+                return Err(ConfigCompositionError::new(format!(
+                    "Could not append '{}' to the defaults list: it is already selected ({}={}).\nTo override it use {}={}, or force it with ++{}={}",
+                    key,
+                    key,
+                    existing.as_deref().unwrap_or("null"),
+                    key,
+                    value,
+                    key,
+                    value
+                ))
+                .into());
+            }
+            appended.push(gd.clone());
+        }
+        for gd in &self.overrides.force_appends {
+            let key = gd.get_override_key();
+            let applied = self
+                .overrides
+                .override_metadata
+                .get(&key)
+                .is_some_and(|meta| meta.used);
+            if !applied {
+                appended.push(gd.clone());
+            }
+        }
+        if !appended.is_empty() {
             let mut children = root.children.take().unwrap_or_default();
-            for gd in &self.overrides.appends {
-                children.push(DefaultsTreeNode::group(gd.clone()));
+            for gd in appended {
+                let key = gd.get_override_key();
+                self.overrides.mark_override_used(&key);
+                self.overrides.record_choice(&key, gd.value.as_single());
+                children.push(self.build_tree_from_group(gd)?);
             }
             root.children = Some(children);
         }
@@ -432,51 +494,8 @@ impl<'a> DefaultsListBuilder<'a> {
             .cloned()
             .unwrap_or_default();
 
-        let mut children = Vec::new();
-        let mut found_self = false;
-
-        for default_val in &defaults_list {
-            match self.parse_default_value(default_val, &config)? {
-                ParsedDefault::SelfRef => {
-                    found_self = true;
-                    children.push(DefaultsTreeNode::config(ConfigDefault::new(
-                        "_self_".to_string(),
-                    )));
-                }
-                ParsedDefault::Config(cd) => {
-                    let child = self.build_tree_from_config(cd, false)?;
-                    children.push(child);
-                }
-                ParsedDefault::Group(mut gd) => {
-                    // Apply override if exists
-                    if let Some(override_val) = self.overrides.get_override(&gd.group) {
-                        gd.value = GroupValue::Single(override_val.to_string());
-                        gd.config_name_overridden = true;
-                        // Mark this override as used
-                        self.overrides.mark_override_used(&gd.group);
-                    }
-
-                    // Skip if deleted
-                    if self
-                        .overrides
-                        .is_deleted_with_value(&gd.group, gd.value.as_single())
-                    {
-                        gd.deleted = true;
-                        // Mark this deletion as used
-                        self.overrides.mark_deletion_used(&gd.group);
-                    }
-
-                    // Record the choice
-                    self.overrides
-                        .record_choice(&gd.group, gd.value.as_single());
-
-                    if !gd.deleted {
-                        let child = self.build_tree_from_group(gd)?;
-                        children.push(child);
-                    }
-                }
-            }
-        }
+        let (mut children, found_self) =
+            self.build_children(&defaults_list, &config, &config_path)?;
 
         // If no _self_ was found, add implicit _self_ at the end
         if !found_self {
@@ -527,33 +546,15 @@ impl<'a> DefaultsListBuilder<'a> {
                 .cloned()
                 .unwrap_or_default();
 
-            let mut children = Vec::new();
-            for default_val in &defaults_list {
-                let fake_parent = ConfigDefault {
-                    path: Some(config_path.clone()),
-                    base: crate::defaults::InputDefaultBase {
-                        parent_base_dir: Some(group.group.clone()),
-                        ..Default::default()
-                    },
+            let fake_parent = ConfigDefault {
+                path: Some(config_path.clone()),
+                base: crate::defaults::InputDefaultBase {
+                    parent_base_dir: Some(group.group.clone()),
                     ..Default::default()
-                };
-
-                match self.parse_default_value(default_val, &fake_parent)? {
-                    ParsedDefault::SelfRef => {
-                        children.push(DefaultsTreeNode::config(ConfigDefault::new(
-                            "_self_".to_string(),
-                        )));
-                    }
-                    ParsedDefault::Config(cd) => {
-                        let child = self.build_tree_from_config(cd, false)?;
-                        children.push(child);
-                    }
-                    ParsedDefault::Group(gd) => {
-                        let child = self.build_tree_from_group(gd)?;
-                        children.push(child);
-                    }
-                }
-            }
+                },
+                ..Default::default()
+            };
+            let (children, _) = self.build_children(&defaults_list, &fake_parent, &config_path)?;
 
             let mut node = DefaultsTreeNode::group(group);
             if !children.is_empty() {
@@ -566,6 +567,97 @@ impl<'a> DefaultsListBuilder<'a> {
         Ok(DefaultsTreeNode::group(group))
     }
 
+    /// Build the child nodes of a config from its defaults list
+    ///
+    /// `override` entries are registered first and replace the choice of the
+    /// matching default wherever it appears in this subtree, keeping that
+    /// default's position (and so its order relative to `_self_`). Entries
+    /// are built last to first, as in Hydra, so an override in a later
+    /// default's config applies to an earlier default. User overrides take
+    /// precedence over both. Returns the children and whether `_self_` was
+    /// among them.
+    fn build_children(
+        &mut self,
+        defaults_list: &[ConfigValue],
+        parent: &ConfigDefault,
+        config_path: &str,
+    ) -> Result<(Vec<DefaultsTreeNode>, bool), ConfigLoadError> {
+        let mut parsed = Vec::new();
+        let mut last_override: Option<String> = None;
+        for default_val in defaults_list {
+            match self.parse_default_value(default_val, parent)? {
+                ParsedDefault::Group(gd) if gd.is_override => {
+                    let key = gd.get_override_key();
+                    let value = gd.value.as_single().unwrap_or_default();
+                    self.overrides
+                        .add_internal_override(config_path, &key, value, Some(&gd.group));
+                    last_override = Some(key);
+                }
+                ParsedDefault::SelfRef => parsed.push(ParsedDefault::SelfRef),
+                other => {
+                    if let Some(key) = &last_override {
+                        return Err(ConfigCompositionError::new(format!(
+                            "In '{}': Override '{}' must be at the end of the defaults list",
+                            config_path, key
+                        ))
+                        .into());
+                    }
+                    parsed.push(other);
+                }
+            }
+        }
+
+        let found_self = parsed.iter().any(|d| matches!(d, ParsedDefault::SelfRef));
+        let mut children = Vec::new();
+        for default in parsed.into_iter().rev() {
+            match default {
+                ParsedDefault::SelfRef => {
+                    children.push(DefaultsTreeNode::config(ConfigDefault::new(
+                        "_self_".to_string(),
+                    )));
+                }
+                ParsedDefault::Config(cd) => {
+                    let child = self.build_tree_from_config(cd, false)?;
+                    children.push(child);
+                }
+                ParsedDefault::Group(mut gd) => {
+                    // Overrides match on group and package (e.g. db@backend)
+                    let override_key = gd.get_override_key();
+
+                    // Apply override if exists
+                    if let Some(override_val) = self.overrides.get_override(&override_key) {
+                        gd.value = GroupValue::Single(override_val.to_string());
+                        gd.config_name_overridden = true;
+                        // Mark this override as used
+                        self.overrides.mark_override_used(&override_key);
+                    }
+
+                    // Skip if deleted
+                    if self
+                        .overrides
+                        .is_deleted_with_value(&override_key, gd.value.as_single())
+                    {
+                        gd.deleted = true;
+                        // Mark this deletion as used
+                        self.overrides.mark_deletion_used(&override_key);
+                    }
+
+                    // Record the choice
+                    self.overrides
+                        .record_choice(&override_key, gd.value.as_single());
+
+                    if !gd.deleted {
+                        let child = self.build_tree_from_group(gd)?;
+                        children.push(child);
+                    }
+                }
+            }
+        }
+        children.reverse();
+
+        Ok((children, found_self))
+    }
+
     /// Parse a default value from config
     fn parse_default_value(
         &self,
@@ -610,20 +702,38 @@ impl<'a> DefaultsListBuilder<'a> {
                         _ => continue,
                     };
 
+                    // `override group: value` overrides a default declared
+                    // elsewhere in the tree instead of adding one
+                    let (key, is_override) = match key.strip_prefix("override ") {
+                        Some(key) => (key.trim(), true),
+                        None => (key, is_override),
+                    };
+
+                    // Split off the package in `group@package: value`
+                    let (key, key_package) = match key.split_once('@') {
+                        Some((group, pkg)) => (group, Some(pkg)),
+                        None => (key, None),
+                    };
+
                     // Determine if this is a group (directory) or config
-                    let full_path = if parent.base.parent_base_dir.is_some() {
+                    // A leading '/' makes the group path absolute
+                    let full_path = if let Some(key) = key.strip_prefix('/') {
+                        key.to_string()
+                    } else if parent.base.parent_base_dir.is_some() {
                         format!("{}/{}", parent.base.parent_base_dir.as_ref().unwrap(), key)
                     } else {
                         key.to_string()
                     };
 
-                    if (self.group_exists)(&full_path) {
+                    if is_override || (self.group_exists)(&full_path) {
                         let mut gd = GroupDefault::new(full_path, value_str);
                         gd.optional = optional;
                         gd.is_override = is_override;
 
                         // Get package if specified
-                        if let Some(ConfigValue::String(pkg)) = dict.get("package") {
+                        if let Some(pkg) = key_package {
+                            gd.base.package = Some(pkg.to_string());
+                        } else if let Some(ConfigValue::String(pkg)) = dict.get("package") {
                             gd.base.package = Some(pkg.clone());
                         }
 
@@ -648,6 +758,10 @@ impl<'a> DefaultsListBuilder<'a> {
     }
 
     /// Flatten the defaults tree to a list
+    ///
+    /// A config with its own defaults list is emitted once, at the position
+    /// of its `_self_` entry, so the composer can merge it between the
+    /// defaults that precede and follow it.
     fn flatten_tree(&self, node: &DefaultsTreeNode) -> Result<Vec<ResultDefault>, ConfigLoadError> {
         let mut result = Vec::new();
         self.flatten_node(node, &mut result, None)?;
@@ -660,73 +774,82 @@ impl<'a> DefaultsListBuilder<'a> {
         result: &mut Vec<ResultDefault>,
         parent_path: Option<&str>,
     ) -> Result<(), ConfigLoadError> {
+        let own = Self::node_result(node, parent_path);
+        let mut emitted_self = false;
+
         // Process children first (depth-first)
         if let Some(children) = &node.children {
+            let current_path = own.as_ref().and_then(|rd| rd.config_path.clone());
             for child in children {
-                let current_path = match &node.node {
-                    DefaultNodeContent::Config(cd) => cd.path.as_deref(),
-                    DefaultNodeContent::Group(gd) => Some(gd.group.as_str()),
-                    DefaultNodeContent::VirtualRoot => None,
-                };
-                self.flatten_node(child, result, current_path)?;
+                if let DefaultNodeContent::Config(cd) = &child.node {
+                    if cd.is_self() {
+                        if let Some(rd) = &own {
+                            if !emitted_self {
+                                let mut rd = rd.clone();
+                                rd.is_self = true;
+                                rd.override_key = None;
+                                result.push(rd);
+                                emitted_self = true;
+                            }
+                        }
+                        continue;
+                    }
+                }
+                self.flatten_node(child, result, current_path.as_deref())?;
             }
         }
 
-        // Add this node's result
+        // Nodes without a _self_ entry are applied after their defaults
+        if !emitted_self {
+            if let Some(rd) = own {
+                result.push(rd);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Build the result entry for a node's own content, if it has any
+    fn node_result(node: &DefaultsTreeNode, parent_path: Option<&str>) -> Option<ResultDefault> {
         match &node.node {
             DefaultNodeContent::Config(cd) => {
-                if !cd.is_self() && !cd.deleted {
-                    let rd = ResultDefault {
-                        config_path: Some(cd.get_config_path()),
-                        parent: parent_path.map(|s| s.to_string()),
-                        package: cd
-                            .base
-                            .package
-                            .clone()
-                            .or_else(|| Some(cd.get_default_package())),
-                        is_self: false,
-                        primary: cd.base.primary,
-                        override_key: None,
-                    };
-                    result.push(rd);
-                } else if cd.is_self() {
-                    let rd = ResultDefault {
-                        config_path: parent_path.map(|s| s.to_string()),
-                        parent: None,
-                        package: None,
-                        is_self: true,
-                        primary: false,
-                        override_key: None,
-                    };
-                    result.push(rd);
+                if cd.is_self() || cd.deleted {
+                    return None;
                 }
+                Some(ResultDefault {
+                    config_path: Some(cd.get_config_path()),
+                    parent: parent_path.map(|s| s.to_string()),
+                    package: cd
+                        .base
+                        .package
+                        .clone()
+                        .or_else(|| Some(cd.get_default_package())),
+                    is_self: false,
+                    primary: cd.base.primary,
+                    override_key: None,
+                })
             }
             DefaultNodeContent::Group(gd) => {
-                if !gd.deleted {
-                    if let Some(value) = gd.value.as_single() {
-                        let config_path = format!("{}/{}", gd.group, value);
-                        let rd = ResultDefault {
-                            config_path: Some(config_path),
-                            parent: parent_path.map(|s| s.to_string()),
-                            package: gd
-                                .base
-                                .package
-                                .clone()
-                                .or_else(|| Some(gd.group.replace("/", "."))),
-                            is_self: false,
-                            primary: gd.base.primary,
-                            override_key: Some(gd.group.clone()),
-                        };
-                        result.push(rd);
-                    }
+                if gd.deleted {
+                    return None;
                 }
+                let value = gd.value.as_single()?;
+                Some(ResultDefault {
+                    config_path: Some(format!("{}/{}", gd.group, value)),
+                    parent: parent_path.map(|s| s.to_string()),
+                    package: gd
+                        .base
+                        .package
+                        .clone()
+                        .or_else(|| Some(gd.group.replace("/", "."))),
+                    is_self: false,
+                    primary: gd.base.primary,
+                    override_key: Some(gd.get_override_key()),
+                })
             }
-            DefaultNodeContent::VirtualRoot => {
-                // Virtual root doesn't produce a result
-            }
+            // Virtual root doesn't produce a result
+            DefaultNodeContent::VirtualRoot => None,
         }
-
-        Ok(())
     }
 }
 
@@ -767,4 +890,219 @@ mod tests {
         assert_eq!(ovrs.get_override("db"), Some("mysql"));
         assert!(ovrs.get_override("db.port").is_none());
     }
+
+    fn build_packaged(overrides: &[&str]) -> Result<DefaultsListResult, ConfigLoadError> {
+        use crate::config::parser::parse_yaml;
+
+        let configs: HashMap<&str, &str> = [
+            ("config", "defaults:\n  - db@backend: mysql\n  - _self_\n"),
+            ("db/mysql", "driver: mysql\n"),
+            ("db/postgres", "driver: postgres\n"),
+        ]
+        .into_iter()
+        .collect();
+
+        let load = |path: &str| match configs.get(path).map(|c| parse_yaml(c)) {
+            Some(Ok(ConfigValue::Dict(dict))) => Ok(dict),
+            _ => Err(ConfigLoadError::with_path("Config not found", path)),
+        };
+        let exists = |path: &str| configs.contains_key(path);
+        let group_exists = |path: &str| path == "db";
+        let overrides: Vec<String> = overrides.iter().map(|s| s.to_string()).collect();
+
+        DefaultsListBuilder::new(load, exists, group_exists, &overrides).build(Some("config"))
+    }
+
+    #[test]
+    fn test_override_matches_group_with_package() {
+        let result = build_packaged(&["db@backend=postgres"]).unwrap();
+        let db = result
+            .defaults
+            .iter()
+            .find(|d| d.override_key.as_deref() == Some("db@backend"))
+            .unwrap();
+
+        assert_eq!(db.config_path.as_deref(), Some("db/postgres"));
+        assert_eq!(db.package.as_deref(), Some("backend"));
+        assert_eq!(
+            result.known_choices.get("db@backend"),
+            Some(&Some("postgres".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_override_with_mismatched_package_errors() {
+        let err = build_packaged(&["db@frontend=postgres"]).unwrap_err();
+        assert!(err
+            .message
+            .starts_with("Could not override 'db@frontend'. Did you mean to override db@backend?"));
+
+        let err = build_packaged(&["cache@backend=redis"]).unwrap_err();
+        assert!(err
+            .message
+            .starts_with("Could not override 'cache@backend'. No match in the defaults list"));
+    }
+
+    #[test]
+    fn test_packageless_override_does_not_match_packaged_default() {
+        let err = build_packaged(&["db=postgres"]).unwrap_err();
+        assert!(err
+            .message
+            .starts_with("Could not override 'db'. Did you mean to override db@backend?"));
+        assert!(err
+            .message
+            .ends_with("To append to your default list use +db=postgres"));
+    }
+
+    fn build_with_experiment(overrides: &[&str]) -> Result<DefaultsListResult, ConfigLoadError> {
+        use crate::config::parser::parse_yaml;
+
+        let configs: HashMap<&str, &str> = [
+            ("config", "defaults:\n  - db: mysql\n"),
+            ("db/mysql", "driver: mysql\n"),
+            ("db/postgres", "driver: postgres\n"),
+            ("experiment/fast", "lr: 0.1\n"),
+        ]
+        .into_iter()
+        .collect();
+
+        let load = |path: &str| match configs.get(path).map(|c| parse_yaml(c)) {
+            Some(Ok(ConfigValue::Dict(dict))) => Ok(dict),
+            _ => Err(ConfigLoadError::with_path("Config not found", path)),
+        };
+        let exists = |path: &str| configs.contains_key(path);
+        let group_exists = |path: &str| path == "db" || path == "experiment";
+        let overrides: Vec<String> = overrides.iter().map(|s| s.to_string()).collect();
+
+        DefaultsListBuilder::new(load, exists, group_exists, &overrides).build(Some("config"))
+    }
+
+    fn config_paths(result: &DefaultsListResult) -> Vec<&str> {
+        result
+            .defaults
+            .iter()
+            .filter_map(|d| d.config_path.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn test_append_new_group() {
+        let result = build_with_experiment(&["+experiment=fast"]).unwrap();
+        assert_eq!(
+            config_paths(&result),
+            vec!["db/mysql", "config", "experiment/fast"]
+        );
+
+        let appended = result.tree.children.as_ref().unwrap().last().unwrap();
+        match &appended.node {
+            DefaultNodeContent::Group(gd) => assert!(gd.external_append),
+            other => panic!("expected group node, got {:?}", other),
+        }
+        assert_eq!(
+            result.known_choices.get("experiment"),
+            Some(&Some("fast".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_append_existing_group_errors() {
+        let err = build_with_experiment(&["+db=postgres"]).unwrap_err();
+        assert!(err.message.starts_with(
+            "Could not append 'db' to the defaults list: it is already selected (db=mysql)."
+        ));
+    }
+
+    #[test]
+    fn test_force_append() {
+        // Present in the defaults list: overridden in place
+        let result = build_with_experiment(&["++db=postgres"]).unwrap();
+        assert_eq!(config_paths(&result), vec!["db/postgres", "config"]);
+
+        // Not present: appended
+        let result = build_with_experiment(&["++experiment=fast"]).unwrap();
+        assert_eq!(
+            config_paths(&result),
+            vec!["db/mysql", "config", "experiment/fast"]
+        );
+    }
+
+    fn build_nested(
+        config: &str,
+        overrides: &[&str],
+    ) -> Result<DefaultsListResult, ConfigLoadError> {
+        use crate::config::parser::parse_yaml;
+
+        let configs: HashMap<&str, &str> = [
+            ("config", config),
+            ("db/mysql", "defaults:\n  - engine: innodb\n  - _self_\n"),
+            ("db/postgres", "driver: postgres\n"),
+            ("db/engine/innodb", "name: innodb\n"),
+            ("db/engine/myisam", "name: myisam\n"),
+            ("experiment/fast", "defaults:\n  - override /db: postgres\n"),
+            (
+                "experiment/bad",
+                "defaults:\n  - override /db: postgres\n  - db: mysql\n",
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let load = |path: &str| match configs.get(path).map(|c| parse_yaml(c)) {
+            Some(Ok(ConfigValue::Dict(dict))) => Ok(dict),
+            _ => Err(ConfigLoadError::with_path("Config not found", path)),
+        };
+        let exists = |path: &str| configs.contains_key(path);
+        let group_exists = |path: &str| ["db", "db/engine", "experiment"].contains(&path);
+        let overrides: Vec<String> = overrides.iter().map(|s| s.to_string()).collect();
+
+        DefaultsListBuilder::new(load, exists, group_exists, &overrides).build(Some("config"))
+    }
+
+    #[test]
+    fn test_override_nested_default() {
+        let config = "defaults:\n  - db: mysql\n  - _self_\n";
+        let result = build_nested(config, &[]).unwrap();
+        assert_eq!(
+            config_paths(&result),
+            vec!["db/engine/innodb", "db/mysql", "config"]
+        );
+
+        let result = build_nested(config, &["db/engine=myisam"]).unwrap();
+        assert_eq!(
+            config_paths(&result),
+            vec!["db/engine/myisam", "db/mysql", "config"]
+        );
+    }
+
+    #[test]
+    fn test_override_entry_in_defaults_list() {
+        // A later default overrides an earlier one, which keeps its position
+        let config = "defaults:\n  - db: mysql\n  - _self_\n  - experiment: fast\n";
+        let result = build_nested(config, &[]).unwrap();
+        assert_eq!(
+            config_paths(&result),
+            vec!["db/postgres", "config", "experiment/fast"]
+        );
+        assert_eq!(
+            result.known_choices.get("db"),
+            Some(&Some("postgres".to_string()))
+        );
+
+        // User overrides take precedence over override entries
+        let result = build_nested(config, &["db=mysql"]).unwrap();
+        assert_eq!(
+            config_paths(&result),
+            vec!["db/engine/innodb", "db/mysql", "config", "experiment/fast"]
+        );
+
+        let err = build_nested("defaults:\n  - experiment: bad\n", &[]).unwrap_err();
+        assert_eq!(
+            err.message,
+            "In 'experiment/bad': Override 'db' must be at the end of the defaults list"
+        );
+        let err = build_nested("defaults:\n  - experiment: fast\n", &[]).unwrap_err();
+        assert!(err
+            .message
+            .starts_with("In 'experiment/fast': Could not override 'db'."));
+    }
 }
@@ -6,6 +6,7 @@
 
 use std::collections::HashMap;
 use std::env;
+use std::path::Path;
 
 /// Result of environment variable resolution
 #[derive(Clone, Debug, PartialEq)]
@@ -279,6 +280,128 @@ pub fn get_many_env(keys: &[&str]) -> HashMap<String, Option<String>> {
         .collect()
 }
 
+/// Error reading or parsing a `.env` file
+#[derive(Debug, Clone, PartialEq)]
+pub struct DotenvError {
+    pub message: String,
+    /// 1-based line of a parse error; None if the file could not be read
+    pub line: Option<usize>,
+}
+
+impl DotenvError {
+    fn at_line(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            message: format!("line {}: {}", line, message.into()),
+            line: Some(line),
+        }
+    }
+}
+
+impl std::fmt::Display for DotenvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for DotenvError {}
+
+/// Parse the contents of a `.env` file into `(key, value)` pairs, in order
+///
+/// Each line is `KEY=VALUE`, optionally prefixed with `export`. Blank lines
+/// and lines starting with `#` are skipped. Double-quoted values support
+/// `\n`, `\t`, `\"` and `\\` escapes, single-quoted values are taken
+/// literally, and unquoted values end at a ` #` comment.
+pub fn parse_dotenv(content: &str) -> Result<Vec<(String, String)>, DotenvError> {
+    let mut vars = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").map_or(line, str::trim_start);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| DotenvError::at_line(line_no, "expected KEY=VALUE"))?;
+        let key = key.trim();
+        let valid_key = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_key {
+            return Err(DotenvError::at_line(
+                line_no,
+                format!("invalid variable name '{}'", key),
+            ));
+        }
+        let value = parse_dotenv_value(value.trim_start())
+            .map_err(|message| DotenvError::at_line(line_no, message))?;
+        vars.push((key.to_string(), value));
+    }
+    Ok(vars)
+}
+
+fn parse_dotenv_value(raw: &str) -> Result<String, String> {
+    let quote = match raw.chars().next() {
+        Some(q @ ('"' | '\'')) => q,
+        _ => {
+            // Unquoted: a `#` after whitespace starts a comment
+            let end = raw
+                .char_indices()
+                .find(|&(i, c)| c == '#' && raw[..i].ends_with([' ', '\t']))
+                .map_or(raw.len(), |(i, _)| i);
+            return Ok(raw[..end].trim_end().to_string());
+        }
+    };
+
+    let mut value = String::new();
+    let mut chars = raw[1..].char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c == quote {
+            let rest = raw[1 + i + 1..].trim_start();
+            if !rest.is_empty() && !rest.starts_with('#') {
+                return Err(format!("unexpected text after closing quote: '{}'", rest));
+            }
+            return Ok(value);
+        }
+        if c == '\\' && quote == '"' {
+            match chars.next().map(|(_, c)| c) {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some(escaped) => value.push(escaped),
+                None => break,
+            }
+        } else {
+            value.push(c);
+        }
+    }
+    Err(format!("unterminated {} quote", quote))
+}
+
+/// Read and parse a `.env` file, as [`parse_dotenv`]
+pub fn read_dotenv(path: impl AsRef<Path>) -> Result<Vec<(String, String)>, DotenvError> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path).map_err(|e| DotenvError {
+        message: format!("Cannot read {}: {}", path.display(), e),
+        line: None,
+    })?;
+    parse_dotenv(&content)
+}
+
+/// Load a `.env` file into the process environment
+///
+/// Variables that are already set keep their value unless `override_existing`
+/// is true, so the real environment takes precedence by default. Returns the
+/// number of variables set.
+pub fn load_dotenv(path: impl AsRef<Path>, override_existing: bool) -> Result<usize, DotenvError> {
+    let mut count = 0;
+    for (key, value) in read_dotenv(path)? {
+        if override_existing || env::var_os(&key).is_none() {
+            env::set_var(&key, value);
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -398,4 +521,65 @@ mod tests {
         assert!(is_env_set("PATH"));
         assert!(!is_env_set("DEFINITELY_NOT_SET_XYZ123"));
     }
+
+    #[test]
+    fn test_parse_dotenv() {
+        let content = r#"
+# Local settings
+export DB_HOST=localhost
+DB_PORT = 5432  # inline comment
+GREETING="hello \"world\"\nbye" # comment
+RAW='no $expansion \n here'
+URL=http://host/#anchor
+EMPTY=
+"#;
+        let vars = parse_dotenv(content).unwrap();
+        assert_eq!(
+            vars,
+            vec![
+                ("DB_HOST".to_string(), "localhost".to_string()),
+                ("DB_PORT".to_string(), "5432".to_string()),
+                ("GREETING".to_string(), "hello \"world\"\nbye".to_string()),
+                ("RAW".to_string(), r"no $expansion \n here".to_string()),
+                ("URL".to_string(), "http://host/#anchor".to_string()),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_dotenv_errors() {
+        let err = parse_dotenv("A=1\nnot a pair\n").unwrap_err();
+        assert_eq!(err.line, Some(2));
+        assert_eq!(err.message, "line 2: expected KEY=VALUE");
+
+        let err = parse_dotenv("1KEY=x").unwrap_err();
+        assert_eq!(err.message, "line 1: invalid variable name '1KEY'");
+
+        let err = parse_dotenv("KEY=\"open").unwrap_err();
+        assert_eq!(err.message, "line 1: unterminated \" quote");
+    }
+
+    #[test]
+    fn test_load_dotenv() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(".env");
+        std::fs::write(
+            &path,
+            "LERNA_DOTENV_NEW=from_file\nLERNA_DOTENV_SET='from file'\n",
+        )
+        .unwrap();
+        env::set_var("LERNA_DOTENV_SET", "from_env");
+
+        // The real environment wins by default
+        assert_eq!(load_dotenv(&path, false).unwrap(), 1);
+        assert_eq!(env::var("LERNA_DOTENV_NEW").unwrap(), "from_file");
+        assert_eq!(env::var("LERNA_DOTENV_SET").unwrap(), "from_env");
+
+        assert_eq!(load_dotenv(&path, true).unwrap(), 2);
+        assert_eq!(env::var("LERNA_DOTENV_SET").unwrap(), "from file");
+
+        let err = load_dotenv(dir.path().join("missing.env"), false).unwrap_err();
+        assert_eq!(err.line, None);
+    }
 }
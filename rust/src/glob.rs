@@ -8,6 +8,8 @@ pub struct Glob {
     pub include: Vec<String>,
     /// Patterns to exclude
     pub exclude: Vec<String>,
+    /// Match names regardless of case (e.g. `Model*` matches `model_resnet`)
+    pub case_insensitive: bool,
 }
 
 impl Glob {
@@ -25,19 +27,33 @@ impl Glob {
         self
     }
 
+    pub fn with_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
     /// Filter a list of names based on include and exclude patterns
     pub fn filter(&self, names: &[String]) -> Vec<String> {
         names
             .iter()
-            .filter(|name| {
-                self.matches_any(name, &self.include) && !self.matches_any(name, &self.exclude)
-            })
+            .filter(|name| self.matches(name))
             .cloned()
             .collect()
     }
 
+    /// Check if a name matches an include pattern and no exclude pattern
+    pub fn matches(&self, name: &str) -> bool {
+        self.matches_any(name, &self.include) && !self.matches_any(name, &self.exclude)
+    }
+
     /// Check if a name matches any of the given glob patterns
     fn matches_any(&self, name: &str, patterns: &[String]) -> bool {
+        if self.case_insensitive {
+            let name = name.to_lowercase();
+            return patterns
+                .iter()
+                .any(|pattern| glob_match(&pattern.to_lowercase(), &name));
+        }
         for pattern in patterns {
             if glob_match(pattern, name) {
                 return true;
@@ -47,14 +63,36 @@ impl Glob {
     }
 }
 
-/// Simple glob pattern matching (supports * and ?)
+/// Glob pattern matching over `/`-separated paths
+///
+/// `*` and `?` match within a single path segment, so `model/*` matches
+/// `model/tiny` but not `model/a/tiny`. A `**` segment matches any number of
+/// segments, including none: `model/**/tiny` matches both `model/tiny` and
+/// `model/a/b/tiny`.
 fn glob_match(pattern: &str, text: &str) -> bool {
-    let pattern_chars = pattern.chars().peekable();
-    let text_chars = text.chars().peekable();
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let text: Vec<&str> = text.split('/').collect();
+    segments_match(&pattern, &text)
+}
 
+fn segments_match(pattern: &[&str], text: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((&"**", rest)) => (0..=text.len()).any(|skip| segments_match(rest, &text[skip..])),
+        Some((segment, rest)) => match text.split_first() {
+            Some((name, text_rest)) => {
+                segment_match(segment, name) && segments_match(rest, text_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Match a single path segment (supports * and ?)
+fn segment_match(pattern: &str, text: &str) -> bool {
     glob_match_impl(
-        &mut pattern_chars.collect::<Vec<_>>(),
-        &text_chars.collect::<Vec<_>>(),
+        &pattern.chars().collect::<Vec<_>>(),
+        &text.chars().collect::<Vec<_>>(),
     )
 }
 
@@ -136,6 +174,25 @@ mod tests {
         assert!(glob_match("test_*", "test_"));
     }
 
+    #[test]
+    fn test_glob_match_recursive() {
+        assert!(glob_match("model/**/tiny", "model/a/b/tiny"));
+        assert!(glob_match("model/**/tiny", "model/a/tiny"));
+        assert!(glob_match("model/**/tiny", "model/tiny"));
+        assert!(!glob_match("model/**/tiny", "model/a/b/small"));
+        assert!(!glob_match("model/**/tiny", "other/a/tiny"));
+        assert!(glob_match("**/small", "small"));
+        assert!(glob_match("**/small", "model/x/small"));
+        assert!(glob_match("model/**", "model/a/b"));
+
+        // `*` stays within one segment while `**` crosses `/`
+        assert!(glob_match("model/*", "model/tiny"));
+        assert!(!glob_match("model/*", "model/a/tiny"));
+        assert!(!glob_match("*", "model/tiny"));
+        assert!(glob_match("**", "model/tiny"));
+        assert!(!glob_match("model/?", "model/a/b"));
+    }
+
     #[test]
     fn test_glob_filter() {
         let glob = Glob::new()
@@ -172,4 +229,35 @@ mod tests {
         let filtered = glob.filter(&names);
         assert_eq!(filtered, names);
     }
+
+    #[test]
+    fn test_glob_matches() {
+        let glob = Glob::new()
+            .with_include(vec!["*.password".to_string()])
+            .with_exclude(vec!["test.*".to_string()]);
+        assert!(glob.matches("db.password"));
+        assert!(glob.matches("a.b.password"));
+        assert!(!glob.matches("password"));
+        assert!(!glob.matches("test.password"));
+    }
+
+    #[test]
+    fn test_glob_case_insensitive() {
+        let names = vec![
+            "model_resnet".to_string(),
+            "Model_VIT".to_string(),
+            "MODEL1".to_string(),
+            "optimizer".to_string(),
+        ];
+        let glob = Glob::new().with_include(vec!["Model*".to_string(), "model?".to_string()]);
+        assert_eq!(glob.filter(&names), vec!["Model_VIT".to_string()]);
+
+        let glob = glob
+            .with_exclude(vec!["*_vit".to_string()])
+            .with_case_insensitive(true);
+        assert_eq!(
+            glob.filter(&names),
+            vec!["model_resnet".to_string(), "MODEL1".to_string()]
+        );
+    }
 }
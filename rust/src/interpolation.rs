@@ -159,18 +159,15 @@ impl ResolutionContext {
             return None;
         }
 
-        let mut current = ConfigValue::Dict(self.config.clone());
-
-        for part in parts {
+        let mut current = self.config.get(parts[0])?;
+        for part in &parts[1..] {
             match current {
-                ConfigValue::Dict(ref dict) => {
-                    current = dict.get(*part)?.clone();
-                }
+                ConfigValue::Dict(dict) => current = dict.get(part)?,
                 _ => return None,
             }
         }
 
-        Some(current)
+        Some(current.clone())
     }
 
     /// Get environment variable
@@ -236,7 +233,7 @@ pub fn resolve_string(s: &str, ctx: &ResolutionContext) -> Result<String, String
         let resolved = resolve_interpolation(&interp_type, ctx)?;
 
         let replacement = match resolved {
-            ConfigValue::String(s) => s,
+            ConfigValue::String(s) | ConfigValue::Interpolation(s) => s,
             ConfigValue::Int(i) => i.to_string(),
             ConfigValue::Float(f) => f.to_string(),
             ConfigValue::Bool(b) => b.to_string(),
@@ -252,7 +249,7 @@ pub fn resolve_string(s: &str, ctx: &ResolutionContext) -> Result<String, String
 /// Resolve all interpolations in a config value recursively
 pub fn resolve_value(value: ConfigValue, ctx: &ResolutionContext) -> Result<ConfigValue, String> {
     match value {
-        ConfigValue::String(s) => {
+        ConfigValue::String(s) | ConfigValue::Interpolation(s) => {
             // Check if entire string is a single interpolation
             let s_trimmed = s.trim();
             if s_trimmed.starts_with("${")
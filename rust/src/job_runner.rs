@@ -5,11 +5,46 @@
 //! - Output directory creation
 //! - Config file serialization (config.yaml, hydra.yaml, overrides.yaml)
 //! - Job state tracking
+//! - Code version capture (git commit and working tree status)
 
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use crate::config::value::{ConfigDict, ConfigValue};
+use crate::glob::Glob;
+use crate::parser::{OverrideParser, ParseError};
+use crate::sweep::override_to_string;
+
+/// Git state of the code a job was launched from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GitInfo {
+    /// Commit hash of HEAD
+    pub commit: String,
+    /// Whether the working tree has uncommitted changes
+    pub dirty: bool,
+}
+
+/// Capture the git commit and working tree status of `dir`
+///
+/// Returns `None` when `dir` is not inside a git repository or git is not
+/// available.
+pub fn capture_git_info(dir: &Path) -> Option<GitInfo> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let commit = git(&["rev-parse", "HEAD"])?;
+    let dirty = !git(&["status", "--porcelain"])?.is_empty();
+    Some(GitInfo { commit, dirty })
+}
 
 /// Job execution context
 #[derive(Clone, Debug)]
@@ -30,6 +65,10 @@ pub struct JobContext {
     pub chdir: bool,
     /// Overrides for this job
     pub overrides: Vec<String>,
+    /// Git state of the original working directory, if captured
+    pub git: Option<GitInfo>,
+    /// Tags of the sweep this job was expanded from
+    pub tags: Vec<String>,
 }
 
 impl JobContext {
@@ -43,6 +82,8 @@ impl JobContext {
             original_cwd: std::env::current_dir().unwrap_or_default(),
             chdir: false,
             overrides: Vec::new(),
+            git: None,
+            tags: Vec::new(),
         }
     }
 
@@ -68,6 +109,41 @@ impl JobContext {
         self.overrides = overrides;
         self
     }
+
+    /// Set the sweep tags (see [`crate::sweep::sweep_tags`])
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Capture the git state of the original working directory
+    pub fn with_git_capture(mut self, enabled: bool) -> Self {
+        self.git = if enabled {
+            capture_git_info(&self.original_cwd)
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Job metadata (name, id, num, sweep tags and git state) as a config
+    pub fn metadata(&self) -> ConfigDict {
+        let mut metadata = ConfigDict::new();
+        metadata.insert("name".to_string(), ConfigValue::from(self.name.as_str()));
+        metadata.insert("id".to_string(), ConfigValue::from(self.id.as_str()));
+        metadata.insert("num".to_string(), ConfigValue::Int(self.num as i64));
+        if !self.tags.is_empty() {
+            let tags = self.tags.iter().map(|t| ConfigValue::from(t.as_str()));
+            metadata.insert("tags".to_string(), ConfigValue::List(tags.collect()));
+        }
+        if let Some(git) = &self.git {
+            let mut git_dict = ConfigDict::new();
+            git_dict.insert("commit".to_string(), ConfigValue::from(git.commit.as_str()));
+            git_dict.insert("dirty".to_string(), ConfigValue::Bool(git.dirty));
+            metadata.insert("git".to_string(), ConfigValue::Dict(git_dict));
+        }
+        metadata
+    }
 }
 
 /// Job execution result status
@@ -76,6 +152,73 @@ pub enum JobStatus {
     Unknown = 0,
     Completed = 1,
     Failed = 2,
+    Running = 3,
+}
+
+impl JobStatus {
+    /// The status name as saved in job metadata, e.g. "COMPLETED"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unknown => "UNKNOWN",
+            Self::Completed => "COMPLETED",
+            Self::Failed => "FAILED",
+            Self::Running => "RUNNING",
+        }
+    }
+
+    /// Status of a finished job with the given exit code
+    pub fn from_status_code(code: i32) -> Self {
+        if code == 0 {
+            Self::Completed
+        } else {
+            Self::Failed
+        }
+    }
+}
+
+impl std::fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error for a job status name that is not recognized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseJobStatusError {
+    pub message: String,
+}
+
+impl ParseJobStatusError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseJobStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ParseJobStatusError {}
+
+impl std::str::FromStr for JobStatus {
+    type Err = ParseJobStatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "UNKNOWN" => Ok(Self::Unknown),
+            "COMPLETED" => Ok(Self::Completed),
+            "FAILED" => Ok(Self::Failed),
+            "RUNNING" => Ok(Self::Running),
+            _ => Err(ParseJobStatusError::new(format!(
+                "Unknown job status '{}', expected one of UNKNOWN, COMPLETED, FAILED, RUNNING",
+                s
+            ))),
+        }
+    }
 }
 
 /// Result of job execution
@@ -117,6 +260,36 @@ pub fn compute_output_dir(job_dir_key_value: &str, job_subdir_key_value: Option<
     output_dir
 }
 
+/// Placeholder replaced by the sweep's tags in output dir templates
+pub const SWEEP_TAG_PLACEHOLDER: &str = "${sweep.tag}";
+
+/// Replace each `${sweep.tag}` in an output dir template with the sweep's tags
+///
+/// Several tags become nested directories (`["a", "b"]` gives `a/b`), so a
+/// tag never has to share a path component with another.
+pub fn substitute_sweep_tags(template: &str, tags: &[String]) -> String {
+    template.replace(SWEEP_TAG_PLACEHOLDER, &tags.join("/"))
+}
+
+/// Compute the output directory for a job, grouping it by sweep tags
+///
+/// Each `${sweep.tag}` in the dir and subdir is replaced as in
+/// [`substitute_sweep_tags`], so `/out/${sweep.tag}` with tags `["grid"]`
+/// gives `/out/grid`. Without tags the placeholder becomes empty and the
+/// path component drops out.
+pub fn compute_output_dir_with_tags(
+    job_dir_key_value: &str,
+    job_subdir_key_value: Option<&str>,
+    tags: &[String],
+) -> PathBuf {
+    let dir = substitute_sweep_tags(job_dir_key_value, tags);
+    let subdir = job_subdir_key_value.map(|s| substitute_sweep_tags(s, tags));
+    // Rebuild from components so an empty tag leaves no `//` behind
+    compute_output_dir(&dir, subdir.as_deref())
+        .components()
+        .collect()
+}
+
 /// Create output directory structure
 pub fn create_output_dirs(output_dir: &Path, subdir: Option<&str>) -> std::io::Result<PathBuf> {
     let full_path = if let Some(sub) = subdir {
@@ -128,6 +301,63 @@ pub fn create_output_dirs(output_dir: &Path, subdir: Option<&str>) -> std::io::R
     Ok(full_path)
 }
 
+/// Whether a string must be quoted to load back as the same string
+///
+/// Plain scalars that YAML reads as another type (`3306`, `1e-5`, `0x1f`,
+/// `true`, `yes`, `null`, `~`, `.inf`), and ones holding YAML syntax or
+/// surrounding whitespace, need quotes.
+fn yaml_string_needs_quotes(s: &str) -> bool {
+    if s.is_empty() || s.trim() != s {
+        return true;
+    }
+    let lower = s.to_lowercase();
+    let non_string = matches!(
+        lower.as_str(),
+        "~" | "null"
+            | "true"
+            | "false"
+            | "yes"
+            | "no"
+            | "on"
+            | "off"
+            | "y"
+            | "n"
+            | ".inf"
+            | "+.inf"
+            | "-.inf"
+            | ".nan"
+    );
+    let numeric = lower.replace('_', "").parse::<f64>().is_ok()
+        || ["0x", "0o"].iter().any(|prefix| lower.starts_with(prefix));
+    let syntax = s.contains(':')
+        || s.contains('#')
+        || s.chars().any(char::is_control)
+        || s.starts_with(|c| "-?,[]{}&*!|>'\"%@`".contains(c));
+    non_string || numeric || syntax
+}
+
+/// Quote a string for YAML: single quotes, or double quotes with escapes
+/// when it holds control characters such as newlines
+fn quote_yaml_string(s: &str) -> String {
+    if !s.chars().any(char::is_control) {
+        return format!("'{}'", s.replace('\'', "''"));
+    }
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c if c.is_control() => quoted.push_str(&format!("\\x{:02x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
 /// Convert ConfigValue to YAML string
 fn config_value_to_yaml(value: &ConfigValue, indent: usize) -> String {
     let prefix = "  ".repeat(indent);
@@ -149,23 +379,16 @@ fn config_value_to_yaml(value: &ConfigValue, indent: usize) -> String {
             }
         }
         ConfigValue::String(s) => {
-            // Quote if needed
-            if s.contains(':')
-                || s.contains('#')
-                || s.contains('\n')
-                || s.starts_with(' ')
-                || s.ends_with(' ')
-            {
-                format!("'{}'", s.replace('\'', "''"))
+            if yaml_string_needs_quotes(s) {
+                quote_yaml_string(s)
             } else {
                 s.clone()
             }
         }
-        ConfigValue::Interpolation(s) => {
-            // Always quote interpolations
-            format!("'{}'", s)
-        }
+        // Always quote interpolations
+        ConfigValue::Interpolation(s) => quote_yaml_string(s),
         ConfigValue::Missing => "???".to_string(),
+        ConfigValue::Enum { type_name, value } => format!("!enum {} {}", type_name, value),
         ConfigValue::List(items) => {
             if items.is_empty() {
                 "[]".to_string()
@@ -220,6 +443,30 @@ pub fn serialize_config_to_yaml(config: &ConfigDict) -> String {
     config_dict_to_yaml(config, 0)
 }
 
+/// Placeholder written in place of redacted values
+pub const REDACTED_VALUE: &str = "***";
+
+/// Return a copy of a config with leaves whose dotted path matches `redact`
+/// replaced by `REDACTED_VALUE`
+pub fn redact_config(config: &ConfigDict, redact: &Glob) -> ConfigDict {
+    let redacted = ConfigValue::Dict(config.clone()).map_leaves(|path, leaf| {
+        if redact.matches(path) {
+            ConfigValue::from(REDACTED_VALUE)
+        } else {
+            leaf.clone()
+        }
+    });
+    match redacted {
+        ConfigValue::Dict(dict) => dict,
+        _ => unreachable!("map_leaves preserves the root dict"),
+    }
+}
+
+/// Serialize config to YAML string, redacting values whose path matches `redact`
+pub fn serialize_redacted_config_to_yaml(config: &ConfigDict, redact: &Glob) -> String {
+    serialize_config_to_yaml(&redact_config(config, redact))
+}
+
 /// Save a config to a YAML file
 pub fn save_config_file(
     config: &ConfigDict,
@@ -232,13 +479,64 @@ pub fn save_config_file(
     Ok(file_path)
 }
 
+/// Save a job's metadata (including its git state, if captured) to a YAML file
+pub fn save_job_metadata_file(
+    ctx: &JobContext,
+    filename: &str,
+    output_dir: &Path,
+) -> std::io::Result<PathBuf> {
+    save_config_file(&ctx.metadata(), filename, output_dir)
+}
+
+/// Parse each override and render it canonically
+///
+/// Overrides that fail to parse are kept verbatim. Returns the normalized
+/// overrides and each malformed override with its parse error.
+pub fn normalize_overrides(overrides: &[String]) -> (Vec<String>, Vec<(String, ParseError)>) {
+    let mut malformed = Vec::new();
+    let normalized = overrides
+        .iter()
+        .map(|o| match OverrideParser::parse(o) {
+            Ok(parsed) => override_to_string(&parsed),
+            Err(e) => {
+                malformed.push((o.clone(), e));
+                o.clone()
+            }
+        })
+        .collect();
+    (normalized, malformed)
+}
+
 /// Save overrides list to a YAML file
 pub fn save_overrides_file(
     overrides: &[String],
     filename: &str,
     output_dir: &Path,
+) -> std::io::Result<PathBuf> {
+    save_overrides_file_with(overrides, filename, output_dir, false, false)
+}
+
+/// Save overrides list to a YAML file, optionally normalized and sorted
+///
+/// With `normalize`, overrides are rewritten by [`normalize_overrides`],
+/// keeping malformed ones as-is; with `sort`, they are written in
+/// lexicographic order.
+pub fn save_overrides_file_with(
+    overrides: &[String],
+    filename: &str,
+    output_dir: &Path,
+    normalize: bool,
+    sort: bool,
 ) -> std::io::Result<PathBuf> {
     let file_path = output_dir.join(filename);
+    let mut overrides = if normalize {
+        normalize_overrides(overrides).0
+    } else {
+        overrides.to_vec()
+    };
+    if sort {
+        overrides.sort();
+    }
     let yaml = if overrides.is_empty() {
         "[]".to_string()
     } else {
@@ -326,6 +624,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_compute_output_dir_with_tags() {
+        use crate::parser::OverrideParser;
+        use crate::sweep::{expand_sweeps, sweep_tags};
+
+        let overrides = vec![OverrideParser::parse("db=tag(grid,choice(a,b))").unwrap()];
+        let tags = sweep_tags(&overrides);
+        assert_eq!(tags, vec!["grid".to_string()]);
+
+        let jobs = expand_sweeps(&overrides);
+        assert_eq!(jobs.len(), 2);
+        for (num, job) in jobs.into_iter().enumerate() {
+            let ctx = JobContext::new("app", "id", num)
+                .with_overrides(job)
+                .with_tags(tags.clone());
+            let dir = compute_output_dir_with_tags(
+                "/out/${sweep.tag}",
+                Some(&num.to_string()),
+                &ctx.tags,
+            );
+            let dir_str = dir.to_string_lossy().replace('\\', "/");
+            assert!(
+                dir_str.ends_with(&format!("/out/grid/{}", num)),
+                "Expected path under /out/grid, got: {}",
+                dir_str
+            );
+        }
+
+        let dir = compute_output_dir_with_tags("/out/${sweep.tag}", Some("0"), &[]);
+        assert!(dir.to_string_lossy().replace('\\', "/").ends_with("/out/0"));
+
+        let tags = vec!["a".to_string(), "b".to_string()];
+        let dir = compute_output_dir_with_tags("/out/${sweep.tag}", Some("0"), &tags);
+        assert!(dir
+            .to_string_lossy()
+            .replace('\\', "/")
+            .ends_with("/out/a/b/0"));
+    }
+
     #[test]
     fn test_serialize_config() {
         let mut config = ConfigDict::new();
@@ -339,10 +676,175 @@ mod tests {
         assert!(yaml.contains("debug: true"));
     }
 
+    #[test]
+    fn test_serialize_config_quotes_ambiguous_strings() {
+        let strings = [
+            "3306",
+            "1e-5",
+            "0x1f",
+            "true",
+            "No",
+            "~",
+            "null",
+            "",
+            " padded",
+            "- item",
+            "*alias",
+            "line\nbreak",
+            "it's",
+        ];
+        let mut config = ConfigDict::new();
+        config.insert("plain".to_string(), ConfigValue::from("localhost"));
+        for (idx, s) in strings.iter().enumerate() {
+            config.insert(format!("s{:02}", idx), ConfigValue::from(*s));
+        }
+
+        let yaml = serialize_config_to_yaml(&config);
+        assert!(yaml.contains("s00: '3306'"));
+        assert!(yaml.contains("s03: 'true'"));
+        assert!(yaml.contains("s05: '~'"));
+        assert!(yaml.contains("plain: localhost"));
+
+        let reloaded = match crate::config::parser::parse_yaml(&yaml).unwrap() {
+            ConfigValue::Dict(dict) => dict,
+            other => panic!("expected dict, got {:?}", other),
+        };
+        assert_eq!(reloaded, config);
+    }
+
+    #[test]
+    fn test_serialize_redacted_config() {
+        let config = match crate::config::parser::parse_yaml(
+            "db:\n  host: localhost\n  password: secret\napi:\n  token: abc123\n  url: https://example.com\n",
+        )
+        .unwrap()
+        {
+            ConfigValue::Dict(dict) => dict,
+            other => panic!("expected dict, got {:?}", other),
+        };
+        let redact =
+            Glob::new().with_include(vec!["*.password".to_string(), "*.token".to_string()]);
+
+        let yaml = serialize_redacted_config_to_yaml(&config, &redact);
+        assert!(yaml.contains("password: '***'"));
+        assert!(yaml.contains("token: '***'"));
+        assert!(!yaml.contains("secret"));
+        assert!(!yaml.contains("abc123"));
+        assert!(yaml.contains("host: localhost"));
+        assert!(yaml.contains("url: 'https://example.com'"));
+
+        // The in-memory config is left intact
+        assert_eq!(
+            config.select("db.password"),
+            Some(ConfigValue::from("secret"))
+        );
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_save_overrides_file_normalized() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let overrides: Vec<String> = ["b=2", "a=1", "db=mysql, postgres", "bad=[1"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let path = save_overrides_file(&overrides, "raw.yaml", temp_dir.path()).unwrap();
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            "- b=2\n- a=1\n- db=mysql, postgres\n- bad=[1"
+        );
+
+        let path =
+            save_overrides_file_with(&overrides[..2], "sorted.yaml", temp_dir.path(), false, true)
+                .unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "- a=1\n- b=2");
+
+        let path =
+            save_overrides_file_with(&overrides, "norm.yaml", temp_dir.path(), true, true).unwrap();
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            "- a=1\n- b=2\n- bad=[1\n- db=mysql,postgres"
+        );
+
+        let (normalized, malformed) = normalize_overrides(&overrides);
+        assert_eq!(normalized, ["b=2", "a=1", "db=mysql,postgres", "bad=[1"]);
+        assert_eq!(malformed.len(), 1);
+        assert_eq!(malformed[0].0, "bad=[1");
+    }
+
+    #[test]
+    fn test_capture_git_info() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = temp_dir.path().join("repo");
+        fs::create_dir(&repo).unwrap();
+        fs::write(repo.join("train.py"), "print('hi')\n").unwrap();
+        git(&repo, &["init", "-q"]);
+        git(&repo, &["add", "train.py"]);
+        git(&repo, &["commit", "-q", "-m", "init"]);
+
+        let mut ctx = JobContext::new("train", "0", 0);
+        ctx.original_cwd = repo.clone();
+        let ctx = ctx.with_git_capture(true);
+        let info = ctx.git.clone().unwrap();
+        assert_eq!(info.commit.len(), 40);
+        assert!(!info.dirty);
+
+        let path = save_job_metadata_file(&ctx, "job.yaml", temp_dir.path()).unwrap();
+        let yaml = fs::read_to_string(path).unwrap();
+        assert!(yaml.contains(&format!("commit: {}", info.commit)));
+        assert!(yaml.contains("dirty: false"));
+
+        fs::write(repo.join("train.py"), "print('changed')\n").unwrap();
+        assert!(capture_git_info(&repo).unwrap().dirty);
+
+        // Outside a repository nothing is captured
+        let outside = temp_dir.path().join("outside");
+        fs::create_dir(&outside).unwrap();
+        assert_eq!(capture_git_info(&outside), None);
+        let ctx = JobContext {
+            original_cwd: outside,
+            ..ctx
+        }
+        .with_git_capture(true);
+        assert_eq!(ctx.git, None);
+        assert!(ctx.metadata().get("git").is_none());
+    }
+
     #[test]
     fn test_job_status() {
         assert_eq!(JobStatus::Unknown as i32, 0);
         assert_eq!(JobStatus::Completed as i32, 1);
         assert_eq!(JobStatus::Failed as i32, 2);
+        assert_eq!(JobStatus::Running as i32, 3);
+    }
+
+    #[test]
+    fn test_job_status_round_trip() {
+        for status in [
+            JobStatus::Unknown,
+            JobStatus::Completed,
+            JobStatus::Failed,
+            JobStatus::Running,
+        ] {
+            assert_eq!(status.as_str().parse::<JobStatus>(), Ok(status));
+            assert_eq!(status.to_string(), status.as_str());
+        }
+        assert_eq!(JobStatus::from_status_code(0), JobStatus::Completed);
+        assert_eq!(JobStatus::from_status_code(2), JobStatus::Failed);
+
+        let err = "completed".parse::<JobStatus>().unwrap_err();
+        assert!(err.message.contains("Unknown job status 'completed'"));
     }
 }
@@ -4,11 +4,19 @@
 //! Launchers are responsible for executing jobs, either locally
 //! (BasicLauncher) or on remote systems (e.g., Submitit, RQ).
 
+use std::collections::HashMap;
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 use crate::callback::JobReturn;
-use crate::config::value::ConfigDict;
+use crate::config::value::{ConfigDict, ConfigValue};
+use crate::interpolation::{resolve_string, ResolutionContext};
+use crate::job::JobConfig;
+use crate::job_runner::{compute_output_dir_with_tags, substitute_sweep_tags};
+use crate::merge::apply_override;
 
 /// Error type for launcher operations
 #[derive(Debug, Clone)]
@@ -38,6 +46,68 @@ pub type JobOverrides = Vec<String>;
 /// Batch of job overrides (multiple jobs)
 pub type JobOverrideBatch = Vec<JobOverrides>;
 
+/// Output sink handed to a task while it runs
+///
+/// When capturing, text written here is buffered and attached to the
+/// job's `JobReturn`; otherwise it is forwarded to the process streams.
+#[derive(Debug, Default)]
+pub struct TaskOutput {
+    capture: bool,
+    stdout: String,
+    stderr: String,
+}
+
+impl TaskOutput {
+    pub fn new(capture: bool) -> Self {
+        Self {
+            capture,
+            ..Self::default()
+        }
+    }
+
+    /// Whether output is being captured rather than forwarded
+    pub fn is_capturing(&self) -> bool {
+        self.capture
+    }
+
+    /// Write text to the task's standard output
+    pub fn write_stdout(&mut self, text: &str) {
+        if self.capture {
+            self.stdout.push_str(text);
+        } else {
+            print!("{}", text);
+        }
+    }
+
+    /// Write text to the task's standard error
+    pub fn write_stderr(&mut self, text: &str) {
+        if self.capture {
+            self.stderr.push_str(text);
+        } else {
+            eprint!("{}", text);
+        }
+    }
+
+    /// Consume the sink, returning the captured (stdout, stderr) if capturing
+    pub fn into_captured(self) -> (Option<String>, Option<String>) {
+        if self.capture {
+            (Some(self.stdout), Some(self.stderr))
+        } else {
+            (None, None)
+        }
+    }
+}
+
+/// Task run by a launcher for each job
+///
+/// Receives the job's overrides and an output sink, and returns the job's
+/// status code (0 = success).
+pub type JobTask = Arc<dyn Fn(&JobOverrides, &mut TaskOutput) -> i32 + Send + Sync>;
+
+/// Hook called as each job of a launch finishes, with the job's index and
+/// result. It may be called from worker threads, in completion order.
+pub type JobCompleted<'a> = &'a (dyn Fn(usize, &JobReturn) + Sync);
+
 /// Launcher trait - implement this to create custom launchers
 pub trait Launcher: Send + Sync + Debug {
     /// Setup the launcher with context
@@ -61,21 +131,272 @@ pub trait Launcher: Send + Sync + Debug {
         initial_job_idx: usize,
     ) -> Result<Vec<JobReturn>, LauncherError>;
 
+    /// Launch a batch of jobs, calling `on_complete` as each job finishes
+    ///
+    /// The default runs [`Launcher::launch`] and then reports every result in
+    /// order; launchers that see jobs finish one by one should override it.
+    fn launch_with(
+        &self,
+        job_overrides: &JobOverrideBatch,
+        initial_job_idx: usize,
+        on_complete: JobCompleted<'_>,
+    ) -> Result<Vec<JobReturn>, LauncherError> {
+        let results = self.launch(job_overrides, initial_job_idx)?;
+        for (offset, result) in results.iter().enumerate() {
+            on_complete(initial_job_idx + offset, result);
+        }
+        Ok(results)
+    }
+
+    /// Launch a batch of jobs expanded from sweeps with the given tags (see
+    /// [`crate::sweep::sweep_tags`]), calling `on_complete` as each job finishes
+    ///
+    /// The default ignores the tags and runs [`Launcher::launch_with`];
+    /// launchers that honour `${sweep.tag}` in output dirs should override it.
+    fn launch_tagged(
+        &self,
+        job_overrides: &JobOverrideBatch,
+        initial_job_idx: usize,
+        _tags: &[String],
+        on_complete: JobCompleted<'_>,
+    ) -> Result<Vec<JobReturn>, LauncherError> {
+        self.launch_with(job_overrides, initial_job_idx, on_complete)
+    }
+
     /// Get the launcher name/type
     fn name(&self) -> &str;
 }
 
-/// BasicLauncher - runs jobs locally and sequentially
-#[derive(Debug, Default)]
+/// BasicLauncher - runs jobs locally, sequentially or on a bounded thread pool
+#[derive(Default)]
 pub struct BasicLauncher {
     config: Option<ConfigDict>,
     task_name: String,
+    task: Option<JobTask>,
+    capture_output: bool,
+    /// Maximum number of jobs run at once (0 or 1 = serial)
+    max_parallel: usize,
+    /// Launch even if several jobs resolve to the same output directory
+    allow_collisions: bool,
+}
+
+impl Debug for BasicLauncher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BasicLauncher")
+            .field("config", &self.config)
+            .field("task_name", &self.task_name)
+            .field("has_task", &self.task.is_some())
+            .field("capture_output", &self.capture_output)
+            .field("max_parallel", &self.max_parallel)
+            .field("allow_collisions", &self.allow_collisions)
+            .finish()
+    }
 }
 
 impl BasicLauncher {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Set the task to run for each job
+    pub fn with_task(mut self, task: JobTask) -> Self {
+        self.task = Some(task);
+        self
+    }
+
+    /// Capture task stdout/stderr into each `JobReturn`
+    pub fn with_capture_output(mut self, capture: bool) -> Self {
+        self.capture_output = capture;
+        self
+    }
+
+    /// Run up to `max_parallel` jobs at once on worker threads (1 = serial)
+    pub fn with_max_parallel(mut self, max_parallel: usize) -> Self {
+        self.max_parallel = max_parallel;
+        self
+    }
+
+    /// Launch batches whose jobs share an output directory instead of failing
+    pub fn with_allow_collisions(mut self, allow: bool) -> Self {
+        self.allow_collisions = allow;
+        self
+    }
+
+    /// Set the task to run for each job
+    pub fn set_task(&mut self, task: JobTask) {
+        self.task = Some(task);
+    }
+
+    /// Whether task output is captured
+    pub fn capture_output(&self) -> bool {
+        self.capture_output
+    }
+
+    /// Maximum number of jobs run at once
+    pub fn max_parallel(&self) -> usize {
+        self.max_parallel.max(1)
+    }
+
+    /// Whether jobs may share an output directory
+    pub fn allow_collisions(&self) -> bool {
+        self.allow_collisions
+    }
+
+    /// Output directory of a job, from the `hydra.sweep.dir` and
+    /// `hydra.sweep.subdir` templates with the job's `hydra.job.num`,
+    /// `hydra.job.override_dirname` and `${sweep.tag}` filled in
+    ///
+    /// Returns `None` without a sweep dir or when a template cannot be
+    /// resolved here (e.g. it uses a custom resolver).
+    fn job_output_dir(
+        &self,
+        overrides: &JobOverrides,
+        job_idx: usize,
+        tags: &[String],
+    ) -> Option<PathBuf> {
+        let mut config = self.config.clone()?;
+        let dir = config.select("hydra.sweep.dir")?;
+        let subdir = config.select("hydra.sweep.subdir");
+
+        let override_dirname = JobConfig::new(&self.task_name, job_idx, overrides.clone())
+            .get_override_dirname("=", ",", &[]);
+        apply_override(
+            &mut config,
+            "hydra.job.num",
+            ConfigValue::Int(job_idx as i64),
+        )
+        .ok()?;
+        apply_override(
+            &mut config,
+            "hydra.job.override_dirname",
+            ConfigValue::String(override_dirname),
+        )
+        .ok()?;
+
+        let ctx = ResolutionContext::new(config);
+        let resolve = |value: &ConfigValue| match value {
+            ConfigValue::String(s) | ConfigValue::Interpolation(s) => {
+                resolve_string(&substitute_sweep_tags(s, tags), &ctx).ok()
+            }
+            ConfigValue::Int(i) => Some(i.to_string()),
+            _ => None,
+        };
+        let dir = resolve(&dir)?;
+        let subdir = match &subdir {
+            Some(value) => Some(resolve(value)?),
+            None => None,
+        };
+        Some(compute_output_dir_with_tags(&dir, subdir.as_deref(), tags))
+    }
+
+    /// Fail if two jobs in the batch resolve to the same output directory
+    fn check_output_dir_collisions(
+        &self,
+        job_overrides: &JobOverrideBatch,
+        initial_job_idx: usize,
+        tags: &[String],
+    ) -> Result<(), LauncherError> {
+        let mut jobs_by_dir: HashMap<PathBuf, Vec<usize>> = HashMap::new();
+        for (offset, overrides) in job_overrides.iter().enumerate() {
+            let job_idx = initial_job_idx + offset;
+            if let Some(dir) = self.job_output_dir(overrides, job_idx, tags) {
+                jobs_by_dir.entry(dir).or_default().push(job_idx);
+            }
+        }
+
+        let mut shared: Vec<(PathBuf, Vec<usize>)> = jobs_by_dir
+            .into_iter()
+            .filter(|(_, jobs)| jobs.len() > 1)
+            .collect();
+        // Report collisions in job order rather than hash order
+        shared.sort_by_key(|(_, jobs)| jobs[0]);
+        let collisions: Vec<String> = shared
+            .iter()
+            .map(|(dir, jobs)| {
+                let jobs: Vec<String> = jobs.iter().map(|j| format!("#{}", j)).collect();
+                format!("{} ({})", dir.display(), jobs.join(", "))
+            })
+            .collect();
+        if collisions.is_empty() {
+            return Ok(());
+        }
+        Err(LauncherError::new(format!(
+            "Jobs would overwrite each other's output directory: {}",
+            collisions.join("; ")
+        )))
+    }
+
+    /// Run a single job and build its `JobReturn`
+    ///
+    /// The job's output dir is its resolved sweep dir, or `outputs/<idx>`
+    /// without one.
+    fn run_job(&self, overrides: &JobOverrides, job_idx: usize, tags: &[String]) -> JobReturn {
+        // Run the task, if any, routing its output through the sink
+        let (status_code, stdout, stderr) = match &self.task {
+            Some(task) => {
+                let mut output = TaskOutput::new(self.capture_output);
+                let status_code = task(overrides, &mut output);
+                let (stdout, stderr) = output.into_captured();
+                (status_code, stdout, stderr)
+            }
+            None => (0, None, None),
+        };
+
+        JobReturn {
+            return_value: None,
+            working_dir: std::env::current_dir()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            output_dir: self
+                .job_output_dir(overrides, job_idx, tags)
+                .map(|dir| dir.to_string_lossy().to_string())
+                .unwrap_or_else(|| format!("outputs/{}", job_idx)),
+            job_name: format!("job_{}", job_idx),
+            task_name: self.task_name.clone(),
+            status_code,
+            stdout,
+            stderr,
+        }
+    }
+
+    /// Run jobs on up to `max_parallel` scoped worker threads
+    ///
+    /// Workers pull the next job offset from a shared counter, so each job
+    /// keeps its `initial_job_idx + offset` index and results stay in input order.
+    fn launch_parallel(
+        &self,
+        job_overrides: &JobOverrideBatch,
+        initial_job_idx: usize,
+        tags: &[String],
+        on_complete: JobCompleted<'_>,
+    ) -> Vec<JobReturn> {
+        let next = AtomicUsize::new(0);
+        let slots: Mutex<Vec<Option<JobReturn>>> = Mutex::new(vec![None; job_overrides.len()]);
+        let workers = self.max_parallel().min(job_overrides.len());
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let offset = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(overrides) = job_overrides.get(offset) else {
+                        break;
+                    };
+                    let job_idx = initial_job_idx + offset;
+                    let job_return = self.run_job(overrides, job_idx, tags);
+                    on_complete(job_idx, &job_return);
+                    slots.lock().unwrap()[offset] = Some(job_return);
+                });
+            }
+        });
+
+        slots
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|slot| slot.expect("every job is run by a worker"))
+            .collect()
+    }
 }
 
 impl Launcher for BasicLauncher {
@@ -90,27 +411,44 @@ impl Launcher for BasicLauncher {
         job_overrides: &JobOverrideBatch,
         initial_job_idx: usize,
     ) -> Result<Vec<JobReturn>, LauncherError> {
-        let mut results = Vec::with_capacity(job_overrides.len());
-
-        for (idx, _overrides) in job_overrides.iter().enumerate() {
-            let job_idx = initial_job_idx + idx;
-
-            // Create a basic JobReturn for this job
-            // In practice, this would actually run the task
-            let job_return = JobReturn {
-                return_value: None,
-                working_dir: std::env::current_dir()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string(),
-                output_dir: format!("outputs/{}", job_idx),
-                job_name: format!("job_{}", job_idx),
-                task_name: self.task_name.clone(),
-                status_code: 0,
-            };
-            results.push(job_return);
+        self.launch_with(job_overrides, initial_job_idx, &|_, _| {})
+    }
+
+    fn launch_with(
+        &self,
+        job_overrides: &JobOverrideBatch,
+        initial_job_idx: usize,
+        on_complete: JobCompleted<'_>,
+    ) -> Result<Vec<JobReturn>, LauncherError> {
+        self.launch_tagged(job_overrides, initial_job_idx, &[], on_complete)
+    }
+
+    fn launch_tagged(
+        &self,
+        job_overrides: &JobOverrideBatch,
+        initial_job_idx: usize,
+        tags: &[String],
+        on_complete: JobCompleted<'_>,
+    ) -> Result<Vec<JobReturn>, LauncherError> {
+        if !self.allow_collisions {
+            self.check_output_dir_collisions(job_overrides, initial_job_idx, tags)?;
         }
 
+        if self.max_parallel() > 1 && job_overrides.len() > 1 {
+            return Ok(self.launch_parallel(job_overrides, initial_job_idx, tags, on_complete));
+        }
+
+        let results = job_overrides
+            .iter()
+            .enumerate()
+            .map(|(idx, overrides)| {
+                let job_idx = initial_job_idx + idx;
+                let job_return = self.run_job(overrides, job_idx, tags);
+                on_complete(job_idx, &job_return);
+                job_return
+            })
+            .collect();
+
         Ok(results)
     }
 
@@ -119,10 +457,15 @@ impl Launcher for BasicLauncher {
     }
 }
 
-/// Launcher manager - holds and manages launcher instances
+/// Launcher manager - holds the launcher that runs a batch of jobs
+///
+/// Launchers are registered under the name a `hydra/launcher=<name>` choice
+/// uses, and [`activate`](Self::activate) picks the one to launch with.
+/// [`launch`](Self::launch) fails until a launcher is active.
 #[derive(Default)]
 pub struct LauncherManager {
     launcher: Option<Arc<dyn Launcher>>,
+    registry: HashMap<String, Arc<dyn Launcher>>,
 }
 
 impl LauncherManager {
@@ -140,6 +483,33 @@ impl LauncherManager {
         self.launcher = Some(Arc::new(BasicLauncher::new()));
     }
 
+    /// Register a launcher under a name, replacing any previous registration
+    pub fn register(&mut self, name: &str, launcher: Arc<dyn Launcher>) {
+        self.registry.insert(name.to_string(), launcher);
+    }
+
+    /// Make the launcher registered under `name` the active one
+    pub fn activate(&mut self, name: &str) -> Result<(), LauncherError> {
+        match self.registry.get(name) {
+            Some(launcher) => {
+                self.launcher = Some(Arc::clone(launcher));
+                Ok(())
+            }
+            None => Err(LauncherError::new(format!(
+                "Unknown launcher '{}'. Available launchers: [{}]",
+                name,
+                self.available_names().join(", ")
+            ))),
+        }
+    }
+
+    /// Names of all registered launchers, sorted
+    pub fn available_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.registry.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
     /// Get current launcher
     pub fn launcher(&self) -> Option<&Arc<dyn Launcher>> {
         self.launcher.as_ref()
@@ -161,6 +531,7 @@ impl LauncherManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Condvar;
 
     #[test]
     fn test_basic_launcher_setup() {
@@ -187,6 +558,107 @@ mod tests {
         assert_eq!(results[1].job_name, "job_1");
     }
 
+    #[test]
+    fn test_basic_launcher_captures_task_output() {
+        let task: JobTask = Arc::new(|overrides, output| {
+            output.write_stdout(&format!("running with {}\n", overrides.join(" ")));
+            output.write_stderr("warning\n");
+            3
+        });
+        let mut launcher = BasicLauncher::new()
+            .with_task(task)
+            .with_capture_output(true);
+        launcher.setup(&ConfigDict::new(), "test_task").unwrap();
+
+        let overrides = vec![vec!["db=mysql".to_string()]];
+        let results = launcher.launch(&overrides, 0).unwrap();
+        assert_eq!(results[0].status_code, 3);
+        assert_eq!(
+            results[0].stdout.as_deref(),
+            Some("running with db=mysql\n")
+        );
+        assert_eq!(results[0].stderr.as_deref(), Some("warning\n"));
+    }
+
+    #[test]
+    fn test_basic_launcher_without_capture() {
+        let task: JobTask = Arc::new(|_, output| {
+            assert!(!output.is_capturing());
+            0
+        });
+        let launcher = BasicLauncher::new().with_task(task);
+
+        let overrides = vec![vec!["db=mysql".to_string()]];
+        let results = launcher.launch(&overrides, 0).unwrap();
+        assert_eq!(results[0].status_code, 0);
+        assert!(results[0].stdout.is_none());
+        assert!(results[0].stderr.is_none());
+    }
+
+    /// A config whose sweep output dir is `/sweep/<subdir>`
+    fn sweep_config(subdir: &str) -> ConfigDict {
+        let mut config = ConfigDict::new();
+        apply_override(
+            &mut config,
+            "hydra.sweep.dir",
+            ConfigValue::String("/sweep".to_string()),
+        )
+        .unwrap();
+        apply_override(
+            &mut config,
+            "hydra.sweep.subdir",
+            ConfigValue::String(subdir.to_string()),
+        )
+        .unwrap();
+        config
+    }
+
+    #[test]
+    fn test_basic_launcher_rejects_output_dir_collisions() {
+        let overrides = vec![
+            vec!["lr=0.1".to_string()],
+            vec!["lr=0.01".to_string()],
+            vec!["lr=0.001".to_string()],
+        ];
+
+        // The subdir ignores the swept parameter, so every job shares it
+        let mut launcher = BasicLauncher::new();
+        launcher.setup(&sweep_config("fixed"), "train").unwrap();
+        let err = launcher.launch(&overrides, 0).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Jobs would overwrite each other's output directory: /sweep/fixed (#0, #1, #2)"
+        );
+
+        let mut launcher = BasicLauncher::new().with_allow_collisions(true);
+        launcher.setup(&sweep_config("fixed"), "train").unwrap();
+        assert_eq!(launcher.launch(&overrides, 0).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_basic_launcher_job_num_avoids_collisions() {
+        let overrides = vec![vec!["lr=0.1".to_string()], vec!["lr=0.01".to_string()]];
+
+        let mut launcher = BasicLauncher::new();
+        launcher
+            .setup(&sweep_config("${hydra.job.num}"), "train")
+            .unwrap();
+        assert_eq!(launcher.launch(&overrides, 0).unwrap().len(), 2);
+        assert_eq!(
+            launcher.job_output_dir(&overrides[1], 5, &[]),
+            Some(PathBuf::from("/sweep/5"))
+        );
+
+        let mut launcher = BasicLauncher::new();
+        launcher
+            .setup(&sweep_config("${hydra.job.override_dirname}"), "train")
+            .unwrap();
+        assert_eq!(
+            launcher.job_output_dir(&overrides[0], 0, &[]),
+            Some(PathBuf::from("/sweep/lr=0.1"))
+        );
+    }
+
     #[test]
     fn test_launcher_manager() {
         let mut manager = LauncherManager::new();
@@ -203,4 +675,122 @@ mod tests {
         let overrides = vec![vec!["key=value".to_string()]];
         assert!(manager.launch(&overrides, 0).is_err());
     }
+
+    #[test]
+    fn test_launcher_manager_registry() {
+        let status_task = |code: i32| -> JobTask { Arc::new(move |_, _| code) };
+        let mut manager = LauncherManager::new();
+        manager.register(
+            "local",
+            Arc::new(BasicLauncher::new().with_task(status_task(0))),
+        );
+        manager.register(
+            "failing",
+            Arc::new(BasicLauncher::new().with_task(status_task(7))),
+        );
+        assert_eq!(manager.available_names(), vec!["failing", "local"]);
+        assert!(manager.launcher().is_none());
+
+        let overrides = vec![vec!["key=value".to_string()]];
+        manager.activate("failing").unwrap();
+        assert_eq!(manager.launch(&overrides, 0).unwrap()[0].status_code, 7);
+        manager.activate("local").unwrap();
+        assert_eq!(manager.launch(&overrides, 0).unwrap()[0].status_code, 0);
+
+        let err = manager.activate("slurm").unwrap_err();
+        assert_eq!(
+            err.message,
+            "Unknown launcher 'slurm'. Available launchers: [failing, local]"
+        );
+    }
+
+    /// Jobs started and running at once, counted across a test launch
+    #[derive(Default)]
+    struct JobCounts {
+        counts: Mutex<(usize, usize)>,
+        changed: Condvar,
+        peak: AtomicUsize,
+    }
+
+    /// A task that counts its jobs in `counts` and, before finishing, waits
+    /// (up to a generous timeout) until `together` jobs have started
+    fn counting_task(counts: Arc<JobCounts>, together: usize) -> JobTask {
+        Arc::new(move |overrides, output| {
+            let mut guard = counts.counts.lock().unwrap();
+            guard.0 += 1;
+            guard.1 += 1;
+            counts.peak.fetch_max(guard.1, Ordering::SeqCst);
+            counts.changed.notify_all();
+            let (mut guard, _) = counts
+                .changed
+                .wait_timeout_while(guard, std::time::Duration::from_secs(10), |(started, _)| {
+                    *started < together
+                })
+                .unwrap();
+            guard.1 -= 1;
+            drop(guard);
+            output.write_stdout(&overrides.join(" "));
+            0
+        })
+    }
+
+    fn seed_jobs(n: usize) -> JobOverrideBatch {
+        (0..n).map(|i| vec![format!("seed={}", i)]).collect()
+    }
+
+    #[test]
+    fn test_basic_launcher_parallel_preserves_order() {
+        let counts = Arc::new(JobCounts::default());
+        let launcher = BasicLauncher::new()
+            .with_task(counting_task(counts.clone(), 4))
+            .with_capture_output(true)
+            .with_max_parallel(4);
+
+        let results = launcher.launch(&seed_jobs(4), 10).unwrap();
+
+        // All four jobs ran at once
+        assert_eq!(counts.peak.load(Ordering::SeqCst), 4);
+        for (i, result) in results.iter().enumerate() {
+            assert_eq!(result.job_name, format!("job_{}", 10 + i));
+            assert_eq!(
+                result.stdout.as_deref(),
+                Some(format!("seed={}", i).as_str())
+            );
+        }
+    }
+
+    #[test]
+    fn test_basic_launcher_reports_each_job_as_it_completes() {
+        let completed = Mutex::new(Vec::new());
+        let mut launcher = BasicLauncher::new()
+            .with_task(counting_task(Arc::new(JobCounts::default()), 2))
+            .with_max_parallel(2);
+        launcher.setup(&ConfigDict::new(), "test").unwrap();
+
+        let results = launcher
+            .launch_with(&seed_jobs(4), 10, &|idx, result| {
+                assert_eq!(result.job_name, format!("job_{}", idx));
+                completed.lock().unwrap().push(idx);
+            })
+            .unwrap();
+        assert_eq!(results.len(), 4);
+        let mut completed = completed.into_inner().unwrap();
+        completed.sort();
+        assert_eq!(completed, [10, 11, 12, 13]);
+    }
+
+    #[test]
+    fn test_basic_launcher_max_parallel_one_is_serial() {
+        let counts = Arc::new(JobCounts::default());
+        let launcher = BasicLauncher::new()
+            .with_task(counting_task(counts.clone(), 1))
+            .with_max_parallel(1);
+        assert_eq!(launcher.max_parallel(), 1);
+        assert_eq!(BasicLauncher::new().max_parallel(), 1);
+
+        let results = launcher.launch(&seed_jobs(3), 0).unwrap();
+        assert_eq!(counts.peak.load(Ordering::SeqCst), 1);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[2].job_name, "job_2");
+    }
 }
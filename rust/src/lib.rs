@@ -39,19 +39,30 @@ pub use core::override_types::*;
 pub use defaults::*;
 pub use glob::Glob;
 pub use job_runner::{
-    compute_output_dir as compute_job_output_dir, create_output_dirs, save_config_file,
-    save_overrides_file, serialize_config_to_yaml, setup_job_environment, JobContext,
-    JobResult as JobRunnerResult, JobStatus,
+    capture_git_info, compute_output_dir as compute_job_output_dir, compute_output_dir_with_tags,
+    create_output_dirs, normalize_overrides, redact_config, save_config_file,
+    save_job_metadata_file, save_overrides_file, save_overrides_file_with,
+    serialize_config_to_yaml, serialize_redacted_config_to_yaml, setup_job_environment, GitInfo,
+    JobContext, JobResult as JobRunnerResult, JobStatus, ParseJobStatusError, REDACTED_VALUE,
 };
 pub use launcher::{
-    BasicLauncher, JobOverrideBatch, JobOverrides, Launcher, LauncherError, LauncherManager,
+    BasicLauncher, JobOverrideBatch, JobOverrides, JobTask, Launcher, LauncherError,
+    LauncherManager, TaskOutput,
 };
-pub use parser::{FunctionCallback, OverrideParser};
-pub use sweep::{expand_simple_sweeps, expand_sweeps};
-pub use sweeper::{BasicSweeper, Sweeper, SweeperError, SweeperManager};
+pub use parser::{
+    is_malformed_number, parse_duration, FunctionCallback, OverrideParser, ParseError,
+    ParseErrorKind,
+};
+pub use sweep::{
+    expand_simple_sweeps, expand_sweeps, expand_sweeps_with_seed, override_to_string, sweep_tags,
+    sweep_to_override_string, SweepResolvers,
+};
+pub use sweeper::{BasicSweeper, SweepCombination, Sweeper, SweeperError, SweeperManager};
 pub use utils::{
-    escape_special_characters, get_valid_filename, is_special_char, is_valid_key, join_key,
-    normalize_file_name, sanitize_path_component, split_key, unescape_string,
+    escape_special_characters, get_valid_filename, get_valid_filename_opts, is_special_char,
+    is_valid_key, is_valid_user_key, join_key, normalize_file_name, normalize_output_file_name,
+    sanitize_path_component, split_key, unescape_string, validate_user_key, OUTPUT_FILE_EXTENSIONS,
+    RESERVED_KEYS,
 };
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -3,7 +3,12 @@
 //!
 //! Implements deep merging of configuration dictionaries, following OmegaConf merge semantics.
 
+use crate::config::enums::{self, EnumError};
 use crate::config::{ConfigDict, ConfigValue};
+use crate::core::override_types::{
+    parse_key_segments, KeySegment, ListExtension, ListOperationType, Override, OverrideType,
+    OverrideValue, ParsedElement,
+};
 use std::collections::HashSet;
 
 /// Merge mode for config values
@@ -23,21 +28,48 @@ pub enum MergeMode {
 /// - Dicts are merged recursively
 /// - Other types override
 /// - Special handling for ??? (MISSING) and None
-pub fn merge_values(base: ConfigValue, override_val: ConfigValue, mode: MergeMode) -> ConfigValue {
-    match (base, override_val.clone(), mode) {
+/// - Interpolations are ordinary values: the later value wins whether it is
+///   an interpolation or concrete, and neither is converted into the other
+/// - A string merged into an enum must name one of the enum's members
+pub fn merge_values(
+    base: ConfigValue,
+    override_val: ConfigValue,
+    mode: MergeMode,
+) -> Result<ConfigValue, EnumError> {
+    // Compared once here; the recursion below never re-compares subtrees.
+    // Annotated dicts still go through the merge, which concatenates their
+    // lists and strips the annotations.
+    if mode != MergeMode::Extend && !has_merge_annotation(&override_val) && base == override_val {
+        return Ok(base);
+    }
+    merge_values_unchecked(base, override_val, mode)
+}
+
+fn merge_values_unchecked(
+    base: ConfigValue,
+    override_val: ConfigValue,
+    mode: MergeMode,
+) -> Result<ConfigValue, EnumError> {
+    let merged = match (base, override_val, mode) {
         // MISSING in override means keep base
         (base, ConfigValue::Missing, _) => base,
 
         // Null in base, any override wins
         (ConfigValue::Null, override_val, _) => override_val,
 
+        // A string merged into an enum keeps the enum's type
+        (ConfigValue::Enum { type_name, .. }, ConfigValue::String(value), _) => {
+            enums::instance().make(&type_name, &value)?
+        }
+
         // Dict + Dict = merge recursively
         (
             ConfigValue::Dict(mut base_dict),
             ConfigValue::Dict(override_dict),
             MergeMode::Default,
         ) => {
-            merge_dicts(&mut base_dict, &override_dict);
+            merge_dicts_at(&mut base_dict, &override_dict, false, false)?;
+            strip_merge_annotations(&mut base_dict);
             ConfigValue::Dict(base_dict)
         }
 
@@ -54,32 +86,142 @@ pub fn merge_values(base: ConfigValue, override_val: ConfigValue, mode: MergeMod
 
         // Any other case: override wins
         (_, override_val, _) => override_val,
-    }
+    };
+    Ok(merged)
 }
 
+/// Key of a dict annotation choosing how lists in that dict are merged
+///
+/// `__merge__: concat` concatenates lists in the annotated dict and its
+/// subtree instead of replacing them; `__merge__: replace` restores the
+/// default for a nested subtree. The annotation may appear on either side of
+/// a merge and is removed from the merged result.
+pub const MERGE_ANNOTATION_KEY: &str = "__merge__";
+
 /// Deep merge two ConfigDicts
 ///
-/// The base dict is modified in place with values from override_dict.
-pub fn merge_dicts(base: &mut ConfigDict, override_dict: &ConfigDict) {
+/// The base dict is modified in place with values from override_dict. A
+/// `null` in override_dict sets the key to null; see [`merge_dicts_with`] to
+/// delete it instead. Lists are replaced unless a [`MERGE_ANNOTATION_KEY`]
+/// annotation says otherwise.
+pub fn merge_dicts(base: &mut ConfigDict, override_dict: &ConfigDict) -> Result<(), EnumError> {
+    merge_dicts_with(base, override_dict, false)
+}
+
+/// Deep merge two ConfigDicts, optionally treating `null` as a deletion
+///
+/// With `null_deletes` set, a `null` in override_dict (at any depth) removes
+/// the key from base rather than setting it to null.
+pub fn merge_dicts_with(
+    base: &mut ConfigDict,
+    override_dict: &ConfigDict,
+    null_deletes: bool,
+) -> Result<(), EnumError> {
+    // An identical dict cannot change anything unless it is annotated.
+    // Compared once here; the recursion never re-compares subtrees.
+    if !null_deletes && !dict_has_merge_annotation(override_dict) && base == override_dict {
+        return Ok(());
+    }
+    merge_dicts_at(base, override_dict, null_deletes, false)?;
+    strip_merge_annotations(base);
+    Ok(())
+}
+
+/// Whether an annotated dict concatenates lists, or None if unannotated
+fn concat_annotation(dict: &ConfigDict) -> Option<bool> {
+    match dict.get(MERGE_ANNOTATION_KEY)?.as_str()? {
+        "concat" => Some(true),
+        "replace" => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether a value is a dict with a [`MERGE_ANNOTATION_KEY`] at any depth
+fn has_merge_annotation(value: &ConfigValue) -> bool {
+    matches!(value, ConfigValue::Dict(dict) if dict_has_merge_annotation(dict))
+}
+
+fn dict_has_merge_annotation(dict: &ConfigDict) -> bool {
+    dict.contains_key(MERGE_ANNOTATION_KEY) || dict.values().any(has_merge_annotation)
+}
+
+/// Merge without removing annotations, so they still apply to later merges
+fn merge_dicts_at(
+    base: &mut ConfigDict,
+    override_dict: &ConfigDict,
+    null_deletes: bool,
+    concat_lists: bool,
+) -> Result<(), EnumError> {
+    let concat_lists = concat_annotation(override_dict)
+        .or_else(|| concat_annotation(base))
+        .unwrap_or(concat_lists);
+    if override_dict.is_empty() {
+        return Ok(());
+    }
     for (key, value) in override_dict.iter() {
-        if let Some(base_val) = base.get(key) {
-            let merged = merge_values(base_val.clone(), value.clone(), MergeMode::Default);
-            base.insert(key.to_string(), merged);
-        } else {
-            base.insert(key.to_string(), value.clone());
+        match (base.get_mut(key), value) {
+            (_, ConfigValue::Null) if null_deletes => {
+                base.remove(key);
+            }
+            (Some(ConfigValue::Dict(base_dict)), ConfigValue::Dict(nested)) => {
+                merge_dicts_at(base_dict, nested, null_deletes, concat_lists)?;
+            }
+            // A dict that adds or replaces a value drops its own nulls too
+            (_, ConfigValue::Dict(nested)) if null_deletes => {
+                let mut dict = ConfigDict::new();
+                merge_dicts_at(&mut dict, nested, true, concat_lists)?;
+                base.insert(key.to_string(), ConfigValue::Dict(dict));
+            }
+            (Some(ConfigValue::List(base_list)), ConfigValue::List(items)) if concat_lists => {
+                base_list.extend(items.iter().cloned());
+            }
+            (Some(ConfigValue::Enum { type_name, .. }), ConfigValue::String(member)) => {
+                let merged = enums::instance().make(type_name, member)?;
+                base.insert(key.to_string(), merged);
+            }
+            (Some(base_val), _) => {
+                let base = std::mem::take(base_val);
+                *base_val = merge_values_unchecked(base, value.clone(), MergeMode::Default)?;
+            }
+            (None, _) => base.insert(key.to_string(), value.clone()),
+        }
+    }
+    Ok(())
+}
+
+/// Deep merge two ConfigDicts, keeping [`MERGE_ANNOTATION_KEY`] annotations
+///
+/// For composing a config from several dicts: an annotation in one of them
+/// still applies to the dicts merged after it. Call
+/// [`strip_merge_annotations`] once the last dict is merged.
+pub fn merge_dicts_annotated(
+    base: &mut ConfigDict,
+    override_dict: &ConfigDict,
+) -> Result<(), EnumError> {
+    merge_dicts_at(base, override_dict, false, false)
+}
+
+/// Remove [`MERGE_ANNOTATION_KEY`] from a dict and its nested dicts
+pub fn strip_merge_annotations(dict: &mut ConfigDict) {
+    dict.remove(MERGE_ANNOTATION_KEY);
+    for value in dict.values_mut() {
+        if let ConfigValue::Dict(nested) = value {
+            strip_merge_annotations(nested);
         }
     }
 }
 
 /// Merge multiple config dicts in order
 ///
-/// Later configs override earlier ones.
-pub fn merge_configs(configs: &[ConfigDict]) -> ConfigDict {
+/// Later configs override earlier ones. A [`MERGE_ANNOTATION_KEY`]
+/// annotation in one config also applies when later configs are merged in.
+pub fn merge_configs(configs: &[ConfigDict]) -> Result<ConfigDict, EnumError> {
     let mut result = ConfigDict::new();
     for config in configs {
-        merge_dicts(&mut result, config);
+        merge_dicts_at(&mut result, config, false, false)?;
     }
-    result
+    strip_merge_annotations(&mut result);
+    Ok(result)
 }
 
 /// Check if a key should be deleted (starts with ~)
@@ -96,96 +238,483 @@ pub fn get_deletion_target(key: &str) -> &str {
     }
 }
 
+/// Error for an override that cannot be applied to a config
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverrideApplyError {
+    pub message: String,
+}
+
+impl OverrideApplyError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for OverrideApplyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for OverrideApplyError {}
+
 /// Apply deletions to a config
+///
+/// Keys may index into lists, e.g. `servers[0]` removes the first server
+/// and `servers[-1]` the last. Out-of-range indices are skipped.
 pub fn apply_deletions(config: &mut ConfigDict, deletions: &[String]) {
     for deletion in deletions {
         let key = get_deletion_target(deletion);
-        // Handle nested deletions with dot notation
-        if key.contains('.') {
-            let parts: Vec<&str> = key.split('.').collect();
-            delete_nested(config, &parts);
-        } else {
-            config.remove(key);
-        }
+        let _ = delete_nested(config, &parse_key_segments(key), key);
     }
 }
 
-fn delete_nested(config: &mut ConfigDict, parts: &[&str]) {
-    if parts.is_empty() {
-        return;
-    }
-
-    if parts.len() == 1 {
-        config.remove(parts[0]);
-        return;
+/// Delete the value at `segments`; a missing key is not an error, but an
+/// index past either end of an existing list is
+fn delete_nested(
+    config: &mut ConfigDict,
+    segments: &[KeySegment],
+    path: &str,
+) -> Result<(), OverrideApplyError> {
+    let Some((last, parents)) = segments.split_last() else {
+        return Ok(());
+    };
+    if parents.is_empty() {
+        config.remove(&last.to_string());
+        return Ok(());
     }
 
     // Navigate to parent and delete
-    if let Some(ConfigValue::Dict(ref mut nested)) = config.get_mut(parts[0]) {
-        delete_nested(nested, &parts[1..]);
+    match (get_nested_mut(config, parents), last) {
+        (Some(ConfigValue::List(list)), KeySegment::Index(index)) => {
+            match list_index(*index, list.len(), 0).filter(|&i| i < list.len()) {
+                Some(i) => {
+                    list.remove(i);
+                }
+                None => {
+                    return Err(OverrideApplyError::new(format!(
+                        "Cannot delete '{}': index {} is out of range for a list of {} items",
+                        path,
+                        index,
+                        list.len()
+                    )))
+                }
+            }
+        }
+        (Some(ConfigValue::Dict(nested)), _) => {
+            nested.remove(&last.to_string());
+        }
+        _ => {}
     }
+    Ok(())
 }
 
 /// Apply an override to a config at a specific path
-pub fn apply_override(config: &mut ConfigDict, path: &str, value: ConfigValue) {
+///
+/// The path may index into existing lists, e.g. `servers[0].host`. An empty
+/// path merges a dict value into the root; if that merge fails (a string
+/// that is not a member of an enum it replaces), the config is left as it was.
+pub fn apply_override(
+    config: &mut ConfigDict,
+    path: &str,
+    value: ConfigValue,
+) -> Result<(), EnumError> {
     if path.is_empty() {
         // Root-level merge
         if let ConfigValue::Dict(dict) = value {
-            merge_dicts(config, &dict);
+            let mut merged = config.clone();
+            merge_dicts(&mut merged, &dict)?;
+            *config = merged;
         }
-        return;
+        return Ok(());
     }
 
-    let parts: Vec<&str> = path.split('.').collect();
-    set_nested(config, &parts, value);
+    let _ = set_nested(config, &parse_key_segments(path), value, true, false);
+    Ok(())
 }
 
-fn set_nested(config: &mut ConfigDict, parts: &[&str], value: ConfigValue) {
-    if parts.is_empty() {
-        return;
+/// Set the value at `segments`, or say why the path cannot be written
+///
+/// A missing last key is always added. With `create_parents`, missing
+/// intermediate dicts are created too; without it, they are an error. With
+/// `extend`, an index past the end of a list grows it (padding with null)
+/// and a missing value before an index is created as a list; without it,
+/// such an index is an error.
+fn set_nested(
+    config: &mut ConfigDict,
+    segments: &[KeySegment],
+    value: ConfigValue,
+    create_parents: bool,
+    extend: bool,
+) -> Result<(), String> {
+    let Some((first, rest)) = segments.split_first() else {
+        return Ok(());
+    };
+    let mut current = dict_child_for_write(config, first, rest.first(), create_parents, extend)?;
+    for (i, segment) in rest.iter().enumerate() {
+        let next = rest.get(i + 1);
+        current = match (current, segment) {
+            (ConfigValue::Dict(dict), _) => {
+                dict_child_for_write(dict, segment, next, create_parents, extend)?
+            }
+            (ConfigValue::List(list), KeySegment::Index(index)) => {
+                list_child_for_write(list, *index, next, extend)?
+            }
+            (parent, _) => {
+                return Err(format!(
+                    "cannot set '{}' inside {}",
+                    segment,
+                    value_kind(parent)
+                ))
+            }
+        };
     }
+    *current = value;
+    Ok(())
+}
 
-    if parts.len() == 1 {
-        config.insert(parts[0].to_string(), value);
-        return;
+fn dict_child_for_write<'a>(
+    dict: &'a mut ConfigDict,
+    segment: &KeySegment,
+    next: Option<&KeySegment>,
+    create_parents: bool,
+    extend: bool,
+) -> Result<&'a mut ConfigValue, String> {
+    let key = segment.to_string();
+    if !dict.contains_key(&key) {
+        let missing = || format!("key '{}' does not exist (use '+' to add it)", key);
+        if next.is_some() && !create_parents {
+            return Err(missing());
+        }
+        dict.insert(
+            key.clone(),
+            new_container(next, extend).ok_or_else(missing)?,
+        );
     }
+    Ok(dict
+        .get_mut(&key)
+        .expect("key was just checked or inserted"))
+}
 
-    // Navigate or create intermediate dicts
-    let key = parts[0].to_string();
-    if !config.contains_key(&key) {
-        config.insert(key.clone(), ConfigValue::Dict(ConfigDict::new()));
+fn list_child_for_write<'a>(
+    list: &'a mut Vec<ConfigValue>,
+    index: i64,
+    next: Option<&KeySegment>,
+    extend: bool,
+) -> Result<&'a mut ConfigValue, String> {
+    let len = list.len();
+    let out_of_range = || {
+        format!(
+            "index {} is out of range for a list of {} items",
+            index, len
+        )
+    };
+    let index = list_index(index, len, 0).ok_or_else(out_of_range)?;
+    if index >= len {
+        if !extend {
+            return Err(out_of_range());
+        }
+        let value = new_container(next, extend).ok_or_else(out_of_range)?;
+        list.resize(index, ConfigValue::Null);
+        list.push(value);
     }
+    Ok(&mut list[index])
+}
 
-    if let Some(ConfigValue::Dict(ref mut nested)) = config.get_mut(&key) {
-        set_nested(nested, &parts[1..], value);
+/// Placeholder for a missing value, shaped for the segment that follows it
+fn new_container(next: Option<&KeySegment>, extend: bool) -> Option<ConfigValue> {
+    match next {
+        None => Some(ConfigValue::Null),
+        Some(KeySegment::Name(_)) => Some(ConfigValue::Dict(ConfigDict::new())),
+        Some(KeySegment::Index(_)) => extend.then(|| ConfigValue::List(Vec::new())),
     }
 }
 
-/// Get a value from a nested path
-pub fn get_nested(config: &ConfigDict, path: &str) -> Option<ConfigValue> {
-    if path.is_empty() {
-        return Some(ConfigValue::Dict(config.clone()));
+/// Convert a parsed override element to a config value
+///
+/// Strings containing `${` become interpolations, as they do when loading YAML.
+pub fn element_to_config_value(elem: &ParsedElement) -> ConfigValue {
+    match elem {
+        ParsedElement::Null => ConfigValue::Null,
+        ParsedElement::Bool(b) => ConfigValue::Bool(*b),
+        ParsedElement::Int(i) => ConfigValue::Int(*i),
+        ParsedElement::Float(f) => ConfigValue::Float(*f),
+        ParsedElement::String(_) | ParsedElement::QuotedString(_) => {
+            let s = elem.as_str().unwrap_or_default();
+            if s.contains("${") {
+                ConfigValue::Interpolation(s.to_string())
+            } else {
+                ConfigValue::String(s.to_string())
+            }
+        }
+        ParsedElement::List(items) => {
+            ConfigValue::List(items.iter().map(element_to_config_value).collect())
+        }
+        ParsedElement::Dict(entries) => {
+            let mut dict = ConfigDict::new();
+            for (key, value) in entries {
+                dict.insert(key.clone(), element_to_config_value(value));
+            }
+            ConfigValue::Dict(dict)
+        }
     }
+}
 
-    let parts: Vec<&str> = path.split('.').collect();
-    get_nested_parts(config, &parts)
+/// Apply parsed overrides to a copy of `config`, returning the new tree
+///
+/// `config` itself is left unchanged. Value overrides (`key=value`,
+/// `+key=value`, `++key=value`), deletions (`~key`, `~key=value`) and list
+/// operations (`key=append(...)` etc.) are applied in order; sweeps, and
+/// overrides that do not fit the config (e.g. a list operation on a
+/// non-list), are skipped.
+///
+/// `key+=value` adds to the current value: strings are concatenated,
+/// numbers added (an int and a float give a float) and lists extended by a
+/// list or a single item. A key that is missing, null or `???` is set as
+/// with `key=value`.
+///
+/// List indices in keys may be negative (`~servers[-1]` deletes the last
+/// server). A delete whose index is out of range is skipped here; use
+/// [`try_apply_overrides_cloned`] to have it reported.
+pub fn apply_overrides_cloned(config: &ConfigValue, overrides: &[Override]) -> ConfigValue {
+    let mut result = config.clone();
+    if let ConfigValue::Dict(dict) = &mut result {
+        for ovr in overrides {
+            let _ = apply_parsed_override(dict, ovr);
+        }
+    }
+    result
 }
 
-fn get_nested_parts(config: &ConfigDict, parts: &[&str]) -> Option<ConfigValue> {
-    if parts.is_empty() {
-        return None;
+/// Like [`apply_overrides_cloned`], but fail on an override that cannot be
+/// applied: a delete whose list index is out of range (`~servers[5]` on a
+/// 2-element list), a `+=` whose value cannot be added to the current one
+/// (`debug+=1` on a bool), or a change whose parent keys are missing
+/// (`db.pool.size=4` without `db.pool`; use `+db.pool.size=4` to add them)
+/// or whose list index is out of range (`tags[2]=c` on a 2-element list)
+pub fn try_apply_overrides_cloned(
+    config: &ConfigValue,
+    overrides: &[Override],
+) -> Result<ConfigValue, OverrideApplyError> {
+    let mut result = config.clone();
+    if let ConfigValue::Dict(dict) = &mut result {
+        for ovr in overrides {
+            apply_parsed_override(dict, ovr)?;
+        }
     }
+    Ok(result)
+}
 
-    let value = config.get(parts[0])?;
+fn apply_parsed_override(
+    config: &mut ConfigDict,
+    ovr: &Override,
+) -> Result<(), OverrideApplyError> {
+    let segments = ovr.key.segments();
+
+    match (&ovr.override_type, &ovr.value) {
+        (OverrideType::Del, value) => {
+            // A conditional delete only applies when the current value matches;
+            // a missing list element is still reported by delete_nested
+            let matches = match value {
+                Some(OverrideValue::Element(elem)) => get_nested_ref(config, &segments)
+                    .is_none_or(|current| current.semantic_eq(&element_to_config_value(elem))),
+                _ => true,
+            };
+            if matches {
+                delete_nested(config, &segments, &ovr.key.key_or_group)?;
+            }
+        }
+        (OverrideType::ExtendList, Some(OverrideValue::Element(elem))) => {
+            let value = element_to_config_value(elem);
+            let cannot = |reason| {
+                OverrideApplyError::new(format!(
+                    "Cannot apply '+=' to '{}': {}",
+                    ovr.key.key_or_group, reason
+                ))
+            };
+            match get_nested_mut(config, &segments) {
+                Some(current) if !matches!(current, ConfigValue::Null | ConfigValue::Missing) => {
+                    add_values(current, value).map_err(cannot)?;
+                }
+                _ => set_nested(config, &segments, value, false, false).map_err(cannot)?,
+            }
+        }
+        (override_type, Some(OverrideValue::Element(elem))) => {
+            // Only additions may create parents or grow a list:
+            // `+servers[2]=x` on a 2-element list
+            let add = matches!(override_type, OverrideType::Add | OverrideType::ForceAdd);
+            set_nested(config, &segments, element_to_config_value(elem), add, add).map_err(
+                |reason| {
+                    OverrideApplyError::new(format!(
+                        "Cannot set '{}': {}",
+                        ovr.key.key_or_group, reason
+                    ))
+                },
+            )?;
+        }
+        (_, Some(OverrideValue::ListExtension(ext))) => {
+            if let Some(list) = get_nested_mut(config, &segments) {
+                apply_list_extension(list, ext);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
 
-    if parts.len() == 1 {
-        return Some(value.clone());
+/// Apply `current += value` in place, or say why the two cannot be added
+fn add_values(current: &mut ConfigValue, value: ConfigValue) -> Result<(), String> {
+    match (&mut *current, value) {
+        (ConfigValue::Int(a), ConfigValue::Int(b)) => {
+            *a = a.checked_add(b).ok_or("integer overflow")?;
+        }
+        (ConfigValue::Int(a), ConfigValue::Float(b)) => {
+            let sum = *a as f64 + b;
+            *current = ConfigValue::Float(sum);
+        }
+        (ConfigValue::Float(a), ConfigValue::Int(b)) => *a += b as f64,
+        (ConfigValue::Float(a), ConfigValue::Float(b)) => *a += b,
+        (
+            ConfigValue::String(a) | ConfigValue::Interpolation(a),
+            b @ (ConfigValue::String(_)
+            | ConfigValue::Interpolation(_)
+            | ConfigValue::Int(_)
+            | ConfigValue::Float(_)
+            | ConfigValue::Bool(_)),
+        ) => {
+            let mut text = std::mem::take(a);
+            text.push_str(&b.to_string());
+            *current = if text.contains("${") {
+                ConfigValue::Interpolation(text)
+            } else {
+                ConfigValue::String(text)
+            };
+        }
+        (ConfigValue::List(items), ConfigValue::List(more)) => items.extend(more),
+        (ConfigValue::List(items), item) => items.push(item),
+        (current, value) => {
+            return Err(format!(
+                "cannot add {} to {}",
+                value_kind(&value),
+                value_kind(current)
+            ))
+        }
     }
+    Ok(())
+}
 
+/// Type name of a value for error messages
+fn value_kind(value: &ConfigValue) -> &'static str {
     match value {
-        ConfigValue::Dict(nested) => get_nested_parts(nested, &parts[1..]),
-        _ => None,
+        ConfigValue::Null => "null",
+        ConfigValue::Bool(_) => "a bool",
+        ConfigValue::Int(_) => "an int",
+        ConfigValue::Float(_) => "a float",
+        ConfigValue::String(_) | ConfigValue::Interpolation(_) => "a string",
+        ConfigValue::List(_) => "a list",
+        ConfigValue::Dict(_) => "a dict",
+        ConfigValue::Missing => "a missing value",
+        ConfigValue::Enum { .. } => "an enum",
+    }
+}
+
+fn get_nested_mut<'a>(
+    config: &'a mut ConfigDict,
+    segments: &[KeySegment],
+) -> Option<&'a mut ConfigValue> {
+    let (first, rest) = segments.split_first()?;
+    let mut current = config.get_mut(&first.to_string())?;
+    for segment in rest {
+        current = match (current, segment) {
+            (ConfigValue::List(list), KeySegment::Index(index)) => {
+                let index = list_index(*index, list.len(), 0)?;
+                list.get_mut(index)?
+            }
+            (ConfigValue::Dict(dict), _) => dict.get_mut(&segment.to_string())?,
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+fn get_nested_ref<'a>(config: &'a ConfigDict, segments: &[KeySegment]) -> Option<&'a ConfigValue> {
+    let (first, rest) = segments.split_first()?;
+    let mut current = config.get(&first.to_string())?;
+    for segment in rest {
+        current = match (current, segment) {
+            (ConfigValue::List(list), KeySegment::Index(index)) => {
+                list.get(list_index(*index, list.len(), 0)?)?
+            }
+            (ConfigValue::Dict(dict), _) => dict.get(&segment.to_string())?,
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+/// Resolve a possibly negative list index against a list of length `len`
+///
+/// Negative indices count from the end, with `-1` meaning `len - 1 + offset`.
+fn list_index(index: i64, len: usize, offset: usize) -> Option<usize> {
+    if index < 0 {
+        (len + offset).checked_sub(index.unsigned_abs() as usize)
+    } else {
+        Some(index as usize)
+    }
+}
+
+fn apply_list_extension(target: &mut ConfigValue, ext: &ListExtension) {
+    let values: Vec<ConfigValue> = ext.values.iter().map(element_to_config_value).collect();
+    // Every operation leaves a value that is not a list unchanged
+    match (ext.operation, target.as_list_mut()) {
+        (_, None) => {}
+        (ListOperationType::AppendUnique, Some(_)) => {
+            target.append_unique(values);
+        }
+        (ListOperationType::PrependUnique, Some(_)) => {
+            target.prepend_unique(values);
+        }
+        (ListOperationType::Append, Some(list)) => list.extend(values),
+        (ListOperationType::Prepend, Some(list)) => {
+            list.splice(0..0, values);
+        }
+        (ListOperationType::Insert, Some(list)) => {
+            // Negative indices insert relative to the end: -1 appends
+            if let Some(index) = list_index(ext.index.unwrap_or(0), list.len(), 1) {
+                let index = index.min(list.len());
+                list.splice(index..index, values);
+            }
+        }
+        (ListOperationType::RemoveAt, Some(list)) => {
+            match list_index(ext.index.unwrap_or(0), list.len(), 0) {
+                Some(index) if index < list.len() => {
+                    list.remove(index);
+                }
+                _ => {}
+            }
+        }
+        (ListOperationType::RemoveValue, Some(list)) => {
+            for value in &values {
+                if let Some(pos) = list.iter().position(|item| item.semantic_eq(value)) {
+                    list.remove(pos);
+                }
+            }
+        }
+        (ListOperationType::Clear, Some(list)) => list.clear(),
+    }
+}
+
+/// Get a value from a nested path
+///
+/// The path may index into lists, e.g. `servers[0].host`.
+pub fn get_nested(config: &ConfigDict, path: &str) -> Option<ConfigValue> {
+    if path.is_empty() {
+        return Some(ConfigValue::Dict(config.clone()));
     }
+
+    get_nested_ref(config, &parse_key_segments(path)).cloned()
 }
 
 /// Collect all keys from a config (flattened with dot notation)
@@ -220,7 +749,11 @@ pub fn diff_keys(config1: &ConfigDict, config2: &ConfigDict) -> Vec<String> {
     for key in keys1.intersection(&keys2) {
         let val1 = get_nested(config1, key);
         let val2 = get_nested(config2, key);
-        if val1 != val2 {
+        let same = match (&val1, &val2) {
+            (Some(a), Some(b)) => a.semantic_eq(b),
+            _ => val1 == val2,
+        };
+        if !same {
             diff.push(key.clone());
         }
     }
@@ -243,7 +776,7 @@ mod tests {
         override_dict.insert("b".to_string(), ConfigValue::Int(20));
         override_dict.insert("c".to_string(), ConfigValue::Int(3));
 
-        merge_dicts(&mut base, &override_dict);
+        merge_dicts(&mut base, &override_dict).unwrap();
 
         assert_eq!(base.get("a"), Some(&ConfigValue::Int(1)));
         assert_eq!(base.get("b"), Some(&ConfigValue::Int(20)));
@@ -268,7 +801,7 @@ mod tests {
         let mut override_dict = ConfigDict::new();
         override_dict.insert("db".to_string(), ConfigValue::Dict(override_inner));
 
-        merge_dicts(&mut base, &override_dict);
+        merge_dicts(&mut base, &override_dict).unwrap();
 
         if let Some(ConfigValue::Dict(db)) = base.get("db") {
             assert_eq!(
@@ -292,12 +825,87 @@ mod tests {
         let mut cfg3 = ConfigDict::new();
         cfg3.insert("a".to_string(), ConfigValue::Int(10));
 
-        let result = merge_configs(&[cfg1, cfg2, cfg3]);
+        let result = merge_configs(&[cfg1, cfg2, cfg3]).unwrap();
 
         assert_eq!(result.get("a"), Some(&ConfigValue::Int(10)));
         assert_eq!(result.get("b"), Some(&ConfigValue::Int(2)));
     }
 
+    #[test]
+    fn test_merge_empty_and_identical_are_noops() {
+        let base = crate::config::parser::parse_yaml(
+            "db:\n  host: localhost\n  pool: {size: 4, tags: [a]}\nlr: 0.1\n",
+        )
+        .unwrap();
+        let base = base.as_dict().unwrap();
+
+        let mut merged = base.clone();
+        merge_dicts(&mut merged, &ConfigDict::new()).unwrap();
+        assert_eq!(&merged, base);
+
+        let mut merged = base.clone();
+        merge_dicts(&mut merged, base).unwrap();
+        assert_eq!(&merged, base);
+        assert_eq!(merged.keys().collect::<Vec<_>>(), ["db", "lr"]);
+
+        let mut merged = base.clone();
+        merged.merge(&ConfigDict::new()).unwrap();
+        merged.merge(base).unwrap();
+        assert_eq!(&merged, base);
+
+        let merged = merge_values(
+            ConfigValue::Dict(base.clone()),
+            ConfigValue::Dict(ConfigDict::new()),
+            MergeMode::Default,
+        )
+        .unwrap();
+        assert_eq!(merged, ConfigValue::Dict(base.clone()));
+
+        // A single changed leaf deep inside an otherwise identical config applies
+        let mut other = base.clone();
+        apply_override(&mut other, "db.pool.size", ConfigValue::Int(8)).unwrap();
+        let mut merged = base.clone();
+        merge_dicts(&mut merged, &other).unwrap();
+        assert_eq!(merged, other);
+        let mut merged = base.clone();
+        merged.merge(&other).unwrap();
+        assert_eq!(merged, other);
+
+        // Identical lists under a concat annotation still concatenate
+        let annotated =
+            crate::config::parser::parse_yaml("__merge__: concat\ntags: [a]\n").unwrap();
+        let annotated = annotated.as_dict().unwrap();
+        let mut merged = annotated.clone();
+        merge_dicts(&mut merged, annotated).unwrap();
+        assert_eq!(
+            merged.get("tags"),
+            Some(&ConfigValue::List(vec!["a".into(), "a".into()]))
+        );
+
+        // Identical annotated dicts are merged like any other: lists in a
+        // concat subtree concatenate and no annotation is left behind
+        let annotated = crate::config::parser::parse_yaml(
+            "model:\n  __merge__: concat\n  tags: [a]\nopt:\n  __merge__: replace\n  betas: [1]\n",
+        )
+        .unwrap();
+        let expected =
+            crate::config::parser::parse_yaml("model:\n  tags: [a, a]\nopt:\n  betas: [1]\n")
+                .unwrap();
+        let merged =
+            merge_values(annotated.clone(), annotated.clone(), MergeMode::Default).unwrap();
+        assert_eq!(merged, expected);
+        let mut merged = annotated.as_dict().unwrap().clone();
+        merge_dicts(&mut merged, annotated.as_dict().unwrap()).unwrap();
+        assert_eq!(ConfigValue::Dict(merged), expected);
+
+        // Identical lists still concatenate in extend mode
+        let list = ConfigValue::List(vec![ConfigValue::Int(1)]);
+        assert_eq!(
+            merge_values(list.clone(), list.clone(), MergeMode::Extend).unwrap(),
+            ConfigValue::List(vec![ConfigValue::Int(1), ConfigValue::Int(1)])
+        );
+    }
+
     #[test]
     fn test_apply_deletions() {
         let mut config = ConfigDict::new();
@@ -317,7 +925,7 @@ mod tests {
         let mut config = ConfigDict::new();
         config.insert("a".to_string(), ConfigValue::Int(1));
 
-        apply_override(&mut config, "b.c.d", ConfigValue::Int(42));
+        apply_override(&mut config, "b.c.d", ConfigValue::Int(42)).unwrap();
 
         if let Some(val) = get_nested(&config, "b.c.d") {
             assert_eq!(val, ConfigValue::Int(42));
@@ -362,16 +970,50 @@ mod tests {
     #[test]
     fn test_missing_preserves_base() {
         let base = ConfigValue::Int(42);
-        let result = merge_values(base, ConfigValue::Missing, MergeMode::Default);
+        let result = merge_values(base, ConfigValue::Missing, MergeMode::Default).unwrap();
         assert_eq!(result, ConfigValue::Int(42));
     }
 
+    #[test]
+    fn test_interpolation_precedence() {
+        let interp = |s: &str| ConfigValue::Interpolation(s.to_string());
+
+        let result = merge_values(ConfigValue::Int(1), interp("${a}"), MergeMode::Default).unwrap();
+        assert_eq!(result, interp("${a}"));
+        let result = merge_values(interp("${a}"), ConfigValue::Int(1), MergeMode::Default).unwrap();
+        assert_eq!(result, ConfigValue::Int(1));
+        let literal = ConfigValue::String("${a}".to_string());
+        let result = merge_values(interp("${a}"), literal.clone(), MergeMode::Default).unwrap();
+        assert_eq!(result, literal);
+        let result = merge_values(interp("${a}"), interp("${b}"), MergeMode::Default).unwrap();
+        assert_eq!(result, interp("${b}"));
+
+        let mut base = ConfigDict::new();
+        base.insert("x".to_string(), ConfigValue::Int(1));
+        base.insert("y".to_string(), interp("${x}"));
+        base.insert("z".to_string(), interp("${x}"));
+        let mut over = ConfigDict::new();
+        over.insert("x".to_string(), interp("${y}"));
+        over.insert("y".to_string(), ConfigValue::from("host"));
+        over.insert("z".to_string(), interp("${y}"));
+        let mut merged = base.clone();
+        merged.merge(&over).unwrap();
+        merge_dicts(&mut base, &over).unwrap();
+        assert_eq!(base, merged);
+        assert_eq!(base.get("x"), Some(&interp("${y}")));
+        assert_eq!(
+            base.get("y"),
+            Some(&ConfigValue::String("host".to_string()))
+        );
+        assert_eq!(base.get("z"), Some(&interp("${y}")));
+    }
+
     #[test]
     fn test_list_extend() {
         let base = ConfigValue::List(vec![ConfigValue::Int(1), ConfigValue::Int(2)]);
         let override_val = ConfigValue::List(vec![ConfigValue::Int(3)]);
 
-        let result = merge_values(base, override_val, MergeMode::Extend);
+        let result = merge_values(base, override_val, MergeMode::Extend).unwrap();
 
         if let ConfigValue::List(list) = result {
             assert_eq!(list.len(), 3);
@@ -379,4 +1021,417 @@ mod tests {
             panic!("Expected list");
         }
     }
+
+    #[test]
+    fn test_apply_overrides_cloned() {
+        use crate::parser::OverrideParser;
+
+        let config = crate::config::parser::parse_yaml(
+            "db:\n  host: localhost\n  port: 3306\ntags: [a, b]\ndebug: true\n",
+        )
+        .unwrap();
+        let overrides = OverrideParser::parse_many(&[
+            "db.port=5432",
+            "+db.user=admin",
+            "tags=append(c)",
+            "~debug",
+            "url=${db.host}",
+        ])
+        .unwrap();
+
+        let result = apply_overrides_cloned(&config, &overrides);
+        let dict = result.as_dict().unwrap();
+        assert_eq!(dict.select("db.port"), Some(ConfigValue::Int(5432)));
+        assert_eq!(dict.select("db.user"), Some(ConfigValue::from("admin")));
+        assert_eq!(
+            dict.get("tags"),
+            Some(&ConfigValue::List(vec!["a".into(), "b".into(), "c".into()]))
+        );
+        assert_eq!(dict.get("debug"), None);
+        assert_eq!(
+            dict.get("url"),
+            Some(&ConfigValue::Interpolation("${db.host}".to_string()))
+        );
+
+        // The original config is unchanged
+        let original = config.as_dict().unwrap();
+        assert_eq!(original.select("db.port"), Some(ConfigValue::Int(3306)));
+        assert_eq!(original.select("db.user"), None);
+        assert_eq!(
+            original.get("tags"),
+            Some(&ConfigValue::List(vec!["a".into(), "b".into()]))
+        );
+        assert_eq!(original.get("debug"), Some(&ConfigValue::Bool(true)));
+    }
+
+    #[test]
+    fn test_apply_overrides_missing_parent() {
+        use crate::parser::OverrideParser;
+
+        let config = crate::config::parser::parse_yaml("db:\n  host: localhost\n").unwrap();
+        let apply = |overrides: &[&str]| {
+            let overrides = OverrideParser::parse_many(overrides).unwrap();
+            try_apply_overrides_cloned(&config, &overrides)
+        };
+
+        // A change may add a missing last key, but not missing parents
+        let result = apply(&["db.port=5432"]).unwrap();
+        assert_eq!(
+            result.as_dict().unwrap().select("db.port"),
+            Some(ConfigValue::Int(5432))
+        );
+        let err = apply(&["db.pool.size=4"]).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Cannot set 'db.pool.size': key 'pool' does not exist (use '+' to add it)"
+        );
+        let err = apply(&["db.host.name=x"]).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Cannot set 'db.host.name': cannot set 'name' inside a string"
+        );
+
+        // `+` creates them
+        let result = apply(&["+db.pool.size=4"]).unwrap();
+        assert_eq!(
+            result.as_dict().unwrap().select("db.pool.size"),
+            Some(ConfigValue::Int(4))
+        );
+
+        // The lenient form skips the override
+        let overrides = OverrideParser::parse_many(&["db.pool.size=4"]).unwrap();
+        assert_eq!(apply_overrides_cloned(&config, &overrides), config);
+    }
+
+    #[test]
+    fn test_apply_overrides_cloned_conditional_delete() {
+        use crate::parser::OverrideParser;
+
+        let config = crate::config::parser::parse_yaml("a: 1\nb: 2\n").unwrap();
+        let overrides = OverrideParser::parse_many(&["~a=1", "~b=3"]).unwrap();
+
+        let result = apply_overrides_cloned(&config, &overrides);
+        let dict = result.as_dict().unwrap();
+        assert_eq!(dict.get("a"), None);
+        assert_eq!(dict.get("b"), Some(&ConfigValue::Int(2)));
+    }
+
+    #[test]
+    fn test_apply_overrides_add_assign() {
+        use crate::parser::OverrideParser;
+
+        let config = crate::config::parser::parse_yaml(
+            "name: run\nport: 3306\nlr: 0.5\ntags: [a]\nhost: ${db.host}\ndebug: true\n",
+        )
+        .unwrap();
+        let apply = |overrides: &[&str]| {
+            let overrides = OverrideParser::parse_many(overrides).unwrap();
+            try_apply_overrides_cloned(&config, &overrides)
+        };
+
+        let result = apply(&[
+            "name+=_v2",
+            "port+=2",
+            "lr+=1",
+            "tags+=b",
+            "tags+=[c,d]",
+            "host+=:80",
+            "workers+=4",
+        ])
+        .unwrap();
+        let dict = result.as_dict().unwrap();
+        assert_eq!(dict.get("name"), Some(&ConfigValue::from("run_v2")));
+        assert_eq!(dict.get("port"), Some(&ConfigValue::Int(3308)));
+        assert_eq!(dict.get("lr"), Some(&ConfigValue::Float(1.5)));
+        assert_eq!(
+            dict.get("tags"),
+            Some(&ConfigValue::List(vec![
+                "a".into(),
+                "b".into(),
+                "c".into(),
+                "d".into()
+            ]))
+        );
+        assert_eq!(
+            dict.get("host"),
+            Some(&ConfigValue::Interpolation("${db.host}:80".to_string()))
+        );
+        // A missing key is set as with `key=value`
+        assert_eq!(dict.get("workers"), Some(&ConfigValue::Int(4)));
+
+        let err = apply(&["debug+=1"]).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Cannot apply '+=' to 'debug': cannot add an int to a bool"
+        );
+        let err = apply(&["port+=x"]).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Cannot apply '+=' to 'port': cannot add a string to an int"
+        );
+    }
+
+    #[test]
+    fn test_apply_overrides_null_and_delete() {
+        use crate::parser::OverrideParser;
+
+        let config = crate::config::parser::parse_yaml("a: 1\nb: 2\n").unwrap();
+        let overrides = OverrideParser::parse_many(&["a=null", "~b"]).unwrap();
+
+        let result = apply_overrides_cloned(&config, &overrides);
+        let dict = result.as_dict().unwrap();
+        assert_eq!(dict.get("a"), Some(&ConfigValue::Null));
+        assert_eq!(dict.get("b"), None);
+    }
+
+    #[test]
+    fn test_apply_overrides_list_index() {
+        use crate::parser::OverrideParser;
+
+        let config = crate::config::parser::parse_yaml(
+            "tags: [a, b]\nservers:\n  - host: x\n    ports: [1, 2]\n",
+        )
+        .unwrap();
+        let apply = |overrides: &[&str]| {
+            let overrides = OverrideParser::parse_many(overrides).unwrap();
+            apply_overrides_cloned(&config, &overrides)
+                .as_dict()
+                .unwrap()
+                .clone()
+        };
+        let tags = |dict: &ConfigDict| dict.get("tags").cloned();
+
+        let dict = apply(&["tags[0]=z", "servers[0].host=y", "servers[0].ports[1]=3"]);
+        assert_eq!(
+            tags(&dict),
+            Some(ConfigValue::List(vec!["z".into(), "b".into()]))
+        );
+        assert_eq!(get_nested(&dict, "servers[0].host"), Some("y".into()));
+        assert_eq!(
+            get_nested(&dict, "servers[0].ports[1]"),
+            Some(ConfigValue::Int(3))
+        );
+
+        // Changing past the end is skipped, or an error when reported;
+        // adding extends the list
+        let dict = apply(&["tags[2]=c"]);
+        assert_eq!(
+            tags(&dict),
+            Some(ConfigValue::List(vec!["a".into(), "b".into()]))
+        );
+        let err = try_apply_overrides_cloned(
+            &config,
+            &OverrideParser::parse_many(&["tags[2]=c"]).unwrap(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.message,
+            "Cannot set 'tags[2]': index 2 is out of range for a list of 2 items"
+        );
+        let dict = apply(&["+tags[3]=d", "+servers[1].host=w"]);
+        assert_eq!(
+            tags(&dict),
+            Some(ConfigValue::List(vec![
+                "a".into(),
+                "b".into(),
+                ConfigValue::Null,
+                "d".into()
+            ]))
+        );
+        assert_eq!(get_nested(&dict, "servers[1].host"), Some("w".into()));
+
+        // Deleting an element and extending a nested list
+        let dict = apply(&["~tags[0]", "servers[0].ports=append(4)"]);
+        assert_eq!(tags(&dict), Some(ConfigValue::List(vec!["b".into()])));
+        assert_eq!(
+            get_nested(&dict, "servers[0].ports"),
+            Some(ConfigValue::List(vec![
+                ConfigValue::Int(1),
+                ConfigValue::Int(2),
+                ConfigValue::Int(4)
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_apply_overrides_delete_list_element() {
+        use crate::parser::OverrideParser;
+
+        let config = crate::config::parser::parse_yaml(
+            "servers: [a, b, c]
+a:
+  b: [x, y, z]
+",
+        )
+        .unwrap();
+        let apply = |overrides: &[&str]| {
+            let overrides = OverrideParser::parse_many(overrides).unwrap();
+            try_apply_overrides_cloned(&config, &overrides)
+        };
+        let list = |value: ConfigValue, path: &str| {
+            get_nested(value.as_dict().unwrap(), path).and_then(|v| v.as_list().cloned())
+        };
+
+        let result = apply(&["~servers[0]"]).unwrap();
+        assert_eq!(list(result, "servers"), Some(vec!["b".into(), "c".into()]));
+        let result = apply(&["~servers[-1]"]).unwrap();
+        assert_eq!(list(result, "servers"), Some(vec!["a".into(), "b".into()]));
+        let result = apply(&["~a.b[1]"]).unwrap();
+        assert_eq!(list(result, "a.b"), Some(vec!["x".into(), "z".into()]));
+
+        for (ovr, index) in [("~servers[3]", "3"), ("~servers[-4]", "-4")] {
+            let err = apply(&[ovr]).unwrap_err();
+            assert_eq!(
+                err.message,
+                format!(
+                    "Cannot delete 'servers[{}]': index {} is out of range for a list of 3 items",
+                    index, index
+                )
+            );
+        }
+        // The lenient variant skips the delete
+        let overrides = OverrideParser::parse_many(&["~servers[3]"]).unwrap();
+        assert_eq!(apply_overrides_cloned(&config, &overrides), config);
+    }
+
+    #[test]
+    fn test_merge_null_deletes() {
+        let base =
+            crate::config::parser::parse_yaml("a: 1\nb: 2\ndb:\n  host: localhost\n  port: 3306\n")
+                .unwrap();
+        let other = crate::config::parser::parse_yaml(
+            "a: null\nb:\n  x: 1\n  y: null\ndb:\n  port: null\nnew:\n  x: 1\n  y: null\n",
+        )
+        .unwrap();
+        let (base, other) = (base.as_dict().unwrap(), other.as_dict().unwrap());
+
+        // By default a null sets the key to null
+        let mut merged = base.clone();
+        merge_dicts(&mut merged, other).unwrap();
+        assert_eq!(merged.get("a"), Some(&ConfigValue::Null));
+        assert_eq!(merged.select("db.port"), Some(ConfigValue::Null));
+        assert_eq!(merged.select("new.y"), Some(ConfigValue::Null));
+
+        // With null_deletes it removes the key, at any depth
+        let mut merged = base.clone();
+        merge_dicts_with(&mut merged, other, true).unwrap();
+        assert_eq!(merged.get("a"), None);
+        assert_eq!(merged.select("b.x"), Some(ConfigValue::Int(1)));
+        assert_eq!(merged.select("b.y"), None);
+        assert_eq!(
+            merged.select("db.host"),
+            Some(ConfigValue::from("localhost"))
+        );
+        assert_eq!(merged.select("db.port"), None);
+        assert_eq!(merged.select("new.x"), Some(ConfigValue::Int(1)));
+        assert_eq!(merged.select("new.y"), None);
+    }
+
+    #[test]
+    fn test_merge_annotation_concat() {
+        let parse = |yaml: &str| {
+            crate::config::parser::parse_yaml(yaml)
+                .unwrap()
+                .as_dict()
+                .unwrap()
+                .clone()
+        };
+        let base = parse(
+            "tags: [a]\nmodel:\n  __merge__: concat\n  layers: [64]\n  head:\n    sizes: [1]\n  opt:\n    __merge__: replace\n    betas: [0.9]\n",
+        );
+        let other = parse(
+            "tags: [b]\nmodel:\n  layers: [32]\n  head:\n    sizes: [2]\n  opt:\n    betas: [0.99]\n",
+        );
+        let list = |items: &[ConfigValue]| Some(ConfigValue::List(items.to_vec()));
+
+        let mut merged = base.clone();
+        merge_dicts(&mut merged, &other).unwrap();
+        assert_eq!(merged.select("tags"), list(&["b".into()]));
+        assert_eq!(
+            merged.select("model.layers"),
+            list(&[ConfigValue::Int(64), ConfigValue::Int(32)])
+        );
+        assert_eq!(
+            merged.select("model.head.sizes"),
+            list(&[ConfigValue::Int(1), ConfigValue::Int(2)])
+        );
+        assert_eq!(
+            merged.select("model.opt.betas"),
+            list(&[ConfigValue::Float(0.99)])
+        );
+        assert_eq!(merged.select("model.__merge__"), None);
+        assert_eq!(merged.select("model.opt.__merge__"), None);
+
+        // Merging an annotated dict with itself still concatenates
+        let mut merged = base.clone();
+        merge_dicts(&mut merged, &base).unwrap();
+        assert_eq!(
+            merged.select("model.layers"),
+            list(&[ConfigValue::Int(64), ConfigValue::Int(64)])
+        );
+        assert_eq!(merged.select("model.__merge__"), None);
+
+        // An annotation on the override side, and across several configs
+        let third = parse("model:\n  layers: [16]\n");
+        let merged = merge_configs(&[other.clone(), base.clone(), third]).unwrap();
+        assert_eq!(
+            merged.select("model.layers"),
+            list(&[
+                ConfigValue::Int(32),
+                ConfigValue::Int(64),
+                ConfigValue::Int(16)
+            ])
+        );
+        assert_eq!(merged.select("model.__merge__"), None);
+    }
+
+    #[test]
+    fn test_merge_enum_values() {
+        crate::config::enums::instance().register("MergeTestColor", &["RED", "GREEN"]);
+        let color = |value: &str| ConfigValue::Enum {
+            type_name: "MergeTestColor".to_string(),
+            value: value.to_string(),
+        };
+
+        assert_eq!(
+            merge_values(color("RED"), color("GREEN"), MergeMode::Default).unwrap(),
+            color("GREEN")
+        );
+        // A plain string keeps the enum type
+        assert_eq!(
+            merge_values(color("RED"), ConfigValue::from("GREEN"), MergeMode::Default).unwrap(),
+            color("GREEN")
+        );
+
+        let mut base = ConfigDict::new();
+        base.insert("color".to_string(), color("RED"));
+        let mut other = ConfigDict::new();
+        other.insert("color".to_string(), ConfigValue::from("GREEN"));
+        merge_dicts(&mut base, &other).unwrap();
+        assert_eq!(base.get("color"), Some(&color("GREEN")));
+        assert!(color("GREEN").semantic_eq(&ConfigValue::from("GREEN")));
+
+        // A string that is not a member is rejected, leaving the enum as is
+        let err =
+            merge_values(color("RED"), ConfigValue::from("BLUE"), MergeMode::Default).unwrap_err();
+        assert!(err.message.contains("'BLUE'"), "{}", err);
+        let mut other = ConfigDict::new();
+        other.insert("color".to_string(), ConfigValue::from("BLUE"));
+        assert!(merge_dicts(&mut base, &other).is_err());
+        assert!(base.merge(&other).is_err());
+        assert_eq!(base.get("color"), Some(&color("GREEN")));
+
+        // A failed root override leaves the whole config untouched
+        other.insert("size".to_string(), ConfigValue::Int(3));
+        let err = apply_override(&mut base, "", ConfigValue::Dict(other)).unwrap_err();
+        assert!(err.message.contains("'BLUE'"), "{}", err);
+        assert_eq!(base.get("size"), None);
+
+        // An enum and the string naming it are not a difference
+        let mut same = ConfigDict::new();
+        same.insert("color".to_string(), ConfigValue::from("GREEN"));
+        assert!(diff_keys(&base, &same).is_empty());
+        same.insert("color".to_string(), ConfigValue::from("RED"));
+        assert_eq!(diff_keys(&base, &same), ["color"]);
+    }
 }
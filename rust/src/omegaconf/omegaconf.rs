@@ -628,6 +628,7 @@ impl OmegaConf {
                 Ok(OmegaConf::create_dict(content))
             }
             serde_yaml::Value::Null => Ok(DictConfig::none()),
+            serde_yaml::Value::Tagged(tagged) => Self::yaml_value_to_dictconfig(tagged.value),
             _ => Err(OmegaConfError::from(super::errors::ValidationError::new(
                 "Expected YAML mapping at root",
             ))),
@@ -666,6 +667,8 @@ impl OmegaConf {
                 }
                 Ok(ConfigValue::Dict(dict))
             }
+            // Tags carry no meaning here, so the tagged value is kept as written
+            serde_yaml::Value::Tagged(tagged) => Self::yaml_value_to_config_value(tagged.value),
             #[allow(unreachable_patterns)]
             _ => Err(OmegaConfError::from(super::errors::ValidationError::new(
                 "Unsupported YAML value type",
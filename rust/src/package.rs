@@ -82,6 +82,40 @@ impl PackageResolver {
     }
 }
 
+/// Compute the final package of a config, as Hydra does for defaults
+///
+/// `group` is the config's group path (`db/mysql` style) and `name` its
+/// config name, if chosen yet. Without a `package_header` (an explicit
+/// package or the config's `@package` header) the package is the group
+/// path; `_group_` and `_name_` in the header are replaced by the group and
+/// name. The result is nested under `parent_package`, and a `_global_`
+/// segment discards everything before it, so `_global_` alone is the root.
+pub fn compute_final_package(
+    group: &str,
+    name: Option<&str>,
+    package_header: Option<&str>,
+    parent_package: &str,
+) -> String {
+    let group_package = group.replace('/', ".");
+    let mut package = package_header
+        .unwrap_or(&group_package)
+        .replace("_group_", &group_package);
+    if let Some(name) = name {
+        package = package.replace("_name_", name);
+    }
+
+    let segments: Vec<&str> = parent_package
+        .split('.')
+        .chain(package.split('.'))
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let start = segments
+        .iter()
+        .rposition(|segment| *segment == "_global_")
+        .map_or(0, |idx| idx + 1);
+    segments[start..].join(".")
+}
+
 /// Parse @package directive from config header
 pub fn parse_package_header(content: &str) -> Option<String> {
     for line in content.lines() {
@@ -182,6 +216,52 @@ mod tests {
         assert_eq!(resolver.resolve(), "db.connection");
     }
 
+    #[test]
+    fn test_compute_final_package() {
+        // Without a header the package is the group path
+        assert_eq!(compute_final_package("db", Some("mysql"), None, ""), "db");
+        assert_eq!(
+            compute_final_package("server/db", Some("mysql"), None, ""),
+            "server.db"
+        );
+        assert_eq!(compute_final_package("", Some("config"), None, ""), "");
+
+        // Special and explicit packages
+        let final_package =
+            |header| compute_final_package("db/sql", Some("mysql"), Some(header), "");
+        assert_eq!(final_package("_group_"), "db.sql");
+        assert_eq!(final_package("_group_._name_"), "db.sql.mysql");
+        assert_eq!(final_package("backend"), "backend");
+        assert_eq!(final_package("_global_"), "");
+        assert_eq!(final_package("_global_.cache"), "cache");
+        assert_eq!(
+            compute_final_package("db", None, Some("_name_"), ""),
+            "_name_"
+        );
+
+        // Nested defaults inherit their parent's package
+        assert_eq!(
+            compute_final_package("db", Some("mysql"), None, "server"),
+            "server.db"
+        );
+        assert_eq!(
+            compute_final_package("db", Some("mysql"), Some("_group_"), "a.b"),
+            "a.b.db"
+        );
+        assert_eq!(
+            compute_final_package("db", Some("mysql"), Some(""), "server"),
+            "server"
+        );
+        assert_eq!(
+            compute_final_package("db", Some("mysql"), Some("_global_.db"), "server"),
+            "db"
+        );
+        assert_eq!(
+            compute_final_package("db", Some("mysql"), None, "_global_"),
+            "db"
+        );
+    }
+
     #[test]
     fn test_parse_package_header() {
         let content = "# @package _global_\ndb:\n  host: localhost";
@@ -2,15 +2,77 @@
 //! Override parser for configuration overrides.
 
 use crate::core::{
-    ChoiceSweep, IntervalSweep, Key, ListExtension, ListOperationType, Override, OverrideType,
-    OverrideValue, ParsedElement, Quote, QuotedString, RangeSweep,
+    format_float, ChoiceSweep, IntervalSweep, Key, ListExtension, ListOperationType, Override,
+    OverrideType, OverrideValue, ParsedElement, Quote, QuotedString, RangeSweep,
 };
 use rand::seq::SliceRandom;
 use std::sync::Arc;
 
+/// Category of a parse error, for callers that need to branch on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseErrorKind {
+    /// A character that does not fit the grammar at this point
+    UnexpectedChar,
+    /// The input ended before the override was complete
+    UnexpectedEnd,
+    /// A quoted string without its closing quote
+    UnterminatedString,
+    /// A `${...}` interpolation without its closing brace
+    UnterminatedInterpolation,
+    /// A call to a function that is neither built-in nor user-defined
+    UnknownFunction,
+    /// A `!!tag` type tag that is not supported
+    UnknownTag,
+    /// A key that is not followed by `=`
+    ExpectedEquals,
+    /// A missing or malformed key
+    ExpectedKey,
+    /// A key repeated within one dict literal (strict dict keys mode)
+    DuplicateKey,
+    /// A missing or malformed package in `key@package`
+    InvalidPackage,
+    /// A missing value, or a value of the wrong type
+    ExpectedValue,
+    /// A numeric literal that cannot be represented
+    InvalidNumber,
+    /// An unknown or malformed escape sequence in a quoted string
+    InvalidEscape,
+    /// Wrong number or type of arguments to a grammar function
+    InvalidArguments,
+    /// A function or cast that failed while being evaluated
+    EvaluationError,
+    /// A parser bug or unimplemented feature
+    Internal,
+}
+
+impl ParseErrorKind {
+    /// The variant name, e.g. "UnexpectedChar"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UnexpectedChar => "UnexpectedChar",
+            Self::UnexpectedEnd => "UnexpectedEnd",
+            Self::UnterminatedString => "UnterminatedString",
+            Self::UnterminatedInterpolation => "UnterminatedInterpolation",
+            Self::UnknownFunction => "UnknownFunction",
+            Self::UnknownTag => "UnknownTag",
+            Self::ExpectedEquals => "ExpectedEquals",
+            Self::ExpectedKey => "ExpectedKey",
+            Self::DuplicateKey => "DuplicateKey",
+            Self::InvalidPackage => "InvalidPackage",
+            Self::ExpectedValue => "ExpectedValue",
+            Self::InvalidNumber => "InvalidNumber",
+            Self::InvalidEscape => "InvalidEscape",
+            Self::InvalidArguments => "InvalidArguments",
+            Self::EvaluationError => "EvaluationError",
+            Self::Internal => "Internal",
+        }
+    }
+}
+
 /// Parser errors
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParseError {
+    pub kind: ParseErrorKind,
     pub message: String,
     pub position: usize,
 }
@@ -55,6 +117,348 @@ pub trait FunctionCallback: Send + Sync {
 /// Result type for parser operations
 pub type ParseResult<T> = Result<T, ParseError>;
 
+/// Overrides parsed while collecting errors: one slot per input override,
+/// holding the overrides it expands to or `None` if it failed, and each
+/// error paired with the index of its input
+pub type CollectedOverrides = (Vec<Option<Vec<Override>>>, Vec<(usize, ParseError)>);
+
+/// Builds the value of a built-in function call from its name, positional
+/// and keyword arguments, and where the arguments start in the input
+type BuiltinFn = fn(
+    &OverrideParser,
+    &str,
+    Vec<ParsedElement>,
+    &[(String, ParsedElement)],
+    &ArgPositions,
+) -> ParseResult<OverrideValue>;
+
+/// The built-in grammar functions, by name
+const BUILTIN_FUNCTIONS: &[(&str, BuiltinFn)] = &[
+    ("choice", |parser, _, args, _, _| {
+        parser.build_choice_sweep(args)
+    }),
+    ("range", |parser, _, args, kwargs, positions| {
+        parser.build_range_sweep(args, kwargs, positions)
+    }),
+    ("interval", |parser, _, args, kwargs, positions| {
+        parser.build_interval_sweep(args, kwargs, positions)
+    }),
+    ("glob", |parser, _, args, kwargs, _| {
+        parser.build_glob(args, kwargs)
+    }),
+    ("tag", |parser, _, args, _, _| {
+        parser.build_tagged_sweep(args)
+    }),
+    // shuffle and sort calls are normally handled before reaching the table
+    ("shuffle", |parser, _, args, kwargs, _| {
+        parser.build_shuffle(args, kwargs)
+    }),
+    ("sort", |parser, _, args, kwargs, _| {
+        parser.build_sort(args, kwargs)
+    }),
+    ("extend_list", |parser, _, args, _, _| {
+        parser.build_list_append(args)
+    }),
+    ("append", |parser, _, args, _, _| {
+        parser.build_list_append(args)
+    }),
+    ("prepend", |parser, _, args, _, _| {
+        parser.build_list_prepend(args)
+    }),
+    ("append_unique", |parser, _, args, _, _| {
+        parser.build_list_append_unique(args)
+    }),
+    ("prepend_unique", |parser, _, args, _, _| {
+        parser.build_list_prepend_unique(args)
+    }),
+    ("insert", |parser, _, args, _, _| {
+        parser.build_list_insert(args)
+    }),
+    ("remove_at", |parser, _, args, _, _| {
+        parser.build_list_remove_at(args)
+    }),
+    ("remove_value", |parser, _, args, _, _| {
+        parser.build_list_remove_value(args)
+    }),
+    ("list_clear", |parser, _, args, _, _| {
+        parser.build_list_clear(args)
+    }),
+    ("int", |parser, name, args, _, _| {
+        parser.build_cast_call(name, args)
+    }),
+    ("float", |parser, name, args, _, _| {
+        parser.build_cast_call(name, args)
+    }),
+    ("str", |parser, name, args, _, _| {
+        parser.build_cast_call(name, args)
+    }),
+    ("bool", |parser, name, args, _, _| {
+        parser.build_cast_call(name, args)
+    }),
+    ("json_str", |parser, name, args, _, _| {
+        parser.build_cast_call(name, args)
+    }),
+];
+
+/// Maximum edit distance for a built-in function to be suggested for an unknown name
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Compute the Levenshtein edit distance between two strings
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+/// Find the closest built-in function name to an unknown one, if any is close enough
+fn suggest_function(name: &str) -> Option<&'static str> {
+    BUILTIN_FUNCTIONS
+        .iter()
+        .map(|(candidate, _)| (levenshtein(name, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Duration units and their length in seconds, longest suffix first
+const DURATION_UNITS: &[(&str, f64)] = &[
+    ("ms", 0.001),
+    ("d", 86400.0),
+    ("h", 3600.0),
+    ("m", 60.0),
+    ("s", 1.0),
+];
+
+/// Parse a duration literal such as `30s`, `1h30m` or `500ms`
+///
+/// A duration is one or more `<number><unit>` components, with units `d`, `h`,
+/// `m`, `s` and `ms`. The result is normalized to seconds: an `Int` when the
+/// total is a whole number of seconds (`1h30m` -> 5400), a `Float` otherwise
+/// (`500ms` -> 0.5). Returns None if the string is not a duration.
+pub fn parse_duration(s: &str) -> Option<ParsedElement> {
+    let mut rest = s;
+    let mut total = 0.0;
+
+    while !rest.is_empty() {
+        let num_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let amount: f64 = rest[..num_len].parse().ok()?;
+        rest = &rest[num_len..];
+
+        let (unit, scale) = DURATION_UNITS
+            .iter()
+            .find(|(unit, _)| rest.starts_with(unit))?;
+        total += amount * scale;
+        rest = &rest[unit.len()..];
+    }
+
+    if s.is_empty() {
+        None
+    } else if total.fract() == 0.0 && total <= i64::MAX as f64 {
+        Some(ParsedElement::Int(total as i64))
+    } else {
+        Some(ParsedElement::Float(total))
+    }
+}
+
+/// Check whether a token looks like a number but is not a valid literal
+///
+/// Underscores must sit between two digits in every numeric segment (integer,
+/// fraction and exponent), so leading, trailing or doubled underscores (`_1`,
+/// `1_`, `1__0`), underscores next to a dot (`1_.5`, `1._5`) or the exponent
+/// marker (`1_e5`, `1e_5`, `1e+_5`) are malformed, as is a trailing dot with no
+/// fractional digits (`1.`). Tokens containing anything other than digits,
+/// underscores, dots, an exponent and signs are not numeric.
+pub fn is_malformed_number(s: &str) -> bool {
+    let unsigned = s.strip_prefix(['-', '+']).unwrap_or(s);
+    let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+        Some((mantissa, exp)) => (mantissa, Some(exp.strip_prefix(['-', '+']).unwrap_or(exp))),
+        None => (unsigned, None),
+    };
+
+    let looks_numeric = |part: &str, allow_dot: bool| {
+        part.chars().any(|c| c.is_ascii_digit())
+            && part
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '_' || (allow_dot && c == '.'))
+    };
+    let misplaced_underscore = |part: &str| {
+        part.starts_with('_')
+            || part.ends_with('_')
+            || part.contains("__")
+            || part.contains("_.")
+            || part.contains("._")
+    };
+
+    if !looks_numeric(mantissa, true) {
+        return false;
+    }
+    match exponent {
+        None => misplaced_underscore(mantissa) || mantissa.ends_with('.'),
+        // An exponent may follow a bare trailing dot, as in "1.e5"
+        Some(exp) if looks_numeric(exp, false) => {
+            misplaced_underscore(mantissa) || misplaced_underscore(exp)
+        }
+        Some(_) => false,
+    }
+}
+
+/// Expand overrides naming several packages (`key@pkg1,pkg2=value`) into one
+/// override per package, each paired with the index of its source override
+fn expand_package_lists<'a>(overrides: &'a [&str]) -> impl Iterator<Item = (usize, String)> + 'a {
+    overrides
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, s)| split_package_list(s).into_iter().map(move |o| (idx, o)))
+}
+
+/// Parse each override with `parse`, keeping going after a failure
+fn collect_each(
+    overrides: &[&str],
+    parse: impl Fn(&str) -> ParseResult<Override>,
+) -> CollectedOverrides {
+    let mut results = Vec::new();
+    let mut errors = Vec::new();
+    for (idx, s) in overrides.iter().enumerate() {
+        let expanded: ParseResult<Vec<_>> =
+            split_package_list(s).iter().map(|o| parse(o)).collect();
+        match expanded {
+            Ok(parsed) => results.push(Some(parsed)),
+            Err(e) => {
+                results.push(None);
+                errors.push((idx, e));
+            }
+        }
+    }
+    (results, errors)
+}
+
+/// Split `key@pkg1,pkg2=value` into `key@pkg1=value` and `key@pkg2=value`
+///
+/// Anything else, including the `@pkg:key` prefix form, is returned unchanged.
+pub fn split_package_list(input: &str) -> Vec<String> {
+    let key_end = input.find('=').unwrap_or(input.len());
+    let (key, rest) = input.split_at(key_end);
+    let Some((head, packages)) = key.split_once('@') else {
+        return vec![input.to_string()];
+    };
+    let is_package_list = packages.contains(',')
+        && packages
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | ',' | ' '));
+    if !is_package_list || head.trim_start_matches(['+', '~']).trim().is_empty() {
+        return vec![input.to_string()];
+    }
+    packages
+        .split(',')
+        .map(|package| format!("{}@{}{}", head, package.trim(), rest))
+        .collect()
+}
+
+/// Check whether `s` ends inside a single- or double-quoted string
+///
+/// Only a quote that starts a token opens a string, so the apostrophe in
+/// `name=O'Brien` is part of the value.
+fn has_unclosed_quote(s: &str) -> bool {
+    let mut quote = None;
+    let mut prev = None;
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (_, '\\') => {
+                chars.next();
+            }
+            (None, '\'' | '"')
+                if prev.is_none_or(|p: char| p.is_whitespace() || "=,[{(:".contains(p)) =>
+            {
+                quote = Some(c)
+            }
+            (Some(q), _) if c == q => quote = None,
+            _ => {}
+        }
+        prev = Some(c);
+    }
+    quote.is_some()
+}
+
+/// Join lines ending in a `\` continuation, as in `a=[1,2,\<newline>3]`
+///
+/// The backslash and the line break (`\n` or `\r\n`) are dropped outside
+/// quotes. Inside a quoted string they are left for the string parser, which
+/// reads them as a literal newline. Error positions refer to the joined input.
+fn join_continuation_lines(input: &str) -> String {
+    if !input.contains('\n') {
+        return input.to_string();
+    }
+    let mut joined = String::with_capacity(input.len());
+    let mut quote = None;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (None, '\\') if chars.peek() == Some(&'\n') => {
+                chars.next();
+            }
+            (None, '\\') if chars.peek() == Some(&'\r') => {
+                let mut rest = chars.clone();
+                rest.next();
+                if rest.next() == Some('\n') {
+                    chars = rest;
+                } else {
+                    joined.push(c);
+                }
+            }
+            (_, '\\') => {
+                joined.push(c);
+                joined.extend(chars.next());
+            }
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                joined.push(c);
+            }
+            (Some(q), _) if c == q => {
+                quote = None;
+                joined.push(c);
+            }
+            _ => joined.push(c),
+        }
+    }
+    joined
+}
+
+/// Where each argument of a function call starts in the input, so errors
+/// about a bad argument can point at it
+#[derive(Debug, Default)]
+struct ArgPositions {
+    args: Vec<usize>,
+    kwargs: Vec<(String, usize)>,
+}
+
+impl ArgPositions {
+    fn arg(&self, index: usize) -> usize {
+        self.args[index]
+    }
+
+    fn kwarg(&self, name: &str) -> usize {
+        self.kwargs
+            .iter()
+            .find(|(k, _)| k == name)
+            .map_or(0, |(_, pos)| *pos)
+    }
+}
+
 /// A simple override parser
 pub struct OverrideParser {
     input: Vec<char>,
@@ -63,42 +467,90 @@ pub struct OverrideParser {
     /// When None, only built-in functions are available (pure Rust mode).
     /// When Some, unknown functions are delegated to the callback (PyO3 mode).
     function_callback: Option<Arc<dyn FunctionCallback>>,
+    /// When true, bare duration literals (e.g. `30s`) are parsed into seconds.
+    /// Off by default so these values stay strings.
+    parse_durations: bool,
+    /// When true, malformed numeric literals (e.g. `1_`, `1.`) are rejected
+    /// instead of falling back to strings.
+    strict_numbers: bool,
+    /// When true, a key repeated within one dict literal is an error.
+    /// Otherwise the last occurrence wins.
+    strict_dict_keys: bool,
+    /// When true, quoted strings support `\n`-style escape sequences.
+    /// Off by default so backslashes stay literal, as in Hydra.
+    escapes: bool,
+    /// When true, `#` after whitespace starts a comment (see
+    /// [`Self::parse_complete`]). On by default.
+    comments: bool,
 }
 
 impl OverrideParser {
     /// Create a new parser for the given input (pure Rust mode, no user-defined functions)
+    ///
+    /// Multi-line input may continue a value on the next line with a
+    /// trailing backslash, which joins the lines before tokenizing.
     pub fn new(input: &str) -> Self {
         Self {
-            input: input.chars().collect(),
+            input: join_continuation_lines(input).chars().collect(),
             pos: 0,
             function_callback: None,
+            parse_durations: false,
+            strict_numbers: false,
+            strict_dict_keys: false,
+            escapes: false,
+            comments: true,
         }
     }
 
     /// Create a new parser with a function callback for user-defined functions
     pub fn with_callback(input: &str, callback: Arc<dyn FunctionCallback>) -> Self {
         Self {
-            input: input.chars().collect(),
+            input: join_continuation_lines(input).chars().collect(),
             pos: 0,
             function_callback: Some(callback),
+            parse_durations: false,
+            strict_numbers: false,
+            strict_dict_keys: false,
+            escapes: false,
+            comments: true,
         }
     }
 
-    /// Parse a complete override string (pure Rust mode)
-    pub fn parse(input: &str) -> ParseResult<Override> {
-        let mut parser = Self::new(input);
-        let result = parser.parse_override()?;
+    /// Enable or disable parsing of bare duration literals (see [`parse_duration`])
+    pub fn with_durations(mut self, enabled: bool) -> Self {
+        self.parse_durations = enabled;
+        self
+    }
 
-        // Ensure we consumed all input
-        parser.skip_whitespace();
-        if parser.pos < parser.input.len() {
-            return Err(ParseError {
-                message: format!("Unexpected character: '{}'", parser.current()),
-                position: parser.pos,
-            });
-        }
+    /// Enable or disable strict numeric mode (see [`is_malformed_number`])
+    pub fn with_strict_numbers(mut self, enabled: bool) -> Self {
+        self.strict_numbers = enabled;
+        self
+    }
 
-        Ok(result)
+    /// Enable or disable strict dict keys mode (duplicate keys are errors)
+    pub fn with_strict_dict_keys(mut self, enabled: bool) -> Self {
+        self.strict_dict_keys = enabled;
+        self
+    }
+
+    /// Enable or disable escape sequences in quoted strings (see
+    /// [`Self::unescape_sequences`])
+    pub fn with_escapes(mut self, enabled: bool) -> Self {
+        self.escapes = enabled;
+        self
+    }
+
+    /// Enable or disable trailing comments, for input that is a value
+    /// rather than a line from an overrides file
+    pub fn with_comments(mut self, enabled: bool) -> Self {
+        self.comments = enabled;
+        self
+    }
+
+    /// Parse a complete override string (pure Rust mode)
+    pub fn parse(input: &str) -> ParseResult<Override> {
+        Self::new(input).parse_complete()
     }
 
     /// Parse a complete override string with user-defined function support
@@ -106,15 +558,44 @@ impl OverrideParser {
         input: &str,
         callback: Arc<dyn FunctionCallback>,
     ) -> ParseResult<Override> {
-        let mut parser = Self::with_callback(input, callback);
-        let result = parser.parse_override()?;
+        Self::with_callback(input, callback).parse_complete()
+    }
+
+    /// Parse a complete override string with duration literals enabled
+    pub fn parse_with_durations(input: &str) -> ParseResult<Override> {
+        Self::new(input).with_durations(true).parse_complete()
+    }
+
+    /// Parse a complete override string with escapes in quoted strings enabled
+    pub fn parse_with_escapes(input: &str) -> ParseResult<Override> {
+        Self::new(input).with_escapes(true).parse_complete()
+    }
+
+    /// Parse a complete override string in strict numeric mode
+    pub fn parse_strict(input: &str) -> ParseResult<Override> {
+        Self::new(input).with_strict_numbers(true).parse_complete()
+    }
+
+    /// Parse this parser's input as a single override
+    ///
+    /// An unquoted `#` after whitespace starts a comment that runs to the
+    /// end of the input, so lines read from an overrides file may carry
+    /// trailing notes (`lr=0.1  # tuned`). A `#` inside quotes or inside a
+    /// value (`url=http://host/page#anchor`) is kept.
+    pub fn parse_complete(mut self) -> ParseResult<Override> {
+        let result = self.parse_override()?;
 
         // Ensure we consumed all input
-        parser.skip_whitespace();
-        if parser.pos < parser.input.len() {
+        self.skip_whitespace();
+        let after_whitespace = self.pos > 0 && self.input[self.pos - 1].is_whitespace();
+        if self.comments && after_whitespace && self.peek() == Some('#') {
+            self.pos = self.input.len();
+        }
+        if self.pos < self.input.len() {
             return Err(ParseError {
-                message: format!("Unexpected character: '{}'", parser.current()),
-                position: parser.pos,
+                kind: ParseErrorKind::UnexpectedChar,
+                message: format!("Unexpected character: '{}'", self.current()),
+                position: self.pos,
             });
         }
 
@@ -122,12 +603,26 @@ impl OverrideParser {
     }
 
     /// Parse multiple overrides (pure Rust mode)
+    ///
+    /// An override naming several packages (`model@gpu0,gpu1=resnet`) is
+    /// expanded into one override per package.
     pub fn parse_many(overrides: &[&str]) -> ParseResult<Vec<Override>> {
-        overrides
-            .iter()
-            .enumerate()
+        Self::parse_many_using(overrides, Self::parse)
+    }
+
+    /// Parse multiple overrides with a custom per-override parse function
+    ///
+    /// Lets callers pick parser options (e.g. [`with_escapes`](Self::with_escapes))
+    /// while keeping the package list expansion and error messages of
+    /// [`parse_many`](Self::parse_many).
+    pub fn parse_many_using(
+        overrides: &[&str],
+        parse: impl Fn(&str) -> ParseResult<Override>,
+    ) -> ParseResult<Vec<Override>> {
+        expand_package_lists(overrides)
             .map(|(idx, s)| {
-                Self::parse(s).map_err(|e| ParseError {
+                parse(&s).map_err(|e| ParseError {
+                    kind: e.kind,
                     message: format!("Error parsing override {}: {}", idx, e.message),
                     position: e.position,
                 })
@@ -135,32 +630,114 @@ impl OverrideParser {
             .collect()
     }
 
+    /// Parse multiple overrides, collecting every error instead of stopping at
+    /// the first (pure Rust mode)
+    ///
+    /// Each override is parsed independently. The results hold one slot per
+    /// input override: the overrides it expands to, several for a package
+    /// list as in [`parse_many`](Self::parse_many), or `None` where parsing
+    /// failed. Each error is paired with the index of its input override.
+    pub fn parse_many_collect(overrides: &[&str]) -> CollectedOverrides {
+        collect_each(overrides, Self::parse)
+    }
+
+    /// Parse multiple overrides with a custom per-override parse function,
+    /// collecting every error as in [`parse_many_collect`](Self::parse_many_collect)
+    pub fn parse_many_collect_using(
+        overrides: &[&str],
+        parse: impl Fn(&str) -> ParseResult<Override>,
+    ) -> CollectedOverrides {
+        collect_each(overrides, parse)
+    }
+
+    /// Reassemble overrides that a shell split across several argv tokens
+    ///
+    /// Standalone `=`, `+=` and `++=` tokens join their neighbours
+    /// (`["key", "=", "value"]` becomes `["key=value"]`), a token ending in `=`
+    /// takes the following value, a token starting with `=` is appended to the
+    /// previous key, and bare `+`, `++` or `~` prefixes join the following
+    /// token. A value with an unclosed quote absorbs the following tokens,
+    /// separated by spaces, until the quote is closed. Complete overrides are
+    /// left untouched.
+    pub fn join_argv(args: &[String]) -> Vec<String> {
+        let is_override = |token: &str| token.contains('=') || token.starts_with(['~', '+']);
+        let mut joined = Vec::new();
+        let mut tokens = args.iter().peekable();
+
+        while let Some(token) = tokens.next() {
+            let mut current = token.clone();
+            while matches!(current.as_str(), "+" | "++" | "~") {
+                match tokens.next() {
+                    Some(next) => current.push_str(next),
+                    None => break,
+                }
+            }
+
+            while let Some(next) = tokens.peek() {
+                let next = next.as_str();
+                if matches!(next, "=" | "+=" | "++=") {
+                    current.push_str(next);
+                    tokens.next();
+                    if let Some(value) = tokens.next() {
+                        current.push_str(value);
+                    }
+                } else if next.starts_with('=') || (current.ends_with('=') && !is_override(next)) {
+                    current.push_str(next);
+                    tokens.next();
+                } else if has_unclosed_quote(&current) {
+                    current.push(' ');
+                    current.push_str(next);
+                    tokens.next();
+                } else {
+                    break;
+                }
+            }
+            joined.push(current);
+        }
+        joined
+    }
+
     /// Parse multiple overrides with user-defined function support
+    ///
+    /// Package lists are expanded as in [`parse_many`](Self::parse_many).
     pub fn parse_many_with_callback(
         overrides: &[&str],
         callback: Arc<dyn FunctionCallback>,
     ) -> ParseResult<Vec<Override>> {
-        overrides
-            .iter()
-            .enumerate()
-            .map(|(idx, s)| {
-                Self::parse_with_callback(s, callback.clone()).map_err(|e| ParseError {
-                    message: format!("Error parsing override {}: {}", idx, e.message),
-                    position: e.position,
-                })
-            })
-            .collect()
+        Self::parse_many_using(overrides, |s| {
+            Self::parse_with_callback(s, callback.clone())
+        })
+    }
+
+    /// Parse multiple overrides with user-defined function support,
+    /// collecting every error as in [`parse_many_collect`](Self::parse_many_collect)
+    pub fn parse_many_collect_with_callback(
+        overrides: &[&str],
+        callback: Arc<dyn FunctionCallback>,
+    ) -> CollectedOverrides {
+        collect_each(overrides, |s| {
+            Self::parse_with_callback(s, callback.clone())
+        })
     }
 
     fn parse_override(&mut self) -> ParseResult<Override> {
         self.skip_whitespace();
 
         // Check for override type prefix
-        let override_type = self.parse_override_type();
+        let mut override_type = self.parse_override_type()?;
 
         // Parse the key
         let key = self.parse_key()?;
 
+        // `key+=value` adds to the current value rather than replacing it
+        if override_type == OverrideType::Change
+            && self.peek() == Some('+')
+            && self.input.get(self.pos + 1) == Some(&'=')
+        {
+            self.advance();
+            override_type = OverrideType::ExtendList;
+        }
+
         // Check for value
         if override_type == OverrideType::Del {
             // Delete operations can optionally have values (~key or ~key=value)
@@ -186,12 +763,30 @@ impl OverrideParser {
 
         // Expect '='
         if !self.consume('=') {
+            if let Some(err) = self.misplaced_assign_prefix(&key.key_or_group) {
+                return Err(err);
+            }
             return Err(ParseError {
+                kind: ParseErrorKind::ExpectedEquals,
                 message: "Expected '=' after key".to_string(),
                 position: self.pos,
             });
         }
 
+        // `key=+value` is almost always a misplaced `+key=value`
+        if override_type == OverrideType::Change && self.is_misplaced_add_prefix() {
+            let value: String = self.input[self.pos + 1..].iter().collect();
+            return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedChar,
+                message: format!(
+                    "Unexpected '+' in value position; did you mean '+{}={}'?",
+                    key.key_or_group,
+                    value.trim()
+                ),
+                position: self.pos,
+            });
+        }
+
         // Parse the value
         let value = self.parse_value()?;
 
@@ -203,18 +798,88 @@ impl OverrideParser {
         })
     }
 
-    fn parse_override_type(&mut self) -> OverrideType {
-        if self.consume('~') {
-            OverrideType::Del
+    fn parse_override_type(&mut self) -> ParseResult<OverrideType> {
+        let (override_type, prefix) = if self.consume('~') {
+            (OverrideType::Del, "~")
         } else if self.consume('+') {
             if self.consume('+') {
-                OverrideType::ForceAdd
+                (OverrideType::ForceAdd, "++")
             } else {
-                OverrideType::Add
+                (OverrideType::Add, "+")
             }
         } else {
-            OverrideType::Change
+            return Ok(OverrideType::Change);
+        };
+
+        // Reject doubled or mixed prefixes such as `~~key`, `+~key` or `+=key=value`
+        let message = match self.peek() {
+            Some('=') => {
+                let rest: String = self.input[self.pos + 1..].iter().collect();
+                format!(
+                    "Unexpected '=' after '{}'; did you mean '{}{}'?",
+                    prefix,
+                    prefix,
+                    rest.trim()
+                )
+            }
+            Some(c @ ('~' | '+')) if override_type == OverrideType::Del => format!(
+                "Unexpected '{}' after '~'; use '~key' to delete a key",
+                c
+            ),
+            Some(c @ ('~' | '+')) => format!(
+                "Unexpected '{}' after '{}'; use '+key=value' to add a key or '++key=value' to force-add it",
+                c, prefix
+            ),
+            _ => return Ok(override_type),
+        };
+        Err(ParseError {
+            kind: ParseErrorKind::UnexpectedChar,
+            message,
+            position: self.pos,
+        })
+    }
+
+    /// Detect `key+=value` / `key ++= value` after a key, where `=` was expected
+    fn misplaced_assign_prefix(&self, key: &str) -> Option<ParseError> {
+        let start = self.pos
+            + self.input[self.pos..]
+                .iter()
+                .take_while(|c| c.is_whitespace())
+                .count();
+        let plus = self.input[start..]
+            .iter()
+            .take_while(|&&c| c == '+')
+            .count();
+        if plus == 0 || self.input.get(start + plus) != Some(&'=') {
+            return None;
         }
+        let prefix = "+".repeat(plus.min(2));
+        let value: String = self.input[start + plus + 1..].iter().collect();
+        Some(ParseError {
+            kind: ParseErrorKind::UnexpectedChar,
+            message: format!(
+                "Unexpected '{}=' after key; did you mean '{}{}={}'?",
+                "+".repeat(plus),
+                prefix,
+                key,
+                value.trim()
+            ),
+            position: start,
+        })
+    }
+
+    /// Check for a `+` at the start of a value that is followed by a word
+    /// rather than a number (`+1`, `+inf` and `+nan` stay valid)
+    fn is_misplaced_add_prefix(&self) -> bool {
+        if self.peek() != Some('+') {
+            return false;
+        }
+        let word: String = self.input[self.pos + 1..]
+            .iter()
+            .take_while(|c| c.is_alphanumeric() || **c == '_')
+            .collect();
+        word.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && !matches!(word.to_lowercase().as_str(), "inf" | "nan")
     }
 
     fn parse_key(&mut self) -> ParseResult<Key> {
@@ -227,6 +892,7 @@ impl OverrideParser {
             let pkg = self.parse_package_name()?;
             if !self.consume(':') {
                 return Err(ParseError {
+                    kind: ParseErrorKind::InvalidPackage,
                     message: "Expected ':' after package name".to_string(),
                     position: self.pos,
                 });
@@ -237,13 +903,31 @@ impl OverrideParser {
         // Parse the key itself (can include dots, slashes, and brackets)
         // For group overrides: group1/group2@package=value
         while let Some(c) = self.peek() {
-            if c.is_alphanumeric() || c == '_' || c == '.' || c == '/' || c == '[' || c == ']' {
+            let negative_index = c == '-' && key.ends_with('[');
+            if c.is_alphanumeric()
+                || c == '_'
+                || c == '.'
+                || c == '/'
+                || c == '['
+                || c == ']'
+                || negative_index
+            {
                 key.push(c);
                 self.advance();
             } else if c == '@' && package.is_none() {
                 // Package suffix: group1/group2@pkg=value
                 self.advance();
                 let pkg = self.parse_package_name()?;
+                if self.peek() == Some(',') {
+                    return Err(ParseError {
+                        kind: ParseErrorKind::InvalidPackage,
+                        message: format!(
+                            "Multiple packages ('{}@{},...') are only supported when parsing a list of overrides",
+                            key, pkg
+                        ),
+                        position: self.pos,
+                    });
+                }
                 package = Some(pkg);
                 break;
             } else {
@@ -253,6 +937,7 @@ impl OverrideParser {
 
         if key.is_empty() {
             return Err(ParseError {
+                kind: ParseErrorKind::ExpectedKey,
                 message: "Expected key".to_string(),
                 position: self.pos,
             });
@@ -279,6 +964,7 @@ impl OverrideParser {
 
         if name.is_empty() {
             return Err(ParseError {
+                kind: ParseErrorKind::InvalidPackage,
                 message: "Expected package name".to_string(),
                 position: self.pos,
             });
@@ -301,6 +987,7 @@ impl OverrideParser {
 
         if ident.is_empty() {
             return Err(ParseError {
+                kind: ParseErrorKind::ExpectedKey,
                 message: "Expected identifier".to_string(),
                 position: self.pos,
             });
@@ -317,6 +1004,11 @@ impl OverrideParser {
             return Ok(OverrideValue::Element(ParsedElement::String(String::new())));
         }
 
+        // Explicit type tag: `!!str 3306`
+        if self.peek() == Some('!') && self.peek_at(1) == Some('!') {
+            return self.parse_tagged_value().map(OverrideValue::Element);
+        }
+
         // Check if this starts with an identifier (might be a function call)
         if let Some(c) = self.peek() {
             if c.is_alphabetic() || c == '_' {
@@ -343,6 +1035,61 @@ impl OverrideParser {
         Ok(OverrideValue::Element(elem))
     }
 
+    /// Parse a value with a leading type tag (`!!str`, `!!int`, `!!float`,
+    /// `!!bool` or `!!null`), casting the element that follows it
+    fn parse_tagged_value(&mut self) -> ParseResult<ParsedElement> {
+        let tag_pos = self.pos;
+        self.advance();
+        self.advance();
+        let tag = self.parse_identifier().unwrap_or_default();
+        self.skip_whitespace();
+
+        match tag.as_str() {
+            "str" => {
+                // Unquoted scalars keep their source text (`!!str 007` is "007")
+                let start = self.pos;
+                match self.parse_element()? {
+                    ParsedElement::Int(_)
+                    | ParsedElement::Float(_)
+                    | ParsedElement::Bool(_)
+                    | ParsedElement::Null => {
+                        let text: String = self.input[start..self.pos].iter().collect();
+                        Ok(ParsedElement::String(text.trim_end().to_string()))
+                    }
+                    elem => self.apply_cast(&tag, elem),
+                }
+            }
+            "int" | "float" | "bool" => {
+                let elem = self.parse_element()?;
+                self.apply_cast(&tag, elem)
+            }
+            "null" => {
+                if self.peek().is_none() {
+                    return Ok(ParsedElement::Null);
+                }
+                match self.parse_element()? {
+                    ParsedElement::Null => Ok(ParsedElement::Null),
+                    elem => Err(ParseError {
+                        kind: ParseErrorKind::EvaluationError,
+                        message: format!(
+                            "Cannot apply !!null to '{}'",
+                            Self::elem_to_source(&elem)
+                        ),
+                        position: tag_pos,
+                    }),
+                }
+            }
+            _ => Err(ParseError {
+                kind: ParseErrorKind::UnknownTag,
+                message: format!(
+                    "Unknown type tag: !!{}. Supported tags: !!str, !!int, !!float, !!bool, !!null",
+                    tag
+                ),
+                position: tag_pos,
+            }),
+        }
+    }
+
     /// Try to parse a simple choice sweep (comma-separated values without function call)
     fn try_parse_simple_choice(&mut self) -> ParseResult<Option<OverrideValue>> {
         let start_pos = self.pos;
@@ -382,11 +1129,43 @@ impl OverrideParser {
         })))
     }
 
+    /// Reject a malformed numeric literal at the current position (strict mode)
+    fn check_strict_number(&self) -> ParseResult<()> {
+        // Signs are part of the token at its start and after an exponent marker
+        let mut token = String::new();
+        for &c in &self.input[self.pos..] {
+            let sign_allowed = token.is_empty() || token.ends_with(['e', 'E']);
+            if c.is_alphanumeric()
+                || c == '_'
+                || c == '.'
+                || (sign_allowed && (c == '-' || c == '+'))
+            {
+                token.push(c);
+            } else {
+                break;
+            }
+        }
+
+        if is_malformed_number(&token) {
+            return Err(ParseError {
+                kind: ParseErrorKind::InvalidNumber,
+                message: format!("Malformed numeric literal: {}", token),
+                position: self.pos,
+            });
+        }
+        Ok(())
+    }
+
     fn parse_element(&mut self) -> ParseResult<ParsedElement> {
         self.skip_whitespace();
 
+        if self.strict_numbers {
+            self.check_strict_number()?;
+        }
+
         match self.peek() {
             None => Err(ParseError {
+                kind: ParseErrorKind::UnexpectedEnd,
                 message: "Unexpected end of input".to_string(),
                 position: self.pos,
             }),
@@ -395,7 +1174,7 @@ impl OverrideParser {
             Some('[') => self.parse_list(),
             Some('{') => self.parse_dict(),
             Some('$') => self.parse_interpolation(),
-            Some(c) if c.is_numeric() || c == '-' || c == '+' => {
+            Some(c) if c.is_numeric() || c == '-' || c == '+' || self.at_leading_dot_float() => {
                 // Try number parsing, but fall back to unquoted value if it doesn't
                 // consume everything up to a delimiter (e.g., "1___0___" should be a string)
                 let saved_pos = self.pos;
@@ -405,9 +1184,15 @@ impl OverrideParser {
                 if let Some(next) = self.peek() {
                     // If next char is part of an identifier/value (not a delimiter),
                     // fall back to unquoted value parsing
-                    if next == '_' || next.is_alphanumeric() {
+                    if next == '_' || next == '#' || next.is_alphanumeric() {
                         self.pos = saved_pos;
-                        return self.parse_unquoted_value();
+                        let value = self.parse_unquoted_value()?;
+                        if self.parse_durations {
+                            if let Some(duration) = value.as_str().and_then(parse_duration) {
+                                return Ok(duration);
+                            }
+                        }
+                        return Ok(value);
                     }
                     // Also check for dot followed by an identifier char (e.g., "0.foo")
                     if next == '.' && self.pos + 1 < self.input.len() {
@@ -434,6 +1219,7 @@ impl OverrideParser {
         // Consume $
         if !self.consume('$') {
             return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedChar,
                 message: "Expected '$'".to_string(),
                 position: self.pos,
             });
@@ -470,6 +1256,7 @@ impl OverrideParser {
             match self.peek() {
                 None => {
                     return Err(ParseError {
+                        kind: ParseErrorKind::UnterminatedInterpolation,
                         message: "Unterminated interpolation".to_string(),
                         position: self.pos,
                     });
@@ -581,6 +1368,7 @@ impl OverrideParser {
                         match self.peek() {
                             None => {
                                 return Err(ParseError {
+                                    kind: ParseErrorKind::UnterminatedInterpolation,
                                     message: "Unterminated interpolation".to_string(),
                                     position: self.pos,
                                 });
@@ -620,6 +1408,8 @@ impl OverrideParser {
                 || c == '+'
                 || c == '@'
                 || c == '|'
+                // `#` inside a value (`page#anchor`); after whitespace it starts a comment
+                || c == '#'
             {
                 ident.push(c);
                 self.advance();
@@ -665,6 +1455,7 @@ impl OverrideParser {
                         || next == '@'
                         || next == '|'
                         || next == '\\'
+                        || (next == '#' && !self.comments)
                     {
                         ident.push_str(&temp_ws);
                         // Don't advance - the while loop will handle the next char
@@ -700,6 +1491,7 @@ impl OverrideParser {
                     // need to be handled at the value level, not element level
                     self.pos = start_pos;
                     Err(ParseError {
+                        kind: ParseErrorKind::EvaluationError,
                         message: format!(
                             "Function '{}' returns a sweep, which cannot be used here",
                             ident
@@ -729,6 +1521,7 @@ impl OverrideParser {
         // Consume opening quote
         if !self.consume(quote_char) {
             return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedChar,
                 message: format!("Expected opening {}", quote_char),
                 position: self.pos,
             });
@@ -736,6 +1529,7 @@ impl OverrideParser {
 
         // Collect the raw content between quotes, handling escaped quotes
         // Following ANTLR's approach: only backslashes before quotes are escape sequences
+        let start = self.pos;
         let mut raw = String::new();
 
         while let Some(c) = self.peek() {
@@ -760,7 +1554,11 @@ impl OverrideParser {
                     // Even number (or zero) - this is the closing quote
                     // Now unescape: for sequences of backslashes before a quote,
                     // each pair becomes a single backslash
-                    let text = self.unescape_quoted_string(&raw, quote_char);
+                    let text = if self.escapes {
+                        Self::unescape_sequences(&raw, start)?
+                    } else {
+                        self.unescape_quoted_string(&raw, quote_char)
+                    };
                     return Ok(ParsedElement::QuotedString(QuotedString::new(text, quote)));
                 }
             } else {
@@ -769,6 +1567,7 @@ impl OverrideParser {
         }
 
         Err(ParseError {
+            kind: ParseErrorKind::UnterminatedString,
             message: "Unterminated quoted string".to_string(),
             position: self.pos,
         })
@@ -777,6 +1576,7 @@ impl OverrideParser {
     /// Unescape a quoted string, processing backslash sequences.
     /// - \\ at the end of string becomes \
     /// - \' or \" (escaped quotes) become the quote character
+    /// - A backslash before a line break (`\n` or `\r\n`) is dropped, keeping the line break
     /// - Other backslash sequences are kept as-is
     fn unescape_quoted_string(&self, s: &str, quote_char: char) -> String {
         let mut result = String::new();
@@ -809,6 +1609,13 @@ impl OverrideParser {
                     for _ in 0..output_backslashes {
                         result.push('\\');
                     }
+                } else if num_backslashes % 2 == 1
+                    && (chars[i] == '\n' || (chars[i] == '\r' && chars.get(i + 1) == Some(&'\n')))
+                {
+                    // Backslash + actual line break -> just the line break
+                    for _ in 0..num_backslashes - 1 {
+                        result.push('\\');
+                    }
                 } else {
                     // Backslashes not before quote: keep as-is
                     for _ in 0..num_backslashes {
@@ -824,9 +1631,92 @@ impl OverrideParser {
         result
     }
 
+    /// Unescape a quoted string in escapes mode
+    ///
+    /// `\\`, `\'`, `\"`, `\n`, `\t` and `\r` are the usual characters, and a
+    /// backslash before a line break (`\n` or `\r\n`) keeps just the line break;
+    /// `\xXX` and `\uXXXX` (or `\u{X...}` with up to six digits) are
+    /// hex code points. Any other backslash sequence is an
+    /// [`ParseErrorKind::InvalidEscape`] error. `start` is the input
+    /// position of the first character of `s`, for error positions.
+    fn unescape_sequences(s: &str, start: usize) -> ParseResult<String> {
+        // A code point written as hex digits, e.g. "1F600"
+        let hex_char = |digits: &[char]| -> Option<char> {
+            if digits.is_empty() || !digits.iter().all(|d| d.is_ascii_hexdigit()) {
+                return None;
+            }
+            let digits: String = digits.iter().collect();
+            u32::from_str_radix(&digits, 16)
+                .ok()
+                .and_then(char::from_u32)
+        };
+
+        let chars: Vec<char> = s.chars().collect();
+        let mut result = String::with_capacity(chars.len());
+        let mut i = 0;
+
+        while i < chars.len() {
+            if chars[i] != '\\' {
+                result.push(chars[i]);
+                i += 1;
+                continue;
+            }
+            let escape_start = i;
+            let Some(&c) = chars.get(i + 1) else {
+                return Err(ParseError {
+                    kind: ParseErrorKind::InvalidEscape,
+                    message: "Trailing backslash in quoted string".to_string(),
+                    position: start + escape_start,
+                });
+            };
+            i += 2;
+            let unescaped = match c {
+                '\\' | '\'' | '"' => Some(c),
+                'n' | '\n' => Some('\n'),
+                '\r' if chars.get(i) == Some(&'\n') => {
+                    result.push('\r');
+                    i += 1;
+                    Some('\n')
+                }
+                't' => Some('\t'),
+                'r' => Some('\r'),
+                'x' | 'u' => {
+                    let braced = c == 'u' && chars.get(i) == Some(&'{');
+                    let digits = if braced {
+                        let len = chars[i..].iter().position(|&d| d == '}');
+                        let digits = len.map(|len| &chars[i + 1..i + len]);
+                        i += len.map_or(chars.len() - i, |len| len + 1);
+                        digits.filter(|d| d.len() <= 6)
+                    } else {
+                        let len = if c == 'x' { 2 } else { 4 };
+                        let digits = &chars[i..(i + len).min(chars.len())];
+                        i += digits.len();
+                        Some(digits).filter(|d| d.len() == len)
+                    };
+                    digits.and_then(hex_char)
+                }
+                _ => None,
+            };
+            match unescaped {
+                Some(ch) => result.push(ch),
+                None => {
+                    let escape: String = chars[escape_start..i].iter().collect();
+                    return Err(ParseError {
+                        kind: ParseErrorKind::InvalidEscape,
+                        message: format!("Invalid escape '{}' in quoted string", escape),
+                        position: start + escape_start,
+                    });
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
     fn parse_list(&mut self) -> ParseResult<ParsedElement> {
         if !self.consume('[') {
             return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedChar,
                 message: "Expected '['".to_string(),
                 position: self.pos,
             });
@@ -850,6 +1740,7 @@ impl OverrideParser {
 
             if !self.consume(',') {
                 return Err(ParseError {
+                    kind: ParseErrorKind::UnexpectedChar,
                     message: "Expected ',' or ']'".to_string(),
                     position: self.pos,
                 });
@@ -864,6 +1755,7 @@ impl OverrideParser {
     fn parse_dict(&mut self) -> ParseResult<ParsedElement> {
         if !self.consume('{') {
             return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedChar,
                 message: "Expected '{'".to_string(),
                 position: self.pos,
             });
@@ -879,6 +1771,7 @@ impl OverrideParser {
 
         loop {
             // Parse key
+            let key_pos = self.pos;
             let key = self.parse_dict_key()?;
 
             self.skip_whitespace();
@@ -886,6 +1779,7 @@ impl OverrideParser {
             // Expect : or =
             if !self.consume(':') && !self.consume('=') {
                 return Err(ParseError {
+                    kind: ParseErrorKind::UnexpectedChar,
                     message: "Expected ':' or '='".to_string(),
                     position: self.pos,
                 });
@@ -893,7 +1787,18 @@ impl OverrideParser {
 
             // Parse value
             let value = self.parse_element()?;
-            items.push((key, value));
+            match items.iter_mut().find(|(k, _)| *k == key) {
+                Some(_) if self.strict_dict_keys => {
+                    return Err(ParseError {
+                        kind: ParseErrorKind::DuplicateKey,
+                        message: format!("Duplicate key '{}' in dict", key),
+                        position: key_pos,
+                    });
+                }
+                // Last occurrence wins, keeping the first position
+                Some(entry) => entry.1 = value,
+                None => items.push((key, value)),
+            }
 
             self.skip_whitespace();
 
@@ -903,6 +1808,7 @@ impl OverrideParser {
 
             if !self.consume(',') {
                 return Err(ParseError {
+                    kind: ParseErrorKind::UnexpectedChar,
                     message: "Expected ',' or '}'".to_string(),
                     position: self.pos,
                 });
@@ -921,6 +1827,7 @@ impl OverrideParser {
             Some('\'') | Some('"') => {
                 // Dict keys cannot be quoted strings in override syntax
                 Err(ParseError {
+                    kind: ParseErrorKind::UnexpectedChar,
                     message: format!(
                         "no viable alternative at input '{{{}",
                         self.input[self.pos..].iter().take(10).collect::<String>()
@@ -944,7 +1851,7 @@ impl OverrideParser {
 
                 match elem {
                     ParsedElement::Int(i) => Ok(i.to_string()),
-                    ParsedElement::Float(f) => Ok(f.to_string()),
+                    ParsedElement::Float(f) => Ok(format_float(f)),
                     _ => unreachable!(),
                 }
             }
@@ -1003,6 +1910,7 @@ impl OverrideParser {
 
         if trimmed.is_empty() {
             return Err(ParseError {
+                kind: ParseErrorKind::ExpectedKey,
                 message: "Expected dict key".to_string(),
                 position: self.pos,
             });
@@ -1011,13 +1919,29 @@ impl OverrideParser {
         Ok(trimmed)
     }
 
+    /// Whether the input at the cursor is a float with no integer part, like ".5"
+    fn at_leading_dot_float(&self) -> bool {
+        self.peek() == Some('.') && self.input.get(self.pos + 1).is_some_and(|c| c.is_numeric())
+    }
+
+    /// Whether an exponent with digits (e.g. "e5", "E-2") starts at `pos`
+    fn exponent_at(&self, pos: usize) -> bool {
+        let mut chars = self.input[pos.min(self.input.len())..].iter();
+        if !matches!(chars.next(), Some('e' | 'E')) {
+            return false;
+        }
+        match chars.next() {
+            Some('-' | '+') => chars.next().is_some_and(|c| c.is_numeric()),
+            next => next.is_some_and(|c| c.is_numeric()),
+        }
+    }
+
     fn parse_number(&mut self) -> ParseResult<ParsedElement> {
         let _start_pos = self.pos;
         let mut num_str = String::new();
         let mut has_dot = false;
         let mut has_exp = false;
         let mut has_underscore = false;
-        let mut last_was_underscore = false;
 
         // Handle sign
         let sign_char = self.peek();
@@ -1058,12 +1982,11 @@ impl OverrideParser {
             if c.is_numeric() {
                 num_str.push(c);
                 self.advance();
-                last_was_underscore = false;
             } else if c == '_' {
-                // Python-style underscore in numbers (e.g., 10_000)
-                // Underscore must be between digits, not at start/end/consecutive
-                if num_str.is_empty() || last_was_underscore {
-                    // Invalid: starts with underscore or consecutive underscores
+                // Python-style underscore in numbers (e.g., 10_000, 1.000_5, 1e1_0)
+                // Underscore must be between digits in every segment, so not
+                // after a sign, dot, exponent marker or another underscore
+                if !num_str.ends_with(|p: char| p.is_numeric()) {
                     break;
                 }
                 // Check next char is a digit
@@ -1071,18 +1994,20 @@ impl OverrideParser {
                     has_underscore = true;
                     num_str.push(c);
                     self.advance();
-                    last_was_underscore = true;
                 } else {
                     // Underscore not followed by digit - stop
                     break;
                 }
             } else if c == '.' && !has_dot && !has_exp {
-                // Check if next char is a digit (to distinguish from key.subkey)
-                if self.pos + 1 < self.input.len() && self.input[self.pos + 1].is_numeric() {
+                // Check if next char is a digit (to distinguish from key.subkey),
+                // or an exponent after an integer part as in "1.e5"
+                let int_part = num_str.ends_with(|p: char| p.is_numeric());
+                if (self.pos + 1 < self.input.len() && self.input[self.pos + 1].is_numeric())
+                    || (int_part && self.exponent_at(self.pos + 1))
+                {
                     has_dot = true;
                     num_str.push(c);
                     self.advance();
-                    last_was_underscore = false;
                 } else {
                     break;
                 }
@@ -1090,7 +2015,6 @@ impl OverrideParser {
                 has_exp = true;
                 num_str.push(c);
                 self.advance();
-                last_was_underscore = false;
                 // Handle optional sign after exponent
                 if let Some(s) = self.peek() {
                     if s == '-' || s == '+' {
@@ -1115,6 +2039,7 @@ impl OverrideParser {
                 .parse::<f64>()
                 .map(ParsedElement::Float)
                 .map_err(|_| ParseError {
+                    kind: ParseErrorKind::InvalidNumber,
                     message: format!("Invalid float: {}", num_str),
                     position: self.pos,
                 })
@@ -1123,6 +2048,7 @@ impl OverrideParser {
                 .parse::<i64>()
                 .map(ParsedElement::Int)
                 .map_err(|_| ParseError {
+                    kind: ParseErrorKind::InvalidNumber,
                     message: format!("Invalid integer: {}", num_str),
                     position: self.pos,
                 })
@@ -1212,6 +2138,8 @@ impl OverrideParser {
                 || c == '+'
                 || c == '@'
                 || c == '|'
+                // `#` inside a value (`page#anchor`); after whitespace it starts a comment
+                || (c == '#' && !value.is_empty())
             {
                 value.push(c);
                 self.advance();
@@ -1247,6 +2175,7 @@ impl OverrideParser {
                         || next == '@'
                         || next == '|'
                         || next == '\\'
+                        || (next == '#' && !self.comments)
                     {
                         value.push_str(&temp_ws);
                         // Don't advance - the while loop will handle the next char
@@ -1267,6 +2196,7 @@ impl OverrideParser {
 
         if value.is_empty() {
             return Err(ParseError {
+                kind: ParseErrorKind::ExpectedValue,
                 message: "Expected value".to_string(),
                 position: self.pos,
             });
@@ -1313,6 +2243,7 @@ impl OverrideParser {
         // Consume opening paren
         if !self.consume('(') {
             return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedChar,
                 message: format!("Expected '(' after function name '{}'", name),
                 position: self.pos,
             });
@@ -1321,6 +2252,7 @@ impl OverrideParser {
         // Parse arguments
         let mut args: Vec<ParsedElement> = Vec::new();
         let mut kwargs: Vec<(String, ParsedElement)> = Vec::new();
+        let mut positions = ArgPositions::default();
         let mut seen_kwarg = false;
         self.skip_whitespace();
 
@@ -1338,6 +2270,7 @@ impl OverrideParser {
                             // This is a keyword argument
                             seen_kwarg = true;
                             self.skip_whitespace();
+                            positions.kwargs.push((ident.clone(), self.pos));
                             let value = self.parse_element()?;
                             kwargs.push((ident, value));
                         } else {
@@ -1346,11 +2279,13 @@ impl OverrideParser {
                             // Check if we've seen a kwarg before this positional
                             if seen_kwarg {
                                 return Err(ParseError {
+                                    kind: ParseErrorKind::InvalidArguments,
                                     message: "positional argument follows keyword argument"
                                         .to_string(),
                                     position: self.pos,
                                 });
                             }
+                            positions.args.push(arg_start);
                             let arg = self.parse_element()?;
                             args.push(arg);
                         }
@@ -1358,10 +2293,12 @@ impl OverrideParser {
                         // Check if we've seen a kwarg before this positional
                         if seen_kwarg {
                             return Err(ParseError {
+                                kind: ParseErrorKind::InvalidArguments,
                                 message: "positional argument follows keyword argument".to_string(),
                                 position: self.pos,
                             });
                         }
+                        positions.args.push(arg_start);
                         let arg = self.parse_element()?;
                         args.push(arg);
                     }
@@ -1375,6 +2312,7 @@ impl OverrideParser {
 
                 if !self.consume(',') {
                     return Err(ParseError {
+                        kind: ParseErrorKind::UnexpectedChar,
                         message: "Expected ',' or ')' in function arguments".to_string(),
                         position: self.pos,
                     });
@@ -1385,6 +2323,7 @@ impl OverrideParser {
         // Consume closing paren
         if !self.consume(')') {
             return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedChar,
                 message: "Expected ')' to close function call".to_string(),
                 position: self.pos,
             });
@@ -1398,6 +2337,7 @@ impl OverrideParser {
                     Ok(result) => return Ok(OverrideValue::Element(result)),
                     Err(e) => {
                         return Err(ParseError {
+                            kind: ParseErrorKind::EvaluationError,
                             message: e,
                             position: self.pos,
                         })
@@ -1407,71 +2347,22 @@ impl OverrideParser {
         }
 
         // Fall back to built-in functions
-        match name {
-            "choice" => self.build_choice_sweep(args),
-            "range" => self.build_range_sweep(args, &kwargs),
-            "interval" => self.build_interval_sweep(args, &kwargs),
-            "glob" => self.build_glob(args, &kwargs),
-            "tag" => self.build_tagged_sweep(args),
-            "shuffle" => self.build_shuffle(args, &kwargs), // won't reach here, handled above
-            "sort" => self.build_sort(args, &kwargs),       // won't reach here, handled above
-            "extend_list" => self.build_list_append(args),
-            "append" => self.build_list_append(args),
-            "prepend" => self.build_list_prepend(args),
-            "insert" => self.build_list_insert(args),
-            "remove_at" => self.build_list_remove_at(args),
-            "remove_value" => self.build_list_remove_value(args),
-            "list_clear" => self.build_list_clear(args),
-            "int" | "float" | "str" | "bool" | "json_str" => {
-                if args.is_empty() {
-                    return Err(ParseError {
-                        message: format!("{}() requires at least 1 argument", name),
-                        position: self.pos,
-                    });
-                }
-                if args.len() == 1 {
-                    // Single arg: apply cast directly
-                    Ok(OverrideValue::Element(
-                        self.apply_cast(name, args.into_iter().next().unwrap())?,
-                    ))
-                } else {
-                    // Multiple args: create a ChoiceSweep with simple_form=true, cast each element
-                    // Build source representation for error messages
-                    let source_parts: Vec<_> = args.iter().map(Self::elem_to_source).collect();
-                    let source = source_parts.join(",");
-
-                    let mut cast_elements: Vec<ParsedElement> = Vec::new();
-                    for arg in args {
-                        match self.apply_cast(name, arg) {
-                            Ok(cast_elem) => cast_elements.push(cast_elem),
-                            Err(e) => {
-                                // Re-wrap error with full simple choice context
-                                if let Some(pos) = e.message.find("': ") {
-                                    let reason = &e.message[pos + 3..];
-                                    return Err(ParseError {
-                                        message: format!(
-                                            "ValueError while evaluating '{}({})': {}",
-                                            name, source, reason
-                                        ),
-                                        position: e.position,
-                                    });
-                                }
-                                return Err(e);
-                            }
-                        }
-                    }
-                    Ok(OverrideValue::ChoiceSweep(ChoiceSweep {
-                        simple_form: true,
-                        shuffle: false,
-                        list: cast_elements,
-                        tags: std::collections::HashSet::new(),
-                    }))
-                }
-            }
-            _ => {
+        match BUILTIN_FUNCTIONS
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+        {
+            Some((_, build)) => build(self, name, args, &kwargs, &positions),
+            None => {
                 // Unknown function and no callback
+                let message = match suggest_function(name) {
+                    Some(suggestion) => {
+                        format!("Unknown function: {}. Did you mean '{}'?", name, suggestion)
+                    }
+                    None => format!("Unknown function: {}", name),
+                };
                 Err(ParseError {
-                    message: format!("Unknown function: {}", name),
+                    kind: ParseErrorKind::UnknownFunction,
+                    message,
                     position: self.pos,
                 })
             }
@@ -1483,6 +2374,7 @@ impl OverrideParser {
         // Consume opening paren
         if !self.consume('(') {
             return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedChar,
                 message: format!("Expected '(' after function name '{}'", name),
                 position: self.pos,
             });
@@ -1597,6 +2489,7 @@ impl OverrideParser {
 
                 if !self.consume(',') {
                     return Err(ParseError {
+                        kind: ParseErrorKind::UnexpectedChar,
                         message: "Expected ',' or ')' in function arguments".to_string(),
                         position: self.pos,
                     });
@@ -1607,6 +2500,7 @@ impl OverrideParser {
         // Consume closing paren
         if !self.consume(')') {
             return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedChar,
                 message: "Expected ')' to close function call".to_string(),
                 position: self.pos,
             });
@@ -1706,6 +2600,7 @@ impl OverrideParser {
                         OverrideValue::IntervalSweep(_) => {
                             // sort(interval(...)) is not valid
                             Err(ParseError {
+                                kind: ParseErrorKind::EvaluationError,
                                 message:
                                     "Function 'interval' returns a sweep, which cannot be used here"
                                         .to_string(),
@@ -1741,6 +2636,7 @@ impl OverrideParser {
                             Ok(OverrideValue::RangeSweep(rs))
                         }
                         _ => Err(ParseError {
+                            kind: ParseErrorKind::InvalidArguments,
                             message: "tag() requires a sweep as final argument".to_string(),
                             position: self.pos,
                         }),
@@ -1750,6 +2646,7 @@ impl OverrideParser {
                     // tag(tag1, a, b, c) - creates a ChoiceSweep with tags
                     if args.len() < 2 {
                         return Err(ParseError {
+                            kind: ParseErrorKind::InvalidArguments,
                             message: "tag() requires at least one tag and a sweep or values"
                                 .to_string(),
                             position: self.pos,
@@ -1797,6 +2694,7 @@ impl OverrideParser {
                                         if let Some(pos) = e.message.find("': ") {
                                             let reason = &e.message[pos + 3..];
                                             return Err(ParseError {
+                                                kind: ParseErrorKind::EvaluationError,
                                                 message: format!(
                                                     "ValueError while evaluating '{}({})': {}",
                                                     name, sweep_source, reason
@@ -1837,6 +2735,7 @@ impl OverrideParser {
                                 }
                                 _ => {
                                     Err(ParseError {
+                                        kind: ParseErrorKind::EvaluationError,
                                         message: format!("ValueError while evaluating '{}({})': Range can only be cast to int or float", name, sweep_source),
                                         position: self.pos,
                                     })
@@ -1862,6 +2761,7 @@ impl OverrideParser {
                                 }
                                 _ => {
                                     Err(ParseError {
+                                        kind: ParseErrorKind::EvaluationError,
                                         message: format!("ValueError while evaluating '{}({})': Intervals cannot be cast to {}", name, sweep_source, name),
                                         position: self.pos,
                                     })
@@ -1869,12 +2769,14 @@ impl OverrideParser {
                             }
                         }
                         _ => Err(ParseError {
+                            kind: ParseErrorKind::EvaluationError,
                             message: format!("{}() cannot be applied to this sweep type", name),
                             position: self.pos,
                         }),
                     }
                 } else if args.is_empty() {
                     Err(ParseError {
+                        kind: ParseErrorKind::InvalidArguments,
                         message: format!("{}() requires at least 1 argument", name),
                         position: self.pos,
                     })
@@ -1898,6 +2800,7 @@ impl OverrideParser {
                                 if let Some(pos) = e.message.find("': ") {
                                     let reason = &e.message[pos + 3..];
                                     return Err(ParseError {
+                                        kind: ParseErrorKind::EvaluationError,
                                         message: format!(
                                             "ValueError while evaluating '{}({})': {}",
                                             name, source, reason
@@ -1918,15 +2821,68 @@ impl OverrideParser {
                 }
             }
             _ => Err(ParseError {
+                kind: ParseErrorKind::Internal,
                 message: format!("Internal error: unexpected function {}", name),
                 position: self.pos,
             }),
         }
     }
 
+    /// Build a cast call (`int(...)`, `str(...)`, ...): one argument is cast
+    /// directly, several become a simple choice sweep of cast elements
+    fn build_cast_call(&self, name: &str, args: Vec<ParsedElement>) -> ParseResult<OverrideValue> {
+        if args.is_empty() {
+            return Err(ParseError {
+                kind: ParseErrorKind::InvalidArguments,
+                message: format!("{}() requires at least 1 argument", name),
+                position: self.pos,
+            });
+        }
+        if args.len() == 1 {
+            // Single arg: apply cast directly
+            Ok(OverrideValue::Element(
+                self.apply_cast(name, args.into_iter().next().unwrap())?,
+            ))
+        } else {
+            // Multiple args: create a ChoiceSweep with simple_form=true, cast each element
+            // Build source representation for error messages
+            let source_parts: Vec<_> = args.iter().map(Self::elem_to_source).collect();
+            let source = source_parts.join(",");
+
+            let mut cast_elements: Vec<ParsedElement> = Vec::new();
+            for arg in args {
+                match self.apply_cast(name, arg) {
+                    Ok(cast_elem) => cast_elements.push(cast_elem),
+                    Err(e) => {
+                        // Re-wrap error with full simple choice context
+                        if let Some(pos) = e.message.find("': ") {
+                            let reason = &e.message[pos + 3..];
+                            return Err(ParseError {
+                                kind: ParseErrorKind::EvaluationError,
+                                message: format!(
+                                    "ValueError while evaluating '{}({})': {}",
+                                    name, source, reason
+                                ),
+                                position: e.position,
+                            });
+                        }
+                        return Err(e);
+                    }
+                }
+            }
+            Ok(OverrideValue::ChoiceSweep(ChoiceSweep {
+                simple_form: true,
+                shuffle: false,
+                list: cast_elements,
+                tags: std::collections::HashSet::new(),
+            }))
+        }
+    }
+
     fn build_choice_sweep(&self, args: Vec<ParsedElement>) -> ParseResult<OverrideValue> {
         if args.is_empty() {
             return Err(ParseError {
+                kind: ParseErrorKind::InvalidArguments,
                 message: "choice() requires at least one argument".to_string(),
                 position: self.pos,
             });
@@ -1943,6 +2899,7 @@ impl OverrideParser {
         &self,
         args: Vec<ParsedElement>,
         kwargs: &[(String, ParsedElement)],
+        positions: &ArgPositions,
     ) -> ParseResult<OverrideValue> {
         // Helper to check if an element is a float (not an integer)
         fn is_float_element(elem: &ParsedElement) -> bool {
@@ -1962,22 +2919,23 @@ impl OverrideParser {
                 // All kwargs form
                 let start = if let Some((_, v)) = start_kwarg {
                     has_explicit_float |= is_float_element(v);
-                    self.element_to_f64(v)?
+                    self.element_to_f64(v, positions.kwarg("start"))?
                 } else {
                     0.0
                 };
                 let stop = if let Some((_, v)) = stop_kwarg {
                     has_explicit_float |= is_float_element(v);
-                    self.element_to_f64(v)?
+                    self.element_to_f64(v, positions.kwarg("stop"))?
                 } else {
                     return Err(ParseError {
+                        kind: ParseErrorKind::InvalidArguments,
                         message: "range() requires 'stop' argument".to_string(),
                         position: self.pos,
                     });
                 };
                 let step = if let Some((_, v)) = step_kwarg {
                     has_explicit_float |= is_float_element(v);
-                    self.element_to_f64(v)?
+                    self.element_to_f64(v, positions.kwarg("step"))?
                 } else {
                     1.0
                 };
@@ -1997,18 +2955,18 @@ impl OverrideParser {
                 match args.len() {
                     1 => {
                         // range(stop) -> 0 to stop, check for step kwarg
-                        let stop = self.element_to_f64(&args[0])?;
+                        let stop = self.element_to_f64(&args[0], positions.arg(0))?;
                         let step = if let Some((_, v)) = step_kwarg {
-                            self.element_to_f64(v)?
+                            self.element_to_f64(v, positions.kwarg("step"))?
                         } else {
                             1.0
                         };
                         // Also check for stop kwarg when first arg might be start
                         if let Some((_, v)) = stop_kwarg {
-                            let start = self.element_to_f64(&args[0])?;
-                            let stop = self.element_to_f64(v)?;
+                            let start = self.element_to_f64(&args[0], positions.arg(0))?;
+                            let stop = self.element_to_f64(v, positions.kwarg("stop"))?;
                             let step = if let Some((_, v)) = step_kwarg {
-                                self.element_to_f64(v)?
+                                self.element_to_f64(v, positions.kwarg("step"))?
                             } else {
                                 1.0
                             };
@@ -2019,10 +2977,10 @@ impl OverrideParser {
                     }
                     2 => {
                         // range(start, stop), check for step kwarg
-                        let start = self.element_to_f64(&args[0])?;
-                        let stop = self.element_to_f64(&args[1])?;
+                        let start = self.element_to_f64(&args[0], positions.arg(0))?;
+                        let stop = self.element_to_f64(&args[1], positions.arg(1))?;
                         let step = if let Some((_, v)) = step_kwarg {
-                            self.element_to_f64(v)?
+                            self.element_to_f64(v, positions.kwarg("step"))?
                         } else {
                             1.0
                         };
@@ -2030,13 +2988,14 @@ impl OverrideParser {
                     }
                     3 => {
                         // range(start, stop, step)
-                        let start = self.element_to_f64(&args[0])?;
-                        let stop = self.element_to_f64(&args[1])?;
-                        let step = self.element_to_f64(&args[2])?;
+                        let start = self.element_to_f64(&args[0], positions.arg(0))?;
+                        let stop = self.element_to_f64(&args[1], positions.arg(1))?;
+                        let step = self.element_to_f64(&args[2], positions.arg(2))?;
                         (start, stop, step)
                     }
                     _ => {
                         return Err(ParseError {
+                            kind: ParseErrorKind::InvalidArguments,
                             message: "range() requires 1, 2, or 3 arguments".to_string(),
                             position: self.pos,
                         });
@@ -2064,6 +3023,7 @@ impl OverrideParser {
         &self,
         args: Vec<ParsedElement>,
         kwargs: &[(String, ParsedElement)],
+        positions: &ArgPositions,
     ) -> ParseResult<OverrideValue> {
         // Support both positional and keyword arguments
         let start_kwarg = kwargs.iter().find(|(k, _)| k == "start");
@@ -2072,28 +3032,31 @@ impl OverrideParser {
         let (start, end) = if args.is_empty() && (start_kwarg.is_some() || end_kwarg.is_some()) {
             // All kwargs form
             let start = if let Some((_, v)) = start_kwarg {
-                self.element_to_f64(v)?
+                self.element_to_f64(v, positions.kwarg("start"))?
             } else {
                 return Err(ParseError {
+                    kind: ParseErrorKind::InvalidArguments,
                     message: "interval() requires 'start' argument".to_string(),
                     position: self.pos,
                 });
             };
             let end = if let Some((_, v)) = end_kwarg {
-                self.element_to_f64(v)?
+                self.element_to_f64(v, positions.kwarg("end"))?
             } else {
                 return Err(ParseError {
+                    kind: ParseErrorKind::InvalidArguments,
                     message: "interval() requires 'end' argument".to_string(),
                     position: self.pos,
                 });
             };
             (start, end)
         } else if args.len() == 2 {
-            let start = self.element_to_f64(&args[0])?;
-            let end = self.element_to_f64(&args[1])?;
+            let start = self.element_to_f64(&args[0], positions.arg(0))?;
+            let end = self.element_to_f64(&args[1], positions.arg(1))?;
             (start, end)
         } else {
             return Err(ParseError {
+                kind: ParseErrorKind::InvalidArguments,
                 message: "interval() requires exactly 2 arguments".to_string(),
                 position: self.pos,
             });
@@ -2112,13 +3075,14 @@ impl OverrideParser {
         args: Vec<ParsedElement>,
         kwargs: &[(String, ParsedElement)],
     ) -> ParseResult<OverrideValue> {
-        // glob(include, exclude=None) or glob(include=*, exclude=*)
+        // glob(include, exclude=None, case_insensitive=false) or glob(include=*, exclude=*)
         let include = if !args.is_empty() {
             self.element_to_string_list(&args[0])?
         } else if let Some((_, v)) = kwargs.iter().find(|(k, _)| k == "include") {
             self.element_to_string_list(v)?
         } else {
             return Err(ParseError {
+                kind: ParseErrorKind::InvalidArguments,
                 message: "glob() requires at least include pattern".to_string(),
                 position: self.pos,
             });
@@ -2131,8 +3095,20 @@ impl OverrideParser {
             .transpose()?
             .unwrap_or_default();
 
+        let case_insensitive = match kwargs.iter().find(|(k, _)| k == "case_insensitive") {
+            None => false,
+            Some((_, ParsedElement::Bool(b))) => *b,
+            Some(_) => {
+                return Err(ParseError {
+                    kind: ParseErrorKind::InvalidArguments,
+                    message: "glob() case_insensitive must be true or false".to_string(),
+                    position: self.pos,
+                })
+            }
+        };
+
         // Return as a special element - glob needs custom handling
-        Ok(OverrideValue::Element(ParsedElement::Dict(vec![
+        let mut entries = vec![
             (
                 "_type".to_string(),
                 ParsedElement::String("glob".to_string()),
@@ -2145,13 +3121,18 @@ impl OverrideParser {
                 "exclude".to_string(),
                 ParsedElement::List(exclude.into_iter().map(ParsedElement::String).collect()),
             ),
-        ])))
+        ];
+        if case_insensitive {
+            entries.push(("case_insensitive".to_string(), ParsedElement::Bool(true)));
+        }
+        Ok(OverrideValue::Element(ParsedElement::Dict(entries)))
     }
 
     fn build_tagged_sweep(&self, args: Vec<ParsedElement>) -> ParseResult<OverrideValue> {
         // tag(tag1, tag2, ..., sweep)
         if args.len() < 2 {
             return Err(ParseError {
+                kind: ParseErrorKind::InvalidArguments,
                 message: "tag() requires at least one tag and a sweep".to_string(),
                 position: self.pos,
             });
@@ -2160,6 +3141,7 @@ impl OverrideParser {
         // Last argument should be a sweep - for now just return as is
         // TODO: Handle tags on sweeps
         Err(ParseError {
+            kind: ParseErrorKind::Internal,
             message: "tag() function not fully implemented yet".to_string(),
             position: self.pos,
         })
@@ -2188,6 +3170,7 @@ impl OverrideParser {
 
         if args.is_empty() {
             return Err(ParseError {
+                kind: ParseErrorKind::InvalidArguments,
                 message: "shuffle() requires at least 1 argument".to_string(),
                 position: self.pos,
             });
@@ -2271,6 +3254,7 @@ impl OverrideParser {
             if let Err((t1, t2)) = validate_comparable(&items) {
                 let args_source: Vec<_> = items.iter().map(Self::elem_to_source).collect();
                 return Err(ParseError {
+                    kind: ParseErrorKind::EvaluationError,
                     message: format!("TypeError while evaluating 'sort([{args_source}])': '<' not supported between instances of '{}' and '{}'", t1, t2, args_source=args_source.join(",")),
                     position: self.pos,
                 });
@@ -2292,6 +3276,7 @@ impl OverrideParser {
 
         if args.is_empty() {
             return Err(ParseError {
+                kind: ParseErrorKind::InvalidArguments,
                 message: "sort() requires at least 1 argument".to_string(),
                 position: self.pos,
             });
@@ -2306,6 +3291,7 @@ impl OverrideParser {
                 if let Err((t1, t2)) = validate_comparable(items) {
                     let args_source: Vec<_> = items.iter().map(Self::elem_to_source).collect();
                     return Err(ParseError {
+                        kind: ParseErrorKind::EvaluationError,
                         message: format!("TypeError while evaluating 'sort([{args_source}])': '<' not supported between instances of '{}' and '{}'", t1, t2, args_source=args_source.join(",")),
                         position: self.pos,
                     });
@@ -2333,6 +3319,7 @@ impl OverrideParser {
         if let Err((t1, t2)) = validate_comparable(&args) {
             let args_source: Vec<_> = args.iter().map(Self::elem_to_source).collect();
             return Err(ParseError {
+                kind: ParseErrorKind::EvaluationError,
                 message: format!("TypeError while evaluating 'sort({})': '<' not supported between instances of '{}' and '{}'", args_source.join(","), t1, t2),
                 position: self.pos,
             });
@@ -2378,10 +3365,27 @@ impl OverrideParser {
         }))
     }
 
+    fn build_list_append_unique(&self, args: Vec<ParsedElement>) -> ParseResult<OverrideValue> {
+        Ok(OverrideValue::ListExtension(ListExtension {
+            operation: ListOperationType::AppendUnique,
+            values: args,
+            index: None,
+        }))
+    }
+
+    fn build_list_prepend_unique(&self, args: Vec<ParsedElement>) -> ParseResult<OverrideValue> {
+        Ok(OverrideValue::ListExtension(ListExtension {
+            operation: ListOperationType::PrependUnique,
+            values: args,
+            index: None,
+        }))
+    }
+
     fn build_list_insert(&self, args: Vec<ParsedElement>) -> ParseResult<OverrideValue> {
         // insert(index, value) - requires at least 2 arguments
         if args.len() < 2 {
             return Err(ParseError {
+                kind: ParseErrorKind::InvalidArguments,
                 message: "insert() requires at least 2 arguments: insert(index, value, ...)"
                     .to_string(),
                 position: self.pos,
@@ -2393,6 +3397,7 @@ impl OverrideParser {
             ParsedElement::Int(i) => *i,
             _ => {
                 return Err(ParseError {
+                    kind: ParseErrorKind::InvalidArguments,
                     message: "insert() first argument must be an integer index".to_string(),
                     position: self.pos,
                 })
@@ -2413,6 +3418,7 @@ impl OverrideParser {
         // remove_at(index) - requires exactly 1 argument
         if args.len() != 1 {
             return Err(ParseError {
+                kind: ParseErrorKind::InvalidArguments,
                 message: "remove_at() requires exactly 1 argument: remove_at(index)".to_string(),
                 position: self.pos,
             });
@@ -2423,6 +3429,7 @@ impl OverrideParser {
             ParsedElement::Int(i) => *i,
             _ => {
                 return Err(ParseError {
+                    kind: ParseErrorKind::InvalidArguments,
                     message: "remove_at() argument must be an integer index".to_string(),
                     position: self.pos,
                 })
@@ -2440,6 +3447,7 @@ impl OverrideParser {
         // remove_value(value) - requires at least 1 argument
         if args.is_empty() {
             return Err(ParseError {
+                kind: ParseErrorKind::InvalidArguments,
                 message: "remove_value() requires at least 1 argument".to_string(),
                 position: self.pos,
             });
@@ -2456,6 +3464,7 @@ impl OverrideParser {
         // list_clear() - no arguments
         if !args.is_empty() {
             return Err(ParseError {
+                kind: ParseErrorKind::InvalidArguments,
                 message: "list_clear() takes no arguments".to_string(),
                 position: self.pos,
             });
@@ -2472,24 +3481,7 @@ impl OverrideParser {
     fn elem_to_source(elem: &ParsedElement) -> String {
         match elem {
             ParsedElement::Int(i) => i.to_string(),
-            ParsedElement::Float(f) => {
-                if f.is_nan() {
-                    "nan".to_string()
-                } else if f.is_infinite() {
-                    if *f > 0.0 {
-                        "inf".to_string()
-                    } else {
-                        "-inf".to_string()
-                    }
-                } else {
-                    let s = f.to_string();
-                    if !s.contains('.') {
-                        format!("{}.0", s)
-                    } else {
-                        s
-                    }
-                }
-            }
+            ParsedElement::Float(f) => format_float(*f),
             ParsedElement::Bool(b) => if *b { "true" } else { "false" }.to_string(),
             ParsedElement::String(s) => s.clone(),
             ParsedElement::QuotedString(qs) => format!("'{}'", qs.text),
@@ -2521,64 +3513,21 @@ impl OverrideParser {
                 }
             }
             OverrideValue::RangeSweep(rs) => {
-                let start = rs
-                    .start
-                    .map(|s| {
-                        if rs.is_int {
-                            // Integer range: show without decimal
-                            format!("{}", s as i64)
-                        } else {
-                            // Float range: always show decimal point
-                            let str_val = s.to_string();
-                            if str_val.contains('.') {
-                                str_val
-                            } else {
-                                format!("{}.0", str_val)
-                            }
-                        }
-                    })
-                    .unwrap_or_default();
-                let stop = rs
-                    .stop
-                    .map(|s| {
-                        if rs.is_int {
-                            format!("{}", s as i64)
-                        } else {
-                            let str_val = s.to_string();
-                            if str_val.contains('.') {
-                                str_val
-                            } else {
-                                format!("{}.0", str_val)
-                            }
-                        }
-                    })
-                    .unwrap_or_default();
+                // Integer ranges show their bounds without a decimal point
+                let bound = |b: f64| {
+                    if rs.is_int {
+                        format!("{}", b as i64)
+                    } else {
+                        format_float(b)
+                    }
+                };
+                let start = rs.start.map(bound).unwrap_or_default();
+                let stop = rs.stop.map(bound).unwrap_or_default();
                 format!("range({},{})", start, stop)
             }
             OverrideValue::IntervalSweep(is) => {
-                let start = is
-                    .start
-                    .map(|s| {
-                        let str_val = s.to_string();
-                        // Always show decimal point for floats
-                        if str_val.contains('.') {
-                            str_val
-                        } else {
-                            format!("{}.0", str_val)
-                        }
-                    })
-                    .unwrap_or("?".to_string());
-                let end = is
-                    .end
-                    .map(|e| {
-                        let str_val = e.to_string();
-                        if str_val.contains('.') {
-                            str_val
-                        } else {
-                            format!("{}.0", str_val)
-                        }
-                    })
-                    .unwrap_or("?".to_string());
+                let start = is.start.map(format_float).unwrap_or("?".to_string());
+                let end = is.end.map(format_float).unwrap_or("?".to_string());
                 format!("interval({}, {})", start, end)
             }
             OverrideValue::GlobChoiceSweep(gs) => {
@@ -2600,11 +3549,13 @@ impl OverrideParser {
                     ParsedElement::Float(f) => {
                         if f.is_infinite() {
                             Err(ParseError {
+                                kind: ParseErrorKind::EvaluationError,
                                 message: "OverflowError while evaluating 'int(inf)': cannot convert float infinity to integer".to_string(),
                                 position: self.pos,
                             })
                         } else if f.is_nan() {
                             Err(ParseError {
+                                kind: ParseErrorKind::EvaluationError,
                                 message: "ValueError while evaluating 'int(nan)': cannot convert float NaN to integer".to_string(),
                                 position: self.pos,
                             })
@@ -2618,6 +3569,7 @@ impl OverrideParser {
                         s.parse::<i64>()
                             .map(ParsedElement::Int)
                             .map_err(|_| ParseError {
+                                kind: ParseErrorKind::EvaluationError,
                                 message: format!("ValueError while evaluating 'int({})': invalid literal for int() with base 10: '{}'", source, s),
                                 position: self.pos,
                             })
@@ -2635,6 +3587,7 @@ impl OverrideParser {
                             if let Some(caps) = e.message.find("': ") {
                                 let reason = &e.message[caps + 3..];
                                 ParseError {
+                                    kind: ParseErrorKind::EvaluationError,
                                     message: format!(
                                         "ValueError while evaluating 'int({})': {}",
                                         source, reason
@@ -2660,6 +3613,7 @@ impl OverrideParser {
                             if let Some(caps) = e.message.find("': ") {
                                 let reason = &e.message[caps + 3..];
                                 ParseError {
+                                    kind: ParseErrorKind::EvaluationError,
                                     message: format!(
                                         "ValueError while evaluating 'int({})': {}",
                                         source, reason
@@ -2684,6 +3638,7 @@ impl OverrideParser {
                         s.parse::<f64>()
                             .map(ParsedElement::Float)
                             .map_err(|_| ParseError {
+                                kind: ParseErrorKind::EvaluationError,
                                 message: format!("ValueError while evaluating 'float({})': could not convert string to float: '{}'", source, s),
                                 position: self.pos,
                             })
@@ -2701,6 +3656,7 @@ impl OverrideParser {
                             if let Some(caps) = e.message.find("': ") {
                                 let reason = &e.message[caps + 3..];
                                 ParseError {
+                                    kind: ParseErrorKind::EvaluationError,
                                     message: format!(
                                         "ValueError while evaluating 'float({})': {}",
                                         source, reason
@@ -2727,6 +3683,7 @@ impl OverrideParser {
                             if let Some(caps) = e.message.find("': ") {
                                 let reason = &e.message[caps + 3..];
                                 ParseError {
+                                    kind: ParseErrorKind::EvaluationError,
                                     message: format!(
                                         "ValueError while evaluating 'float({})': {}",
                                         source, reason
@@ -2744,25 +3701,7 @@ impl OverrideParser {
             "str" => {
                 match &elem {
                     ParsedElement::Int(i) => Ok(ParsedElement::String(i.to_string())),
-                    ParsedElement::Float(f) => {
-                        // Handle special cases: nan, inf, -inf
-                        if f.is_nan() {
-                            Ok(ParsedElement::String("nan".to_string()))
-                        } else if f.is_infinite() {
-                            if *f > 0.0 {
-                                Ok(ParsedElement::String("inf".to_string()))
-                            } else {
-                                Ok(ParsedElement::String("-inf".to_string()))
-                            }
-                        } else {
-                            let s = f.to_string();
-                            if s.contains('.') || s.contains('e') || s.contains('E') {
-                                Ok(ParsedElement::String(s))
-                            } else {
-                                Ok(ParsedElement::String(format!("{}.0", s)))
-                            }
-                        }
-                    }
+                    ParsedElement::Float(f) => Ok(ParsedElement::String(format_float(*f))),
                     ParsedElement::String(s) => Ok(ParsedElement::String(s.clone())),
                     ParsedElement::QuotedString(qs) => Ok(ParsedElement::String(qs.text.clone())),
                     ParsedElement::Bool(b) => Ok(ParsedElement::String(
@@ -2801,6 +3740,7 @@ impl OverrideParser {
                             "true" | "yes" | "on" | "1" => Ok(ParsedElement::Bool(true)),
                             "false" | "no" | "off" | "0" => Ok(ParsedElement::Bool(false)),
                             _ => Err(ParseError {
+                                kind: ParseErrorKind::EvaluationError,
                                 message: format!("ValueError while evaluating 'bool({})': Cannot cast '{}' to bool", source, s),
                                 position: self.pos,
                             }),
@@ -2818,6 +3758,7 @@ impl OverrideParser {
                             if let Some(caps) = e.message.find("': ") {
                                 let reason = &e.message[caps + 3..];
                                 ParseError {
+                                    kind: ParseErrorKind::EvaluationError,
                                     message: format!(
                                         "ValueError while evaluating 'bool({})': {}",
                                         source, reason
@@ -2843,6 +3784,7 @@ impl OverrideParser {
                             if let Some(caps) = e.message.find("': ") {
                                 let reason = &e.message[caps + 3..];
                                 ParseError {
+                                    kind: ParseErrorKind::EvaluationError,
                                     message: format!(
                                         "ValueError while evaluating 'bool({})': {}",
                                         source, reason
@@ -2872,13 +3814,7 @@ impl OverrideParser {
                         } else if f.is_nan() {
                             Ok(ParsedElement::String("NaN".to_string()))
                         } else {
-                            let s = f.to_string();
-                            // Preserve decimal for whole numbers
-                            if !s.contains('.') {
-                                Ok(ParsedElement::String(format!("{}.0", s)))
-                            } else {
-                                Ok(ParsedElement::String(s))
-                            }
+                            Ok(ParsedElement::String(format_float(*f)))
                         }
                     }
                     ParsedElement::String(s) => {
@@ -2932,23 +3868,28 @@ impl OverrideParser {
                 }
             }
             _ => Err(ParseError {
+                kind: ParseErrorKind::EvaluationError,
                 message: format!("Unknown cast type: {}", cast_type),
                 position: self.pos,
             }),
         }
     }
 
-    fn element_to_f64(&self, elem: &ParsedElement) -> ParseResult<f64> {
+    /// Convert a numeric argument; errors point at `position`, where the
+    /// argument starts in the input
+    fn element_to_f64(&self, elem: &ParsedElement, position: usize) -> ParseResult<f64> {
         match elem {
             ParsedElement::Int(i) => Ok(*i as f64),
             ParsedElement::Float(f) => Ok(*f),
             ParsedElement::String(s) => s.parse::<f64>().map_err(|_| ParseError {
+                kind: ParseErrorKind::ExpectedValue,
                 message: format!("Expected number, got '{}'", s),
-                position: self.pos,
+                position,
             }),
             _ => Err(ParseError {
+                kind: ParseErrorKind::ExpectedValue,
                 message: "Expected number".to_string(),
-                position: self.pos,
+                position,
             }),
         }
     }
@@ -2963,12 +3904,14 @@ impl OverrideParser {
                     ParsedElement::String(s) => Ok(s.clone()),
                     ParsedElement::QuotedString(qs) => Ok(qs.text.clone()),
                     _ => Err(ParseError {
+                        kind: ParseErrorKind::ExpectedValue,
                         message: "Expected string in list".to_string(),
                         position: self.pos,
                     }),
                 })
                 .collect(),
             _ => Err(ParseError {
+                kind: ParseErrorKind::ExpectedValue,
                 message: "Expected string or list of strings".to_string(),
                 position: self.pos,
             }),
@@ -3251,4 +4194,813 @@ mod tests {
             panic!("Expected string");
         }
     }
+
+    #[test]
+    fn test_unknown_function_suggests_choice() {
+        let err = OverrideParser::parse("db=choic(a,b)").unwrap_err();
+        assert_eq!(
+            err.message,
+            "Unknown function: choic. Did you mean 'choice'?"
+        );
+    }
+
+    #[test]
+    fn test_unknown_function_suggests_range() {
+        let err = OverrideParser::parse("x=rang(1,10)").unwrap_err();
+        assert_eq!(err.message, "Unknown function: rang. Did you mean 'range'?");
+    }
+
+    #[test]
+    fn test_unknown_function_without_suggestion() {
+        let err = OverrideParser::parse("x=frobnicate(1)").unwrap_err();
+        assert_eq!(err.message, "Unknown function: frobnicate");
+    }
+
+    fn parse_duration_value(input: &str) -> Option<OverrideValue> {
+        OverrideParser::parse_with_durations(input).unwrap().value
+    }
+
+    #[test]
+    fn test_parse_duration_single_unit() {
+        assert_eq!(
+            parse_duration_value("timeout=30s"),
+            Some(OverrideValue::Element(ParsedElement::Int(30)))
+        );
+        assert_eq!(
+            parse_duration_value("timeout=2h"),
+            Some(OverrideValue::Element(ParsedElement::Int(7200)))
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_compound() {
+        assert_eq!(
+            parse_duration_value("timeout=1h30m"),
+            Some(OverrideValue::Element(ParsedElement::Int(5400)))
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_sub_second() {
+        assert_eq!(
+            parse_duration_value("timeout=500ms"),
+            Some(OverrideValue::Element(ParsedElement::Float(0.5)))
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_invalid_unit_is_string() {
+        assert_eq!(
+            parse_duration_value("timeout=5x"),
+            Some(OverrideValue::Element(ParsedElement::String(
+                "5x".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_disabled_by_default() {
+        let result = OverrideParser::parse("timeout=30s").unwrap();
+        assert_eq!(
+            result.value,
+            Some(OverrideValue::Element(ParsedElement::String(
+                "30s".to_string()
+            )))
+        );
+    }
+
+    fn escaped_text(input: &str) -> String {
+        match OverrideParser::parse_with_escapes(input).unwrap().value {
+            Some(OverrideValue::Element(ParsedElement::QuotedString(qs))) => qs.text,
+            other => panic!("Expected quoted string, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_quoted_string_escapes() {
+        assert_eq!(escaped_text(r#"name="line1\nline2""#), "line1\nline2");
+        assert_eq!(escaped_text(r"name='a\tb\r'"), "a\tb\r");
+        assert_eq!(escaped_text(r"name='caf\u00e9'"), "café");
+        assert_eq!(escaped_text(r#"emoji="\u{1F600}""#), "\u{1F600}");
+        assert_eq!(escaped_text(r"name='\x41\\n\'x\''"), "A\\n'x'");
+
+        let err = OverrideParser::parse_with_escapes(r"x='a\qb'").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::InvalidEscape);
+        assert_eq!(err.message, r"Invalid escape '\q' in quoted string");
+        assert_eq!(err.position, 4);
+        for input in [r"x='\u12'", r"x='\x4g'", r"x='\u{110000}'", r"x='\u{1F600'"] {
+            let err = OverrideParser::parse_with_escapes(input).unwrap_err();
+            assert_eq!(err.kind, ParseErrorKind::InvalidEscape, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_quoted_string_escapes_disabled_by_default() {
+        let result = OverrideParser::parse(r"path='C:\temp\new'").unwrap();
+        match result.value {
+            Some(OverrideValue::Element(ParsedElement::QuotedString(qs))) => {
+                assert_eq!(qs.text, r"C:\temp\new")
+            }
+            other => panic!("Expected quoted string, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_many_using_escapes() {
+        let parse = |s: &str| OverrideParser::new(s).with_escapes(true).parse_complete();
+        let results = OverrideParser::parse_many_using(&[r"a@x,y='1\t2'"], parse).unwrap();
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            match &result.value {
+                Some(OverrideValue::Element(ParsedElement::QuotedString(qs))) => {
+                    assert_eq!(qs.text, "1\t2")
+                }
+                other => panic!("Expected quoted string, got {:?}", other),
+            }
+        }
+
+        let err = OverrideParser::parse_many_using(&["a=1", r"b='\q'"], parse).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::InvalidEscape);
+        assert!(err.message.starts_with("Error parsing override 1: "));
+        let (results, errors) =
+            OverrideParser::parse_many_collect_using(&[r"b='\q'", "a=1"], parse);
+        assert!(results[0].is_none() && results[1].is_some());
+        assert_eq!(errors[0].0, 0);
+    }
+
+    #[test]
+    fn test_parse_line_continuation() {
+        let expected = ParsedElement::List(vec![
+            ParsedElement::Int(1),
+            ParsedElement::Int(2),
+            ParsedElement::Int(3),
+        ]);
+        for input in ["items=[1, 2,\\\n 3]", "items=[1, 2,\\\r\n 3]"] {
+            let result = OverrideParser::parse(input).unwrap();
+            assert_eq!(
+                result.value,
+                Some(OverrideValue::Element(expected.clone())),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_quoted_backslash_newline() {
+        // Inside quotes a backslash before a line break is a newline, not a continuation
+        for (result, expected) in [
+            (
+                OverrideParser::parse("msg='line1\\\nline2'"),
+                "line1\nline2",
+            ),
+            (
+                OverrideParser::parse_with_escapes("msg='line1\\\nline2'"),
+                "line1\nline2",
+            ),
+            (
+                OverrideParser::parse("msg='line1\\\r\nline2'"),
+                "line1\r\nline2",
+            ),
+            (
+                OverrideParser::parse_with_escapes("msg='line1\\\r\nline2'"),
+                "line1\r\nline2",
+            ),
+        ] {
+            match result.unwrap().value {
+                Some(OverrideValue::Element(ParsedElement::QuotedString(qs))) => {
+                    assert_eq!(qs.text, expected)
+                }
+                other => panic!("Expected quoted string, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_error_kinds() {
+        let kind = |input: &str| OverrideParser::parse(input).unwrap_err().kind;
+
+        assert_eq!(kind("db"), ParseErrorKind::ExpectedEquals);
+        assert_eq!(kind("=value"), ParseErrorKind::ExpectedKey);
+        assert_eq!(kind("key='abc"), ParseErrorKind::UnterminatedString);
+        assert_eq!(kind("key=${foo"), ParseErrorKind::UnterminatedInterpolation);
+        assert_eq!(kind("key=choic(1,2)"), ParseErrorKind::UnknownFunction);
+        assert_eq!(kind("key=[1,2"), ParseErrorKind::UnexpectedChar);
+    }
+
+    #[test]
+    fn test_parse_error_display_unchanged() {
+        let err = OverrideParser::parse("db").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Parse error at position {}: Expected '=' after key",
+                err.position
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_unique_list_operations() {
+        let op = |input: &str| match OverrideParser::parse(input).unwrap().value {
+            Some(OverrideValue::ListExtension(ext)) => (ext.operation, ext.values),
+            other => panic!("expected list extension, got {:?}", other),
+        };
+
+        assert_eq!(
+            op("tags=append_unique(a,b)"),
+            (
+                ListOperationType::AppendUnique,
+                vec![
+                    ParsedElement::String("a".to_string()),
+                    ParsedElement::String("b".to_string())
+                ]
+            )
+        );
+        assert_eq!(
+            op("nums=prepend_unique(1)"),
+            (
+                ListOperationType::PrependUnique,
+                vec![ParsedElement::Int(1)]
+            )
+        );
+    }
+
+    #[test]
+    fn test_malformed_numbers_lenient_by_default() {
+        for input in ["x=1_", "x=1__0", "x=_1"] {
+            let value = input[2..].to_string();
+            assert_eq!(
+                OverrideParser::parse(input).unwrap().value,
+                Some(OverrideValue::Element(ParsedElement::String(value)))
+            );
+        }
+        // A trailing dot is left unconsumed rather than rejected as a number
+        let err = OverrideParser::parse("x=1.").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedChar);
+    }
+
+    #[test]
+    fn test_parse_many_collect() {
+        let (results, errors) =
+            OverrideParser::parse_many_collect(&["db=mysql", "=oops", "m@a,b=x", "x=[1,2"]);
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results
+                .iter()
+                .map(|r| r
+                    .as_ref()
+                    .map(|os| os.iter().map(|o| o.key.to_string()).collect::<Vec<_>>()))
+                .collect::<Vec<_>>(),
+            [
+                Some(vec!["db".to_string()]),
+                None,
+                Some(vec!["@a:m".to_string(), "@b:m".to_string()]),
+                None
+            ]
+        );
+        assert_eq!(
+            errors.iter().map(|(idx, _)| *idx).collect::<Vec<_>>(),
+            [1, 3]
+        );
+        assert_eq!(errors[0].1, OverrideParser::parse("=oops").unwrap_err());
+
+        let (results, errors) = OverrideParser::parse_many_collect(&["db=mysql", "lr=0.1"]);
+        assert!(errors.is_empty());
+        assert!(results.iter().all(Option::is_some));
+    }
+
+    #[test]
+    fn test_parse_many_package_list() {
+        let overrides =
+            OverrideParser::parse_many(&["model@gpu0,gpu1=resnet", "db=mysql"]).unwrap();
+        assert_eq!(overrides.len(), 3);
+        for (o, package) in overrides.iter().zip(["gpu0", "gpu1"]) {
+            assert_eq!(o.key.key_or_group, "model");
+            assert_eq!(o.key.package.as_deref(), Some(package));
+            assert_eq!(o.override_type, OverrideType::Change);
+            assert_eq!(
+                o.value,
+                Some(OverrideValue::Element(ParsedElement::String(
+                    "resnet".to_string()
+                )))
+            );
+        }
+        assert_eq!(overrides[2].key.key_or_group, "db");
+
+        // Prefixes and deletions carry over to every package
+        let overrides = OverrideParser::parse_many(&["~model@a, b"]).unwrap();
+        assert_eq!(overrides.len(), 2);
+        assert!(overrides
+            .iter()
+            .all(|o| o.override_type == OverrideType::Del));
+        assert_eq!(overrides[1].key.package.as_deref(), Some("b"));
+
+        // Errors still point at the original override
+        let err = OverrideParser::parse_many(&["db=mysql", "model@gpu0,=resnet"]).unwrap_err();
+        assert!(
+            err.message.starts_with("Error parsing override 1:"),
+            "{}",
+            err.message
+        );
+    }
+
+    #[test]
+    fn test_single_package_unchanged() {
+        let many = OverrideParser::parse_many(&["model@gpu0=resnet"]).unwrap();
+        assert_eq!(
+            many,
+            vec![OverrideParser::parse("model@gpu0=resnet").unwrap()]
+        );
+
+        // Commas in the value are a sweep, not a package list
+        let many = OverrideParser::parse_many(&["model@gpu0=resnet,vit"]).unwrap();
+        assert_eq!(many.len(), 1);
+        assert!(many[0].is_sweep());
+
+        let err = OverrideParser::parse("model@gpu0,gpu1=resnet").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::InvalidPackage);
+    }
+
+    #[test]
+    fn test_dict_duplicate_keys_lenient_last_wins() {
+        let result = OverrideParser::parse("key={a:1,b:2,a:3}").unwrap();
+        assert_eq!(
+            result.value,
+            Some(OverrideValue::Element(ParsedElement::Dict(vec![
+                ("a".to_string(), ParsedElement::Int(3)),
+                ("b".to_string(), ParsedElement::Int(2)),
+            ])))
+        );
+    }
+
+    #[test]
+    fn test_dict_duplicate_keys_strict() {
+        let parse = |input: &str| {
+            OverrideParser::new(input)
+                .with_strict_dict_keys(true)
+                .parse_complete()
+        };
+
+        let err = parse("key={a:1, b:{c:1, c:2}}").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::DuplicateKey);
+        assert!(err.message.contains("'c'"), "{}", err.message);
+
+        let err = parse("key={1:a, 1:b}").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::DuplicateKey);
+
+        // Distinct keys, including the same key in sibling dicts, are fine
+        let result = parse("key={a:1, A:2, b:{a:3}, c:[{a:1},{a:2}]}").unwrap();
+        assert!(matches!(
+            result.value,
+            Some(OverrideValue::Element(ParsedElement::Dict(ref items))) if items.len() == 4
+        ));
+    }
+
+    #[test]
+    fn test_malformed_numbers_strict() {
+        for (input, token) in [
+            ("x=1_", "1_"),
+            ("x=1.", "1."),
+            ("x=1__0", "1__0"),
+            ("x=_1", "_1"),
+            ("x=[1,-2_]", "-2_"),
+        ] {
+            let err = OverrideParser::parse_strict(input).unwrap_err();
+            assert_eq!(err.kind, ParseErrorKind::InvalidNumber, "{}", input);
+            assert_eq!(err.message, format!("Malformed numeric literal: {}", token));
+        }
+    }
+
+    #[test]
+    fn test_strict_numbers_accepts_valid_values() {
+        let value = |input: &str| OverrideParser::parse_strict(input).unwrap().value;
+
+        assert_eq!(
+            value("x=1_000"),
+            Some(OverrideValue::Element(ParsedElement::Int(1000)))
+        );
+        assert_eq!(
+            value("x=-1.5"),
+            Some(OverrideValue::Element(ParsedElement::Float(-1.5)))
+        );
+        assert_eq!(
+            value("x=1_abc"),
+            Some(OverrideValue::Element(ParsedElement::String(
+                "1_abc".to_string()
+            )))
+        );
+        assert_eq!(
+            value("x=_private"),
+            Some(OverrideValue::Element(ParsedElement::String(
+                "_private".to_string()
+            )))
+        );
+    }
+
+    fn tagged(input: &str) -> ParseResult<Option<OverrideValue>> {
+        OverrideParser::parse(input).map(|o| o.value)
+    }
+
+    #[test]
+    fn test_type_tag_str() {
+        assert_eq!(
+            tagged("port=!!str 3306").unwrap(),
+            Some(OverrideValue::Element(ParsedElement::String(
+                "3306".to_string()
+            )))
+        );
+        assert_eq!(
+            tagged("flag=!!str true").unwrap(),
+            Some(OverrideValue::Element(ParsedElement::String(
+                "true".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_type_tag_str_keeps_source_text() {
+        assert_eq!(
+            tagged("id=!!str 007").unwrap(),
+            Some(OverrideValue::Element(ParsedElement::String(
+                "007".to_string()
+            )))
+        );
+        assert_eq!(
+            tagged("price=!!str 1.50").unwrap(),
+            Some(OverrideValue::Element(ParsedElement::String(
+                "1.50".to_string()
+            )))
+        );
+        assert_eq!(
+            tagged("flag=!!str True").unwrap(),
+            Some(OverrideValue::Element(ParsedElement::String(
+                "True".to_string()
+            )))
+        );
+        assert_eq!(
+            tagged("x=!!str null").unwrap(),
+            Some(OverrideValue::Element(ParsedElement::String(
+                "null".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_type_tag_int() {
+        assert_eq!(
+            tagged("port=!!int '3306'").unwrap(),
+            Some(OverrideValue::Element(ParsedElement::Int(3306)))
+        );
+        // Follows int() cast rules: floats truncate
+        assert_eq!(
+            tagged("port=!!int 3.9").unwrap(),
+            Some(OverrideValue::Element(ParsedElement::Int(3)))
+        );
+        assert_eq!(
+            tagged("port=!!int abc").unwrap_err().kind,
+            ParseErrorKind::EvaluationError
+        );
+    }
+
+    #[test]
+    fn test_type_tag_float() {
+        assert_eq!(
+            tagged("lr=!!float 1").unwrap(),
+            Some(OverrideValue::Element(ParsedElement::Float(1.0)))
+        );
+    }
+
+    #[test]
+    fn test_type_tag_bool() {
+        assert_eq!(
+            tagged("flag=!!bool 1").unwrap(),
+            Some(OverrideValue::Element(ParsedElement::Bool(true)))
+        );
+        assert_eq!(
+            tagged("flag=!!bool off").unwrap(),
+            Some(OverrideValue::Element(ParsedElement::Bool(false)))
+        );
+    }
+
+    #[test]
+    fn test_type_tag_null() {
+        assert_eq!(
+            tagged("x=!!null").unwrap(),
+            Some(OverrideValue::Element(ParsedElement::Null))
+        );
+        assert_eq!(
+            tagged("x=!!null null").unwrap(),
+            Some(OverrideValue::Element(ParsedElement::Null))
+        );
+        assert_eq!(
+            tagged("x=!!null 5").unwrap_err().message,
+            "Cannot apply !!null to '5'"
+        );
+    }
+
+    #[test]
+    fn test_unknown_type_tag() {
+        let err = tagged("x=!!map 5").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnknownTag);
+        assert!(err.message.starts_with("Unknown type tag: !!map"));
+    }
+
+    #[test]
+    fn test_function_argument_error_position() {
+        let err = OverrideParser::parse("key=range(x,10)").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::ExpectedValue);
+        assert_eq!(err.message, "Expected number, got 'x'");
+        assert_eq!(err.position, 10);
+
+        let err = OverrideParser::parse("key=range(1, 10, step=abc)").unwrap_err();
+        assert_eq!(err.position, 22);
+
+        let err = OverrideParser::parse("key=interval(0, y)").unwrap_err();
+        assert_eq!(err.position, 16);
+    }
+
+    #[test]
+    fn test_glob_case_insensitive() {
+        let flag = |input: &str| match OverrideParser::parse(input).unwrap().value {
+            Some(OverrideValue::Element(ParsedElement::Dict(entries))) => entries
+                .into_iter()
+                .find(|(k, _)| k == "case_insensitive")
+                .map(|(_, v)| v),
+            other => panic!("expected glob dict, got {:?}", other),
+        };
+        assert_eq!(flag("db=glob(Model*)"), None);
+        assert_eq!(
+            flag("db=glob(Model*,case_insensitive=true)"),
+            Some(ParsedElement::Bool(true))
+        );
+        assert_eq!(flag("db=glob(Model*,case_insensitive=false)"), None);
+
+        let err = OverrideParser::parse("db=glob(Model*,case_insensitive=1)").unwrap_err();
+        assert_eq!(err.message, "glob() case_insensitive must be true or false");
+    }
+
+    #[test]
+    fn test_misplaced_add_prefix_in_value() {
+        let err = OverrideParser::parse("db=+mysql").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedChar);
+        assert_eq!(
+            err.message,
+            "Unexpected '+' in value position; did you mean '+db=mysql'?"
+        );
+        assert_eq!(err.position, 3);
+
+        // Signed numbers and `+` inside a value are still fine
+        assert!(OverrideParser::parse("x=+1").is_ok());
+        assert!(OverrideParser::parse("x=+inf").is_ok());
+        assert!(OverrideParser::parse("x=a+b").is_ok());
+    }
+
+    #[test]
+    fn test_parse_add_assign() {
+        let ovr = OverrideParser::parse("name+=_v2").unwrap();
+        assert_eq!(ovr.override_type, OverrideType::ExtendList);
+        assert_eq!(ovr.key.key_or_group, "name");
+        assert_eq!(
+            ovr.value,
+            Some(OverrideValue::Element(ParsedElement::String(
+                "_v2".to_string()
+            )))
+        );
+
+        let ovr = OverrideParser::parse("db.port+=-1").unwrap();
+        assert_eq!(ovr.override_type, OverrideType::ExtendList);
+        assert_eq!(ovr.key.key_or_group, "db.port");
+        assert_eq!(
+            ovr.value,
+            Some(OverrideValue::Element(ParsedElement::Int(-1)))
+        );
+
+        // Only plain keys take `+=`; a prefixed key is still a typo
+        assert!(OverrideParser::parse("++db+=1").is_err());
+        assert!(OverrideParser::parse("db++=1").is_err());
+    }
+
+    #[test]
+    fn test_malformed_override_prefixes() {
+        let cases = [
+            (
+                "+=db=mysql",
+                "Unexpected '=' after '+'; did you mean '+db=mysql'?",
+            ),
+            (
+                "~~db",
+                "Unexpected '~' after '~'; use '~key' to delete a key",
+            ),
+            (
+                "~+db",
+                "Unexpected '+' after '~'; use '~key' to delete a key",
+            ),
+            (
+                "+++db=mysql",
+                "Unexpected '+' after '++'; use '+key=value' to add a key or '++key=value' to force-add it",
+            ),
+            (
+                "+db+=mysql",
+                "Unexpected '+=' after key; did you mean '+db=mysql'?",
+            ),
+            (
+                "db ++= mysql",
+                "Unexpected '++=' after key; did you mean '++db=mysql'?",
+            ),
+        ];
+        for (input, message) in cases {
+            let err = OverrideParser::parse(input).unwrap_err();
+            assert_eq!(err.kind, ParseErrorKind::UnexpectedChar, "{}", input);
+            assert_eq!(err.message, message, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_join_argv() {
+        let join = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+            OverrideParser::join_argv(&args)
+        };
+
+        assert_eq!(join(&["db", "=", "mysql"]), ["db=mysql"]);
+        assert_eq!(join(&["db=", "mysql", "x=1"]), ["db=mysql", "x=1"]);
+        assert_eq!(join(&["db", "=mysql"]), ["db=mysql"]);
+        assert_eq!(
+            join(&["+", "db=mysql", "~", "server"]),
+            ["+db=mysql", "~server"]
+        );
+        assert_eq!(join(&["+db", "=", "mysql"]), ["+db=mysql"]);
+        assert_eq!(join(&["tags", "+=", "a"]), ["tags+=a"]);
+        assert_eq!(
+            join(&["msg='hello", "big", "world'", "x=1"]),
+            ["msg='hello big world'", "x=1"]
+        );
+        assert_eq!(join(&["msg", "=", "hello world"]), ["msg=hello world"]);
+
+        // Complete overrides are left untouched
+        assert_eq!(
+            join(&["db=mysql", "~server", "x=", "+y=2"]),
+            ["db=mysql", "~server", "x=", "+y=2"]
+        );
+        assert_eq!(join(&["a='it\\'s'", "b=1"]), ["a='it\\'s'", "b=1"]);
+
+        // An apostrophe inside a value doesn't open a quote
+        assert_eq!(join(&["name=O'Brien", "x=1"]), ["name=O'Brien", "x=1"]);
+        assert_eq!(
+            join(&["tags=[a,'b", "c']", "x=1"]),
+            ["tags=[a,'b c']", "x=1"]
+        );
+    }
+
+    #[test]
+    fn test_underscores_in_fraction_and_exponent() {
+        let cases = [
+            ("1_000.5", 1000.5),
+            ("1.000_5", 1.0005),
+            ("1e1_0", 1e10),
+            ("1_0.0_5e1_0", 10.05e10),
+            ("1.5e-1_0", 1.5e-10),
+            ("-1_0.5", -10.5),
+        ];
+        for (input, expected) in cases {
+            let value = format!("x={}", input);
+            for result in [
+                OverrideParser::parse(&value),
+                OverrideParser::parse_strict(&value),
+            ] {
+                assert_eq!(
+                    result.unwrap().value,
+                    Some(OverrideValue::Element(ParsedElement::Float(expected))),
+                    "{}",
+                    input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_floats_without_integer_or_fraction_digits() {
+        let cases = [
+            (".5", 0.5),
+            ("-.5", -0.5),
+            ("+.5", 0.5),
+            (".5e2", 50.0),
+            ("1.e5", 100000.0),
+            ("1.E-2", 0.01),
+        ];
+        for (input, expected) in cases {
+            let value = format!("x={}", input);
+            for result in [
+                OverrideParser::parse(&value),
+                OverrideParser::parse_strict(&value),
+            ] {
+                assert_eq!(
+                    result.unwrap().value,
+                    Some(OverrideValue::Element(ParsedElement::Float(expected))),
+                    "{}",
+                    input
+                );
+            }
+        }
+        assert_eq!(
+            OverrideParser::parse("x=[.5,1.e5]").unwrap().value,
+            Some(OverrideValue::Element(ParsedElement::List(vec![
+                ParsedElement::Float(0.5),
+                ParsedElement::Float(100000.0)
+            ])))
+        );
+
+        // Without digits these are not numbers
+        for input in ["e5", ".e5", "1.e", "1.foo", "..5"] {
+            assert_eq!(
+                OverrideParser::parse(&format!("x={}", input))
+                    .unwrap()
+                    .value,
+                Some(OverrideValue::Element(ParsedElement::String(
+                    input.to_string()
+                ))),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_misplaced_underscores_in_fraction_and_exponent() {
+        for input in [
+            "1._5", "1_.5", "1.5_", "1_e5", "1e_10", "1e+_1", "1e10_", "1e1__0",
+        ] {
+            assert!(is_malformed_number(input), "{}", input);
+
+            // Lenient mode falls back to a string
+            let value = format!("x={}", input);
+            assert_eq!(
+                OverrideParser::parse(&value).unwrap().value,
+                Some(OverrideValue::Element(ParsedElement::String(
+                    input.to_string()
+                ))),
+                "{}",
+                input
+            );
+
+            // Strict mode rejects it
+            let err = OverrideParser::parse_strict(&value).unwrap_err();
+            assert_eq!(err.kind, ParseErrorKind::InvalidNumber, "{}", input);
+            assert_eq!(err.message, format!("Malformed numeric literal: {}", input));
+        }
+        assert!(!is_malformed_number("1e5"));
+        assert!(!is_malformed_number("e_1"));
+        assert!(!is_malformed_number("1ex"));
+    }
+
+    #[test]
+    fn test_trailing_comment() {
+        let o = OverrideParser::parse("key=value # note").unwrap();
+        assert_eq!(
+            o.value.unwrap(),
+            OverrideValue::Element(ParsedElement::String("value".into()))
+        );
+
+        let o = OverrideParser::parse("key='#hash'  # keep the quoted one").unwrap();
+        match o.value.unwrap() {
+            OverrideValue::Element(ParsedElement::QuotedString(q)) => assert_eq!(q.text, "#hash"),
+            other => panic!("expected quoted string, got {:?}", other),
+        }
+
+        let err = OverrideParser::parse("key=#bad").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::ExpectedValue);
+    }
+
+    #[test]
+    fn test_hash_inside_value_is_not_a_comment() {
+        for (input, expected) in [
+            ("url=http://host/page#anchor", "http://host/page#anchor"),
+            ("tag=v1#2", "v1#2"),
+            ("tag=v1#2 # note", "v1#2"),
+            ("color=red#", "red#"),
+            ("build=1#2", "1#2"),
+        ] {
+            let o = OverrideParser::parse(input).unwrap();
+            assert_eq!(
+                o.value.unwrap(),
+                OverrideValue::Element(ParsedElement::String(expected.into())),
+                "{}",
+                input
+            );
+        }
+
+        let err = OverrideParser::parse("db=choice(mysql,postgres)#sweep").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedChar);
+
+        // Without comments, `#` after whitespace is part of the value too
+        let o = OverrideParser::new("tag=v1 # note")
+            .with_comments(false)
+            .parse_complete()
+            .unwrap();
+        assert_eq!(
+            o.value.unwrap(),
+            OverrideValue::Element(ParsedElement::String("v1 # note".into()))
+        );
+    }
 }
@@ -6,6 +6,37 @@
 
 use std::fmt;
 
+use crate::config::value::ConfigValue;
+use crate::config::{get_path_scheme, SearchPathEntry};
+
+/// Provider name for search path entries read from the primary config
+pub const CONFIG_SEARCHPATH_PROVIDER: &str = "hydra.searchpath in main";
+
+/// Schemes accepted in `hydra.searchpath` entries
+const SEARCHPATH_SCHEMES: &[&str] = &["file", "pkg", "structured"];
+
+/// Error reading search path entries from a config
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchPathError {
+    pub message: String,
+}
+
+impl SearchPathError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SearchPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for SearchPathError {}
+
 /// A single element in the config search path
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SearchPathElement {
@@ -267,6 +298,50 @@ impl fmt::Display for ConfigSearchPath {
     }
 }
 
+/// Read the `hydra.searchpath` list from a config
+///
+/// Each entry is a path such as `pkg://my.configs` or `file:///abs/path`
+/// (a path without a scheme is a file path). Entries are returned with the
+/// `hydra.searchpath in main` provider, in config order, ready to pass to a
+/// `ConfigLoader`. Returns an empty list if the config has no search path.
+pub fn from_config(config: &ConfigValue) -> Result<Vec<SearchPathEntry>, SearchPathError> {
+    let searchpath = match config.as_dict().and_then(|d| d.select("hydra.searchpath")) {
+        None | Some(ConfigValue::Null) => return Ok(Vec::new()),
+        Some(value) => value,
+    };
+
+    let entries = searchpath.as_list().ok_or_else(|| {
+        SearchPathError::new(format!(
+            "hydra.searchpath must be a list of strings. Got: {}",
+            searchpath
+        ))
+    })?;
+
+    entries
+        .iter()
+        .map(|entry| {
+            let path = match entry {
+                ConfigValue::String(s) => s.as_str(),
+                other => {
+                    return Err(SearchPathError::new(format!(
+                        "hydra.searchpath must be a list of strings. Got entry: {}",
+                        other
+                    )))
+                }
+            };
+            let location = path.split_once("://").map_or(path, |(_, rest)| rest);
+            if !SEARCHPATH_SCHEMES.contains(&get_path_scheme(path).as_str()) || location.is_empty()
+            {
+                return Err(SearchPathError::new(format!(
+                    "Invalid hydra.searchpath entry: '{}'",
+                    path
+                )));
+            }
+            Ok(SearchPathEntry::new(CONFIG_SEARCHPATH_PROVIDER, path))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -452,4 +527,45 @@ mod tests {
         let providers: Vec<_> = sp.iter().map(|e| e.provider.as_str()).collect();
         assert_eq!(providers, vec!["hydra", "main"]);
     }
+
+    fn config_with_searchpath(yaml: &str) -> ConfigValue {
+        crate::config::parser::parse_yaml(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_from_config_reads_entries() {
+        let config = config_with_searchpath(
+            "hydra:\n  searchpath:\n    - pkg://my.configs\n    - file:///abs/path\n",
+        );
+        let entries = from_config(&config).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].provider, "hydra.searchpath in main");
+        assert_eq!(entries[0].path, "pkg://my.configs");
+        assert_eq!(get_path_scheme(&entries[0].path), "pkg");
+        assert_eq!(entries[1].path, "file:///abs/path");
+        assert_eq!(get_path_scheme(&entries[1].path), "file");
+    }
+
+    #[test]
+    fn test_from_config_without_searchpath() {
+        let config = config_with_searchpath("db:\n  host: localhost\n");
+        assert!(from_config(&config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_from_config_invalid_entry() {
+        let config =
+            config_with_searchpath("hydra:\n  searchpath:\n    - pkg://ok\n    - http://nope\n");
+        assert_eq!(
+            from_config(&config).unwrap_err().message,
+            "Invalid hydra.searchpath entry: 'http://nope'"
+        );
+
+        let config = config_with_searchpath("hydra:\n  searchpath: pkg://ok\n");
+        assert!(from_config(&config)
+            .unwrap_err()
+            .message
+            .starts_with("hydra.searchpath must be a list of strings"));
+    }
 }
@@ -1,7 +1,16 @@
 // Copyright (c) Facebook, Inc. and its affiliates. All Rights Reserved
 //! Sweep expansion for multirun configurations
 
-use crate::core::{Override, OverrideValue, ParsedElement};
+use std::collections::{HashMap, HashSet};
+
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::core::{
+    format_float, ListOperationType, Override, OverrideType, OverrideValue, ParsedElement,
+};
+use crate::parser::{OverrideParser, ParseError, ParseErrorKind, ParseResult};
 
 /// Expand sweep overrides into individual override sets.
 ///
@@ -14,54 +23,42 @@ use crate::core::{Override, OverrideValue, ParsedElement};
 /// - [db=mysql, server=prod]
 /// - [db=postgresql, server=dev]
 /// - [db=postgresql, server=prod]
+///
+/// Shuffled ranges (`shuffle(range(...))`) come out in random order; use
+/// [`expand_sweeps_with_seed`] for an order that is stable across runs.
 pub fn expand_sweeps(overrides: &[Override]) -> Vec<Vec<String>> {
+    expand_sweeps_with_seed(overrides, None)
+}
+
+/// Expand sweep overrides, shuffling shuffled ranges with a seeded RNG
+///
+/// With `Some(seed)` a `shuffle(range(...))` sweep is materialized and then
+/// permuted deterministically, so the same seed and overrides always give
+/// the same job order. With `None` the shuffle is random, as in
+/// [`expand_sweeps`]. One RNG is shared by all shuffled ranges, in
+/// override order.
+pub fn expand_sweeps_with_seed(overrides: &[Override], seed: Option<u64>) -> Vec<Vec<String>> {
+    let mut seeded = seed.map(sweep_rng);
+
     // Collect sweep dimensions
     let mut dimensions: Vec<Vec<String>> = Vec::new();
 
     for ovr in overrides {
-        let key = &ovr.key.key_or_group;
+        // Each job keeps the override's prefix, package and `=` or `+=`
+        let key = override_key(ovr);
+        let assign = override_assign(ovr);
 
-        match &ovr.value {
-            Some(OverrideValue::ChoiceSweep(cs)) => {
-                // Expand choice sweep
-                let choices: Vec<String> = cs
-                    .list
+        match sweep_values(ovr, seeded.as_mut()) {
+            Some(values) => dimensions.push(
+                values
                     .iter()
-                    .map(|elem| format!("{}={}", key, element_to_string(elem)))
-                    .collect();
-                dimensions.push(choices);
-            }
-            Some(OverrideValue::RangeSweep(rs)) => {
-                // Expand range sweep
-                let start = rs.start.unwrap_or(0.0);
-                let stop = rs.stop.unwrap_or(10.0);
-                let step = rs.step;
-
-                let mut choices = Vec::new();
-                let mut current = start;
-                while current < stop {
-                    if step == step.floor() && current == current.floor() {
-                        // Integer range
-                        choices.push(format!("{}={}", key, current as i64));
-                    } else {
-                        choices.push(format!("{}={}", key, current));
-                    }
-                    current += step;
-                }
-                dimensions.push(choices);
-            }
-            Some(OverrideValue::Element(elem)) => {
-                // Non-sweep override - single value
-                dimensions.push(vec![format!("{}={}", key, element_to_string(elem))]);
-            }
-            None => {
-                // Delete override
-                dimensions.push(vec![format!("~{}", key)]);
-            }
-            _ => {
-                // Other sweep types not yet supported
-                dimensions.push(vec![format!("{}=<unsupported>", key)]);
-            }
+                    .map(|value| format!("{}{}{}", key, assign, value))
+                    .collect(),
+            ),
+            // Delete override
+            None if ovr.value.is_none() => dimensions.push(vec![key]),
+            // Other sweep types not yet supported
+            None => dimensions.push(vec![format!("{}{}<unsupported>", key, assign)]),
         }
     }
 
@@ -69,6 +66,89 @@ pub fn expand_sweeps(overrides: &[Override]) -> Vec<Vec<String>> {
     cartesian_product(&dimensions)
 }
 
+/// RNG used for seeded shuffles
+///
+/// ChaCha8 is value-stable: a seed gives the same stream on every platform
+/// and rand version, so a seeded sweep keeps its job order.
+pub type SweepRng = ChaCha8Rng;
+
+/// The RNG for shuffled sweeps seeded from `seed`
+pub fn sweep_rng(seed: u64) -> SweepRng {
+    let mut key = [0u8; 32];
+    key[..8].copy_from_slice(&seed.to_le_bytes());
+    SweepRng::from_seed(key)
+}
+
+/// Whether an override is a shuffled sweep, whose order changes between
+/// runs unless it is seeded
+pub fn is_shuffled(ovr: &Override) -> bool {
+    match &ovr.value {
+        Some(OverrideValue::ChoiceSweep(cs)) => cs.shuffle,
+        Some(OverrideValue::RangeSweep(rs)) => rs.shuffle,
+        _ => false,
+    }
+}
+
+/// The values an override takes across a sweep, as override source
+///
+/// A choice sweep gives its choices and a range sweep its values; shuffled
+/// sweeps are permuted with `rng` if given and randomly otherwise. A plain
+/// value is the only choice. Returns `None` for deletions and for sweeps
+/// that cannot be listed, such as intervals and globs.
+pub fn sweep_values(ovr: &Override, rng: Option<&mut SweepRng>) -> Option<Vec<String>> {
+    let mut values: Vec<String> = match ovr.value.as_ref()? {
+        OverrideValue::ChoiceSweep(cs) => cs.list.iter().map(element_to_string).collect(),
+        OverrideValue::RangeSweep(rs) => {
+            let start = rs.start.unwrap_or(0.0);
+            let stop = rs.stop.unwrap_or(10.0);
+
+            let mut values = Vec::new();
+            let mut current = start;
+            while current < stop {
+                if rs.is_int {
+                    values.push((current as i64).to_string());
+                } else {
+                    values.push(format_float(current));
+                }
+                current += rs.step;
+            }
+            values
+        }
+        OverrideValue::Element(elem) => vec![element_to_string(elem)],
+        _ => return None,
+    };
+    if is_shuffled(ovr) {
+        match rng {
+            Some(rng) => values.shuffle(rng),
+            None => values.shuffle(&mut rand::rng()),
+        }
+    }
+    Some(values)
+}
+
+/// Tags of the sweeps in a set of overrides, sorted and deduplicated
+///
+/// Tags belong to a whole sweep rather than one of its values, so every
+/// job expanded from these overrides carries the same tags:
+/// `tag(grid,choice(a,b))` gives `["grid"]` for both jobs.
+pub fn sweep_tags(overrides: &[Override]) -> Vec<String> {
+    let mut tags: Vec<String> = overrides
+        .iter()
+        .filter_map(|ovr| match &ovr.value {
+            Some(OverrideValue::ChoiceSweep(cs)) => Some(&cs.tags),
+            Some(OverrideValue::RangeSweep(rs)) => Some(&rs.tags),
+            Some(OverrideValue::IntervalSweep(is)) => Some(&is.tags),
+            Some(OverrideValue::GlobChoiceSweep(gs)) => Some(&gs.tags),
+            _ => None,
+        })
+        .flatten()
+        .cloned()
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
 /// Compute cartesian product of all dimensions
 fn cartesian_product(dimensions: &[Vec<String>]) -> Vec<Vec<String>> {
     if dimensions.is_empty() {
@@ -96,20 +176,21 @@ fn cartesian_product(dimensions: &[Vec<String>]) -> Vec<Vec<String>> {
     result
 }
 
-/// Convert a ParsedElement to its string representation
+/// Convert a ParsedElement to override source that parses back to the same type
 fn element_to_string(elem: &ParsedElement) -> String {
     match elem {
         ParsedElement::Null => "null".to_string(),
         ParsedElement::Bool(b) => b.to_string(),
         ParsedElement::Int(i) => i.to_string(),
-        ParsedElement::Float(f) => f.to_string(),
-        ParsedElement::String(s) => s.clone(),
+        ParsedElement::Float(f) => format_float(*f),
+        ParsedElement::String(s) if reparses_as_string(s) => s.clone(),
+        ParsedElement::String(s) => quoted('\'', s),
         ParsedElement::QuotedString(qs) => {
             let quote = match qs.quote {
                 crate::core::Quote::Single => '\'',
                 crate::core::Quote::Double => '"',
             };
-            format!("{}{}{}", quote, qs.text, quote)
+            quoted(quote, &qs.text)
         }
         ParsedElement::List(items) => {
             let inner: Vec<String> = items.iter().map(element_to_string).collect();
@@ -125,6 +206,252 @@ fn element_to_string(elem: &ParsedElement) -> String {
     }
 }
 
+/// Whether unquoted `text` parses back to the same string, rather than to
+/// another type (`1`, `true`), a sweep (`a,b`) or an error (`a]`)
+fn reparses_as_string(text: &str) -> bool {
+    matches!(
+        OverrideParser::parse(&format!("x={}", text)).map(|o| o.value),
+        Ok(Some(OverrideValue::Element(ParsedElement::String(parsed)))) if parsed == text
+    )
+}
+
+fn quoted(quote: char, text: &str) -> String {
+    let text = text.replace(quote, &format!("\\{}", quote));
+    format!("{}{}{}", quote, text, quote)
+}
+
+/// Render an override value as a complete `key=value` override
+///
+/// The result parses back to the same value: sweeps keep their function
+/// form (`range(1,10)`, `choice(a,b)`, `interval(0.0,1.0)`), shuffled
+/// sweeps are wrapped in `shuffle(...)` and tagged sweeps in
+/// `tag(t1,t2,...)` with the tags sorted.
+pub fn sweep_to_override_string(key: &str, value: &OverrideValue) -> String {
+    format!("{}={}", key, value_to_string(value))
+}
+
+/// Render a parsed override in canonical form
+///
+/// Prefixes, package and value are written the same way whatever spacing
+/// or quoting the input used, so the result parses back to an equal
+/// override: `+db=mysql, postgres` becomes `+db=mysql,postgres` and
+/// `@pkg:db=mysql` becomes `db@pkg=mysql`. Strings that would parse back as
+/// another type are quoted, so `s=str(1)` becomes `s='1'`.
+pub fn override_to_string(ov: &Override) -> String {
+    let key = override_key(ov);
+    match &ov.value {
+        Some(value) => format!("{}{}{}", key, override_assign(ov), value_to_string(value)),
+        None => key,
+    }
+}
+
+/// Render an override's prefix and key, e.g. `+db@pkg` or `~db`
+fn override_key(ov: &Override) -> String {
+    let prefix = match ov.override_type {
+        OverrideType::Add => "+",
+        OverrideType::ForceAdd => "++",
+        OverrideType::Del => "~",
+        OverrideType::Change | OverrideType::ExtendList => "",
+    };
+    match &ov.key.package {
+        Some(package) => format!("{}{}@{}", prefix, ov.key.key_or_group, package),
+        None => format!("{}{}", prefix, ov.key.key_or_group),
+    }
+}
+
+/// The operator between an override's key and value: `+=` or `=`
+fn override_assign(ov: &Override) -> &'static str {
+    match ov.override_type {
+        OverrideType::ExtendList => "+=",
+        _ => "=",
+    }
+}
+
+/// Render an OverrideValue as override source
+fn value_to_string(value: &OverrideValue) -> String {
+    match value {
+        OverrideValue::Element(elem) => element_to_string(elem),
+        OverrideValue::ChoiceSweep(cs) => {
+            let items: Vec<String> = cs.list.iter().map(element_to_string).collect();
+            let sweep = if cs.simple_form && !cs.shuffle && cs.tags.is_empty() {
+                items.join(",")
+            } else {
+                format!("choice({})", items.join(","))
+            };
+            tagged(&cs.tags, shuffled(cs.shuffle, sweep))
+        }
+        OverrideValue::RangeSweep(rs) => {
+            let bound = |v: f64| {
+                if rs.is_int {
+                    (v as i64).to_string()
+                } else {
+                    format_float(v)
+                }
+            };
+            let mut args = vec![
+                bound(rs.start.unwrap_or(0.0)),
+                bound(rs.stop.unwrap_or(0.0)),
+            ];
+            if rs.step != 1.0 {
+                args.push(bound(rs.step));
+            }
+            let sweep = format!("range({})", args.join(","));
+            tagged(&rs.tags, shuffled(rs.shuffle, sweep))
+        }
+        OverrideValue::IntervalSweep(is) => {
+            let sweep = format!(
+                "interval({},{})",
+                format_float(is.start.unwrap_or(0.0)),
+                format_float(is.end.unwrap_or(0.0))
+            );
+            let sweep = if is.is_int {
+                format!("int({})", sweep)
+            } else {
+                sweep
+            };
+            tagged(&is.tags, sweep)
+        }
+        OverrideValue::GlobChoiceSweep(gs) => {
+            let mut sweep = format!(
+                "glob([{}],exclude=[{}]",
+                gs.include.join(","),
+                gs.exclude.join(",")
+            );
+            if gs.case_insensitive {
+                sweep.push_str(",case_insensitive=true");
+            }
+            sweep.push(')');
+            tagged(&gs.tags, sweep)
+        }
+        OverrideValue::ListExtension(ext) => {
+            let values: Vec<String> = ext.values.iter().map(element_to_string).collect();
+            let index = ext.index.unwrap_or(0).to_string();
+            match ext.operation {
+                ListOperationType::Append => format!("append({})", values.join(",")),
+                ListOperationType::Prepend => format!("prepend({})", values.join(",")),
+                ListOperationType::AppendUnique => format!("append_unique({})", values.join(",")),
+                ListOperationType::PrependUnique => {
+                    format!("prepend_unique({})", values.join(","))
+                }
+                ListOperationType::Insert => format!("insert({},{})", index, values.join(",")),
+                ListOperationType::RemoveAt => format!("remove_at({})", index),
+                ListOperationType::RemoveValue => format!("remove_value({})", values.join(",")),
+                ListOperationType::Clear => "list_clear()".to_string(),
+            }
+        }
+    }
+}
+
+fn shuffled(shuffle: bool, sweep: String) -> String {
+    if shuffle {
+        format!("shuffle({})", sweep)
+    } else {
+        sweep
+    }
+}
+
+fn tagged(tags: &HashSet<String>, sweep: String) -> String {
+    if tags.is_empty() {
+        return sweep;
+    }
+    let mut tags: Vec<&str> = tags.iter().map(|t| t.as_str()).collect();
+    tags.sort();
+    format!("tag({},{})", tags.join(","), sweep)
+}
+
+/// A sweep resolver, called with the text after `name:` in `${name:...}`
+pub type SweepResolverFn = Box<dyn Fn(&str) -> Result<OverrideValue, String> + Send + Sync>;
+
+/// Resolvers that turn an interpolation in value position into a sweep
+///
+/// The parser keeps `${...}` values as strings. Before expansion,
+/// [`resolve_overrides`](Self::resolve_overrides) replaces each override
+/// value that is exactly `${name:args}`, for a registered `name`, with the
+/// value the resolver returns. The built-in `sweep` resolver parses `args`
+/// as an override value, so `lr=${sweep:0.1,0.01}` sweeps like
+/// `lr=0.1,0.01` and `n=${sweep:range(1,4)}` like `n=range(1,4)`. Other
+/// interpolations are left for config resolution.
+pub struct SweepResolvers {
+    resolvers: HashMap<String, SweepResolverFn>,
+}
+
+impl std::fmt::Debug for SweepResolvers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<&String> = self.resolvers.keys().collect();
+        names.sort();
+        f.debug_struct("SweepResolvers")
+            .field("names", &names)
+            .finish()
+    }
+}
+
+impl Default for SweepResolvers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SweepResolvers {
+    /// Create a registry holding the built-in `sweep` resolver
+    pub fn new() -> Self {
+        let mut resolvers = Self {
+            resolvers: HashMap::new(),
+        };
+        resolvers.register("sweep", |args| {
+            OverrideParser::parse(&format!("sweep={}", args))
+                .map_err(|e| e.message)?
+                .value
+                .ok_or_else(|| "expected a value".to_string())
+        });
+        resolvers
+    }
+
+    /// Register a sweep resolver, replacing any with the same name
+    pub fn register(
+        &mut self,
+        name: &str,
+        resolver: impl Fn(&str) -> Result<OverrideValue, String> + Send + Sync + 'static,
+    ) {
+        self.resolvers.insert(name.to_string(), Box::new(resolver));
+    }
+
+    /// Check if a resolver is registered under `name`
+    pub fn has_resolver(&self, name: &str) -> bool {
+        self.resolvers.contains_key(name)
+    }
+
+    /// Replace `${name:args}` values of registered resolvers in `overrides`
+    pub fn resolve_overrides(&self, overrides: &mut [Override]) -> ParseResult<()> {
+        for ovr in overrides {
+            let Some(OverrideValue::Element(ParsedElement::String(text))) = &ovr.value else {
+                continue;
+            };
+            let Some((name, args)) = text
+                .strip_prefix("${")
+                .and_then(|inner| inner.strip_suffix('}'))
+                .and_then(|inner| inner.split_once(':'))
+            else {
+                continue;
+            };
+            let Some(resolver) = self.resolvers.get(name.trim()) else {
+                continue;
+            };
+            let value = resolver(args).map_err(|e| ParseError {
+                kind: ParseErrorKind::EvaluationError,
+                message: format!(
+                    "Sweep resolver '{}' failed for '{}': {}",
+                    name.trim(),
+                    ovr.key.key_or_group,
+                    e
+                ),
+                position: 0,
+            })?;
+            ovr.value = Some(value);
+        }
+        Ok(())
+    }
+}
+
 /// Expand sweep strings without full parsing.
 ///
 /// For simple sweeps like "db=mysql,postgresql", expands directly from strings.
@@ -175,6 +502,7 @@ pub fn expand_simple_sweeps(overrides: &[&str]) -> Vec<Vec<String>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ChoiceSweep;
 
     #[test]
     fn test_simple_sweep_expansion() {
@@ -236,6 +564,166 @@ mod tests {
         assert_eq!(result.len(), 27);
     }
 
+    #[test]
+    fn test_expand_sweeps_seeded_shuffle() {
+        let overrides =
+            vec![crate::parser::OverrideParser::parse("x=shuffle(range(0,10))").unwrap()];
+        let plain = vec![crate::parser::OverrideParser::parse("x=range(0,10)").unwrap()];
+        let unshuffled: Vec<String> = expand_sweeps(&plain).into_iter().flatten().collect();
+
+        let first: Vec<String> = expand_sweeps_with_seed(&overrides, Some(42))
+            .into_iter()
+            .flatten()
+            .collect();
+        for _ in 0..5 {
+            let again: Vec<String> = expand_sweeps_with_seed(&overrides, Some(42))
+                .into_iter()
+                .flatten()
+                .collect();
+            assert_eq!(again, first);
+        }
+
+        let mut sorted = first.clone();
+        sorted.sort_by_key(|o| o[2..].parse::<i64>().unwrap());
+        assert_eq!(sorted, unshuffled);
+        assert_ne!(first, unshuffled);
+
+        // Unshuffled ranges ignore the seed
+        assert_eq!(
+            expand_sweeps_with_seed(&plain, Some(42)).concat(),
+            unshuffled
+        );
+    }
+
+    fn parsed_values(combos: &[Vec<String>]) -> Vec<ParsedElement> {
+        combos
+            .iter()
+            .flatten()
+            .map(
+                |o| match crate::parser::OverrideParser::parse(o).unwrap().value {
+                    Some(OverrideValue::Element(elem)) => elem,
+                    other => panic!("expected a single element for {}, got {:?}", o, other),
+                },
+            )
+            .collect()
+    }
+
+    #[test]
+    fn test_expand_sweeps_preserves_choice_types() {
+        let overrides = crate::parser::OverrideParser::parse_many(&[
+            "x=choice(1, 2.0, 2.5, foo, 'bar baz', \"it's\", null, true, [1,2.0], 'it\\'s')",
+        ])
+        .unwrap();
+        let result = expand_sweeps(&overrides);
+        assert_eq!(result[1], vec!["x=2.0".to_string()]);
+
+        let expected = match &overrides[0].value {
+            Some(OverrideValue::ChoiceSweep(cs)) => cs.list.clone(),
+            other => panic!("expected a choice sweep, got {:?}", other),
+        };
+        assert_eq!(parsed_values(&result), expected);
+    }
+
+    #[test]
+    fn test_expand_sweeps_keeps_operator_and_key() {
+        let overrides =
+            crate::parser::OverrideParser::parse_many(&["x+=1,2", "+db@pkg=a", "~old"]).unwrap();
+        assert_eq!(
+            expand_sweeps(&overrides),
+            vec![
+                vec!["x+=1", "+db@pkg=a", "~old"],
+                vec!["x+=2", "+db@pkg=a", "~old"]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_sweeps_float_range() {
+        let overrides = crate::parser::OverrideParser::parse_many(&["lr=range(0,2,0.5)"]).unwrap();
+        let result = expand_sweeps(&overrides);
+        assert_eq!(
+            result,
+            vec![
+                vec!["lr=0.0"],
+                vec!["lr=0.5"],
+                vec!["lr=1.0"],
+                vec!["lr=1.5"]
+            ]
+        );
+        assert!(parsed_values(&result)
+            .iter()
+            .all(|v| matches!(v, ParsedElement::Float(_))));
+
+        let overrides = crate::parser::OverrideParser::parse_many(&["n=range(1,3)"]).unwrap();
+        assert_eq!(
+            parsed_values(&expand_sweeps(&overrides)),
+            vec![ParsedElement::Int(1), ParsedElement::Int(2)]
+        );
+    }
+
+    #[test]
+    fn test_sweep_to_override_string_round_trip() {
+        let cases = [
+            ("db=mysql,postgres", "db=mysql,postgres"),
+            ("db=choice(mysql,postgres)", "db=choice(mysql,postgres)"),
+            ("x=range(1,10)", "x=range(1,10)"),
+            ("x=range(0,2,0.5)", "x=range(0.0,2.0,0.5)"),
+            ("x=range(0.5,2.5,0.5)", "x=range(0.5,2.5,0.5)"),
+            ("x=interval(0,1)", "x=interval(0.0,1.0)"),
+            ("x=int(interval(0,10))", "x=int(interval(0.0,10.0))"),
+            ("x=shuffle(choice(1,2.0))", "x=shuffle(choice(1,2.0))"),
+            ("x=shuffle(range(1,5))", "x=shuffle(range(1,5))"),
+            (
+                "x=tag(lr,grid,choice(a,'b c'))",
+                "x=tag(grid,lr,choice(a,'b c'))",
+            ),
+            (
+                "x=tag(grid,shuffle(range(1,3)))",
+                "x=tag(grid,shuffle(range(1,3)))",
+            ),
+            ("x=tag(t,interval(0,1))", "x=tag(t,interval(0.0,1.0))"),
+            ("x=[1,{a:2}]", "x=[1,{a:2}]"),
+            ("x=append(1,2)", "x=append(1,2)"),
+            ("x=insert(0,a)", "x=insert(0,a)"),
+        ];
+        for (input, expected) in cases {
+            let parsed = crate::parser::OverrideParser::parse(input).unwrap();
+            let value = parsed.value.unwrap();
+            let rendered = sweep_to_override_string(&parsed.key.key_or_group, &value);
+            assert_eq!(rendered, expected, "rendering {}", input);
+
+            let reparsed = crate::parser::OverrideParser::parse(&rendered).unwrap();
+            assert_eq!(reparsed.value.unwrap(), value, "round-tripping {}", input);
+        }
+    }
+
+    #[test]
+    fn test_override_to_string() {
+        let cases = [
+            ("db=mysql, postgres", "db=mysql,postgres"),
+            ("+x= \"a b\" ", "+x=\"a b\""),
+            ("++x=[1, 2]", "++x=[1,2]"),
+            ("~db", "~db"),
+            ("~db=mysql", "~db=mysql"),
+            ("db@src=mysql", "db@src=mysql"),
+            ("@src:db=mysql", "db@src=mysql"),
+            ("s=str(1)", "s='1'"),
+            ("s=str(true)", "s='true'"),
+            ("xs=[str(1), a]", "xs=['1',a]"),
+            ("s=str(a b)", "s=a b"),
+            ("lr=interval(0, 1)", "lr=interval(0.0,1.0)"),
+            ("name+= 'x y'", "name+='x y'"),
+        ];
+        for (input, expected) in cases {
+            let parsed = crate::parser::OverrideParser::parse(input).unwrap();
+            let rendered = override_to_string(&parsed);
+            assert_eq!(rendered, expected, "rendering {}", input);
+
+            let reparsed = crate::parser::OverrideParser::parse(&rendered).unwrap();
+            assert_eq!(override_to_string(&reparsed), rendered);
+        }
+    }
+
     #[test]
     fn test_cartesian_product() {
         let dims = vec![
@@ -250,4 +738,45 @@ mod tests {
         assert!(result.contains(&vec!["b".to_string(), "1".to_string()]));
         assert!(result.contains(&vec!["b".to_string(), "2".to_string()]));
     }
+
+    #[test]
+    fn test_sweep_resolvers() {
+        let mut resolvers = SweepResolvers::new();
+        resolvers.register("pow2", |args| {
+            let n: u32 = args.trim().parse().map_err(|_| "expected a count")?;
+            Ok(OverrideValue::ChoiceSweep(ChoiceSweep {
+                tags: HashSet::new(),
+                list: (0..n).map(|i| ParsedElement::Int(1 << i)).collect(),
+                simple_form: true,
+                shuffle: false,
+            }))
+        });
+
+        let mut overrides = OverrideParser::parse_many(&[
+            "lr=${sweep:0.1,0.01}",
+            "batch=${pow2:3}",
+            "name=${other:x}",
+        ])
+        .unwrap();
+        resolvers.resolve_overrides(&mut overrides).unwrap();
+
+        let jobs = expand_sweeps(&overrides);
+        assert_eq!(jobs.len(), 6);
+        assert_eq!(jobs[0], vec!["lr=0.1", "batch=1", "name=${other:x}"]);
+        assert_eq!(jobs[5], vec!["lr=0.01", "batch=4", "name=${other:x}"]);
+
+        let mut overrides = OverrideParser::parse_many(&["n=${sweep:range(1,4)}"]).unwrap();
+        resolvers.resolve_overrides(&mut overrides).unwrap();
+        assert_eq!(
+            expand_sweeps(&overrides),
+            vec![vec!["n=1"], vec!["n=2"], vec!["n=3"]]
+        );
+
+        let mut overrides = OverrideParser::parse_many(&["batch=${pow2:many}"]).unwrap();
+        let err = resolvers.resolve_overrides(&mut overrides).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Sweep resolver 'pow2' failed for 'batch': expected a count"
+        );
+    }
 }
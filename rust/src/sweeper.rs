@@ -4,12 +4,18 @@
 //! Sweepers are responsible for generating job parameter combinations
 //! and coordinating job execution through a Launcher.
 
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 use crate::callback::JobReturn;
 use crate::config::value::ConfigDict;
 use crate::launcher::{JobOverrideBatch, Launcher, LauncherError};
+use crate::parser::OverrideParser;
+use crate::sweep::{is_shuffled, sweep_rng, sweep_tags, sweep_values, SweepResolvers};
 
 /// Error type for sweeper operations
 #[derive(Debug, Clone)]
@@ -65,12 +71,145 @@ pub trait Sweeper: Send + Sync + Debug {
     fn name(&self) -> &str;
 }
 
+/// How BasicSweeper combines the values of swept parameters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SweepCombination {
+    /// Cartesian product of all values: `a=1,2 b=x,y` gives 4 jobs
+    #[default]
+    Grid,
+    /// Position-wise pairing of values: `a=1,2 b=x,y` gives (1,x) and (2,y).
+    /// Single-valued parameters are repeated for every job.
+    Zip,
+}
+
+impl std::str::FromStr for SweepCombination {
+    type Err = SweeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "grid" => Ok(Self::Grid),
+            "zip" => Ok(Self::Zip),
+            _ => Err(SweeperError::new(format!(
+                "Unknown sweep combination '{}', expected 'grid' or 'zip'",
+                s
+            ))),
+        }
+    }
+}
+
+/// A predicate over one sweep combination, given as `(key, value)` pairs in
+/// argument order. Combinations for which it returns `Ok(false)` are dropped.
+pub type SweepConstraint =
+    Arc<dyn Fn(&[(String, String)]) -> Result<bool, SweeperError> + Send + Sync>;
+
+/// Build a constraint that drops combinations containing all of `pairs`
+///
+/// `exclude_combination(&[("optimizer", "sgd"), ("beta2", "0.9")])` rejects
+/// any job that has both `optimizer=sgd` and `beta2=0.9`.
+pub fn exclude_combination(pairs: &[(&str, &str)]) -> SweepConstraint {
+    let pairs: Vec<(String, String)> = pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    Arc::new(move |combination| {
+        Ok(!pairs
+            .iter()
+            .all(|pair| combination.iter().any(|item| item == pair)))
+    })
+}
+
+/// Called as each job of a sweep completes with `(job_index, total,
+/// job_return)`; `job_index` counts from 0 across all batches. With a
+/// parallel launcher it runs on worker threads, in completion order.
+pub type SweepProgress = Arc<dyn Fn(usize, usize, &JobReturn) + Send + Sync>;
+
+/// Read a sweep checkpoint: the indices of completed jobs, one per line
+///
+/// A checkpoint that does not exist yet means no job has completed.
+pub fn read_checkpoint(path: &Path) -> Result<BTreeSet<usize>, SweeperError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeSet::new()),
+        Err(e) => {
+            return Err(SweeperError::new(format!(
+                "Cannot read checkpoint '{}': {}",
+                path.display(),
+                e
+            )))
+        }
+    };
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.parse().map_err(|_| {
+                SweeperError::new(format!(
+                    "Invalid job index '{}' in checkpoint '{}'",
+                    line,
+                    path.display()
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Append the indices of completed jobs to a sweep checkpoint
+pub fn append_checkpoint(path: &Path, job_indices: &[usize]) -> Result<(), SweeperError> {
+    let lines: String = job_indices.iter().map(|idx| format!("{}\n", idx)).collect();
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .map_err(|e| {
+            SweeperError::new(format!(
+                "Cannot write checkpoint '{}': {}",
+                path.display(),
+                e
+            ))
+        })
+}
+
+/// Swept keys with their values, in argument order
+type SweepParams = Vec<(String, Vec<String>)>;
+
 /// BasicSweeper - generates cartesian product of parameter values
-#[derive(Debug)]
 pub struct BasicSweeper {
     config: Option<ConfigDict>,
     launcher: Option<Arc<dyn Launcher>>,
     max_batch_size: Option<usize>,
+    combination: SweepCombination,
+    /// Parameters zipped together in grid mode, one list of keys per group
+    zip_groups: Vec<Vec<String>>,
+    constraints: Vec<SweepConstraint>,
+    progress: Option<SweepProgress>,
+    /// Indices of jobs completed by an earlier run, skipped when sweeping
+    completed: BTreeSet<usize>,
+    /// Checkpoint that successful jobs are appended to
+    checkpoint: Option<PathBuf>,
+    /// Resolvers that turn `${name:...}` argument values into sweeps
+    resolvers: SweepResolvers,
+    /// Seed for shuffled ranges, so their job order is stable across runs
+    seed: Option<u64>,
+}
+
+impl Debug for BasicSweeper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BasicSweeper")
+            .field("config", &self.config)
+            .field("launcher", &self.launcher)
+            .field("max_batch_size", &self.max_batch_size)
+            .field("combination", &self.combination)
+            .field("zip_groups", &self.zip_groups)
+            .field("constraints", &self.constraints.len())
+            .field("progress", &self.progress.is_some())
+            .field("completed", &self.completed)
+            .field("checkpoint", &self.checkpoint)
+            .field("resolvers", &self.resolvers)
+            .field("seed", &self.seed)
+            .finish()
+    }
 }
 
 impl Default for BasicSweeper {
@@ -85,9 +224,214 @@ impl BasicSweeper {
             config: None,
             launcher: None,
             max_batch_size,
+            combination: SweepCombination::Grid,
+            zip_groups: Vec::new(),
+            constraints: Vec::new(),
+            progress: None,
+            completed: BTreeSet::new(),
+            checkpoint: None,
+            resolvers: SweepResolvers::new(),
+            seed: None,
         }
     }
 
+    /// Use these resolvers for `${name:...}` argument values
+    pub fn with_resolvers(mut self, resolvers: SweepResolvers) -> Self {
+        self.set_resolvers(resolvers);
+        self
+    }
+
+    /// Use these resolvers for `${name:...}` argument values
+    pub fn set_resolvers(&mut self, resolvers: SweepResolvers) {
+        self.resolvers = resolvers;
+    }
+
+    /// Resolvers applied to argument values before expansion
+    pub fn resolvers(&self) -> &SweepResolvers {
+        &self.resolvers
+    }
+
+    /// Shuffle `shuffle(...)` arguments with an RNG seeded from `seed`
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.set_seed(seed);
+        self
+    }
+
+    /// Shuffle `shuffle(...)` arguments with an RNG seeded from `seed`
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
+    /// Seed for shuffled ranges, if any
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Skip the jobs with these indices, e.g. ones finished by an earlier run
+    pub fn with_completed(mut self, job_indices: impl IntoIterator<Item = usize>) -> Self {
+        self.completed.extend(job_indices);
+        self
+    }
+
+    /// Indices of the jobs skipped as already completed
+    pub fn completed(&self) -> &BTreeSet<usize> {
+        &self.completed
+    }
+
+    /// Append the index of each successful job to a checkpoint file
+    pub fn with_checkpoint(mut self, path: impl Into<PathBuf>) -> Self {
+        self.set_checkpoint(path);
+        self
+    }
+
+    /// Append the index of each successful job to a checkpoint file
+    pub fn set_checkpoint(&mut self, path: impl Into<PathBuf>) {
+        self.checkpoint = Some(path.into());
+    }
+
+    /// The checkpoint file successful jobs are recorded in, if any
+    pub fn checkpoint(&self) -> Option<&Path> {
+        self.checkpoint.as_deref()
+    }
+
+    /// Resume an interrupted sweep: skip the jobs listed in the checkpoint at
+    /// `path` and keep recording newly completed jobs there
+    pub fn resume_from(&mut self, path: impl Into<PathBuf>) -> Result<(), SweeperError> {
+        let path = path.into();
+        self.completed.extend(read_checkpoint(&path)?);
+        self.checkpoint = Some(path);
+        Ok(())
+    }
+
+    /// Report progress after each completed job
+    pub fn with_progress(mut self, progress: SweepProgress) -> Self {
+        self.set_progress(progress);
+        self
+    }
+
+    /// Report progress after each completed job
+    pub fn set_progress(&mut self, progress: SweepProgress) {
+        self.progress = Some(progress);
+    }
+
+    /// Add a constraint that prunes combinations before launching
+    pub fn with_constraint(mut self, constraint: SweepConstraint) -> Self {
+        self.add_constraint(constraint);
+        self
+    }
+
+    /// Add a constraint that prunes combinations before launching
+    pub fn add_constraint(&mut self, constraint: SweepConstraint) {
+        self.constraints.push(constraint);
+    }
+
+    /// Check a combination of `key=value` overrides against all constraints
+    fn satisfies_constraints(&self, combination: &[String]) -> Result<bool, SweeperError> {
+        if self.constraints.is_empty() {
+            return Ok(true);
+        }
+        let pairs: Vec<(String, String)> = combination
+            .iter()
+            .filter_map(|o| o.split_once('='))
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        for constraint in &self.constraints {
+            if !constraint(&pairs)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Set how swept parameter values are combined into jobs
+    pub fn with_combination(mut self, combination: SweepCombination) -> Self {
+        self.combination = combination;
+        self
+    }
+
+    /// Get the combination mode
+    pub fn combination(&self) -> SweepCombination {
+        self.combination
+    }
+
+    /// Zip the parameters with these keys together in grid mode
+    ///
+    /// The group's values are paired position-wise and the pairs are crossed
+    /// with every other parameter: with `["a", "b"]` zipped, `a=1,2 b=x,y
+    /// c=p,q` gives the 4 jobs (1,x,p), (1,x,q), (2,y,p) and (2,y,q).
+    /// Ignored in [`SweepCombination::Zip`] mode, which zips everything.
+    pub fn with_zip_group(mut self, keys: &[&str]) -> Self {
+        self.add_zip_group(keys);
+        self
+    }
+
+    /// Zip the parameters with these keys together in grid mode
+    pub fn add_zip_group(&mut self, keys: &[&str]) {
+        self.zip_groups
+            .push(keys.iter().map(|k| k.to_string()).collect());
+    }
+
+    /// Combine parameter values into jobs, each a list of `key=value`
+    /// overrides in argument order
+    fn combine(&self, params: &[(String, Vec<String>)]) -> Result<Vec<Vec<String>>, SweeperError> {
+        if self.combination == SweepCombination::Zip {
+            return self.zip_combinations(params);
+        }
+
+        // Each dimension lists alternatives, each a set of (argument index,
+        // override) pairs: one pair for a plain parameter, one per member
+        // for a zip group, placed where the group's first member appears
+        let group_of = |key: &String| self.zip_groups.iter().position(|g| g.contains(key));
+        let mut dimensions: Vec<Vec<Vec<(usize, String)>>> = Vec::new();
+        let mut emitted: Vec<usize> = Vec::new();
+        for (idx, (key, values)) in params.iter().enumerate() {
+            let Some(group) = group_of(key) else {
+                dimensions.push(
+                    values
+                        .iter()
+                        .map(|value| vec![(idx, format!("{}={}", key, value))])
+                        .collect(),
+                );
+                continue;
+            };
+            if emitted.contains(&group) {
+                continue;
+            }
+            emitted.push(group);
+            let members: Vec<usize> = (idx..params.len())
+                .filter(|&i| group_of(&params[i].0) == Some(group))
+                .collect();
+            let zipped: Vec<(String, Vec<String>)> =
+                members.iter().map(|&i| params[i].clone()).collect();
+            dimensions.push(
+                self.zip_combinations(&zipped)?
+                    .into_iter()
+                    .map(|combo| members.iter().copied().zip(combo).collect())
+                    .collect(),
+            );
+        }
+
+        let mut combos: Vec<Vec<(usize, String)>> = vec![vec![]];
+        for dimension in &dimensions {
+            let mut new_combos = Vec::new();
+            for combo in &combos {
+                for alternative in dimension {
+                    let mut new_combo = combo.clone();
+                    new_combo.extend(alternative.iter().cloned());
+                    new_combos.push(new_combo);
+                }
+            }
+            combos = new_combos;
+        }
+        Ok(combos
+            .into_iter()
+            .map(|mut combo| {
+                combo.sort_by_key(|(idx, _)| *idx);
+                combo.into_iter().map(|(_, o)| o).collect()
+            })
+            .collect())
+    }
+
     /// Parse arguments into sweep combinations
     ///
     /// Handles patterns like:
@@ -143,8 +487,97 @@ impl BasicSweeper {
             .collect()
     }
 
+    /// Pair parameter values position-wise
+    ///
+    /// All multi-valued parameters must have the same number of values;
+    /// single-valued parameters are repeated for every combination.
+    fn zip_combinations(
+        &self,
+        params: &[(String, Vec<String>)],
+    ) -> Result<Vec<Vec<String>>, SweeperError> {
+        let mut len = 1;
+        let mut len_key: Option<&str> = None;
+        for (key, values) in params {
+            if values.len() == 1 {
+                continue;
+            }
+            match len_key {
+                None => {
+                    len = values.len();
+                    len_key = Some(key);
+                }
+                Some(first) if values.len() != len => {
+                    return Err(SweeperError::new(format!(
+                        "Cannot zip sweep parameters of different lengths: '{}' has {} values, '{}' has {}",
+                        first,
+                        len,
+                        key,
+                        values.len()
+                    )));
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok((0..len)
+            .map(|i| {
+                params
+                    .iter()
+                    .map(|(key, values)| {
+                        let value = if values.len() == 1 {
+                            &values[0]
+                        } else {
+                            &values[i]
+                        };
+                        format!("{}={}", key, value)
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Parse `key=value` arguments into each key's swept values and the
+    /// sweeps' tags
+    ///
+    /// Values are parsed as overrides, after registered resolvers have
+    /// replaced `${name:...}` values, so `a=1,2`, `a=range(1,3)` and
+    /// `a=${sweep:1,2}` all sweep `a`. Arguments without a value are ignored.
+    /// Shuffled sweeps share one RNG, seeded from [`Self::seed`] if set.
+    /// Resuming an unseeded shuffled sweep is an error, since its job
+    /// indices would not match the checkpoint.
+    fn parse_arguments(
+        &self,
+        arguments: &[String],
+    ) -> Result<(SweepParams, Vec<String>), SweeperError> {
+        let mut param_values = Vec::new();
+        let mut parsed = Vec::new();
+        let mut rng = self.seed.map(sweep_rng);
+        for arg in arguments {
+            let Some((key, _)) = arg.split_once('=') else {
+                continue;
+            };
+            let invalid = |message: String| {
+                SweeperError::new(format!("Invalid sweep argument '{}': {}", arg, message))
+            };
+            let mut overrides = [OverrideParser::parse(arg).map_err(|e| invalid(e.message))?];
+            self.resolvers
+                .resolve_overrides(&mut overrides)
+                .map_err(|e| invalid(e.message))?;
+            if self.seed.is_none() && !self.completed.is_empty() && is_shuffled(&overrides[0]) {
+                return Err(invalid(
+                    "a shuffled sweep needs a seed to resume from completed jobs".to_string(),
+                ));
+            }
+            let values = sweep_values(&overrides[0], rng.as_mut())
+                .ok_or_else(|| invalid("this sweep cannot be listed".to_string()))?;
+            param_values.push((key.to_string(), values));
+            parsed.extend(overrides);
+        }
+        Ok((param_values, sweep_tags(&parsed)))
+    }
+
     /// Split combinations into batches
-    fn split_into_batches(&self, combinations: Vec<Vec<String>>) -> Vec<Vec<Vec<String>>> {
+    fn split_into_batches<T: Clone>(&self, combinations: Vec<T>) -> Vec<Vec<T>> {
         match self.max_batch_size {
             None => vec![combinations],
             Some(size) if size == 0 => vec![combinations],
@@ -170,53 +603,59 @@ impl Sweeper for BasicSweeper {
             .as_ref()
             .ok_or_else(|| SweeperError::new("Sweeper not set up - no launcher"))?;
 
-        // Parse arguments and generate combinations
-        let mut all_combinations: Vec<Vec<String>> = Vec::new();
+        let (param_values, tags) = self.parse_arguments(arguments)?;
 
-        // Simple parsing: split comma-separated values
-        let mut param_values: Vec<(String, Vec<String>)> = Vec::new();
+        // Generate combinations: zipped, or cartesian product by default
+        let all_combinations = self.combine(&param_values)?;
 
-        for arg in arguments {
-            if let Some((key, value)) = arg.split_once('=') {
-                let values: Vec<String> = if value.contains(',') && !value.starts_with('[') {
-                    value.split(',').map(|s| s.trim().to_string()).collect()
-                } else {
-                    vec![value.to_string()]
-                };
-                param_values.push((key.to_string(), values));
+        // Drop combinations rejected by a constraint
+        let mut allowed = Vec::with_capacity(all_combinations.len());
+        for combination in all_combinations {
+            if self.satisfies_constraints(&combination)? {
+                allowed.push(combination);
             }
         }
 
-        // Generate cartesian product
-        if param_values.is_empty() {
-            all_combinations.push(vec![]);
-        } else {
-            let mut combos: Vec<Vec<String>> = vec![vec![]];
-            for (key, values) in &param_values {
-                let mut new_combos = Vec::new();
-                for combo in &combos {
-                    for value in values {
-                        let mut new_combo = combo.clone();
-                        new_combo.push(format!("{}={}", key, value));
-                        new_combos.push(new_combo);
-                    }
-                }
-                combos = new_combos;
-            }
-            all_combinations = combos;
-        }
+        // Skip jobs completed by an earlier run; the rest keep their index
+        let total = allowed.len();
+        let pending: Vec<(usize, Vec<String>)> = allowed
+            .into_iter()
+            .enumerate()
+            .filter(|(idx, _)| !self.completed.contains(idx))
+            .collect();
 
         // Split into batches
-        let batches = self.split_into_batches(all_combinations);
+        let batches = self.split_into_batches(pending);
 
-        // Launch all batches
+        // Launch all batches, one launch per run of consecutive indices so
+        // that each job is numbered as it would be in an uninterrupted sweep
         let mut all_results = Vec::new();
-        let mut job_idx = 0;
+        let checkpoint_error = Mutex::new(None);
 
         for batch in batches {
-            let results = launcher.launch(&batch, job_idx)?;
-            job_idx += results.len();
-            all_results.extend(results);
+            for run in batch.chunk_by(|a, b| b.0 == a.0 + 1) {
+                let job_idx = run[0].0;
+                let overrides: JobOverrideBatch =
+                    run.iter().map(|(_, overrides)| overrides.clone()).collect();
+                // Each job is checkpointed as soon as it succeeds, so an
+                // interrupted launch keeps the jobs it finished
+                let results =
+                    launcher.launch_tagged(&overrides, job_idx, &tags, &|idx, result| {
+                        if let Some(progress) = &self.progress {
+                            progress(idx, total, result);
+                        }
+                        if let (Some(checkpoint), 0) = (&self.checkpoint, result.status_code) {
+                            let mut error = checkpoint_error.lock().unwrap();
+                            if error.is_none() {
+                                *error = append_checkpoint(checkpoint, &[idx]).err();
+                            }
+                        }
+                    })?;
+                if let Some(err) = checkpoint_error.lock().unwrap().take() {
+                    return Err(err);
+                }
+                all_results.extend(results);
+            }
         }
 
         Ok(all_results)
@@ -227,10 +666,16 @@ impl Sweeper for BasicSweeper {
     }
 }
 
-/// Sweeper manager - holds and manages sweeper instances
+/// Sweeper manager - holds the sweeper that expands multirun arguments
+///
+/// A registry maps names such as `basic` to sweepers, so the one named by
+/// `hydra/sweeper=<name>` can be made active. Each sweeper keeps the
+/// launcher it was set up with, so activating one does not change how its
+/// jobs are launched.
 #[derive(Default)]
 pub struct SweeperManager {
     sweeper: Option<Arc<dyn Sweeper>>,
+    registry: HashMap<String, Arc<dyn Sweeper>>,
 }
 
 impl SweeperManager {
@@ -248,6 +693,33 @@ impl SweeperManager {
         self.sweeper = Some(Arc::new(BasicSweeper::new(max_batch_size)));
     }
 
+    /// Register a sweeper under a name, replacing any previous registration
+    pub fn register(&mut self, name: &str, sweeper: Arc<dyn Sweeper>) {
+        self.registry.insert(name.to_string(), sweeper);
+    }
+
+    /// Make the sweeper registered under `name` the active one
+    pub fn activate(&mut self, name: &str) -> Result<(), SweeperError> {
+        match self.registry.get(name) {
+            Some(sweeper) => {
+                self.sweeper = Some(Arc::clone(sweeper));
+                Ok(())
+            }
+            None => Err(SweeperError::new(format!(
+                "Unknown sweeper '{}'. Available sweepers: [{}]",
+                name,
+                self.available_names().join(", ")
+            ))),
+        }
+    }
+
+    /// Names of all registered sweepers, sorted
+    pub fn available_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.registry.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
     /// Get current sweeper
     pub fn sweeper(&self) -> Option<&Arc<dyn Sweeper>> {
         self.sweeper.as_ref()
@@ -335,4 +807,429 @@ mod tests {
         let manager = SweeperManager::new();
         assert!(manager.sweep(&[]).is_err());
     }
+
+    #[test]
+    fn test_sweeper_manager_registry() {
+        let mut manager = SweeperManager::new();
+        manager.register("grid", Arc::new(setup_sweeper(SweepCombination::Grid)));
+        manager.register("zip", Arc::new(setup_sweeper(SweepCombination::Zip)));
+        assert_eq!(manager.available_names(), vec!["grid", "zip"]);
+
+        let args = vec!["a=1,2".to_string(), "b=x,y".to_string()];
+        manager.activate("grid").unwrap();
+        assert_eq!(manager.sweep(&args).unwrap().len(), 4);
+        manager.activate("zip").unwrap();
+        assert_eq!(manager.sweep(&args).unwrap().len(), 2);
+
+        assert!(manager.activate("optuna").is_err());
+    }
+
+    fn setup_sweeper(combination: SweepCombination) -> BasicSweeper {
+        let mut sweeper = BasicSweeper::new(None).with_combination(combination);
+        let config = ConfigDict::new();
+        let mut launcher = BasicLauncher::new();
+        launcher.setup(&config, "test").unwrap();
+        sweeper.setup(&config, Arc::new(launcher)).unwrap();
+        sweeper
+    }
+
+    #[test]
+    fn test_basic_sweeper_progress() {
+        use crate::launcher::JobTask;
+        use std::sync::Mutex;
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let mut sweeper =
+            BasicSweeper::new(Some(2)).with_progress(Arc::new(move |idx, total, _| {
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(format!("done {}/{}", idx, total));
+            }));
+        let recorded = calls.clone();
+        let task: JobTask = Arc::new(move |overrides, _| {
+            recorded
+                .lock()
+                .unwrap()
+                .push(format!("run {}", overrides[0]));
+            0
+        });
+        let config = ConfigDict::new();
+        let mut launcher = BasicLauncher::new().with_task(task);
+        launcher.setup(&config, "test").unwrap();
+        sweeper.setup(&config, Arc::new(launcher)).unwrap();
+
+        // Each job is reported as it finishes, not once its batch is done
+        let args = vec!["a=1,2,3".to_string(), "b=x".to_string()];
+        let results = sweeper.sweep(&args).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            *calls.lock().unwrap(),
+            ["run a=1", "done 0/3", "run a=2", "done 1/3", "run a=3", "done 2/3"]
+        );
+    }
+
+    #[test]
+    fn test_basic_sweeper_zip() {
+        let sweeper = setup_sweeper(SweepCombination::Zip);
+
+        // a=1,2,3 b=x,y,z should pair position-wise into 3 jobs
+        let args = vec!["a=1,2,3".to_string(), "b=x,y,z".to_string()];
+        assert_eq!(sweeper.sweep(&args).unwrap().len(), 3);
+
+        let combos = sweeper
+            .zip_combinations(&[
+                ("a".to_string(), vec!["1".to_string(), "2".to_string()]),
+                ("b".to_string(), vec!["x".to_string(), "y".to_string()]),
+                ("c".to_string(), vec!["fixed".to_string()]),
+            ])
+            .unwrap();
+        assert_eq!(
+            combos,
+            vec![vec!["a=1", "b=x", "c=fixed"], vec!["a=2", "b=y", "c=fixed"]]
+        );
+    }
+
+    #[test]
+    fn test_basic_sweeper_zip_length_mismatch() {
+        let sweeper = setup_sweeper(SweepCombination::Zip);
+
+        let args = vec!["a=1,2,3".to_string(), "b=x,y".to_string()];
+        let err = sweeper.sweep(&args).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Cannot zip sweep parameters of different lengths: 'a' has 3 values, 'b' has 2"
+        );
+    }
+
+    #[test]
+    fn test_basic_sweeper_zip_group() {
+        let sweeper = setup_sweeper(SweepCombination::Grid).with_zip_group(&["a", "b"]);
+
+        let params = vec![
+            ("a".to_string(), vec!["1".to_string(), "2".to_string()]),
+            ("c".to_string(), vec!["p".to_string(), "q".to_string()]),
+            ("b".to_string(), vec!["x".to_string(), "y".to_string()]),
+        ];
+        assert_eq!(
+            sweeper.combine(&params).unwrap(),
+            vec![
+                vec!["a=1", "c=p", "b=x"],
+                vec!["a=1", "c=q", "b=x"],
+                vec!["a=2", "c=p", "b=y"],
+                vec!["a=2", "c=q", "b=y"],
+            ]
+        );
+
+        let args = vec![
+            "a=1,2,3".to_string(),
+            "b=x,y,z".to_string(),
+            "c=p,q".to_string(),
+        ];
+        assert_eq!(sweeper.sweep(&args).unwrap().len(), 6);
+
+        let args = vec![
+            "a=1,2,3".to_string(),
+            "b=x,y".to_string(),
+            "c=p,q".to_string(),
+        ];
+        let err = sweeper.sweep(&args).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Cannot zip sweep parameters of different lengths: 'a' has 3 values, 'b' has 2"
+        );
+    }
+
+    #[test]
+    fn test_sweep_combination_from_str() {
+        assert_eq!(
+            "grid".parse::<SweepCombination>().unwrap(),
+            SweepCombination::Grid
+        );
+        assert_eq!(
+            "zip".parse::<SweepCombination>().unwrap(),
+            SweepCombination::Zip
+        );
+        assert!("product".parse::<SweepCombination>().is_err());
+    }
+
+    #[test]
+    fn test_basic_sweeper_constraint() {
+        let sweeper =
+            setup_sweeper(SweepCombination::Grid).with_constraint(exclude_combination(&[
+                ("optimizer", "sgd"),
+                ("beta2", "0.9"),
+            ]));
+
+        let args = vec![
+            "optimizer=sgd,adam".to_string(),
+            "beta2=0.9,0.99".to_string(),
+        ];
+        let results = sweeper.sweep(&args).unwrap();
+        assert_eq!(results.len(), 3);
+
+        let allowed = |combination: &[&str]| {
+            let combination: Vec<String> = combination.iter().map(|s| s.to_string()).collect();
+            sweeper.satisfies_constraints(&combination).unwrap()
+        };
+        assert!(!allowed(&["optimizer=sgd", "beta2=0.9"]));
+        assert!(allowed(&["optimizer=sgd", "beta2=0.99"]));
+        assert!(allowed(&["optimizer=adam", "beta2=0.9"]));
+    }
+
+    #[test]
+    fn test_basic_sweeper_constraint_error() {
+        let sweeper = setup_sweeper(SweepCombination::Grid)
+            .with_constraint(Arc::new(|_| Err(SweeperError::new("constraint failed"))));
+
+        let err = sweeper.sweep(&["a=1,2".to_string()]).unwrap_err();
+        assert_eq!(err.message, "constraint failed");
+    }
+
+    #[test]
+    fn test_basic_sweeper_resume_from_checkpoint() {
+        use crate::launcher::JobTask;
+        use std::sync::Mutex;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let checkpoint = dir.path().join("completed.txt");
+        std::fs::write(&checkpoint, "0\n2\n").unwrap();
+
+        let launched = Arc::new(Mutex::new(Vec::new()));
+        let recorded = launched.clone();
+        let task: JobTask = Arc::new(move |overrides, _| {
+            recorded.lock().unwrap().push(overrides.join(" "));
+            0
+        });
+        let mut launcher = BasicLauncher::new().with_task(task);
+        launcher.setup(&ConfigDict::new(), "test").unwrap();
+
+        let mut sweeper = BasicSweeper::new(None);
+        sweeper.resume_from(&checkpoint).unwrap();
+        sweeper
+            .setup(&ConfigDict::new(), Arc::new(launcher))
+            .unwrap();
+        assert_eq!(
+            sweeper.completed().iter().copied().collect::<Vec<_>>(),
+            [0, 2]
+        );
+
+        let results = sweeper.sweep(&["a=1,2,3,4".to_string()]).unwrap();
+        let names: Vec<&str> = results.iter().map(|r| r.job_name.as_str()).collect();
+        assert_eq!(names, ["job_1", "job_3"]);
+        assert_eq!(*launched.lock().unwrap(), ["a=2", "a=4"]);
+        assert_eq!(
+            read_checkpoint(&checkpoint)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_basic_sweeper_resolves_sweep_interpolations() {
+        use crate::launcher::JobTask;
+        use std::sync::Mutex;
+
+        let launched = Arc::new(Mutex::new(Vec::new()));
+        let recorded = launched.clone();
+        let task: JobTask = Arc::new(move |overrides, _| {
+            recorded.lock().unwrap().push(overrides.join(" "));
+            0
+        });
+        let mut launcher = BasicLauncher::new().with_task(task);
+        launcher.setup(&ConfigDict::new(), "test").unwrap();
+
+        let mut resolvers = SweepResolvers::new();
+        resolvers.register("pow2", |args| {
+            let n: u32 = args.trim().parse().map_err(|_| "expected a count")?;
+            let choices: Vec<String> = (0..n).map(|i| (1 << i).to_string()).collect();
+            let sweep = OverrideParser::parse(&format!("x={}", choices.join(",")));
+            Ok(sweep.unwrap().value.unwrap())
+        });
+        let mut sweeper = BasicSweeper::new(None).with_resolvers(resolvers);
+        sweeper
+            .setup(&ConfigDict::new(), Arc::new(launcher))
+            .unwrap();
+
+        let args = vec![
+            "lr=${sweep:0.1,0.01}".to_string(),
+            "bs=${pow2:2}".to_string(),
+            "name=${now:%H}".to_string(),
+        ];
+        sweeper.sweep(&args).unwrap();
+        assert_eq!(
+            *launched.lock().unwrap(),
+            [
+                "lr=0.1 bs=1 name=${now:%H}",
+                "lr=0.1 bs=2 name=${now:%H}",
+                "lr=0.01 bs=1 name=${now:%H}",
+                "lr=0.01 bs=2 name=${now:%H}",
+            ]
+        );
+
+        let err = sweeper.sweep(&["bs=${pow2:x}".to_string()]).unwrap_err();
+        assert!(err.message.contains("expected a count"), "{}", err);
+    }
+
+    #[test]
+    fn test_basic_sweeper_seeded_shuffle() {
+        use crate::launcher::JobTask;
+
+        let launched = |seed: Option<u64>| -> Vec<String> {
+            let launched = Arc::new(Mutex::new(Vec::new()));
+            let recorded = launched.clone();
+            let task: JobTask = Arc::new(move |overrides, _| {
+                recorded.lock().unwrap().push(overrides.join(" "));
+                0
+            });
+            let mut launcher = BasicLauncher::new().with_task(task);
+            launcher.setup(&ConfigDict::new(), "test").unwrap();
+            let mut sweeper = BasicSweeper::new(None);
+            if let Some(seed) = seed {
+                sweeper.set_seed(seed);
+            }
+            sweeper
+                .setup(&ConfigDict::new(), Arc::new(launcher))
+                .unwrap();
+            sweeper
+                .sweep(&["a=shuffle(range(0,20))".to_string()])
+                .unwrap();
+            let launched = launched.lock().unwrap().clone();
+            launched
+        };
+
+        let first = launched(Some(7));
+        assert_eq!(launched(Some(7)), first);
+        let in_order: Vec<String> = (0..20).map(|i| format!("a={}", i)).collect();
+        assert_ne!(first, in_order);
+        let mut sorted = first.clone();
+        sorted.sort_by_key(|job| job[2..].parse::<i64>().unwrap());
+        assert_eq!(sorted, in_order);
+        assert_eq!(launched(None).len(), 20);
+
+        let mut sweeper = BasicSweeper::new(None).with_completed([0]);
+        sweeper
+            .setup(&ConfigDict::new(), Arc::new(BasicLauncher::new()))
+            .unwrap();
+        let err = sweeper
+            .sweep(&["a=shuffle(range(0,20))".to_string()])
+            .unwrap_err();
+        assert!(err.message.contains("needs a seed"), "{}", err);
+    }
+
+    #[test]
+    fn test_basic_sweeper_groups_output_dirs_by_sweep_tag() {
+        use crate::config::value::ConfigValue;
+        use crate::merge::apply_override;
+
+        let mut config = ConfigDict::new();
+        apply_override(
+            &mut config,
+            "hydra.sweep.dir",
+            ConfigValue::String("/out/${sweep.tag}".to_string()),
+        )
+        .unwrap();
+        apply_override(
+            &mut config,
+            "hydra.sweep.subdir",
+            ConfigValue::String("${hydra.job.num}".to_string()),
+        )
+        .unwrap();
+        let mut launcher = BasicLauncher::new();
+        launcher.setup(&config, "test").unwrap();
+        let mut sweeper = BasicSweeper::new(None);
+        sweeper.setup(&config, Arc::new(launcher)).unwrap();
+
+        let output_dirs = |args: &[&str]| -> Vec<String> {
+            let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+            let results = sweeper.sweep(&args).unwrap();
+            results.into_iter().map(|r| r.output_dir).collect()
+        };
+        assert_eq!(
+            output_dirs(&["db=tag(grid,choice(a,b))"]),
+            ["/out/grid/0", "/out/grid/1"]
+        );
+        assert_eq!(
+            output_dirs(&["db=tag(grid,choice(a,b))", "lr=tag(fast,choice(1,2))"]),
+            [
+                "/out/fast/grid/0",
+                "/out/fast/grid/1",
+                "/out/fast/grid/2",
+                "/out/fast/grid/3"
+            ]
+        );
+        assert_eq!(output_dirs(&["db=a,b"]), ["/out/0", "/out/1"]);
+    }
+
+    #[test]
+    fn test_basic_sweeper_resumes_interrupted_sweep() {
+        use crate::launcher::JobTask;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let checkpoint = dir.path().join("completed.txt");
+        let sweeper_with_task = |task: JobTask| {
+            let mut launcher = BasicLauncher::new().with_task(task);
+            launcher.setup(&ConfigDict::new(), "test").unwrap();
+            let mut sweeper = BasicSweeper::new(None);
+            sweeper.resume_from(&checkpoint).unwrap();
+            sweeper
+                .setup(&ConfigDict::new(), Arc::new(launcher))
+                .unwrap();
+            sweeper
+        };
+        let args = vec!["a=1,2,3,4".to_string()];
+
+        // The run is interrupted during the third job of a single launch
+        let sweeper = sweeper_with_task(Arc::new(|overrides, _| {
+            if overrides[0] == "a=3" {
+                panic!("interrupted");
+            }
+            0
+        }));
+        let interrupted =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| sweeper.sweep(&args)));
+        assert!(interrupted.is_err());
+        assert_eq!(
+            read_checkpoint(&checkpoint)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [0, 1]
+        );
+
+        // Resuming runs only the jobs that had not finished
+        let launched = Arc::new(Mutex::new(Vec::new()));
+        let recorded = launched.clone();
+        let sweeper = sweeper_with_task(Arc::new(move |overrides, _| {
+            recorded.lock().unwrap().push(overrides.join(" "));
+            0
+        }));
+        let results = sweeper.sweep(&args).unwrap();
+        let names: Vec<&str> = results.iter().map(|r| r.job_name.as_str()).collect();
+        assert_eq!(names, ["job_2", "job_3"]);
+        assert_eq!(*launched.lock().unwrap(), ["a=3", "a=4"]);
+        assert_eq!(
+            read_checkpoint(&checkpoint)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_read_checkpoint_errors() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let checkpoint = dir.path().join("completed.txt");
+        assert!(read_checkpoint(&checkpoint).unwrap().is_empty());
+
+        std::fs::write(&checkpoint, "1\nlast\n").unwrap();
+        let err = read_checkpoint(&checkpoint).unwrap_err();
+        assert!(err
+            .message
+            .starts_with("Invalid job index 'last' in checkpoint"));
+    }
 }
@@ -80,12 +80,67 @@ pub fn is_valid_key(key: &str) -> bool {
         .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
 }
 
+/// Keys with special meaning to Hydra that must not be used as top-level user config keys
+///
+/// - `hydra`: the Hydra runtime config node
+/// - `defaults`: the defaults list of a config
+/// - `_self_`: the marker for a config's own position in its defaults list
+pub const RESERVED_KEYS: &[&str] = &["hydra", "defaults", "_self_"];
+
+/// Validate a user config key, rejecting invalid keys and reserved Hydra names
+///
+/// A key is reserved when its first segment is one of `RESERVED_KEYS`
+/// (e.g. `defaults` or `hydra.run.dir`). Returns the reason on rejection.
+pub fn validate_user_key(key: &str) -> Result<(), String> {
+    if !is_valid_key(key) {
+        return Err(format!("Invalid key '{}'", key));
+    }
+
+    let first = key.split('.').next().unwrap_or(key);
+    if RESERVED_KEYS.contains(&first) {
+        return Err(format!("Key '{}' uses the reserved name '{}'", key, first));
+    }
+
+    Ok(())
+}
+
+/// Check if a key is valid and not reserved by Hydra
+pub fn is_valid_user_key(key: &str) -> bool {
+    validate_user_key(key).is_ok()
+}
+
 /// Split a dotted key into parts
+///
+/// Every unescaped dot separates two parts, so empty parts are kept:
+/// `a..b` is `["a", "", "b"]`, `.a` is `["", "a"]`, `a.` is `["a", ""]`
+/// and the empty key is `[""]`. A backslash escapes the next character, so
+/// `a\.b` is the single part `a\.b`; parts are returned as written, still
+/// escaped. [`join_key`] reverses this exactly: `join_key(&split_key(s)) == s`.
 pub fn split_key(key: &str) -> Vec<&str> {
-    key.split('.').collect()
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut chars = key.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '.' => {
+                parts.push(&key[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&key[start..]);
+    parts
 }
 
 /// Join key parts into a dotted key
+///
+/// Parts are joined as-is, so a part that should keep a literal dot must
+/// escape it (`a\.b`). Splitting the result with [`split_key`] gives the
+/// parts back, except that no parts join to the empty key, which splits to `[""]`.
 pub fn join_key(parts: &[&str]) -> String {
     parts.join(".")
 }
@@ -104,9 +159,93 @@ pub fn normalize_file_name(filename: &str) -> String {
 pub fn get_valid_filename(s: &str) -> String {
     let s = s.trim().replace(' ', "_");
     // Remove any characters that are not alphanumeric, underscores, dots, or hyphens
-    s.chars()
-        .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '.' || *c == '-')
-        .collect()
+    s.chars().filter(|c| is_filename_char(*c)).collect()
+}
+
+/// Whether `c` is kept by [`get_valid_filename`]
+fn is_filename_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-'
+}
+
+/// Length of the hash suffix appended by [`get_valid_filename_opts`] on truncation
+const FILENAME_HASH_LEN: usize = 8;
+
+/// Get a valid filename, replacing invalid characters and capping its length
+///
+/// Every character other than ASCII alphanumerics, `_`, `.` and `-` (including
+/// spaces) is replaced by `replacement`. When the result is longer than
+/// `max_len` characters it is truncated and suffixed with `-` and a hash of the
+/// full name, so distinct names sharing a long prefix stay distinct.
+///
+/// `replacement` must itself be one of the kept characters; anything else
+/// (e.g. `/`) is rejected with the reason.
+pub fn get_valid_filename_opts(
+    s: &str,
+    replacement: char,
+    max_len: Option<usize>,
+) -> Result<String, String> {
+    if !is_filename_char(replacement) {
+        return Err(format!(
+            "Replacement '{}' is not a valid filename character",
+            replacement
+        ));
+    }
+    let name: String = s
+        .trim()
+        .chars()
+        .map(|c| if is_filename_char(c) { c } else { replacement })
+        .collect();
+
+    let max_len = match max_len {
+        Some(max_len) if name.chars().count() > max_len => max_len,
+        _ => return Ok(name),
+    };
+    let hash = format!("{:016x}", fnv1a_hash(s));
+    let hash = &hash[..FILENAME_HASH_LEN];
+    if max_len <= FILENAME_HASH_LEN {
+        return Ok(hash[..max_len].to_string());
+    }
+    let prefix: String = name.chars().take(max_len - FILENAME_HASH_LEN - 1).collect();
+    Ok(format!("{}-{}", prefix, hash))
+}
+
+/// 64-bit FNV-1a hash, stable across platforms and Rust versions
+fn fnv1a_hash(s: &str) -> u64 {
+    s.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Extensions kept by [`normalize_output_file_name`] by default
+pub const OUTPUT_FILE_EXTENSIONS: &[&str] = &[".yaml", ".yml", ".json", ".txt", ".log", ".csv"];
+
+/// Turn a name built from config values into a valid output file name
+///
+/// Unlike [`normalize_file_name`], which resolves config paths, this
+/// sanitizes the name: a trailing extension from `preserve_extensions` (e.g.
+/// `".yaml"`) is split off and kept, and every other character that is not
+/// an ASCII alphanumeric, `_` or `-` (including other dots) becomes `_`.
+pub fn normalize_output_file_name(name: &str, preserve_extensions: &[&str]) -> String {
+    let name = name.trim();
+    let (stem, extension) = preserve_extensions
+        .iter()
+        .find_map(|ext| {
+            name.strip_suffix(ext)
+                .filter(|stem| !stem.is_empty())
+                .map(|stem| (stem, *ext))
+        })
+        .unwrap_or((name, ""));
+    let stem: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}{}", stem, extension)
 }
 
 /// Sanitize a string for use in a file path
@@ -173,6 +312,29 @@ mod tests {
         assert!(!is_valid_key("db[0]"));
     }
 
+    #[test]
+    fn test_validate_user_key_reserved() {
+        for key in RESERVED_KEYS {
+            assert_eq!(
+                validate_user_key(key),
+                Err(format!("Key '{}' uses the reserved name '{}'", key, key))
+            );
+            assert!(!is_valid_user_key(key));
+        }
+        assert!(!is_valid_user_key("hydra.run.dir"));
+        assert!(is_valid_user_key("db.defaults"));
+    }
+
+    #[test]
+    fn test_validate_user_key_normal() {
+        assert_eq!(validate_user_key("db.host"), Ok(()));
+        assert!(is_valid_user_key("hydra_config"));
+        assert_eq!(
+            validate_user_key("1db"),
+            Err("Invalid key '1db'".to_string())
+        );
+    }
+
     #[test]
     fn test_split_key() {
         assert_eq!(split_key("db.host.port"), vec!["db", "host", "port"]);
@@ -185,6 +347,49 @@ mod tests {
         assert_eq!(join_key(&["db"]), "db");
     }
 
+    #[test]
+    fn test_split_key_empty_and_escaped_parts() {
+        assert_eq!(split_key("a..b"), vec!["a", "", "b"]);
+        assert_eq!(split_key(".a"), vec!["", "a"]);
+        assert_eq!(split_key("a."), vec!["a", ""]);
+        assert_eq!(split_key("."), vec!["", ""]);
+        assert_eq!(split_key(""), vec![""]);
+        assert_eq!(split_key(r"a\.b.c"), vec![r"a\.b", "c"]);
+        // An escaped backslash does not escape the dot after it
+        assert_eq!(split_key(r"a\\.b"), vec![r"a\\", "b"]);
+        assert_eq!(split_key(r"a\"), vec![r"a\"]);
+    }
+
+    #[test]
+    fn test_split_join_key_round_trip() {
+        // Every key of up to three parts drawn from these, including empty
+        // parts and escaped dots and backslashes
+        let atoms = ["", "a", "db", r"a\.b", r"\.", r"x\\", "é", r"a\.b\.c"];
+        let mut keys: Vec<Vec<&str>> = atoms.iter().map(|a| vec![*a]).collect();
+        for _ in 0..2 {
+            let longer: Vec<Vec<&str>> = keys
+                .iter()
+                .filter(|k| k.len() == keys.last().unwrap().len())
+                .flat_map(|k| {
+                    atoms.iter().map(move |a| {
+                        let mut k = k.clone();
+                        k.push(a);
+                        k
+                    })
+                })
+                .collect();
+            keys.extend(longer);
+        }
+        assert_eq!(keys.len(), 8 + 64 + 512);
+
+        for parts in keys {
+            let key = join_key(&parts);
+            assert_eq!(split_key(&key), parts, "splitting {:?}", key);
+            assert_eq!(join_key(&split_key(&key)), key);
+        }
+        assert_eq!(join_key(&[]), "");
+    }
+
     #[test]
     fn test_normalize_file_name() {
         assert_eq!(normalize_file_name("config"), "config.yaml");
@@ -193,6 +398,23 @@ mod tests {
         assert_eq!(normalize_file_name("db/mysql"), "db/mysql.yaml");
     }
 
+    #[test]
+    fn test_normalize_output_file_name() {
+        let exts = OUTPUT_FILE_EXTENSIONS;
+        assert_eq!(
+            normalize_output_file_name("my model.yaml", exts),
+            "my_model.yaml"
+        );
+        assert_eq!(normalize_output_file_name("my_model", exts), "my_model");
+        assert_eq!(
+            normalize_output_file_name("lr=0.1.json", exts),
+            "lr_0_1.json"
+        );
+        assert_eq!(normalize_output_file_name("lr=0.1", exts), "lr_0_1");
+        assert_eq!(normalize_output_file_name("run.yaml", &[]), "run_yaml");
+        assert_eq!(normalize_output_file_name(".yaml", exts), "_yaml");
+    }
+
     #[test]
     fn test_get_valid_filename() {
         assert_eq!(get_valid_filename("my_app"), "my_app");
@@ -203,6 +425,56 @@ mod tests {
         assert_eq!(get_valid_filename("test-file"), "test-file");
     }
 
+    #[test]
+    fn test_get_valid_filename_opts_replacement() {
+        assert_eq!(
+            get_valid_filename_opts("my app", '_', None).unwrap(),
+            "my_app"
+        );
+        assert_eq!(
+            get_valid_filename_opts("app@123", '-', None).unwrap(),
+            "app-123"
+        );
+        assert_eq!(
+            get_valid_filename_opts(" a/b:c ", '.', None).unwrap(),
+            "a.b.c"
+        );
+        assert_eq!(
+            get_valid_filename_opts("short", '_', Some(5)).unwrap(),
+            "short"
+        );
+
+        // The replacement must itself be valid in a filename
+        assert_eq!(
+            get_valid_filename_opts("a b", '/', None).unwrap_err(),
+            "Replacement '/' is not a valid filename character"
+        );
+        assert!(get_valid_filename_opts("a b", ' ', None).is_err());
+    }
+
+    #[test]
+    fn test_get_valid_filename_opts_truncation() {
+        let a =
+            get_valid_filename_opts("lr=0.1,batch_size=32,model=resnet", '_', Some(20)).unwrap();
+        let b =
+            get_valid_filename_opts("lr=0.1,batch_size=64,model=resnet", '_', Some(20)).unwrap();
+        assert_eq!(a.len(), 20);
+        assert!(a.starts_with("lr_0.1_batc-"), "{}", a);
+        assert_ne!(a, b);
+        // Deterministic
+        assert_eq!(
+            a,
+            get_valid_filename_opts("lr=0.1,batch_size=32,model=resnet", '_', Some(20)).unwrap()
+        );
+        // Caps shorter than the suffix keep only (part of) the hash
+        assert_eq!(
+            get_valid_filename_opts("abcdefghijkl", '_', Some(4))
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+
     #[test]
     fn test_sanitize_path_component() {
         assert_eq!(sanitize_path_component("file"), "file");
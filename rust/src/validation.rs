@@ -3,9 +3,15 @@
 //!
 //! Provides validation for structured configs and type checking.
 
+use std::cell::RefCell;
 use std::collections::HashMap;
 
 use crate::config::value::{ConfigDict, ConfigValue};
+use crate::config::{ConfigRepository, ResolverContext};
+use crate::core::override_types::{
+    ElementKind, Override, OverrideType, OverrideValue, ParsedElement,
+};
+use crate::defaults::ResultDefault;
 
 /// Validation error
 #[derive(Debug, Clone)]
@@ -195,10 +201,300 @@ impl Default for ConfigSchema {
     }
 }
 
+/// Validation warning (non-fatal)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationWarning {
+    pub path: String,
+    pub message: String,
+}
+
+impl ValidationWarning {
+    pub fn new(path: &str, message: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Type name of a config value, as used in validation messages
+fn config_type_name(value: &ConfigValue) -> &'static str {
+    match value {
+        ConfigValue::Null | ConfigValue::Missing => "null",
+        ConfigValue::Bool(_) => "bool",
+        ConfigValue::Int(_) => "int",
+        ConfigValue::Float(_) => "float",
+        // Enum values are set from strings
+        ConfigValue::String(_) | ConfigValue::Interpolation(_) | ConfigValue::Enum { .. } => "str",
+        ConfigValue::List(_) => "list",
+        ConfigValue::Dict(_) => "dict",
+    }
+}
+
+/// Type name of a parsed override element, as used in validation messages
+fn element_type_name(elem: &ParsedElement) -> &'static str {
+    match elem {
+        ParsedElement::Null => "null",
+        ParsedElement::Bool(_) => "bool",
+        ParsedElement::Int(_) => "int",
+        ParsedElement::Float(_) => "float",
+        ParsedElement::String(_) | ParsedElement::QuotedString(_) => "str",
+        ParsedElement::List(_) => "list",
+        ParsedElement::Dict(_) => "dict",
+    }
+}
+
+/// Element type name shared by every item of a non-empty list value
+fn uniform_list_type(value: &ConfigValue) -> Option<&'static str> {
+    let items = value.as_list()?;
+    let first = config_type_name(items.first()?);
+    items
+        .iter()
+        .all(|item| config_type_name(item) == first)
+        .then_some(first)
+}
+
+/// Whether `elem` is a list of only ints and floats, which may replace a
+/// list of floats
+fn is_numeric_list(elem: &ParsedElement) -> bool {
+    matches!(elem, ParsedElement::List(items)
+        if items.iter().all(|item| matches!(item.kind(), ElementKind::Int | ElementKind::Float)))
+}
+
+/// Check that `key=value` overrides keep the type of the values they replace
+///
+/// Returns a warning for each change override whose key exists in `config`
+/// and whose value has a different type (e.g. `port=mysql` for an int port),
+/// or which replaces a list of one element type with a mixed list.
+/// Setting or replacing null/missing values, int values for float fields
+/// (also inside lists, so `[1, 0.5]` may replace a list of floats),
+/// interpolations, sweeps and config group selections are not flagged.
+pub fn check_override_types(
+    config: &ConfigValue,
+    overrides: &[Override],
+) -> Vec<ValidationWarning> {
+    let Some(dict) = config.as_dict() else {
+        return Vec::new();
+    };
+
+    let mut warnings = Vec::new();
+    for ovr in overrides {
+        if ovr.override_type != OverrideType::Change || ovr.key.has_package() {
+            continue;
+        }
+        let Some(OverrideValue::Element(elem)) = &ovr.value else {
+            continue;
+        };
+        let path = &ovr.key.key_or_group;
+        let Some(existing) = dict.select(path) else {
+            continue;
+        };
+
+        let from = config_type_name(&existing);
+        let to = element_type_name(elem);
+        let compatible = from == to
+            || from == "null"
+            || to == "null"
+            || (from == "float" && to == "int")
+            // A string replacing a dict is a config group selection (db=mysql)
+            || (from == "dict" && to == "str")
+            || elem.as_str().is_some_and(|s| s.contains("${"));
+        if !compatible {
+            warnings.push(ValidationWarning::new(
+                path,
+                &format!("Override changes type from {} to {}", from, to),
+            ));
+        } else if elem.list_element_type() == Some(ElementKind::Mixed) {
+            let item_type = uniform_list_type(&existing)
+                .filter(|item_type| *item_type != "float" || !is_numeric_list(elem));
+            if let Some(item_type) = item_type {
+                warnings.push(ValidationWarning::new(
+                    path,
+                    &format!("Override mixes element types in a list of {}", item_type),
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+/// The `group` or `group@package` a default selects a config for
+///
+/// Returns `None` for defaults that are not config group selections
+/// (`_self_`, the primary config and top-level configs).
+fn default_selection_key(rd: &ResultDefault) -> Option<String> {
+    if rd.is_self || rd.primary {
+        return None;
+    }
+    let group = match &rd.override_key {
+        Some(key) => key.split('@').next().unwrap_or_default().to_string(),
+        None => rd.config_path.as_deref()?.rsplit_once('/')?.0.to_string(),
+    };
+    if group.is_empty() {
+        return None;
+    }
+    Some(match rd.package.as_deref() {
+        Some(package) if !package.is_empty() => format!("{}@{}", group, package),
+        _ => group,
+    })
+}
+
+/// Check a resolved defaults list for conflicting group selections
+///
+/// Returns an error for each `group@package` selected more than once with
+/// different configs, naming both selections.
+pub fn validate_defaults(defaults: &[ResultDefault]) -> Vec<ValidationError> {
+    let mut selected: HashMap<String, &str> = HashMap::new();
+    let mut errors = Vec::new();
+    for rd in defaults {
+        let (Some(key), Some(config_path)) = (default_selection_key(rd), rd.config_path.as_deref())
+        else {
+            continue;
+        };
+        match selected.get(&key) {
+            Some(first) if *first != config_path => errors.push(ValidationError::new(
+                &key,
+                &format!(
+                    "Conflicting selections for '{}': '{}' and '{}'",
+                    key, first, config_path
+                ),
+            )),
+            Some(_) => {}
+            None => {
+                selected.insert(key, config_path);
+            }
+        }
+    }
+    errors
+}
+
+/// Like [`validate_defaults`], also reporting defaults whose config does not
+/// exist in `repo`
+pub fn validate_defaults_in_repo(
+    defaults: &[ResultDefault],
+    repo: &ConfigRepository,
+) -> Vec<ValidationError> {
+    let mut errors = validate_defaults(defaults);
+    let paths: Vec<&str> = defaults
+        .iter()
+        .filter(|rd| !rd.is_self)
+        .filter_map(|rd| rd.config_path.as_deref())
+        .collect();
+    for (path, exists) in paths.iter().zip(repo.exists_any(&paths)) {
+        if !exists {
+            errors.push(ValidationError::new(
+                path,
+                &format!("Config '{}' not found in the repository", path),
+            ));
+        }
+    }
+    errors
+}
+
+/// Resolvers the application registers when it starts, e.g. `${now:%H-%M}`
+///
+/// They are resolved by the Python side, so a [`ResolverContext`] does not
+/// know them, but references to them are valid.
+pub const APP_RESOLVERS: &[&str] = &["now", "hydra", "python_version"];
+
+/// Check that every `${...}` reference in a config can be resolved
+///
+/// Key references must name an existing key of `config`, and resolver
+/// references (`${name:args}`) a resolver known to a default
+/// [`ResolverContext`] or one of [`APP_RESOLVERS`]. See
+/// [`validate_interpolations_with`].
+pub fn validate_interpolations(config: &ConfigValue) -> Vec<ValidationError> {
+    let root = match config {
+        ConfigValue::Dict(dict) => dict.clone(),
+        _ => ConfigDict::new(),
+    };
+    validate_interpolations_with(config, &ResolverContext::new(&root))
+}
+
+/// Like [`validate_interpolations`], resolving against `ctx`'s root and
+/// resolvers
+///
+/// Each unresolvable reference is reported at the path of the value that
+/// contains it. References nested in another reference's arguments are
+/// checked, but the outer reference is not, since its target is only known
+/// once they resolve.
+pub fn validate_interpolations_with(
+    config: &ConfigValue,
+    ctx: &ResolverContext,
+) -> Vec<ValidationError> {
+    let errors = RefCell::new(Vec::new());
+    config.map_leaves(|path, leaf| {
+        if let ConfigValue::String(s) | ConfigValue::Interpolation(s) = leaf {
+            check_references(s, path, ctx, &mut errors.borrow_mut());
+        }
+        ConfigValue::Null
+    });
+    errors.into_inner()
+}
+
+fn check_references(s: &str, path: &str, ctx: &ResolverContext, errors: &mut Vec<ValidationError>) {
+    for (_, _, reference) in crate::interpolation::find_interpolations(s) {
+        let expr = &reference[2..reference.len() - 1];
+        if expr.contains("${") {
+            check_references(expr, path, ctx, errors);
+            continue;
+        }
+        let message = match expr.split_once(':') {
+            Some((resolver, _))
+                if !ctx.has_resolver(resolver) && !APP_RESOLVERS.contains(&resolver) =>
+            {
+                format!("Unknown resolver '{}' in '{}'", resolver, reference)
+            }
+            Some(_) => continue,
+            None if ctx.root.select(expr.trim()).is_none() => {
+                format!("Reference '{}' points at a missing key", reference)
+            }
+            None => continue,
+        };
+        errors.push(ValidationError::new(path, &message));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_validate_interpolations() {
+        let config = crate::config::parser::parse_yaml(
+            "db:\n  host: localhost\n  url: 'mysql://${db.host}/${missing.key}'\n\
+             name: ${bogus:x}\n\
+             home: ${oc.env:HOME,/tmp}\n\
+             run: ${now:%H-%M}/${hydra:job.name}\n\
+             servers: ['${db.host}', '${oc.select:${nope},a}']\n",
+        )
+        .unwrap();
+
+        let errors: Vec<_> = validate_interpolations(&config)
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "db.url: Reference '${missing.key}' points at a missing key",
+                "name: Unknown resolver 'bogus' in '${bogus:x}'",
+                "servers.1: Reference '${nope}' points at a missing key",
+            ]
+        );
+
+        let root = config.as_dict().unwrap().clone();
+        let mut ctx = ResolverContext::new(&root);
+        ctx.register_resolver("bogus", |_| Ok(ConfigValue::Null));
+        assert_eq!(validate_interpolations_with(&config, &ctx).len(), 2);
+    }
+
     #[test]
     fn test_type_spec_parse() {
         assert_eq!(TypeSpec::parse("int"), Some(TypeSpec::Int));
@@ -268,4 +564,124 @@ mod tests {
         assert_eq!(config.get("host").unwrap().as_str(), Some("localhost"));
         assert_eq!(config.get("port").unwrap().as_int(), Some(8080));
     }
+
+    fn parse_overrides(lines: &[&str]) -> Vec<Override> {
+        lines
+            .iter()
+            .map(|line| crate::OverrideParser::parse(line).unwrap())
+            .collect()
+    }
+
+    fn override_config() -> ConfigValue {
+        crate::config::parser::parse_yaml(
+            "db:\n  port: 3306\n  debug: false\nlr: 0.1\nname: app\nlayers: [64, 32]\n\
+             rates: [0.1, 0.2]\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_override_types_preserved() {
+        let overrides = parse_overrides(&[
+            "db.port=5432",
+            "db.debug=true",
+            "lr=1",
+            "name=other",
+            "layers=[128,64,32]",
+            "rates=[1,0.5]",
+        ]);
+        assert!(check_override_types(&override_config(), &overrides).is_empty());
+    }
+
+    #[test]
+    fn test_check_override_types_changed() {
+        let overrides = parse_overrides(&[
+            "db.port=mysql",
+            "db.debug=1",
+            "+extra=1",
+            "db=postgres",
+            "layers=[128,wide]",
+            "rates=[1,wide]",
+        ]);
+        let warnings = check_override_types(&override_config(), &overrides);
+
+        assert_eq!(
+            warnings,
+            vec![
+                ValidationWarning::new("db.port", "Override changes type from int to str"),
+                ValidationWarning::new("db.debug", "Override changes type from bool to int"),
+                ValidationWarning::new("layers", "Override mixes element types in a list of int"),
+                ValidationWarning::new("rates", "Override mixes element types in a list of float"),
+            ]
+        );
+    }
+
+    fn group_default(group: &str, name: &str) -> ResultDefault {
+        ResultDefault {
+            override_key: Some(group.to_string()),
+            ..ResultDefault::new().with_config_path(format!("{}/{}", group, name))
+        }
+    }
+
+    #[test]
+    fn test_validate_defaults_valid() {
+        let defaults = vec![
+            ResultDefault::new()
+                .with_config_path("config".to_string())
+                .as_primary(),
+            group_default("db", "mysql"),
+            group_default("server", "dev"),
+            group_default("db", "mysql"),
+            ResultDefault::new()
+                .with_config_path("config".to_string())
+                .as_self(),
+        ];
+        assert!(validate_defaults(&defaults).is_empty());
+
+        // The same group in different packages is not a conflict
+        let defaults = vec![
+            group_default("db", "mysql").with_package("src".to_string()),
+            group_default("db", "postgres").with_package("dst".to_string()),
+        ];
+        assert!(validate_defaults(&defaults).is_empty());
+    }
+
+    #[test]
+    fn test_validate_defaults_conflict() {
+        let defaults = vec![
+            group_default("db", "mysql"),
+            group_default("server", "dev"),
+            group_default("db", "postgres"),
+        ];
+        let errors = validate_defaults(&defaults);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "db");
+        assert_eq!(
+            errors[0].message,
+            "Conflicting selections for 'db': 'db/mysql' and 'db/postgres'"
+        );
+    }
+
+    #[test]
+    fn test_validate_defaults_in_repo() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("db")).unwrap();
+        std::fs::write(temp_dir.path().join("db/mysql.yaml"), "port: 3306\n").unwrap();
+        let repo = ConfigRepository::new(&[crate::SearchPathElement::new(
+            "main",
+            temp_dir.path().to_str().unwrap(),
+        )]);
+
+        let defaults = vec![
+            group_default("db", "mysql"),
+            group_default("cache", "redis"),
+        ];
+        let errors = validate_defaults_in_repo(&defaults, &repo);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "cache/redis");
+        assert_eq!(
+            errors[0].message,
+            "Config 'cache/redis' not found in the repository"
+        );
+    }
 }